@@ -1,23 +1,11 @@
-use axum::{
-    extract::{Query, State},
-    http::StatusCode,
-    middleware,
-    response::{IntoResponse, Json, Response},
-    routing::get,
-    Router,
-};
+use axum::{extract::Query, http::StatusCode, response::Json, routing::get, Router};
 use openapi_rs::model::parse::OpenAPI;
-use openapi_rs::request::axum::RequestData;
+use openapi_rs::model::shared::SharedOpenAPI;
+use openapi_rs::request::axum::OpenApiValidationLayer;
+use openapi_rs::validator::ValidationOptions;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
 use tower_http::cors::CorsLayer;
 
-// Application state containing OpenAPI instance
-#[derive(Clone)]
-struct AppState {
-    openapi: Arc<OpenAPI>,
-}
-
 #[derive(Serialize, Deserialize, Debug)]
 struct User {
     id: Option<u32>,
@@ -32,69 +20,6 @@ struct UserQuery {
     limit: u32,
 }
 
-// OpenAPI validation middleware
-async fn openapi_middleware(
-    State(state): State<AppState>,
-    request: axum::http::Request<axum::body::Body>,
-    next: axum::middleware::Next,
-) -> Result<axum::response::Response, Response> {
-    // Get request path
-    let path = request.uri().path().to_string();
-
-    // Read request body (if exists)
-    let (parts, body) = request.into_parts();
-    let body_bytes = match axum::body::to_bytes(body, usize::MAX).await {
-        Ok(bytes) => bytes,
-        Err(e) => {
-            eprintln!("Failed to read request body: {}", e);
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "error": "Invalid request body",
-                    "message": "Failed to read request body"
-                })),
-            )
-                .into_response());
-        }
-    };
-
-    // Rebuild request
-    let rebuilt_request =
-        axum::http::Request::from_parts(parts.clone(), axum::body::Body::from(body_bytes.clone()));
-
-    // Create request data for validation
-    let request_data = RequestData {
-        path: path.clone(),
-        inner: rebuilt_request,
-        body: if body_bytes.is_empty() {
-            None
-        } else {
-            Some(body_bytes.clone())
-        },
-    };
-
-    // Validate using cached OpenAPI instance
-    if let Err(validation_error) = state.openapi.validator(request_data) {
-        eprintln!(
-            "OpenAPI validation failed - path: {}, error: {:?}",
-            path, validation_error
-        );
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "error": "Validation failed",
-                "message": format!("Request does not conform to OpenAPI specification: {}", validation_error),
-                "path": path
-            }))
-        ).into_response());
-    }
-
-    // Rebuild request for next middleware
-    let final_request = axum::http::Request::from_parts(parts, axum::body::Body::from(body_bytes));
-
-    Ok(next.run(final_request).await)
-}
-
 // User related handlers
 async fn get_users(Query(params): Query<UserQuery>) -> Json<Vec<User>> {
     let page = params.page;
@@ -138,23 +63,19 @@ async fn main() {
     // Read and parse OpenAPI specification at startup
     let content = std::fs::read_to_string("api.yaml").expect("Unable to read api.yaml file");
 
-    let openapi = OpenAPI::yaml(&content).expect("Unable to parse OpenAPI specification");
+    let openapi = OpenAPI::yaml(&content)
+        .expect("Unable to parse OpenAPI specification")
+        .with_options(ValidationOptions::new().with_max_body_size(1024 * 1024));
 
-    // Create application state
-    let app_state = AppState {
-        openapi: Arc::new(openapi),
-    };
+    // Keep a handle to the document so it can be replaced at runtime
+    let openapi = SharedOpenAPI::new(openapi);
 
     // Build routes
     let app = Router::new()
         .route("/health", get(health_check))
         .route("/users", get(get_users).post(create_user))
-        .layer(middleware::from_fn_with_state(
-            app_state.clone(),
-            openapi_middleware,
-        ))
-        .layer(CorsLayer::permissive())
-        .with_state(app_state);
+        .layer(OpenApiValidationLayer::from_shared(openapi))
+        .layer(CorsLayer::permissive());
 
     // Start server
     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080")
@@ -230,9 +230,9 @@ paths:
 
     // Demonstrate that other 3.0 features still work
     println!();
-    println!("   📄 Paths Available: {}", openapi.paths.len());
+    println!("   📄 Paths Available: {}", openapi.paths().len());
 
-    for (path, _path_item) in openapi.paths.iter() {
+    for (path, _path_item) in openapi.paths().iter() {
         println!("      - {}", path);
     }
 
@@ -226,7 +226,7 @@ paths:
 
     println!("   Analyzing parameters for /search endpoint:");
 
-    let search_path = openapi.paths.get("/search").unwrap();
+    let search_path = openapi.paths().get("/search").unwrap();
     let get_operation = search_path.operations.get("get").unwrap();
 
     if let Some(params) = &get_operation.parameters {
@@ -321,7 +321,7 @@ paths:
     println!("   Endpoint: /users");
     println!();
 
-    let users_path = openapi.paths.get("/users").unwrap();
+    let users_path = openapi.paths().get("/users").unwrap();
 
     // Show traditional GET method
     if let Some(get_op) = users_path.operations.get("get") {
@@ -419,48 +419,39 @@ paths:
 
     // $self field
     println!();
-    if openapi.self_ref.is_some() {
-        println!("   ✅ $self field: {}", openapi.self_ref.as_ref().unwrap());
+    if let Some(self_ref) = &openapi.self_ref {
+        println!("   ✅ $self field: {self_ref}");
     } else {
         println!("   ❌ $self field: not present");
     }
 
     // info.summary
     println!();
-    if openapi.info.summary.is_some() {
-        println!(
-            "   ✅ info.summary: {}",
-            openapi.info.summary.as_ref().unwrap()
-        );
+    if let Some(summary) = &openapi.info.summary {
+        println!("   ✅ info.summary: {summary}");
     } else {
         println!("   ❌ info.summary: not present");
     }
 
     // jsonSchemaDialect (3.1 but still usable in 3.2)
     println!();
-    if openapi.json_schema_dialect.is_some() {
-        println!(
-            "   ✅ jsonSchemaDialect: {}",
-            openapi.json_schema_dialect.as_ref().unwrap()
-        );
+    if let Some(dialect) = &openapi.json_schema_dialect {
+        println!("   ✅ jsonSchemaDialect: {dialect}");
     } else {
         println!("   ❌ jsonSchemaDialect: not present");
     }
 
     // webhooks
     println!();
-    if openapi.webhooks.is_some() {
-        println!(
-            "   ✅ webhooks: {} defined",
-            openapi.webhooks.as_ref().unwrap().len()
-        );
+    if let Some(webhooks) = &openapi.webhooks {
+        println!("   ✅ webhooks: {} defined", webhooks.len());
     } else {
         println!("   ❌ webhooks: not present");
     }
 
     // querystring parameter
     println!();
-    let resources_path = openapi.paths.get("/resources").unwrap();
+    let resources_path = openapi.paths().get("/resources").unwrap();
     let get_op = resources_path.operations.get("get").unwrap();
     let has_querystring = get_op
         .parameters
@@ -558,7 +549,7 @@ paths:
         "#;
 
         let openapi: OpenAPI = OpenAPI::yaml(yaml).unwrap();
-        let test_path = openapi.paths.get("/test").unwrap();
+        let test_path = openapi.paths().get("/test").unwrap();
         let get_op = test_path.operations.get("get").unwrap();
         let params = get_op.parameters.as_ref().unwrap();
         assert_eq!(params[0].r#in, Some(In::QueryString));
@@ -581,7 +572,7 @@ paths:
         "#;
 
         let openapi: OpenAPI = OpenAPI::yaml(yaml).unwrap();
-        let test_path = openapi.paths.get("/test").unwrap();
+        let test_path = openapi.paths().get("/test").unwrap();
         assert!(test_path.query.is_some());
         assert_eq!(
             test_path.query.as_ref().unwrap().summary.as_ref().unwrap(),
@@ -0,0 +1,55 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/* C API of openapi-rs, built with the `ffi` feature. */
+
+#ifndef OPENAPI_RS_H
+#define OPENAPI_RS_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct OpenAPI OpenAPI;
+
+/* Parses a YAML or JSON document. Returns NULL on failure, including a
+ * panic while loading, and, unless `error` is NULL, stores
+ * {"error": "..."} in *error. */
+OpenAPI *openapi_load(const char *spec, char **error);
+
+/* Validates a request given as JSON:
+ *   {"method": "POST", "path": "/users?page=1",
+ *    "headers": {"content-type": "application/json"}, "body": "{...}"}
+ * Bodies that are not UTF-8 are passed base64 encoded as "body_base64"
+ * instead of "body"; setting both is an error.
+ * Returns {"valid": true} or {"valid": false, "status": 400, "error": "..."},
+ * with "allow" set for status 405 and "request_id" taken from the
+ * X-Request-Id or traceparent header. A panic inside the validator is
+ * reported with status 500 rather than unwinding into the caller. */
+char *openapi_validate_request(const OpenAPI *openapi, const char *request);
+
+/* Frees a document returned by openapi_load. */
+void openapi_free(OpenAPI *openapi);
+
+/* Frees a string returned by this library. */
+void openapi_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* OPENAPI_RS_H */
@@ -0,0 +1,91 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! `include_spec!`: embeds an OpenAPI document at compile time.
+
+use openapi_rs::model::lint::Severity;
+use openapi_rs::model::parse::OpenAPI;
+use proc_macro::TokenStream;
+use quote::quote;
+use std::path::PathBuf;
+use syn::{parse_macro_input, LitStr};
+
+/// Embeds the YAML or JSON document at the given path, relative to the
+/// invoking crate's `Cargo.toml`, and expands to a `&'static OpenAPI`.
+///
+/// The document is parsed and linted during compilation: parse failures and
+/// lint errors (such as examples contradicting their schema) fail the build.
+/// The embedded form is the parsed document serialized back to JSON, so the
+/// program only deserializes it, once, on first use.
+///
+/// ```ignore
+/// use openapi_rs_macros::include_spec;
+///
+/// let open_api = include_spec!("api.yaml");
+/// assert!(open_api.operation_by_id("createUser").is_some());
+/// ```
+#[proc_macro]
+pub fn include_spec(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match embed(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(message) => syn::Error::new(path.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+fn embed(path: &LitStr) -> Result<proc_macro2::TokenStream, String> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| "CARGO_MANIFEST_DIR is not set".to_string())?;
+    let file = PathBuf::from(manifest_dir).join(path.value());
+    let display = file.display();
+
+    let contents = std::fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read OpenAPI document '{display}': {e}"))?;
+    let open_api = OpenAPI::yaml(&contents)
+        .map_err(|e| format!("Invalid OpenAPI document '{display}': {e}"))?;
+
+    let errors: Vec<String> = open_api
+        .lint()
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .map(|diagnostic| diagnostic.to_string())
+        .collect();
+    if !errors.is_empty() {
+        return Err(format!(
+            "Invalid OpenAPI document '{display}':\n{}",
+            errors.join("\n")
+        ));
+    }
+
+    let json = open_api
+        .to_json()
+        .map_err(|e| format!("Failed to embed OpenAPI document '{display}': {e}"))?;
+    let file = file.to_string_lossy();
+
+    Ok(quote! {{
+        // Rebuilds the invoking crate whenever the document changes
+        const _: &str = ::core::include_str!(#file);
+        static SPEC: ::std::sync::LazyLock<::openapi_rs::model::parse::OpenAPI> =
+            ::std::sync::LazyLock::new(|| {
+                ::openapi_rs::model::parse::OpenAPI::json(#json)
+                    .expect("OpenAPI document validated at compile time")
+            });
+        &*SPEC
+    }})
+}
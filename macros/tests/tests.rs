@@ -0,0 +1,40 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use openapi_rs::model::parse::OpenAPI;
+    use openapi_rs_macros::include_spec;
+    use std::ptr;
+
+    #[test]
+    fn include_spec_embeds_parsed_document() -> Result<(), Box<dyn std::error::Error>> {
+        let embedded = include_spec!("../tests/example/example.yaml");
+
+        let content = std::fs::read_to_string("../tests/example/example.yaml")?;
+        let parsed = OpenAPI::yaml(&content)?;
+        assert_eq!(embedded.to_json()?, parsed.to_json()?);
+
+        let open_api = include_spec!("../examples/api.yaml");
+        assert!(open_api.operation_by_id("createUser").is_some());
+        assert!(open_api.match_path("/users/42").is_some());
+
+        let again = || include_spec!("../examples/api.yaml");
+        assert!(ptr::eq(again(), again()));
+        Ok(())
+    }
+}
@@ -0,0 +1,748 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! `openapi-rs validate <file>`: parses an OpenAPI document in strict mode,
+//! lints it and prints the diagnostics found.
+//!
+//! `openapi-rs lint <file>` does the same with the rules disabled or
+//! re-graded by a [`Ruleset`] read from `--config`, or from
+//! `.openapi-rs.yaml` when the current directory has one.
+//!
+//! Both exit with 0 when the document has no errors, 1 when it has some and 2
+//! when it cannot be read or the arguments are invalid.
+//!
+//! `openapi-rs bundle <file> -o <output>` inlines the references of a
+//! document to other files or URLs, writing a single self-contained document
+//! as JSON when `output` ends with `.json` and as YAML otherwise, or to the
+//! standard output. It exits with 1 when the document or a reference cannot
+//! be loaded.
+//!
+//! `openapi-rs convert --to <json|yaml|3.1|3.2> <file> -o <output>` rewrites
+//! a document as JSON or YAML, by default in the syntax of `output` or
+//! `file`, and upgrades OpenAPI 3.0 and Swagger 2.0 documents to a later
+//! version. Swagger documents are always upgraded, to 3.1 unless `--to`
+//! names another version. It exits with 1 when the document cannot be
+//! converted.
+//!
+//! `openapi-rs mock <file> --port 8080` serves the example or
+//! schema-generated response of every operation to the requests that pass
+//! validation, see [`openapi_rs::request::axum::mock`].
+//!
+//! `openapi-rs replay <traffic> <file>` validates the requests recorded in a
+//! HAR file, or in a file of curl commands, and with `--responses` their
+//! responses, printing the violations of each operation. It exits with 1
+//! when there are violations.
+//!
+//! `openapi-rs scaffold <file> -o <output>` writes an axum router with a
+//! `501 Not Implemented` handler stub per operation, see
+//! [`openapi_rs::codegen::scaffold`].
+
+mod replay;
+
+use openapi_rs::codegen::scaffold;
+use openapi_rs::model::bundle::{self, bundle};
+use openapi_rs::model::lint::{Diagnostic, Ruleset, Severity, RULES};
+use openapi_rs::model::parse::{expand_merge_keys, OpenAPI};
+use openapi_rs::model::strict::UnknownKeys;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::path::Path;
+use std::process::ExitCode;
+use url::Url;
+
+const USAGE: &str = "Usage: openapi-rs validate [--format text|json] <file>
+       openapi-rs lint [--config <ruleset.yaml>] [--format text|json] <file>
+       openapi-rs bundle [-o <output>] <file>
+       openapi-rs convert [--to json|yaml|3.1|3.2]... [-o <output>] <file>
+       openapi-rs mock [--host <host>] [--port <port>] <file>
+       openapi-rs replay [--responses] [--format text|json] <traffic.har> <file>
+       openapi-rs scaffold [-o <output>] <file>";
+
+/// The ruleset `lint` reads when no `--config` is given, if it exists
+const DEFAULT_CONFIG: &str = ".openapi-rs.yaml";
+
+/// The rule of the keys [`OpenAPI::yaml_strict`] rejects
+const UNKNOWN_KEY: &str = "unknown-key";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Validate,
+    Lint {
+        config: Option<String>,
+    },
+    Bundle {
+        output: Option<String>,
+    },
+    Convert {
+        syntax: Option<Syntax>,
+        version: Option<String>,
+        output: Option<String>,
+    },
+    Mock {
+        host: String,
+        port: u16,
+    },
+    Replay {
+        traffic: String,
+        responses: bool,
+    },
+    Scaffold {
+        output: Option<String>,
+    },
+}
+
+/// The serialization of a written document
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Syntax {
+    Yaml,
+    Json,
+}
+
+impl Syntax {
+    /// JSON for `.json` files, YAML otherwise
+    fn of(file: &str) -> Self {
+        if file.ends_with(".json") {
+            Syntax::Json
+        } else {
+            Syntax::Yaml
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    command: Command,
+    file: String,
+    format: Format,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let ruleset = match &args.command {
+        Command::Bundle { output } => return write_bundle(&args.file, output.as_deref()),
+        Command::Convert {
+            syntax,
+            version,
+            output,
+        } => return write_conversion(&args.file, *syntax, version.as_deref(), output.as_deref()),
+        Command::Mock { host, port } => return serve_mock(&args.file, host, *port),
+        Command::Replay { traffic, responses } => {
+            return replay_traffic(traffic, &args.file, *responses, &args.format)
+        }
+        Command::Scaffold { output } => return write_scaffold(&args.file, output.as_deref()),
+        Command::Validate => Ok(Ruleset::default()),
+        Command::Lint {
+            config: Some(config),
+        } => ruleset(config),
+        Command::Lint { config: None } if Path::new(DEFAULT_CONFIG).exists() => {
+            ruleset(DEFAULT_CONFIG)
+        }
+        Command::Lint { config: None } => Ok(Ruleset::default()),
+    };
+    let ruleset = match ruleset {
+        Ok(ruleset) => ruleset,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::from(2);
+        }
+    };
+    let contents = match std::fs::read_to_string(&args.file) {
+        Ok(contents) => contents,
+        Err(error) => {
+            eprintln!("Failed to read {}: {error}", args.file);
+            return ExitCode::from(2);
+        }
+    };
+
+    let diagnostics = diagnose(&contents, &ruleset);
+    match args.format {
+        Format::Text => {
+            for diagnostic in &diagnostics {
+                println!("{}: {diagnostic}", args.file);
+            }
+        }
+        Format::Json => match serde_json::to_string_pretty(&diagnostics) {
+            Ok(json) => println!("{json}"),
+            Err(error) => {
+                eprintln!("Failed to serialize diagnostics: {error}");
+                return ExitCode::from(2);
+            }
+        },
+    }
+
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut args = args.into_iter();
+    let mut command = match args.next().as_deref() {
+        Some("validate") => Command::Validate,
+        Some("lint") => Command::Lint { config: None },
+        Some("bundle") => Command::Bundle { output: None },
+        Some("convert") => Command::Convert {
+            syntax: None,
+            version: None,
+            output: None,
+        },
+        Some("mock") => Command::Mock {
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+        },
+        Some("replay") => Command::Replay {
+            traffic: String::new(),
+            responses: false,
+        },
+        Some("scaffold") => Command::Scaffold { output: None },
+        Some(command) => return Err(format!("Unknown command '{command}'")),
+        None => return Err("Missing command".to_string()),
+    };
+
+    let mut files = Vec::new();
+    let mut format = Format::Text;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => match &mut command {
+                Command::Lint { config } => {
+                    *config = Some(args.next().ok_or("--config takes a file")?);
+                }
+                _ => return Err("Only lint takes --config".to_string()),
+            },
+            "-o" | "--output" => match &mut command {
+                Command::Bundle { output }
+                | Command::Convert { output, .. }
+                | Command::Scaffold { output } => {
+                    *output = Some(args.next().ok_or("--output takes a file")?);
+                }
+                _ => return Err("Only bundle, convert and scaffold take --output".to_string()),
+            },
+            "--to" => match &mut command {
+                Command::Convert {
+                    syntax, version, ..
+                } => match args.next().ok_or("--to takes a format or a version")? {
+                    to if to == "json" => *syntax = Some(Syntax::Json),
+                    to if to == "yaml" => *syntax = Some(Syntax::Yaml),
+                    to => *version = Some(to),
+                },
+                _ => return Err("Only convert takes --to".to_string()),
+            },
+            "--host" => match &mut command {
+                Command::Mock { host, .. } => *host = args.next().ok_or("--host takes a host")?,
+                _ => return Err("Only mock takes --host".to_string()),
+            },
+            "--port" => match &mut command {
+                Command::Mock { port, .. } => {
+                    *port = args
+                        .next()
+                        .and_then(|port| port.parse().ok())
+                        .ok_or("--port takes a port number")?;
+                }
+                _ => return Err("Only mock takes --port".to_string()),
+            },
+            "--responses" => match &mut command {
+                Command::Replay { responses, .. } => *responses = true,
+                _ => return Err("Only replay takes --responses".to_string()),
+            },
+            "--format"
+                if !matches!(
+                    command,
+                    Command::Validate | Command::Lint { .. } | Command::Replay { .. }
+                ) =>
+            {
+                return Err("Only validate, lint and replay take --format".to_string())
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    _ => return Err("--format takes 'text' or 'json'".to_string()),
+                }
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option '{arg}'")),
+            _ => files.push(arg),
+        }
+    }
+
+    let mut files = files.into_iter();
+    if let Command::Replay { traffic, .. } = &mut command {
+        *traffic = files.next().ok_or("Missing traffic file")?;
+    }
+    let file = files.next().ok_or("Missing file")?;
+    if let Some(arg) = files.next() {
+        return Err(format!("Unexpected argument '{arg}'"));
+    }
+    Ok(Args {
+        command,
+        file,
+        format,
+    })
+}
+
+/// Bundles the document at `file`, a path or an `http(s)` URL, into `output`
+fn write_bundle(file: &str, output: Option<&str>) -> ExitCode {
+    let root = match Url::parse(file) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(url),
+        _ => std::path::absolute(file)
+            .map_err(|error| error.to_string())
+            .and_then(|path| {
+                Url::from_file_path(&path).map_err(|()| format!("Invalid path {file}"))
+            }),
+    };
+    let root = match root {
+        Ok(root) => root,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let syntax = Syntax::of(output.unwrap_or_default());
+    match bundle(&root, load).and_then(|document| serialize(&document, syntax)) {
+        Ok(bundled) => write_output(output, &bundled),
+        Err(error) => {
+            eprintln!("Failed to bundle {file}: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Converts the document at `file` to `syntax` and `version` into `output`
+fn write_conversion(
+    file: &str,
+    syntax: Option<Syntax>,
+    version: Option<&str>,
+    output: Option<&str>,
+) -> ExitCode {
+    let contents = match std::fs::read_to_string(file) {
+        Ok(contents) => contents,
+        Err(error) => {
+            eprintln!("Failed to read {file}: {error}");
+            return ExitCode::from(2);
+        }
+    };
+    let syntax = syntax.unwrap_or_else(|| Syntax::of(output.unwrap_or(file)));
+    match convert(&contents, syntax, version) {
+        Ok(converted) => write_output(output, &converted),
+        Err(error) => {
+            eprintln!("Failed to convert {file}: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// `contents` in `syntax`, upgraded to `version` when given. Documents that
+/// are not upgraded are rewritten as authored, with merge keys expanded
+fn convert(contents: &str, syntax: Syntax, version: Option<&str>) -> anyhow::Result<String> {
+    let mut document: Value = serde_yaml::from_str(contents)?;
+    expand_merge_keys(&mut document)?;
+    let swagger = document.get("swagger").is_some();
+    if swagger || version.is_some() {
+        let openapi = if swagger {
+            OpenAPI::swagger(contents)?
+        } else {
+            OpenAPI::yaml(contents)?
+        };
+        let upgraded = openapi.upgrade(version.unwrap_or("3.1"))?;
+        return serialize(&upgraded, syntax);
+    }
+    serialize(&document, syntax)
+}
+
+fn serialize(document: &impl Serialize, syntax: Syntax) -> anyhow::Result<String> {
+    Ok(match syntax {
+        Syntax::Json => serde_json::to_string_pretty(document)? + "\n",
+        Syntax::Yaml => serde_yaml::to_string(document)?,
+    })
+}
+
+/// Serves the mock responses of the document at `file` on `host:port` until
+/// the process is stopped
+fn serve_mock(file: &str, host: &str, port: u16) -> ExitCode {
+    let openapi = std::fs::read_to_string(file)
+        .map_err(|error| format!("Failed to read {file}: {error}"))
+        .and_then(|contents| {
+            OpenAPI::yaml(&contents).map_err(|error| format!("Invalid document {file}: {error}"))
+        });
+    let openapi = match openapi {
+        Ok(openapi) => openapi,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let served = tokio::runtime::Runtime::new().and_then(|runtime| {
+        runtime.block_on(async {
+            let listener = tokio::net::TcpListener::bind((host, port)).await?;
+            println!("Serving mock responses of {file} on http://{host}:{port}");
+            axum::serve(listener, openapi_rs::request::axum::mock(openapi)).await
+        })
+    });
+    match served {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Failed to serve on {host}:{port}: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Validates the traffic recorded in `traffic` against the document at
+/// `file`
+fn replay_traffic(traffic: &str, file: &str, responses: bool, format: &Format) -> ExitCode {
+    let read = |file: &str| {
+        std::fs::read_to_string(file).map_err(|error| format!("Failed to read {file}: {error}"))
+    };
+    let openapi = read(file).and_then(|contents| {
+        OpenAPI::yaml(&contents).map_err(|error| format!("Invalid document {file}: {error}"))
+    });
+    let exchanges = read(traffic)
+        .and_then(|contents| replay::parse(&contents).map_err(|error| format!("{error:#}")));
+    let (openapi, exchanges) = match (openapi, exchanges) {
+        (Ok(openapi), Ok(exchanges)) => (openapi, exchanges),
+        (Err(error), _) | (_, Err(error)) => {
+            eprintln!("{error}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let reports = replay::replay(&openapi, &exchanges, responses);
+    match format {
+        Format::Text => {
+            for report in &reports {
+                let plural = |count: usize, noun: &str| match count {
+                    1 => format!("1 {noun}"),
+                    count => format!("{count} {noun}s"),
+                };
+                println!(
+                    "{}: {}, {}",
+                    report.operation,
+                    plural(report.requests, "request"),
+                    plural(report.violations.len(), "violation")
+                );
+                for violation in &report.violations {
+                    println!(
+                        "  #{} {}: {}",
+                        violation.entry, violation.request, violation.message
+                    );
+                }
+            }
+        }
+        Format::Json => match serde_json::to_string_pretty(&reports) {
+            Ok(json) => println!("{json}"),
+            Err(error) => {
+                eprintln!("Failed to serialize the report: {error}");
+                return ExitCode::from(2);
+            }
+        },
+    }
+
+    if reports.iter().any(|report| !report.violations.is_empty()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Writes `contents` to `output`, or to the standard output
+fn write_output(output: Option<&str>, contents: &str) -> ExitCode {
+    match output {
+        Some(output) => {
+            if let Err(error) = std::fs::write(output, contents) {
+                eprintln!("Failed to write {output}: {error}");
+                return ExitCode::from(2);
+            }
+        }
+        None => print!("{contents}"),
+    }
+    ExitCode::SUCCESS
+}
+
+/// Writes the axum router scaffold of the document at `file` into `output`
+fn write_scaffold(file: &str, output: Option<&str>) -> ExitCode {
+    let openapi = std::fs::read_to_string(file)
+        .map_err(|error| format!("Failed to read {file}: {error}"))
+        .and_then(|contents| {
+            OpenAPI::yaml(&contents).map_err(|error| format!("Invalid document {file}: {error}"))
+        });
+    match openapi {
+        Ok(openapi) => write_output(output, &scaffold::axum_router(&openapi)),
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Reads the documents references point to, from files or over HTTP
+fn load(url: &Url) -> anyhow::Result<String> {
+    match url.scheme() {
+        "http" | "https" => Ok(ureq::get(url.as_str())
+            .call()?
+            .body_mut()
+            .read_to_string()?),
+        _ => bundle::load_file(url),
+    }
+}
+
+/// Reads the ruleset at `path`, rejecting rules that are never reported
+fn ruleset(path: &str) -> Result<Ruleset, String> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|error| format!("Failed to read {path}: {error}"))?;
+    let ruleset =
+        Ruleset::yaml(&contents).map_err(|error| format!("Invalid ruleset {path}: {error}"))?;
+    match ruleset
+        .rules
+        .keys()
+        .find(|rule| rule.as_str() != UNKNOWN_KEY && !RULES.contains(&rule.as_str()))
+    {
+        Some(rule) => Err(format!("Unknown rule '{rule}' in {path}")),
+        None => Ok(ruleset),
+    }
+}
+
+/// The unknown keys and lint diagnostics of `contents` as graded by
+/// `ruleset`, or its parse failure
+fn diagnose(contents: &str, ruleset: &Ruleset) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let openapi = match OpenAPI::yaml_strict(contents) {
+        Ok(openapi) => Ok(openapi),
+        Err(error) => match error.downcast::<UnknownKeys>() {
+            Ok(unknown) => {
+                diagnostics.extend(unknown.pointers.into_iter().map(|pointer| Diagnostic {
+                    rule: UNKNOWN_KEY,
+                    severity: Severity::Error,
+                    message: "Key is not defined by the OpenAPI specification".to_string(),
+                    pointer,
+                }));
+                OpenAPI::yaml(contents).map_err(anyhow::Error::from)
+            }
+            Err(error) => Err(error),
+        },
+    };
+    match openapi {
+        Ok(openapi) => diagnostics.extend(openapi.lint()),
+        Err(error) => diagnostics.push(Diagnostic {
+            rule: "parse",
+            severity: Severity::Error,
+            pointer: String::new(),
+            message: error.to_string(),
+        }),
+    }
+    ruleset.apply(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: CLI
+  version: 1.0.0
+paths:
+  /users:
+    get:
+      operationId: listUsers
+      responses:
+        '200':
+          description: OK
+"#;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args(&["validate", "api.yaml"]),
+            Ok(Args {
+                command: Command::Validate,
+                file: "api.yaml".to_string(),
+                format: Format::Text,
+            })
+        );
+        assert_eq!(
+            args(&["lint", "--config", "rules.yaml", "api.yaml"]).map(|args| args.command),
+            Ok(Command::Lint {
+                config: Some("rules.yaml".to_string())
+            })
+        );
+        assert!(args(&["validate", "--config", "rules.yaml", "api.yaml"]).is_err());
+        assert_eq!(
+            args(&["bundle", "root.yaml", "-o", "bundled.json"]).map(|args| args.command),
+            Ok(Command::Bundle {
+                output: Some("bundled.json".to_string())
+            })
+        );
+        assert!(args(&["lint", "-o", "out.yaml", "api.yaml"]).is_err());
+        assert!(args(&["bundle", "--format", "json", "root.yaml"]).is_err());
+        assert_eq!(
+            args(&["convert", "--to", "json", "--to", "3.1", "api.yaml"]).map(|args| args.command),
+            Ok(Command::Convert {
+                syntax: Some(Syntax::Json),
+                version: Some("3.1".to_string()),
+                output: None,
+            })
+        );
+        assert!(args(&["bundle", "--to", "json", "root.yaml"]).is_err());
+        assert_eq!(
+            args(&["mock", "--port", "4010", "api.yaml"]).map(|args| args.command),
+            Ok(Command::Mock {
+                host: "127.0.0.1".to_string(),
+                port: 4010,
+            })
+        );
+        assert!(args(&["mock", "--port", "http", "api.yaml"]).is_err());
+        assert_eq!(
+            args(&["replay", "--responses", "traffic.har", "api.yaml"]).map(|args| args.command),
+            Ok(Command::Replay {
+                traffic: "traffic.har".to_string(),
+                responses: true,
+            })
+        );
+        assert!(args(&["replay", "api.yaml"]).is_err());
+        assert_eq!(
+            args(&["scaffold", "api.yaml", "-o", "src/routes.rs"]).map(|args| args.command),
+            Ok(Command::Scaffold {
+                output: Some("src/routes.rs".to_string())
+            })
+        );
+        assert_eq!(
+            args(&["validate", "--format", "json", "api.yaml"]).map(|args| args.format),
+            Ok(Format::Json)
+        );
+        assert!(args(&[]).is_err());
+        assert!(args(&["check", "api.yaml"]).is_err());
+        assert!(args(&["validate"]).is_err());
+        assert!(args(&["validate", "--format", "xml", "api.yaml"]).is_err());
+        assert!(args(&["validate", "a.yaml", "b.yaml"]).is_err());
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let defaults = Ruleset::default();
+        assert_eq!(diagnose(SPEC, &defaults), Vec::new());
+
+        let misspelled = SPEC.replace("description: OK", "descripton: OK");
+        let diagnostics = diagnose(&misspelled, &defaults);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "unknown-key");
+        assert_eq!(
+            diagnostics[0].pointer,
+            "/paths/~1users/get/responses/200/descripton"
+        );
+
+        let diagnostics = diagnose("openapi: [", &defaults);
+        assert_eq!(diagnostics[0].rule, "parse");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+
+        let unnamed = SPEC.replace("      operationId: listUsers\n", "");
+        let diagnostics = diagnose(&unnamed, &defaults);
+        assert_eq!(diagnostics[0].rule, "missing-operation-id");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_diagnose_with_ruleset() {
+        let ruleset =
+            Ruleset::yaml("rules:\n  missing-operation-id: error\n  unknown-key: off\n").unwrap();
+        let document = SPEC
+            .replace("      operationId: listUsers\n", "")
+            .replace("description: OK", "description: OK\n          summary: OK");
+
+        let diagnostics = diagnose(&document, &ruleset);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "missing-operation-id");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_convert() {
+        let document = r#"
+openapi: 3.0.3
+info: &info
+  title: Convert
+  version: 1.0.0
+x-copy:
+  <<: *info
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: string
+                nullable: true
+"#;
+
+        let json: serde_json::Value =
+            serde_json::from_str(&convert(document, Syntax::Json, None).unwrap()).unwrap();
+        assert_eq!(json["openapi"], "3.0.3");
+        assert_eq!(json["x-copy"]["title"], "Convert");
+        let schema = &json["paths"]["/users"]["get"]["responses"]["200"]["content"]
+            ["application/json"]["schema"];
+        assert_eq!(schema["nullable"], true);
+
+        let upgraded = convert(document, Syntax::Yaml, Some("3.1")).unwrap();
+        let upgraded = OpenAPI::yaml(&upgraded).unwrap();
+        assert_eq!(upgraded.openapi, "3.1.0");
+        assert!(upgraded.to_json().unwrap().contains(r#""null""#));
+
+        let error = convert(document, Syntax::Yaml, Some("3.0")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unsupported target version '3.0', expected 3.1 or 3.2"
+        );
+        let current = document.replace("3.0.3", "3.2.0");
+        let error = convert(&current, Syntax::Yaml, Some("3.1")).unwrap_err();
+        assert_eq!(error.to_string(), "Cannot downgrade OpenAPI 3.2.0 to 3.1");
+
+        let swagger = r#"
+swagger: '2.0'
+info:
+  title: Legacy
+  version: 1.0.0
+paths: {}
+"#;
+        let upgraded = OpenAPI::yaml(&convert(swagger, Syntax::Yaml, None).unwrap()).unwrap();
+        assert_eq!(upgraded.openapi, "3.1.0");
+    }
+}
@@ -0,0 +1,571 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! `openapi-rs replay`: recorded traffic, from HAR files or curl commands,
+//! checked against a document.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use bytes::Bytes;
+use indexmap::IndexMap;
+use openapi_rs::model::parse::OpenAPI;
+use openapi_rs::request::RequestData;
+use openapi_rs::validator;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use url::Url;
+
+/// curl options followed by a value that does not matter for validation
+const IGNORED_CURL_OPTIONS: &[&str] = &[
+    "-o",
+    "--output",
+    "-w",
+    "--write-out",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "--retry",
+    "-x",
+    "--proxy",
+    "--cacert",
+    "--cert",
+    "--key",
+    "-A",
+    "--user-agent",
+    "-e",
+    "--referer",
+    "-r",
+    "--range",
+];
+
+/// A recorded request, with its response when the recording has one
+#[derive(Debug, PartialEq)]
+pub struct Exchange {
+    pub method: String,
+    pub url: Url,
+    /// Keyed by lower-cased name
+    pub headers: HashMap<String, String>,
+    pub body: Option<Vec<u8>>,
+    pub response: Option<RecordedResponse>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// The violations of the recorded requests of one operation
+#[derive(Debug, PartialEq, Serialize)]
+pub struct OperationReport {
+    /// The `operationId`, `METHOD /template` for operations without one, or
+    /// `unmatched` for requests to undocumented paths
+    pub operation: String,
+    pub requests: usize,
+    pub violations: Vec<Violation>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Violation {
+    /// The position of the request in the recording, from 1
+    pub entry: usize,
+    /// The method and URL of the request
+    pub request: String,
+    /// `request`, or `response` for violations of the recorded response
+    pub kind: &'static str,
+    pub message: String,
+}
+
+/// Reads the exchanges of a HAR file, or of curl commands, one per line
+pub fn parse(contents: &str) -> Result<Vec<Exchange>> {
+    if contents.trim_start().starts_with('{') {
+        let har: Value = serde_json::from_str(contents).context("Invalid HAR file")?;
+        return har_exchanges(&har);
+    }
+    curl_exchanges(contents)
+}
+
+/// Validates every exchange against `openapi`, and their responses too when
+/// `responses` is set, grouping violations by operation in order of first
+/// request
+pub fn replay(openapi: &OpenAPI, exchanges: &[Exchange], responses: bool) -> Vec<OperationReport> {
+    let mut reports: IndexMap<String, OperationReport> = IndexMap::new();
+    for (index, exchange) in exchanges.iter().enumerate() {
+        let operation = operation(openapi, exchange);
+        let report = reports
+            .entry(operation.clone())
+            .or_insert_with(|| OperationReport {
+                operation,
+                requests: 0,
+                violations: Vec::new(),
+            });
+        report.requests += 1;
+
+        let path = exchange.url.path();
+        let method = exchange.method.to_ascii_lowercase();
+        let request = format!(
+            "{} {}",
+            exchange.method,
+            &exchange.url[url::Position::BeforePath..]
+        );
+        let violation = |kind, message| Violation {
+            entry: index + 1,
+            request: request.clone(),
+            kind,
+            message,
+        };
+
+        let data = RequestData {
+            path: path.to_string(),
+            method: method.clone(),
+            query_string: exchange.url.query().unwrap_or_default().to_string(),
+            content_type: exchange.headers.get("content-type").cloned(),
+            headers: exchange.headers.clone(),
+            body: exchange.body.clone().map(Bytes::from),
+            body_validated: false,
+        };
+        if let Err(message) = openapi.validator(data) {
+            report.violations.push(violation("request", message));
+        }
+
+        let Some(response) = exchange.response.as_ref().filter(|_| responses) else {
+            continue;
+        };
+        let checked = validator::response_message(
+            path,
+            &method,
+            response.status,
+            response.content_type.as_deref(),
+            &response.body,
+            openapi,
+        );
+        if let Err(error) = checked {
+            let message = format!("Response {}: {error}", response.status);
+            report.violations.push(violation("response", message));
+        }
+    }
+    reports.into_values().collect()
+}
+
+fn operation(openapi: &OpenAPI, exchange: &Exchange) -> String {
+    let Some(route) = openapi.match_path(exchange.url.path()) else {
+        return "unmatched".to_string();
+    };
+    let operation = openapi.paths().get(route.path).and_then(|path_item| {
+        path_item
+            .all_operations()
+            .find(|(method, _)| method.eq_ignore_ascii_case(&exchange.method))
+    });
+    match operation.and_then(|(_, operation)| operation.operation_id.clone()) {
+        Some(operation_id) => operation_id,
+        None => format!("{} {}", exchange.method.to_ascii_uppercase(), route.path),
+    }
+}
+
+fn har_exchanges(har: &Value) -> Result<Vec<Exchange>> {
+    let entries = har
+        .pointer("/log/entries")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("Invalid HAR file: missing log.entries"))?;
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            har_exchange(entry).with_context(|| format!("Invalid HAR entry {}", index + 1))
+        })
+        .collect()
+}
+
+fn har_exchange(entry: &Value) -> Result<Exchange> {
+    let text = |value: &Value, pointer: &str| {
+        value
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    let request = entry
+        .get("request")
+        .ok_or_else(|| anyhow!("missing request"))?;
+    let method = text(request, "/method").ok_or_else(|| anyhow!("missing request method"))?;
+    let url = text(request, "/url").ok_or_else(|| anyhow!("missing request url"))?;
+
+    let response = entry
+        .get("response")
+        .and_then(|response| {
+            // Aborted requests are recorded with status 0
+            let status = response
+                .get("status")?
+                .as_u64()
+                .filter(|status| *status > 0)?;
+            Some((response, status))
+        })
+        .map(|(response, status)| -> Result<RecordedResponse> {
+            let body = match text(response, "/content/text") {
+                Some(body) if text(response, "/content/encoding").as_deref() == Some("base64") => {
+                    base64::engine::general_purpose::STANDARD.decode(body)?
+                }
+                Some(body) => body.into_bytes(),
+                None => Vec::new(),
+            };
+            Ok(RecordedResponse {
+                status: u16::try_from(status)?,
+                content_type: text(response, "/content/mimeType")
+                    .filter(|mime_type| !mime_type.is_empty()),
+                body,
+            })
+        })
+        .transpose()?;
+
+    Ok(Exchange {
+        method,
+        url: Url::parse(&url)?,
+        headers: har_headers(request),
+        body: text(request, "/postData/text").map(String::into_bytes),
+        response,
+    })
+}
+
+/// The headers of a HAR request, without HTTP/2 pseudo-headers
+fn har_headers(request: &Value) -> HashMap<String, String> {
+    request
+        .get("headers")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|header| {
+            let name = header.get("name")?.as_str()?;
+            let value = header.get("value")?.as_str()?;
+            (!name.starts_with(':')).then(|| (name.to_ascii_lowercase(), value.to_string()))
+        })
+        .collect()
+}
+
+fn curl_exchanges(contents: &str) -> Result<Vec<Exchange>> {
+    contents
+        .replace("\\\r\n", " ")
+        .replace("\\\n", " ")
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            curl_exchange(line).with_context(|| format!("Invalid curl command {}", index + 1))
+        })
+        .collect()
+}
+
+fn curl_exchange(command: &str) -> Result<Exchange> {
+    let mut words = split_words(command)?.into_iter();
+    if words.next().as_deref() != Some("curl") {
+        bail!("not a curl command");
+    }
+
+    let mut method = None;
+    let mut url = None;
+    let mut headers = HashMap::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut get = false;
+    while let Some(word) = words.next() {
+        match word.as_str() {
+            "-X" | "--request" => method = Some(value(&mut words, &word)?),
+            "-H" | "--header" => {
+                let header = value(&mut words, &word)?;
+                let (name, value) = header
+                    .split_once(':')
+                    .ok_or_else(|| anyhow!("invalid header '{header}'"))?;
+                headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii"
+            | "--data-urlencode" => {
+                let body = value(&mut words, &word)?;
+                if word != "--data-raw" && body.starts_with('@') {
+                    bail!("request bodies read from files are not supported");
+                }
+                data.push(body);
+            }
+            "--json" => {
+                data.push(value(&mut words, &word)?);
+                headers
+                    .entry("content-type".to_string())
+                    .or_insert_with(|| "application/json".to_string());
+            }
+            "-u" | "--user" => {
+                let credentials =
+                    base64::engine::general_purpose::STANDARD.encode(value(&mut words, &word)?);
+                headers.insert("authorization".to_string(), format!("Basic {credentials}"));
+            }
+            "-b" | "--cookie" => {
+                headers.insert("cookie".to_string(), value(&mut words, &word)?);
+            }
+            "-G" | "--get" => get = true,
+            "--url" => url = Some(value(&mut words, &word)?),
+            "-F" | "--form" => bail!("multipart forms are not supported"),
+            option if IGNORED_CURL_OPTIONS.contains(&option) => {
+                value(&mut words, option)?;
+            }
+            option if option.starts_with("-X") => method = Some(option[2..].to_string()),
+            option if option.starts_with('-') => {}
+            _ if url.is_none() => url = Some(word),
+            _ => bail!("unexpected argument '{word}'"),
+        }
+    }
+
+    let mut url = Url::parse(&url.ok_or_else(|| anyhow!("missing URL"))?)?;
+    let data = (!data.is_empty()).then(|| data.join("&"));
+    let body = match data {
+        Some(data) if get => {
+            let query = match url.query() {
+                Some(query) => format!("{query}&{data}"),
+                None => data,
+            };
+            url.set_query(Some(&query));
+            None
+        }
+        Some(data) => {
+            headers
+                .entry("content-type".to_string())
+                .or_insert_with(|| "application/x-www-form-urlencoded".to_string());
+            Some(data.into_bytes())
+        }
+        None => None,
+    };
+    let method = method.unwrap_or_else(|| if body.is_some() { "POST" } else { "GET" }.to_string());
+
+    Ok(Exchange {
+        method,
+        url,
+        headers,
+        body,
+        response: None,
+    })
+}
+
+fn value(words: &mut impl Iterator<Item = String>, option: &str) -> Result<String> {
+    words
+        .next()
+        .ok_or_else(|| anyhow!("{option} takes a value"))
+}
+
+/// Splits a shell command line into words, honouring single and double
+/// quotes and backslash escapes
+fn split_words(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => bail!("unterminated quote"),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => bail!("unterminated quote"),
+                        },
+                        Some(c) => word.push(c),
+                        None => bail!("unterminated quote"),
+                    }
+                }
+            }
+            '\\' => word.get_or_insert_with(String::new).extend(chars.next()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Replay
+  version: 1.0.0
+paths:
+  /users:
+    get:
+      operationId: listUsers
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: array
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+              properties:
+                name:
+                  type: string
+      responses:
+        '201':
+          description: Created
+"#;
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(r#"curl -H 'a: b c' "x\"y" d\ e"#).unwrap(),
+            ["curl", "-H", "a: b c", "x\"y", "d e"]
+        );
+        assert!(split_words("curl 'open").is_err());
+    }
+
+    #[test]
+    fn test_parse_curl() {
+        let exchanges = parse(
+            r#"
+# Create a user
+curl -X POST https://api.example.com/users \
+  -H 'Content-Type: application/json' \
+  -d '{"name": "Ada"}'
+curl -s 'https://api.example.com/users?limit=2' -u ada:secret
+curl -G https://api.example.com/users --data limit=5
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(exchanges.len(), 3);
+        assert_eq!(exchanges[0].method, "POST");
+        assert_eq!(exchanges[0].url.path(), "/users");
+        assert_eq!(exchanges[0].headers["content-type"], "application/json");
+        assert_eq!(
+            exchanges[0].body.as_deref(),
+            Some(&br#"{"name": "Ada"}"#[..])
+        );
+        assert_eq!(exchanges[1].method, "GET");
+        assert_eq!(
+            exchanges[1].headers["authorization"],
+            "Basic YWRhOnNlY3JldA=="
+        );
+        assert_eq!(exchanges[2].url.query(), Some("limit=5"));
+        assert_eq!(exchanges[2].body, None);
+
+        let error = parse("wget https://api.example.com").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Invalid curl command 1: not a curl command"
+        );
+    }
+
+    #[test]
+    fn test_replay_har() {
+        let har = serde_json::json!({
+            "log": {
+                "entries": [
+                    {
+                        "request": {
+                            "method": "GET",
+                            "url": "https://api.example.com/users?limit=10",
+                            "headers": [{"name": ":authority", "value": "api.example.com"}]
+                        },
+                        "response": {
+                            "status": 200,
+                            "content": {"mimeType": "application/json", "text": "W10=", "encoding": "base64"}
+                        }
+                    },
+                    {
+                        "request": {
+                            "method": "GET",
+                            "url": "https://api.example.com/users?limit=many",
+                            "headers": []
+                        },
+                        "response": {"status": 500, "content": {"mimeType": "", "text": ""}}
+                    },
+                    {
+                        "request": {
+                            "method": "POST",
+                            "url": "https://api.example.com/users",
+                            "headers": [{"name": "Content-Type", "value": "application/json"}],
+                            "postData": {"mimeType": "application/json", "text": "{}"}
+                        },
+                        "response": {"status": 0, "content": {}}
+                    },
+                    {
+                        "request": {"method": "GET", "url": "https://api.example.com/groups", "headers": []}
+                    }
+                ]
+            }
+        });
+        let exchanges = parse(&har.to_string()).unwrap();
+        assert!(exchanges[0].headers.is_empty());
+        assert_eq!(exchanges[0].response.as_ref().unwrap().body, b"[]");
+        assert_eq!(exchanges[2].response, None);
+
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+        let reports = replay(&openapi, &exchanges, false);
+        let summary: Vec<(&str, usize, Vec<usize>)> = reports
+            .iter()
+            .map(|report| {
+                let entries = report
+                    .violations
+                    .iter()
+                    .map(|violation| violation.entry)
+                    .collect();
+                (report.operation.as_str(), report.requests, entries)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("listUsers", 2, vec![2]),
+                ("POST /users", 1, vec![3]),
+                ("unmatched", 1, vec![4]),
+            ]
+        );
+        assert_eq!(reports[0].violations[0].request, "GET /users?limit=many");
+        assert!(reports[0].violations[0]
+            .message
+            .contains("Query validation failed"));
+
+        let reports = replay(&openapi, &exchanges, true);
+        let response = &reports[0].violations[1];
+        assert_eq!((response.entry, response.kind), (2, "response"));
+        assert!(response.message.starts_with("Response 500: "));
+        assert_eq!(reports[0].violations.len(), 2);
+    }
+}
@@ -0,0 +1,534 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A `reqwest` client with one method per operation, generated with
+//! [`Codegen::with_client`](super::Codegen::with_client) next to the types
+//! of `components.schemas`. Requests can be validated against the document
+//! before they are sent, so consumers keep to the contract as well.
+
+use super::{
+    field_name, is_json, operation_name, resolve, schema_type, type_name, unique_name, Emitter,
+};
+use crate::model::parse::{In, OpenAPI, Parameter, PathBase};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// The parts of the generated client that do not depend on the document
+const CLIENT: &str = r#"
+/// A client of the operations of the document
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: reqwest::Url,
+    openapi: Option<std::sync::Arc<openapi_rs::model::parse::OpenAPI>>,
+}
+
+/// Why a call failed
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request does not match the document, and was not sent
+    Invalid(String),
+    /// The server answered with an unsuccessful status, and this body
+    Status(reqwest::StatusCode, String),
+    /// The request could not be sent, or its response decoded
+    Http(reqwest::Error),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Invalid(error) => write!(f, "Invalid request: {error}"),
+            ClientError::Status(status, body) => write!(f, "{status}: {body}"),
+            ClientError::Http(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        ClientError::Http(error)
+    }
+}
+
+impl Client {
+    /// A client of the server at `base_url`, which the paths of the
+    /// document are relative to
+    pub fn new(base_url: reqwest::Url) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url,
+            openapi: None,
+        }
+    }
+
+    /// Send requests through `http`, such as a client with default headers
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Validate every request against `openapi` before sending it, failing
+    /// with [`ClientError::Invalid`] instead of sending an invalid request
+    pub fn with_validation(mut self, openapi: openapi_rs::model::parse::OpenAPI) -> Self {
+        self.openapi = Some(std::sync::Arc::new(openapi));
+        self
+    }
+
+    /// The base URL joined with the path `segments`, percent-encoded
+    fn url(&self, segments: &[&str]) -> reqwest::Url {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().extend(segments);
+        }
+        url
+    }
+
+    /// Validates `request` when enabled, sends it and turns unsuccessful
+    /// statuses into errors
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, ClientError> {
+        let request = request.build()?;
+        if let Some(openapi) = &self.openapi {
+            openapi
+                .validator(self.request_data(&request))
+                .map_err(ClientError::Invalid)?;
+        }
+        let response = self.http.execute(request).await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        Err(ClientError::Status(status, response.text().await.unwrap_or_default()))
+    }
+
+    /// `request` as the validator sees it, with its path relative to the base URL
+    fn request_data(&self, request: &reqwest::Request) -> openapi_rs::request::RequestData {
+        let url = request.url();
+        let base = self.base_url.path().trim_end_matches('/');
+        let headers: std::collections::HashMap<String, String> = request
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        openapi_rs::request::RequestData {
+            path: url.path().strip_prefix(base).unwrap_or(url.path()).to_string(),
+            method: request.method().as_str().to_ascii_lowercase(),
+            query_string: url.query().unwrap_or_default().to_string(),
+            content_type: headers.get("content-type").cloned(),
+            headers,
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|body| body.to_vec().into()),
+            body_validated: false,
+        }
+    }
+}
+"#;
+
+/// Methods with a `reqwest::Method` constant
+const METHODS: &[&str] = &[
+    "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE",
+];
+
+impl Emitter<'_> {
+    /// The `Client` of the operations of `openapi`, emitting the types of
+    /// their inline request and response bodies
+    pub(super) fn client(&mut self, openapi: &OpenAPI) -> String {
+        let mut methods = String::new();
+        let mut names = HashSet::new();
+        for (path, path_item) in &openapi.paths {
+            for (method, operation) in path_item.all_operations() {
+                let name = unique_name(&mut names, operation_name(method, path, operation));
+                let parameters = openapi
+                    .compiled()
+                    .operation(path, method)
+                    .map(|compiled| compiled.parameters.as_slice())
+                    .unwrap_or_default();
+                methods.push('\n');
+                self.operation(&mut methods, &name, method, path, operation, parameters);
+            }
+        }
+
+        let mut client = CLIENT.to_string();
+        let end = client.rfind('}').unwrap_or_default();
+        client.insert_str(end, &methods);
+        client
+    }
+
+    /// The method `name` of the client, sending `method` requests to `path`
+    fn operation(
+        &mut self,
+        item: &mut String,
+        name: &str,
+        method: &str,
+        path: &str,
+        operation: &PathBase,
+        parameters: &[Parameter],
+    ) {
+        let hint = type_name(name);
+        let mut arguments = vec!["&self".to_string()];
+        let mut idents = HashSet::from(["self".to_string()]);
+        let mut ident = |name: &str| unique_name(&mut idents, field_name(name));
+        let mut body = String::new();
+
+        let segments: Vec<String> = path
+            .split('/')
+            .skip(1)
+            .map(|segment| {
+                let mut literal = String::new();
+                let mut captures = Vec::new();
+                for (index, part) in segment.split(['{', '}']).enumerate() {
+                    if index % 2 == 0 {
+                        literal.push_str(part);
+                        continue;
+                    }
+                    let parameter = path_parameter(parameters, part);
+                    let ident = ident(part);
+                    arguments.push(format!("{ident}: {}", parameter_type(parameter, false)));
+                    literal.push_str("{}");
+                    captures.push(ident);
+                }
+                match captures.as_slice() {
+                    [] => format!("{literal:?}"),
+                    [capture] if literal == "{}" => format!("&{capture}.to_string()"),
+                    captures => format!("&format!({literal:?}, {})", captures.join(", ")),
+                }
+            })
+            .collect();
+
+        let method = method.to_ascii_uppercase();
+        let constant = match METHODS.contains(&method.as_str()) {
+            true => format!("reqwest::Method::{method}"),
+            false => format!("reqwest::Method::from_bytes(b{method:?}).expect(\"a valid method\")"),
+        };
+        let _ = writeln!(
+            body,
+            "        let request = self\n            .http\n            .request({constant}, self.url(&[{}]));",
+            segments.join(", ")
+        );
+
+        let query: Vec<&Parameter> = parameters
+            .iter()
+            .filter(|parameter| parameter.r#in == Some(In::Query))
+            .collect();
+        if !query.is_empty() {
+            body.push_str("        let mut query: Vec<(&str, String)> = Vec::new();\n");
+        }
+        for parameter in &query {
+            let name = parameter.name.as_deref().unwrap_or_default();
+            let ident = ident(name);
+            let r#type = parameter_type(Some(parameter), true);
+            let push = format!("query.push(({name:?}, value.to_string()))");
+            let _ = match (parameter.required, r#type.starts_with("&[")) {
+                (true, false) => writeln!(
+                    body,
+                    "        query.push(({name:?}, {ident}.to_string()));"
+                ),
+                (true, true) => writeln!(body, "        for value in {ident} {{\n            {push};\n        }}"),
+                (false, false) => writeln!(
+                    body,
+                    "        if let Some(value) = {ident} {{\n            {push};\n        }}"
+                ),
+                (false, true) => writeln!(
+                    body,
+                    "        for value in {ident}.into_iter().flatten() {{\n            {push};\n        }}"
+                ),
+            };
+            arguments.push(match parameter.required {
+                true => format!("{ident}: {type}"),
+                false => format!("{ident}: Option<{type}>"),
+            });
+        }
+        if !query.is_empty() {
+            body.push_str("        let request = request.query(&query);\n");
+        }
+
+        for parameter in parameters
+            .iter()
+            .filter(|parameter| parameter.r#in == Some(In::Header))
+        {
+            let name = parameter.name.as_deref().unwrap_or_default();
+            let ident = ident(name);
+            let r#type = parameter_type(Some(parameter), false);
+            let _ = match parameter.required {
+                true => writeln!(
+                    body,
+                    "        let request = request.header({name:?}, {ident}.to_string());"
+                ),
+                false => writeln!(
+                    body,
+                    "        let request = match {ident} {{\n            Some(value) => request.header({name:?}, value.to_string()),\n            None => request,\n        }};"
+                ),
+            };
+            arguments.push(match parameter.required {
+                true => format!("{ident}: {type}"),
+                false => format!("{ident}: Option<{type}>"),
+            });
+        }
+
+        if let Some(request) = &operation.request {
+            let json = request
+                .content
+                .iter()
+                .find(|(media_type, _)| is_json(media_type));
+            let (r#type, send) = match json {
+                Some((_, content)) => {
+                    let schema = serde_json::to_value(&content.schema).unwrap_or_default();
+                    let r#type = self.type_of(&schema, &format!("{hint}Request"));
+                    let r#type = match r#type
+                        .strip_prefix("Vec<")
+                        .and_then(|items| items.strip_suffix('>'))
+                    {
+                        Some(items) => format!("&[{items}]"),
+                        None => format!("&{type}"),
+                    };
+                    (r#type, "request.json(body)".to_string())
+                }
+                None => match request.content.keys().next() {
+                    Some(media_type) => (
+                        "Vec<u8>".to_string(),
+                        format!("request.header(\"content-type\", {media_type:?}).body(body)"),
+                    ),
+                    None => (String::new(), String::new()),
+                },
+            };
+            if !r#type.is_empty() {
+                let ident = ident("body");
+                let send = send.replace("(body)", &format!("({ident})"));
+                let _ = match request.required {
+                    true => writeln!(body, "        let request = {send};"),
+                    false => writeln!(
+                        body,
+                        "        let request = match {ident} {{\n            Some({ident}) => {send},\n            None => request,\n        }};"
+                    ),
+                };
+                arguments.push(match request.required {
+                    true => format!("{ident}: {type}"),
+                    false => format!("{ident}: Option<{type}>"),
+                });
+            }
+        }
+
+        let response = operation
+            .responses
+            .iter()
+            .filter(|(status, _)| status.starts_with('2'))
+            .min_by_key(|(status, _)| status.to_ascii_uppercase())
+            .map(|(_, response)| serde_json::to_value(response).unwrap_or_default());
+        let response = response
+            .as_ref()
+            .map(|response| resolve(response, self.document));
+        let content = response
+            .and_then(|response| response.get("content")?.as_object())
+            .and_then(|content| {
+                content
+                    .iter()
+                    .find(|(media_type, _)| is_json(media_type))
+                    .or_else(|| content.iter().next())
+            });
+        let output = match content {
+            Some((media_type, content)) if is_json(media_type) => {
+                let schema = content.get("schema").cloned().unwrap_or_default();
+                let r#type = self.type_of(&schema, &format!("{hint}Response"));
+                body.push_str("        Ok(self.send(request).await?.json().await?)\n");
+                r#type
+            }
+            Some(_) => {
+                body.push_str("        Ok(self.send(request).await?.bytes().await?.to_vec())\n");
+                "Vec<u8>".to_string()
+            }
+            None => {
+                body.push_str("        self.send(request).await?;\n        Ok(())\n");
+                "()".to_string()
+            }
+        };
+
+        let mut doc = String::new();
+        if let Some(summary) = &operation.summary {
+            let _ = writeln!(doc, "    /// {}\n    ///", summary.trim());
+        }
+        let _ = writeln!(doc, "    /// `{method} {path}`");
+        if operation.deprecated {
+            doc.push_str("    #[deprecated]\n");
+        }
+        let _ = writeln!(
+            item,
+            "{doc}    pub async fn {name}({}) -> Result<{output}, ClientError> {{\n{body}    }}",
+            arguments.join(", ")
+        );
+    }
+}
+
+/// The path parameter `name`
+fn path_parameter<'a>(parameters: &'a [Parameter], name: &str) -> Option<&'a Parameter> {
+    parameters.iter().find(|parameter| {
+        parameter.r#in == Some(In::Path) && parameter.name.as_deref() == Some(name)
+    })
+}
+
+/// The argument type of a parameter: its scalar type, `&str` for strings and
+/// anything else, or a slice of them for arrays when `array` is allowed
+fn parameter_type(parameter: Option<&Parameter>, array: bool) -> String {
+    let schema = parameter
+        .and_then(|parameter| serde_json::to_value(&parameter.schema).ok())
+        .unwrap_or_default();
+    let scalar = |schema: &Value| {
+        let format = schema.get("format").and_then(Value::as_str);
+        match schema_type(schema) {
+            Some("integer") if format == Some("int32") => "i32",
+            Some("integer") => "i64",
+            Some("number") if format == Some("float") => "f32",
+            Some("number") => "f64",
+            Some("boolean") => "bool",
+            _ => "&str",
+        }
+    };
+    match (schema_type(&schema), schema.get("items")) {
+        (Some("array"), Some(items)) if array => format!("&[{}]", scalar(items)),
+        _ => scalar(&schema).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::Codegen;
+    use crate::model::parse::OpenAPI;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info: {title: Users, version: 1.0.0}
+servers: [{url: /api}]
+paths:
+  /users:
+    get:
+      operationId: listUsers
+      summary: List users
+      parameters:
+        - {name: limit, in: query, schema: {type: integer, maximum: 10}}
+        - {name: tags, in: query, schema: {type: array, items: {type: string}}}
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema: {type: array, items: {$ref: '#/components/schemas/User'}}
+    post:
+      operationId: createUser
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: {$ref: '#/components/schemas/User'}
+      responses:
+        '201':
+          description: Created
+          content:
+            application/json:
+              schema: {$ref: '#/components/schemas/User'}
+  /users/{id}:
+    parameters:
+      - {name: id, in: path, required: true, schema: {type: integer}}
+    get:
+      operationId: getUser
+      parameters:
+        - {name: X-Trace, in: header, schema: {type: string}}
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [user]
+                properties:
+                  user: {$ref: '#/components/schemas/User'}
+                  seen: {type: boolean}
+    delete:
+      responses: {'204': {description: Deleted}}
+  /files/{name}.json:
+    get:
+      operationId: getFile
+      parameters:
+        - {name: name, in: path, required: true, schema: {type: string}}
+      responses:
+        '200':
+          description: OK
+          content:
+            text/plain: {schema: {type: string}}
+components:
+  schemas:
+    User:
+      type: object
+      required: [name]
+      properties:
+        id: {type: integer}
+        name: {type: string, minLength: 1}
+"#;
+
+    #[test]
+    fn test_client() {
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+        let source = Codegen::new().with_client(true).generate(&openapi);
+
+        assert!(source.contains("pub struct Client {"));
+        assert!(source.contains(
+            "    /// List users
+    ///
+    /// `GET /users`
+    pub async fn list_users(&self, limit: Option<i64>, tags: Option<&[&str]>) -> Result<Vec<User>, ClientError> {
+        let request = self
+            .http
+            .request(reqwest::Method::GET, self.url(&[\"users\"]));
+        let mut query: Vec<(&str, String)> = Vec::new();
+        if let Some(value) = limit {
+            query.push((\"limit\", value.to_string()));
+        }
+        for value in tags.into_iter().flatten() {
+            query.push((\"tags\", value.to_string()));
+        }
+        let request = request.query(&query);
+        Ok(self.send(request).await?.json().await?)
+    }
+"
+        ));
+        assert!(source.contains(
+            "    pub async fn create_user(&self, body: &User) -> Result<User, ClientError> {"
+        ));
+        assert!(source.contains("        let request = request.json(body);\n"));
+        // Inline response schemas get a type of their own
+        assert!(source.contains("pub struct GetUserResponse {"));
+        assert!(source.contains(
+            "    pub async fn get_user(&self, id: i64, x_trace: Option<&str>) -> Result<GetUserResponse, ClientError> {"
+        ));
+        assert!(source.contains(
+            "    pub async fn delete_users_id(&self, id: i64) -> Result<(), ClientError> {"
+        ));
+        assert!(source.contains("self.url(&[\"files\", &format!(\"{}.json\", name)])"));
+        assert!(source.contains(
+            "    pub async fn get_file(&self, name: &str) -> Result<Vec<u8>, ClientError> {"
+        ));
+
+        let types = Codegen::new().generate(&openapi);
+        assert!(!types.contains("Client"));
+    }
+}
@@ -0,0 +1,811 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Rust types generated from the `components.schemas` of a document, so that
+//! the document stays the single source of truth for request and response
+//! types. Meant to be run from a build script:
+//!
+//! ```no_run
+//! // build.rs
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("api.rs");
+//! openapi_rs::codegen::Codegen::new()
+//!     .write("api.yaml", out)
+//!     .unwrap();
+//! ```
+//!
+//! and `include!(concat!(env!("OUT_DIR"), "/api.rs"));` in the crate. The
+//! generated code depends on `serde` and `serde_json`, on `validator` with
+//! [`Codegen::with_validation`], and on `reqwest` with its `json` feature
+//! and `openapi-rs` with its `client` feature with [`Codegen::with_client`].
+
+mod client;
+pub mod scaffold;
+
+use crate::model::parse::{OpenAPI, PathBase};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Words that cannot be identifiers, even raw ones
+const RESERVED: &[&str] = &["self", "Self", "super", "crate", "_"];
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "unsafe", "use",
+    "where", "while", "yield",
+];
+
+/// How Rust types are generated from a document:
+///
+/// - object schemas become structs with a field per property, in the
+///   order of the schema, optional and nullable properties `Option`s, and
+///   `allOf` compositions structs merging every part
+/// - string `enum`s become enums, `oneOf`/`anyOf` untagged enums with one
+///   variant per alternative
+/// - other schemas become type aliases: `String`, `i32`/`i64`, `f32`/`f64`,
+///   `bool`, `Vec`, `HashMap` for `additionalProperties`, and
+///   `serde_json::Value` for anything else
+///
+/// Inline object, enum and union schemas of properties become types of
+/// their own, named after the parent type and the property.
+#[derive(Debug, Clone)]
+pub struct Codegen {
+    derives: Vec<String>,
+    validation: bool,
+    client: bool,
+}
+
+impl Default for Codegen {
+    fn default() -> Self {
+        Self {
+            derives: ["Debug", "Clone", "PartialEq", "Serialize", "Deserialize"]
+                .map(String::from)
+                .to_vec(),
+            validation: false,
+            client: false,
+        }
+    }
+}
+
+impl Codegen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derive `derive` on every generated struct and enum, on top of
+    /// `Debug`, `Clone`, `PartialEq`, `Serialize` and `Deserialize`
+    pub fn with_derive(mut self, derive: impl Into<String>) -> Self {
+        self.derives.push(derive.into());
+        self
+    }
+
+    /// Enable/disable deriving `validator::Validate` on structs, with
+    /// `length`, `range`, `email`, `url` and `nested` attributes from the
+    /// schema constraints
+    pub fn with_validation(mut self, enabled: bool) -> Self {
+        self.validation = enabled;
+        self
+    }
+
+    /// Enable/disable generating a `reqwest` `Client` with an async method
+    /// per operation, named after its `operationId`, taking its path, query
+    /// and header parameters and its body, and returning its lowest 2XX
+    /// response. `Client::with_validation` has requests validated against
+    /// the document before they are sent.
+    pub fn with_client(mut self, enabled: bool) -> Self {
+        self.client = enabled;
+        self
+    }
+
+    /// The Rust source of every type of `components.schemas`, followed by
+    /// the client with [`Codegen::with_client`]
+    pub fn generate(&self, openapi: &OpenAPI) -> String {
+        let document = serde_json::to_value(openapi).unwrap_or_default();
+        let schemas = document
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+
+        let mut emitter = Emitter {
+            codegen: self,
+            document: &document,
+            structs: schemas
+                .iter()
+                .filter(|(_, schema)| is_struct(schema, &document))
+                .map(|(name, _)| type_name(name))
+                .collect(),
+            output: String::new(),
+        };
+        for (name, schema) in &schemas {
+            emitter.item(&type_name(name), schema);
+        }
+        let client = match self.client {
+            true => emitter.client(openapi),
+            false => String::new(),
+        };
+
+        let mut source = match self.client {
+            true => String::from(
+                "// Generated by openapi-rs from `components.schemas` and `paths`; do not edit.\n\n",
+            ),
+            false => String::from(
+                "// Generated by openapi-rs from `components.schemas`; do not edit.\n\n",
+            ),
+        };
+        source.push_str("use serde::{Deserialize, Serialize};\n");
+        if self.validation {
+            source.push_str("use validator::Validate;\n");
+        }
+        source.push_str(&emitter.output);
+        source.push_str(&client);
+        source
+    }
+
+    /// Generate the types of the document at `spec` into `out`, and have
+    /// Cargo rerun the build script when the document changes
+    #[cfg(feature = "fs")]
+    pub fn write(
+        &self,
+        spec: impl AsRef<std::path::Path>,
+        out: impl AsRef<std::path::Path>,
+    ) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let (spec, out) = (spec.as_ref(), out.as_ref());
+        println!("cargo:rerun-if-changed={}", spec.display());
+        let contents = std::fs::read_to_string(spec)
+            .with_context(|| format!("Failed to read '{}'", spec.display()))?;
+        let openapi = OpenAPI::yaml(&contents)
+            .with_context(|| format!("Invalid OpenAPI document '{}'", spec.display()))?;
+        std::fs::write(out, self.generate(&openapi))
+            .with_context(|| format!("Failed to write '{}'", out.display()))
+    }
+}
+
+struct Emitter<'a> {
+    codegen: &'a Codegen,
+    document: &'a Value,
+    /// Names of the generated structs, which `nested` validation applies to
+    structs: HashSet<String>,
+    output: String,
+}
+
+impl Emitter<'_> {
+    /// Emits the type `name` for `schema`
+    fn item(&mut self, name: &str, schema: &Value) {
+        // Inline types are emitted while this one is built, and follow it
+        let start = self.output.len();
+        let mut item = String::from("\n");
+        doc(&mut item, schema, "");
+
+        if let Some(values) = string_enum(schema) {
+            self.derive(&mut item, false);
+            let _ = writeln!(item, "pub enum {name} {{");
+            for value in values {
+                let variant = type_name(value);
+                if variant != value {
+                    let _ = writeln!(item, "    #[serde(rename = {value:?})]");
+                }
+                let _ = writeln!(item, "    {variant},");
+            }
+            item.push_str("}\n");
+        } else if let Some(alternatives) = alternatives(schema) {
+            self.derive(&mut item, false);
+            item.push_str("#[serde(untagged)]\n");
+            let _ = writeln!(item, "pub enum {name} {{");
+            let mut variants = HashSet::new();
+            for (index, alternative) in alternatives.iter().enumerate() {
+                let mut variant = variant_name(alternative);
+                if !variants.insert(variant.clone()) {
+                    variant = format!("{variant}{index}");
+                }
+                let hint = format!("{name}{variant}");
+                let r#type = self.type_of(alternative, &hint);
+                let _ = writeln!(item, "    {variant}({type}),");
+            }
+            item.push_str("}\n");
+        } else if let Some(object) = merged_object(schema, self.document) {
+            self.derive(&mut item, self.codegen.validation);
+            let _ = writeln!(item, "pub struct {name} {{");
+            let required: Vec<&str> = object
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            for (property, schema) in object
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+            {
+                self.field(
+                    &mut item,
+                    name,
+                    property,
+                    schema,
+                    required.contains(&&**property),
+                );
+            }
+            item.push_str("}\n");
+        } else {
+            let r#type = self.type_of(schema, name);
+            let _ = writeln!(item, "pub type {name} = {type};");
+        }
+        self.output.insert_str(start, &item);
+    }
+
+    fn field(
+        &mut self,
+        item: &mut String,
+        parent: &str,
+        name: &str,
+        schema: &Value,
+        required: bool,
+    ) {
+        let ident = field_name(name);
+        doc(item, schema, "    ");
+        if ident.trim_start_matches("r#") != name {
+            let _ = writeln!(item, "    #[serde(rename = {name:?})]");
+        }
+        let optional = !required;
+        if optional {
+            item.push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+        }
+        let mut r#type = self.type_of(schema, &format!("{parent}{}", type_name(name)));
+        if self.codegen.validation {
+            let rules = self.rules(schema, parent, &r#type);
+            if !rules.is_empty() {
+                let _ = writeln!(item, "    #[validate({})]", rules.join(", "));
+            }
+        }
+        if r#type == parent {
+            r#type = format!("Box<{type}>");
+        }
+        if optional || is_nullable(schema) {
+            r#type = format!("Option<{type}>");
+        }
+        let _ = writeln!(item, "    pub {ident}: {type},");
+    }
+
+    /// `validator` rules for a field of type `type` of the struct `parent`
+    fn rules(&self, schema: &Value, parent: &str, r#type: &str) -> Vec<String> {
+        let schema = resolve(schema, self.document);
+        let get = |keyword: &str, integer: bool| {
+            let bound = schema.get(keyword)?.as_f64()?;
+            // Bounds are read as floats, integer fields need integer ones
+            Some(match integer {
+                true => (bound as i64).to_string(),
+                false => format!("{bound:?}"),
+            })
+        };
+        let mut rules = Vec::new();
+
+        let bounds = |min: &str, max: &str, integer: bool| {
+            [("min", min), ("max", max)]
+                .into_iter()
+                .filter_map(|(name, keyword)| Some(format!("{name} = {}", get(keyword, integer)?)))
+                .collect::<Vec<_>>()
+        };
+        let length = match r#type {
+            "String" => bounds("minLength", "maxLength", true),
+            r#type if r#type.starts_with("Vec<") => bounds("minItems", "maxItems", true),
+            _ => Vec::new(),
+        };
+        if !length.is_empty() {
+            rules.push(format!("length({})", length.join(", ")));
+        }
+        let integer = matches!(r#type, "i32" | "i64");
+        let mut range = bounds("minimum", "maximum", integer);
+        range.extend(
+            bounds("exclusiveMinimum", "exclusiveMaximum", integer)
+                .into_iter()
+                .map(|bound| format!("exclusive_{bound}")),
+        );
+        if !range.is_empty() {
+            rules.push(format!("range({})", range.join(", ")));
+        }
+        match schema.get("format").and_then(Value::as_str) {
+            Some("email") => rules.push("email".to_string()),
+            Some("uri" | "url") => rules.push("url".to_string()),
+            _ => {}
+        }
+        let inner = r#type.trim_start_matches("Vec<").trim_end_matches('>');
+        // `Box`ed fields of recursive structs are not validated
+        if self.structs.contains(inner) && r#type != parent {
+            rules.push("nested".to_string());
+        }
+        rules
+    }
+
+    /// The Rust type of `schema`, emitting the types of its inline objects,
+    /// enums and unions under names starting with `hint`
+    fn type_of(&mut self, schema: &Value, hint: &str) -> String {
+        if let Some(name) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix("#/components/schemas/"))
+        {
+            return type_name(name);
+        }
+        // `allOf` wrapping a single schema, to describe a reference
+        if let Some([part]) = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            if schema.get("properties").is_none() {
+                return self.type_of(part, hint);
+            }
+        }
+        if string_enum(schema).is_some()
+            || alternatives(schema).is_some()
+            || merged_object(schema, self.document).is_some()
+        {
+            if is_struct(schema, self.document) {
+                self.structs.insert(hint.to_string());
+            }
+            self.item(hint, schema);
+            return hint.to_string();
+        }
+        self.type_of_value(schema, hint)
+    }
+
+    /// The Rust type of a schema that needs no type of its own
+    fn type_of_value(&mut self, schema: &Value, hint: &str) -> String {
+        let format = schema.get("format").and_then(Value::as_str);
+        match schema_type(schema) {
+            Some("string") => "String".to_string(),
+            Some("integer") if format == Some("int32") => "i32".to_string(),
+            Some("integer") => "i64".to_string(),
+            Some("number") if format == Some("float") => "f32".to_string(),
+            Some("number") => "f64".to_string(),
+            Some("boolean") => "bool".to_string(),
+            Some("array") => {
+                let items = schema.get("items").cloned().unwrap_or_default();
+                format!("Vec<{}>", self.type_of(&items, &format!("{hint}Item")))
+            }
+            Some("object") | None => match schema.get("additionalProperties") {
+                Some(values @ Value::Object(_)) => {
+                    let values = self.type_of(values, &format!("{hint}Value"));
+                    format!("std::collections::HashMap<String, {values}>")
+                }
+                _ => "serde_json::Value".to_string(),
+            },
+            Some(_) => "serde_json::Value".to_string(),
+        }
+    }
+
+    fn derive(&self, item: &mut String, validate: bool) {
+        let mut derives = self.codegen.derives.clone();
+        if validate {
+            derives.push("Validate".to_string());
+        }
+        let _ = writeln!(item, "#[derive({})]", derives.join(", "));
+    }
+}
+
+/// Whether `schema` is generated as a struct
+fn is_struct(schema: &Value, document: &Value) -> bool {
+    string_enum(schema).is_none()
+        && alternatives(schema).is_none()
+        && merged_object(schema, document).is_some()
+}
+
+/// The values of a string `enum`, `null` left out
+fn string_enum(schema: &Value) -> Option<Vec<&str>> {
+    let values = schema.get("enum")?.as_array()?;
+    values
+        .iter()
+        .filter(|value| !value.is_null())
+        .map(Value::as_str)
+        .collect::<Option<Vec<_>>>()
+        .filter(|values| !values.is_empty())
+}
+
+/// The non-null alternatives of a `oneOf` or `anyOf`
+fn alternatives(schema: &Value) -> Option<Vec<&Value>> {
+    let alternatives: Vec<&Value> = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|keyword| schema.get(*keyword)?.as_array())?
+        .iter()
+        .filter(|alternative| schema_type(alternative) != Some("null"))
+        .collect();
+    (alternatives.len() > 1).then_some(alternatives)
+}
+
+/// The properties and required names of an object schema, those of its
+/// `allOf` parts merged in, or `None` when it declares no properties
+fn merged_object(schema: &Value, document: &Value) -> Option<Map<String, Value>> {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    collect_object(schema, document, &mut properties, &mut required, 0);
+    if properties.is_empty() {
+        return None;
+    }
+    let mut object = Map::new();
+    object.insert("properties".to_string(), Value::Object(properties));
+    object.insert("required".to_string(), Value::Array(required));
+    Some(object)
+}
+
+fn collect_object(
+    schema: &Value,
+    document: &Value,
+    properties: &mut Map<String, Value>,
+    required: &mut Vec<Value>,
+    depth: usize,
+) {
+    if depth > 0 {
+        if depth > 16 {
+            return;
+        }
+        if let Some(target) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| document.pointer(pointer))
+        {
+            return collect_object(target, document, properties, required, depth + 1);
+        }
+    }
+    for part in schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        collect_object(part, document, properties, required, depth + 1);
+    }
+    if let Some(own) = schema.get("properties").and_then(Value::as_object) {
+        properties.extend(
+            own.iter()
+                .map(|(name, schema)| (name.clone(), schema.clone())),
+        );
+    }
+    for name in schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if !required.contains(name) {
+            required.push(name.clone());
+        }
+    }
+}
+
+/// Follows a local `$ref`, returning `schema` itself otherwise
+fn resolve<'a>(schema: &'a Value, document: &'a Value) -> &'a Value {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix('#'))
+        .and_then(|pointer| document.pointer(pointer))
+        .unwrap_or(schema)
+}
+
+/// The declared type, the first non-null one of a list
+fn schema_type(schema: &Value) -> Option<&str> {
+    match schema.get("type")? {
+        Value::String(r#type) => Some(r#type),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|r#type| *r#type != "null"),
+        _ => None,
+    }
+}
+
+fn is_nullable(schema: &Value) -> bool {
+    let null_type = match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().any(|r#type| r#type == "null"),
+        _ => false,
+    };
+    let null_alternative = ["oneOf", "anyOf"]
+        .iter()
+        .filter_map(|keyword| schema.get(*keyword)?.as_array())
+        .flatten()
+        .any(|alternative| schema_type(alternative) == Some("null"));
+    null_type || null_alternative
+}
+
+/// The name of the variant holding an alternative of a union
+fn variant_name(schema: &Value) -> String {
+    if let Some(name) = schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.rsplit('/').next())
+    {
+        return type_name(name);
+    }
+    if let Some(title) = schema.get("title").and_then(Value::as_str) {
+        return type_name(title);
+    }
+    match schema_type(schema) {
+        Some(r#type) => type_name(r#type),
+        None => "Object".to_string(),
+    }
+}
+
+/// `description` as doc comment lines indented by `indent`
+fn doc(item: &mut String, schema: &Value, indent: &str) {
+    let Some(description) = schema.get("description").and_then(Value::as_str) else {
+        return;
+    };
+    for line in description.trim().lines() {
+        let _ = writeln!(
+            item,
+            "{indent}///{}{}",
+            if line.is_empty() { "" } else { " " },
+            line
+        );
+    }
+}
+
+/// Whether a `content` key is JSON, such as `application/problem+json`
+fn is_json(media_type: &str) -> bool {
+    let media_type = media_type.split(';').next().unwrap_or_default().trim();
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+/// The snake_case name of the function of an operation, after its
+/// `operationId` or else its method and path
+fn operation_name(method: &str, path: &str, operation: &PathBase) -> String {
+    match &operation.operation_id {
+        Some(operation_id) => field_name(operation_id),
+        None => field_name(&format!("{method} {path}")),
+    }
+}
+
+/// `name`, or `name_2`, `name_3`... when `names` already has it
+fn unique_name(names: &mut HashSet<String>, name: String) -> String {
+    let mut unique = name.clone();
+    for suffix in 2.. {
+        if names.insert(unique.clone()) {
+            break;
+        }
+        unique = format!("{name}_{suffix}");
+    }
+    unique
+}
+
+/// `name` in UpperCamelCase, such as `UserProfile` for `user-profile`
+fn type_name(name: &str) -> String {
+    let mut pascal = String::new();
+    for word in words(name) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            pascal.extend(first.to_uppercase());
+            pascal.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+    match pascal.chars().next() {
+        None => "Empty".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("V{pascal}"),
+        Some(_) => pascal,
+    }
+}
+
+/// `name` in snake_case, such as `first_name` for `firstName`, as a raw
+/// identifier when it is a keyword
+fn field_name(name: &str) -> String {
+    let snake = words(name)
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    match snake.as_str() {
+        "" => "field".to_string(),
+        snake if snake.starts_with(|c: char| c.is_ascii_digit()) => format!("_{snake}"),
+        snake if RESERVED.contains(&snake) => format!("{snake}_"),
+        snake if KEYWORDS.contains(&snake) => format!("r#{snake}"),
+        snake => snake.to_string(),
+    }
+}
+
+/// The words of an identifier, split at non-alphanumerics and at case
+/// changes: `userID-v2` gives `user`, `ID` and `v2`
+fn words(name: &str) -> impl Iterator<Item = &str> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|part| {
+            let mut words = Vec::new();
+            let mut start = 0;
+            let chars: Vec<(usize, char)> = part.char_indices().collect();
+            for window in chars.windows(2) {
+                let ((_, previous), (index, current)) = (window[0], window[1]);
+                let next_lower = part[index..]
+                    .chars()
+                    .nth(1)
+                    .is_some_and(|c| c.is_ascii_lowercase());
+                let boundary = current.is_ascii_uppercase()
+                    && (previous.is_ascii_lowercase()
+                        || previous.is_ascii_digit()
+                        || (previous.is_ascii_uppercase() && next_lower));
+                if boundary {
+                    words.push(&part[start..index]);
+                    start = index;
+                }
+            }
+            words.push(&part[start..]);
+            words
+        })
+        .filter(|word| !word.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Codegen
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    User:
+      description: A registered user
+      type: object
+      required: [id, role]
+      properties:
+        id:
+          type: integer
+          minimum: 1
+        firstName:
+          type: string
+          maxLength: 40
+        role:
+          $ref: '#/components/schemas/Role'
+        type:
+          type: [string, "null"]
+        address:
+          type: object
+          properties:
+            city:
+              type: string
+        manager:
+          $ref: '#/components/schemas/User'
+    Role:
+      type: string
+      enum: [admin, read-only]
+    Pet:
+      oneOf:
+        - $ref: '#/components/schemas/Cat'
+        - type: string
+    Cat:
+      allOf:
+        - $ref: '#/components/schemas/Animal'
+        - properties:
+            meows:
+              type: boolean
+    Animal:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+          format: email
+    Ids:
+      type: array
+      items:
+        type: integer
+        format: int32
+    Labels:
+      type: object
+      additionalProperties:
+        type: string
+"#;
+
+    #[test]
+    fn test_generate() {
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+        let source = Codegen::new().generate(&openapi);
+
+        let user = r#"
+/// A registered user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: i64,
+    #[serde(rename = "firstName")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    pub role: Role,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<UserAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manager: Option<Box<User>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserAddress {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Role {
+    #[serde(rename = "admin")]
+    Admin,
+    #[serde(rename = "read-only")]
+    ReadOnly,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Pet {
+    Cat(Cat),
+    String(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cat {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meows: Option<bool>,
+}
+"#;
+        assert!(source.contains(user), "{source}");
+        assert!(source.contains("pub type Ids = Vec<i32>;\n"));
+        assert!(source.contains("pub type Labels = std::collections::HashMap<String, String>;\n"));
+        assert!(!source.contains("Validate"));
+    }
+
+    #[test]
+    fn test_generate_validation() {
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+        let source = Codegen::new()
+            .with_derive("Eq")
+            .with_validation(true)
+            .generate(&openapi);
+
+        assert!(source.contains("use validator::Validate;\n"));
+        assert!(source.contains(
+            "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq, Validate)]\npub struct User {"
+        ));
+        assert!(source.contains("    #[validate(range(min = 1))]\n    pub id: i64,"));
+        assert!(source.contains("    #[validate(length(max = 40))]\n    pub first_name"));
+        assert!(source.contains("    #[validate(nested)]\n    pub address: Option<UserAddress>,"));
+        assert!(source.contains("    #[validate(email)]\n    pub name: String,"));
+        assert!(source.contains("    pub manager: Option<Box<User>>,"));
+        assert!(!source.contains("#[validate(nested)]\n    pub manager"));
+        // Enums do not derive `Validate`
+        assert!(source.contains(
+            "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq)]\npub enum Role"
+        ));
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(type_name("user_profile"), "UserProfile");
+        assert_eq!(type_name("HTTPServer"), "HttpServer");
+        assert_eq!(type_name("in-transit"), "InTransit");
+        assert_eq!(type_name("2fa"), "V2fa");
+        assert_eq!(type_name(""), "Empty");
+        assert_eq!(field_name("firstName"), "first_name");
+        assert_eq!(field_name("userID"), "user_id");
+        assert_eq!(field_name("x-rate-limit"), "x_rate_limit");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("self"), "self_");
+        assert_eq!(field_name("1st"), "_1st");
+    }
+}
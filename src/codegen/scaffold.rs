@@ -0,0 +1,263 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! An axum router skeleton generated from the `paths` of a document, to
+//! bootstrap a service from its contract: one handler stub per operation,
+//! answering `501 Not Implemented`, with the validation layer in front.
+
+use crate::codegen::{field_name, is_json, operation_name, schema_type, unique_name};
+use crate::model::parse::{In, OpenAPI};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+
+/// Methods with an axum routing function of the same name
+const METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "trace",
+];
+
+/// The Rust source of a module with a `router(openapi: OpenAPI) -> Router`
+/// function routing every operation of `openapi` to a handler stub named
+/// after its `operationId`, or else after its method and path.
+///
+/// Handlers extract the path parameters with their declared scalar types,
+/// the query as a `HashMap` and a JSON body as `serde_json::Value`. Routes
+/// use the `:name` captures of axum 0.7, so path parameters must fill whole
+/// segments; operations on methods axum has no router for, such as `QUERY`,
+/// are left out with a comment.
+pub fn axum_router(openapi: &OpenAPI) -> String {
+    let mut routes = String::new();
+    let mut handlers = String::new();
+    let mut names = HashSet::new();
+    let mut routing = BTreeSet::new();
+    let mut extractors = BTreeSet::new();
+
+    for (path, path_item) in &openapi.paths {
+        let mut methods = Vec::new();
+        for (method, operation) in path_item.all_operations() {
+            let method = method.to_ascii_lowercase();
+            if !METHODS.contains(&method.as_str()) {
+                let method = method.to_ascii_uppercase();
+                let _ = writeln!(
+                    routes,
+                    "        // {method} {path}: axum has no method router for it"
+                );
+                continue;
+            }
+
+            let name = unique_name(&mut names, operation_name(&method, path, operation));
+
+            let parameters = openapi
+                .compiled()
+                .operation(path, &method)
+                .map(|compiled| compiled.parameters.as_slice())
+                .unwrap_or_default();
+            let mut arguments = Vec::new();
+            let captures: Vec<(String, &str)> = path
+                .split('/')
+                .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+                .map(|capture| {
+                    let schema = parameters
+                        .iter()
+                        .find(|p| p.r#in == Some(In::Path) && p.name.as_deref() == Some(capture))
+                        .and_then(|p| serde_json::to_value(&p.schema).ok())
+                        .unwrap_or_default();
+                    let r#type = match schema_type(&schema) {
+                        Some("integer") => "i64",
+                        Some("number") => "f64",
+                        Some("boolean") => "bool",
+                        _ => "String",
+                    };
+                    (
+                        format!("_{}", field_name(capture).trim_start_matches("r#")),
+                        r#type,
+                    )
+                })
+                .collect();
+            match captures.as_slice() {
+                [] => {}
+                [(ident, r#type)] => arguments.push(format!("Path({ident}): Path<{type}>")),
+                captures => {
+                    let (idents, types): (Vec<_>, Vec<_>) = captures.iter().cloned().unzip();
+                    arguments.push(format!(
+                        "Path(({})): Path<({})>",
+                        idents.join(", "),
+                        types.join(", ")
+                    ));
+                }
+            }
+            if !captures.is_empty() {
+                extractors.insert("Path");
+            }
+            if parameters.iter().any(|p| p.r#in == Some(In::Query)) {
+                extractors.insert("Query");
+                arguments.push("Query(_query): Query<HashMap<String, String>>".to_string());
+            }
+            let json_body = operation
+                .request
+                .as_ref()
+                .is_some_and(|request| request.content.keys().any(|key| is_json(key)));
+            if json_body {
+                extractors.insert("Json");
+                arguments.push("Json(_body): Json<serde_json::Value>".to_string());
+            }
+
+            handlers.push('\n');
+            if let Some(summary) = &operation.summary {
+                let _ = writeln!(handlers, "/// {}\n///", summary.trim());
+            }
+            let _ = writeln!(handlers, "/// `{} {path}`", method.to_ascii_uppercase());
+            let _ = writeln!(
+                handlers,
+                "pub async fn {name}({}) -> impl IntoResponse {{",
+                arguments.join(", ")
+            );
+            handlers.push_str("    StatusCode::NOT_IMPLEMENTED\n}\n");
+
+            // Further methods of a route chain on the first one's router
+            if methods.is_empty() {
+                routing.insert(method.clone());
+            }
+            methods.push(format!("{method}({name})"));
+        }
+        if !methods.is_empty() {
+            let route = path
+                .split('/')
+                .map(
+                    |segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                        Some(capture) => format!(":{capture}"),
+                        None => segment.to_string(),
+                    },
+                )
+                .collect::<Vec<_>>()
+                .join("/");
+            let _ = writeln!(routes, "        .route({route:?}, {})", methods.join("."));
+        }
+    }
+
+    let mut source = String::from(
+        "// Generated by openapi-rs from `paths`: a router skeleton whose handlers answer\n\
+         // 501 Not Implemented until they are filled in.\n\n",
+    );
+    if !extractors.is_empty() {
+        let extractors: Vec<&str> = extractors.into_iter().collect();
+        let _ = writeln!(source, "use axum::extract::{{{}}};", extractors.join(", "));
+    }
+    source.push_str("use axum::http::StatusCode;\nuse axum::response::IntoResponse;\n");
+    if !routing.is_empty() {
+        let routing: Vec<String> = routing.into_iter().collect();
+        let _ = writeln!(source, "use axum::routing::{{{}}};", routing.join(", "));
+    }
+    source.push_str(
+        "use axum::Router;\n\
+         use openapi_rs::model::parse::OpenAPI;\n\
+         use openapi_rs::request::axum::OpenApiValidationLayer;\n",
+    );
+    if handlers.contains("HashMap") {
+        source.push_str("use std::collections::HashMap;\n");
+    }
+    source.push_str(
+        "\n/// The routes of the document, with requests validated against it\n\
+         pub fn router(openapi: OpenAPI) -> Router {\n    Router::new()\n",
+    );
+    source.push_str(&routes);
+    source.push_str("        .layer(OpenApiValidationLayer::new(openapi))\n}\n");
+    source.push_str(&handlers);
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info: {title: Users, version: 1.0.0}
+paths:
+  /users:
+    get:
+      operationId: listUsers
+      summary: List users
+      parameters:
+        - {name: limit, in: query, schema: {type: integer}}
+      responses: {'200': {description: OK}}
+    post:
+      operationId: createUser
+      requestBody:
+        content:
+          application/json:
+            schema: {type: object}
+      responses: {'201': {description: Created}}
+  /users/{id}:
+    parameters:
+      - {name: id, in: path, required: true, schema: {type: integer}}
+    get:
+      operationId: getUser
+      responses: {'200': {description: OK}}
+    delete:
+      responses: {'204': {description: Deleted}}
+  /orgs/{org}/members/{name}:
+    put:
+      parameters:
+        - {name: org, in: path, required: true, schema: {type: string}}
+        - {name: name, in: path, required: true, schema: {type: string}}
+      responses: {'204': {description: Added}}
+    query:
+      responses: {'200': {description: OK}}
+  /teams:
+    get:
+      operationId: getUser
+      responses: {'200': {description: OK}}
+"#;
+
+    #[test]
+    fn test_axum_router() {
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+        let source = axum_router(&openapi);
+
+        assert!(source.contains(
+            "use axum::extract::{Json, Path, Query};\n\
+             use axum::http::StatusCode;\n\
+             use axum::response::IntoResponse;\n\
+             use axum::routing::{get, put};\n"
+        ));
+        assert!(source.contains(
+            r#"    Router::new()
+        .route("/users", get(list_users).post(create_user))
+        .route("/users/:id", get(get_user).delete(delete_users_id))
+        // QUERY /orgs/{org}/members/{name}: axum has no method router for it
+        .route("/orgs/:org/members/:name", put(put_orgs_org_members_name))
+        .route("/teams", get(get_user_2))
+        .layer(OpenApiValidationLayer::new(openapi))
+"#
+        ));
+        assert!(source.contains(
+            "/// List users\n\
+             ///\n\
+             /// `GET /users`\n\
+             pub async fn list_users(Query(_query): Query<HashMap<String, String>>) -> impl IntoResponse {\n    \
+                 StatusCode::NOT_IMPLEMENTED\n\
+             }\n"
+        ));
+        assert!(source.contains(
+            "pub async fn create_user(Json(_body): Json<serde_json::Value>) -> impl IntoResponse"
+        ));
+        assert!(source.contains("pub async fn get_user(Path(_id): Path<i64>) -> impl IntoResponse"));
+        assert!(source.contains(
+            "pub async fn put_orgs_org_members_name(Path((_org, _name)): Path<(String, String)>)"
+        ));
+    }
+}
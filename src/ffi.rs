@@ -0,0 +1,372 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! C ABI for embedding the validator in gateways and other languages.
+//!
+//! Documents and requests go in and results come out as NUL-terminated JSON
+//! strings; see `include/openapi_rs.h`. Build the library with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or
+//! `staticlib`).
+
+use crate::model::parse::OpenAPI;
+use crate::request::validation::RequestData;
+use crate::validator::{MethodNotAllowed, Unauthorized, ValidateRequest};
+use base64::{engine::general_purpose, Engine};
+use bytes::Bytes;
+use serde::Deserialize;
+use serde_json::json;
+use std::any::Any;
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// A request as passed to [`openapi_validate_request`]
+#[derive(Debug, Deserialize)]
+struct Request {
+    method: String,
+    /// The request path, optionally followed by `?` and the query string
+    path: String,
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    /// A UTF-8 body
+    #[serde(default)]
+    body: Option<String>,
+    /// A body of any bytes, base64 encoded, in place of `body`
+    #[serde(default)]
+    body_base64: Option<String>,
+}
+
+/// Parses the YAML or JSON document `spec`. Returns null when it cannot be
+/// parsed, storing the reason as `{"error": "..."}` in `*error` unless
+/// `error` is null; free it with [`openapi_string_free`].
+///
+/// # Safety
+///
+/// `spec` must be a valid NUL-terminated string, and `error` null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openapi_load(
+    spec: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut OpenAPI {
+    let loaded = str_arg(spec).and_then(|spec| {
+        panic::catch_unwind(|| OpenAPI::yaml(spec))
+            .map_err(|payload| {
+                format!("Loading the document panicked: {}", panic_message(payload))
+            })?
+            .map_err(|e| format!("Invalid document: {e}"))
+    });
+    match loaded {
+        Ok(open_api) => Box::into_raw(Box::new(open_api)),
+        Err(message) => {
+            if !error.is_null() {
+                *error = into_c_string(json!({ "error": message }).to_string());
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Validates the JSON request `request`, e.g.
+/// `{"method": "POST", "path": "/users?notify=true", "headers": {...}, "body": "{...}"}`,
+/// against `open_api`; bodies that are not UTF-8 go base64 encoded in
+/// `"body_base64"` instead. Returns `{"valid": true}`, or `{"valid": false,
+/// "status": ..., "error": "..."}` with the status a server would answer
+/// with, plus `"allow"` for `405` and `"request_id"` from the
+/// `X-Request-Id` or `traceparent` header. A panic while validating is
+/// answered with status `500`. Free the result with
+/// [`openapi_string_free`].
+///
+/// # Safety
+///
+/// `open_api` must come from [`openapi_load`] and not be freed yet, and
+/// `request` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn openapi_validate_request(
+    open_api: *const OpenAPI,
+    request: *const c_char,
+) -> *mut c_char {
+    let result = match (open_api.as_ref(), str_arg(request)) {
+        (None, _) => rejection(400, "No document".to_string()),
+        (_, Err(message)) => rejection(400, message),
+        (Some(open_api), Ok(request)) => guarded(|| validate(open_api, request)),
+    };
+    into_c_string(result.to_string())
+}
+
+/// Frees a document returned by [`openapi_load`]
+///
+/// # Safety
+///
+/// `open_api` must be null or come from [`openapi_load`], and not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn openapi_free(open_api: *mut OpenAPI) {
+    if !open_api.is_null() {
+        drop(Box::from_raw(open_api));
+    }
+}
+
+/// Frees a string returned by this library
+///
+/// # Safety
+///
+/// `string` must be null or come from this library, and not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn openapi_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Runs `f`, answering a panic with a `500` rejection rather than unwinding
+/// into the host
+fn guarded(f: impl FnOnce() -> serde_json::Value) -> serde_json::Value {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = format!("Validation panicked: {}", panic_message(payload));
+        rejection(500, message)
+    })
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or_else(
+            || "unknown panic".to_string(),
+            |message| message.to_string(),
+        ),
+    }
+}
+
+fn validate(open_api: &OpenAPI, request: &str) -> serde_json::Value {
+    let request: Request = match serde_json::from_str(request) {
+        Ok(request) => request,
+        Err(e) => return rejection(400, format!("Invalid request JSON: {e}")),
+    };
+    let (path, query_string) = match (request.path.split_once('?'), request.query) {
+        (Some((path, query)), None) => (path.to_string(), query.to_string()),
+        (_, query) => (request.path.clone(), query.unwrap_or_default()),
+    };
+    let body = match (request.body, request.body_base64) {
+        (Some(_), Some(_)) => {
+            return rejection(
+                400,
+                "Only one of body and body_base64 may be set".to_string(),
+            )
+        }
+        (body, None) => body.map(Bytes::from),
+        (None, Some(encoded)) => match general_purpose::STANDARD.decode(encoded) {
+            Ok(decoded) => Some(Bytes::from(decoded)),
+            Err(e) => return rejection(400, format!("body_base64 is not valid base64: {e}")),
+        },
+    };
+    let headers: HashMap<String, String> = request
+        .headers
+        .into_iter()
+        .map(|(name, value)| (name.to_lowercase(), value))
+        .collect();
+
+    let request_data = RequestData {
+        path,
+        method: request.method.to_lowercase(),
+        query_string,
+        content_type: headers.get("content-type").cloned(),
+        headers,
+        body: body.filter(|body| !body.is_empty()),
+        body_validated: false,
+    };
+
+    let request_id = request_data.context().request_id;
+    let mut result = check(open_api, request_data);
+    if let Some(request_id) = request_id.filter(|_| result["valid"] == false) {
+        result["request_id"] = request_id.into();
+    }
+    result
+}
+
+fn check(open_api: &OpenAPI, request_data: RequestData) -> serde_json::Value {
+    let Err(e) = open_api.validate(request_data) else {
+        return json!({ "valid": true });
+    };
+    if let Some(not_allowed) = e.downcast_ref::<MethodNotAllowed>() {
+        let mut result = rejection(405, e.to_string());
+        result["allow"] = not_allowed.allow_header().into();
+        result
+    } else if e.is::<Unauthorized>() {
+        rejection(401, e.to_string())
+    } else {
+        rejection(400, e.to_string())
+    }
+}
+
+fn rejection(status: u16, error: String) -> serde_json::Value {
+    json!({ "valid": false, "status": status, "error": error })
+}
+
+unsafe fn str_arg<'a>(string: *const c_char) -> Result<&'a str, String> {
+    if string.is_null() {
+        return Err("Null string argument".to_string());
+    }
+    CStr::from_ptr(string)
+        .to_str()
+        .map_err(|e| format!("String argument is not UTF-8: {e}"))
+}
+
+fn into_c_string(string: String) -> *mut c_char {
+    // JSON escapes control characters, so there are no interior NULs
+    CString::new(string).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Users API
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      parameters:
+        - name: notify
+          in: query
+          schema:
+            type: boolean
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+      responses:
+        '201':
+          description: Created
+"#;
+
+    fn validate(open_api: *const OpenAPI, request: serde_json::Value) -> serde_json::Value {
+        let request = CString::new(request.to_string()).unwrap();
+        unsafe {
+            let result = openapi_validate_request(open_api, request.as_ptr());
+            let value = serde_json::from_str(CStr::from_ptr(result).to_str().unwrap()).unwrap();
+            openapi_string_free(result);
+            value
+        }
+    }
+
+    #[test]
+    fn test_load_and_validate() {
+        let spec = CString::new(SPEC).unwrap();
+        let open_api = unsafe { openapi_load(spec.as_ptr(), ptr::null_mut()) };
+        assert!(!open_api.is_null());
+
+        let valid = validate(
+            open_api,
+            json!({
+                "method": "POST",
+                "path": "/users?notify=true",
+                "headers": {"Content-Type": "application/json"},
+                "body": r#"{"name": "alice"}"#
+            }),
+        );
+        assert_eq!(valid, json!({"valid": true}));
+
+        let invalid = validate(
+            open_api,
+            json!({
+                "method": "POST",
+                "path": "/users",
+                "headers": {"content-type": "application/json", "X-Request-Id": "req-42"},
+                "body": "{}"
+            }),
+        );
+        assert_eq!(invalid["status"], 400);
+        assert_eq!(invalid["request_id"], "req-42");
+
+        let not_allowed = validate(open_api, json!({"method": "GET", "path": "/users"}));
+        assert_eq!(not_allowed["status"], 405);
+        assert_eq!(not_allowed["allow"], "POST");
+
+        let malformed = validate(open_api, json!({"path": "/users"}));
+        assert_eq!(malformed["valid"], false);
+
+        unsafe { openapi_free(open_api) };
+    }
+
+    #[test]
+    fn test_binary_bodies() {
+        let spec = CString::new(SPEC).unwrap();
+        let open_api = unsafe { openapi_load(spec.as_ptr(), ptr::null_mut()) };
+        let request = |body: serde_json::Value| {
+            let mut request = json!({
+                "method": "POST",
+                "path": "/users",
+                "headers": {"content-type": "application/json"}
+            });
+            request
+                .as_object_mut()
+                .unwrap()
+                .extend(body.as_object().unwrap().clone());
+            validate(open_api, request)
+        };
+
+        let encoded = general_purpose::STANDARD.encode(r#"{"name": "alice"}"#);
+        assert_eq!(
+            request(json!({"body_base64": encoded})),
+            json!({"valid": true})
+        );
+
+        let not_json = general_purpose::STANDARD.encode([0xff, 0xfe, 0x00]);
+        assert_eq!(request(json!({"body_base64": not_json}))["status"], 400);
+
+        let invalid = request(json!({"body_base64": "%%%"}));
+        assert!(invalid["error"]
+            .as_str()
+            .unwrap()
+            .contains("not valid base64"));
+
+        let both = request(json!({"body": "{}", "body_base64": encoded}));
+        assert_eq!(both["status"], 400);
+
+        unsafe { openapi_free(open_api) };
+    }
+
+    #[test]
+    fn test_panics_become_rejections() {
+        let result = guarded(|| panic!("plan missing"));
+        assert_eq!(result["valid"], false);
+        assert_eq!(result["status"], 500);
+        assert_eq!(result["error"], "Validation panicked: plan missing");
+    }
+
+    #[test]
+    fn test_load_reports_errors() {
+        let spec = CString::new("openapi: [").unwrap();
+        let mut error = ptr::null_mut();
+        let open_api = unsafe { openapi_load(spec.as_ptr(), &mut error) };
+        assert!(open_api.is_null());
+
+        let message = unsafe { CStr::from_ptr(error) }.to_str().unwrap();
+        assert!(message.contains("Invalid document"));
+        unsafe { openapi_string_free(error) };
+    }
+}
@@ -15,7 +15,12 @@
  * limitations under the License.
  */
 
+pub mod codegen;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod model;
 pub mod observability;
 pub mod request;
+#[cfg(feature = "test-util")]
+pub mod test;
 pub mod validator;
@@ -0,0 +1,312 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Bundling: inlines the `$ref`s of a document that point into other
+//! documents, producing a single self-contained one.
+
+use crate::model::lint::unescape;
+use crate::model::parse::expand_merge_keys;
+use anyhow::{anyhow, bail, Context, Result};
+use percent_encoding::percent_decode_str;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use url::Url;
+
+/// Loads the document at `root` and replaces every `$ref` reaching another
+/// document, such as `schemas.yaml#/User` or
+/// `https://example.com/common.yaml#/components/parameters/Page`, with the
+/// value it points to. References local to the root document are kept.
+///
+/// `load` returns the contents of a document, without fragment, in YAML or
+/// JSON; it decides which URL schemes are supported. Fails on circular
+/// references through other documents, which cannot be inlined.
+pub fn bundle(root: &Url, load: impl FnMut(&Url) -> Result<String>) -> Result<Value> {
+    let mut root = root.clone();
+    root.set_fragment(None);
+    let mut bundler = Bundler {
+        root: root.clone(),
+        load,
+        documents: HashMap::new(),
+        resolving: Vec::new(),
+    };
+    let mut document = bundler.document(&root)?;
+    bundler.resolve(&mut document, &root)?;
+    Ok(document)
+}
+
+/// Reads a `file:` URL for [`bundle`], rejecting other schemes
+#[cfg(feature = "fs")]
+pub fn load_file(url: &Url) -> Result<String> {
+    let path = url
+        .to_file_path()
+        .map_err(|()| anyhow!("Unsupported reference '{url}'"))?;
+    std::fs::read_to_string(&path).with_context(|| format!("Failed to read '{}'", path.display()))
+}
+
+struct Bundler<L> {
+    root: Url,
+    load: L,
+    /// Parsed documents by URL
+    documents: HashMap<Url, Value>,
+    /// The references being inlined, innermost last
+    resolving: Vec<Url>,
+}
+
+impl<L: FnMut(&Url) -> Result<String>> Bundler<L> {
+    fn document(&mut self, url: &Url) -> Result<Value> {
+        if let Some(document) = self.documents.get(url) {
+            return Ok(document.clone());
+        }
+        let contents = (self.load)(url)?;
+        let mut document: Value =
+            serde_yaml::from_str(&contents).with_context(|| format!("Invalid document '{url}'"))?;
+        expand_merge_keys(&mut document).with_context(|| format!("Invalid document '{url}'"))?;
+        self.documents.insert(url.clone(), document.clone());
+        Ok(document)
+    }
+
+    /// Inlines the references of `value`, a part of the document at `base`
+    fn resolve(&mut self, value: &mut Value, base: &Url) -> Result<()> {
+        match value {
+            Value::Mapping(mapping) => {
+                if let Some(Value::String(reference)) = mapping.get("$ref") {
+                    let mut target = base
+                        .join(reference)
+                        .with_context(|| format!("Invalid reference '{reference}' in '{base}'"))?;
+                    if target.fragment() == Some("") {
+                        target.set_fragment(None);
+                    }
+                    let mut url = target.clone();
+                    url.set_fragment(None);
+                    // Local references stay valid as long as they are in the
+                    // root document
+                    if url != self.root || *base != self.root {
+                        let mut inlined = self.target(&target, &url)?;
+                        // Keys next to the `$ref`, such as `description`,
+                        // override those of its target
+                        mapping.remove("$ref");
+                        if let Value::Mapping(inlined) = &mut inlined {
+                            for (key, value) in std::mem::take(mapping) {
+                                inlined.insert(key, value);
+                            }
+                        }
+                        *value = inlined;
+                        return Ok(());
+                    }
+                }
+                for value in mapping.values_mut() {
+                    self.resolve(value, base)?;
+                }
+            }
+            Value::Sequence(sequence) => {
+                for value in sequence {
+                    self.resolve(value, base)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// The value `target` points to in the document at `url`, with its own
+    /// references inlined
+    fn target(&mut self, target: &Url, url: &Url) -> Result<Value> {
+        if self.resolving.contains(target) {
+            bail!("Circular reference '{target}'");
+        }
+        let document = self.document(url)?;
+        let fragment = percent_decode_str(target.fragment().unwrap_or_default()).decode_utf8()?;
+        let mut value = pointer(&document, &fragment)
+            .ok_or_else(|| anyhow!("Reference '{target}' points to nothing"))?
+            .clone();
+
+        self.resolving.push(target.clone());
+        let resolved = self.resolve(&mut value, url);
+        self.resolving.pop();
+        resolved?;
+        Ok(value)
+    }
+}
+
+/// The value at the JSON pointer `pointer`, such as `/components/schemas/User`
+fn pointer<'a>(document: &'a Value, pointer: &str) -> Option<&'a Value> {
+    if pointer.is_empty() {
+        return Some(document);
+    }
+    pointer
+        .strip_prefix('/')?
+        .split('/')
+        .try_fold(document, |value, token| {
+            let token = unescape(token);
+            match value {
+                Value::Mapping(mapping) => mapping.get(token.as_str()),
+                Value::Sequence(sequence) => sequence.get(token.parse::<usize>().ok()?),
+                _ => None,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn documents(documents: &[(&str, &str)]) -> impl FnMut(&Url) -> Result<String> {
+        let documents: HashMap<String, String> = documents
+            .iter()
+            .map(|(url, contents)| (url.to_string(), contents.to_string()))
+            .collect();
+        move |url| {
+            documents
+                .get(url.as_str())
+                .cloned()
+                .ok_or_else(|| anyhow!("No document '{url}'"))
+        }
+    }
+
+    fn root() -> Url {
+        Url::parse("https://example.com/api/root.yaml").unwrap()
+    }
+
+    #[test]
+    fn test_bundle_inlines_external_references() {
+        let root_document = r#"
+openapi: 3.1.0
+paths:
+  /users:
+    get:
+      parameters:
+        - $ref: 'https://example.com/common.yaml#/parameters/Page'
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Users'
+components:
+  schemas:
+    Users:
+      type: array
+      items:
+        $ref: 'schemas/user.yaml'
+        description: A user
+"#;
+        let user = r#"
+type: object
+properties:
+  address:
+    $ref: '#/$defs/Address'
+$defs:
+  Address:
+    type: string
+"#;
+        let common = r#"
+parameters:
+  Page:
+    name: page
+    in: query
+    schema:
+      type: integer
+"#;
+        let bundled = bundle(
+            &root(),
+            documents(&[
+                ("https://example.com/api/root.yaml", root_document),
+                ("https://example.com/api/schemas/user.yaml", user),
+                ("https://example.com/common.yaml", common),
+            ]),
+        )
+        .unwrap();
+
+        let expected: Value = serde_yaml::from_str(
+            r#"
+openapi: 3.1.0
+paths:
+  /users:
+    get:
+      parameters:
+        - name: page
+          in: query
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Users'
+components:
+  schemas:
+    Users:
+      type: array
+      items:
+        type: object
+        properties:
+          address:
+            type: string
+        $defs:
+          Address:
+            type: string
+        description: A user
+"#,
+        )
+        .unwrap();
+        assert_eq!(bundled, expected);
+    }
+
+    #[test]
+    fn test_bundle_errors() {
+        let error = bundle(
+            &root(),
+            documents(&[
+                ("https://example.com/api/root.yaml", "a: {$ref: 'a.yaml'}"),
+                (
+                    "https://example.com/api/a.yaml",
+                    "$ref: '#/b'\nb: {$ref: '#'}",
+                ),
+            ]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Circular reference 'https://example.com/api/a.yaml'"
+        );
+
+        let error = bundle(
+            &root(),
+            documents(&[
+                (
+                    "https://example.com/api/root.yaml",
+                    "a: {$ref: 'a.yaml#/missing'}",
+                ),
+                ("https://example.com/api/a.yaml", "b: 1"),
+            ]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Reference 'https://example.com/api/a.yaml#/missing' points to nothing"
+        );
+
+        let error = bundle(&root(), documents(&[])).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No document 'https://example.com/api/root.yaml'"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Drift between the routes an application registers and the operations of
+//! its document, checked at startup: documented operations nothing handles
+//! and handlers the document does not describe.
+
+use crate::model::parse::{OpenAPI, ServerObject};
+use serde::Serialize;
+use std::fmt;
+
+/// Stands for a trailing catch-all segment, such as axum's `*rest`
+const WILDCARD: &str = "*";
+
+/// A method and path, of a registered route or of an operation
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Route {
+    /// The upper-case method, such as `GET`
+    pub method: String,
+    pub path: String,
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.path)
+    }
+}
+
+/// How the routes of an application and the operations of its document
+/// differ, see [`OpenAPI::route_drift`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RouteDrift {
+    /// Operations of the document no route handles, by path template
+    pub unhandled: Vec<Route>,
+    /// Routes matching no operation of the document, as registered
+    pub undocumented: Vec<Route>,
+}
+
+impl RouteDrift {
+    pub fn is_empty(&self) -> bool {
+        self.unhandled.is_empty() && self.undocumented.is_empty()
+    }
+
+    /// Logs a warning for each unhandled operation and undocumented route
+    pub fn log(&self) {
+        for route in &self.unhandled {
+            log::warn!("No route handles the documented operation '{route}'");
+        }
+        for route in &self.undocumented {
+            log::warn!("Route '{route}' is not described by the OpenAPI document");
+        }
+    }
+}
+
+impl OpenAPI {
+    /// Compares the `(method, path)` routes of an application with the
+    /// operations of the document.
+    ///
+    /// Route paths may use the parameter syntax of axum (`/users/:id`,
+    /// `/files/*path`) or of actix-web and axum 0.8 (`/users/{id}`,
+    /// `/users/{id:\d+}`, `/files/{path}*`): parameter names do not need to
+    /// match the document's, and a trailing catch-all handles every
+    /// operation below it. Routes may carry the base path of a server, such
+    /// as `/v2/users` under `servers: [{url: https://api.example.com/v2}]`.
+    pub fn route_drift<M, P>(&self, routes: impl IntoIterator<Item = (M, P)>) -> RouteDrift
+    where
+        M: AsRef<str>,
+        P: AsRef<str>,
+    {
+        let routes: Vec<Route> = routes
+            .into_iter()
+            .map(|(method, path)| Route {
+                method: method.as_ref().to_ascii_uppercase(),
+                path: path.as_ref().to_string(),
+            })
+            .collect();
+        let operations: Vec<Route> = self
+            .operations()
+            .map(|(path, method, _)| Route {
+                method: method.to_ascii_uppercase(),
+                path: path.to_string(),
+            })
+            .collect();
+
+        let bases: Vec<&str> = self
+            .servers
+            .iter()
+            .filter_map(ServerObject::base_path)
+            .collect();
+        let routed: Vec<Vec<Vec<String>>> = routes
+            .iter()
+            .map(|route| {
+                let mut paths = vec![segments(&route.path)];
+                paths.extend(bases.iter().filter_map(|base| {
+                    let rest = route.path.strip_prefix(base)?;
+                    (rest.is_empty() || rest.starts_with('/')).then(|| segments(rest))
+                }));
+                paths
+            })
+            .collect();
+        let declared: Vec<Vec<String>> = operations
+            .iter()
+            .map(|operation| segments(&operation.path))
+            .collect();
+        let handles = |route: usize, operation: usize| {
+            routes[route].method == operations[operation].method
+                && routed[route]
+                    .iter()
+                    .any(|path| matches(path, &declared[operation]))
+        };
+
+        RouteDrift {
+            unhandled: (0..operations.len())
+                .filter(|&operation| !(0..routes.len()).any(|route| handles(route, operation)))
+                .map(|operation| operations[operation].clone())
+                .collect(),
+            undocumented: (0..routes.len())
+                .filter(|&route| !(0..operations.len()).any(|operation| handles(route, operation)))
+                .map(|route| routes[route].clone())
+                .collect(),
+        }
+    }
+}
+
+/// The segments of a path, each parameter replaced with `{}` whatever its
+/// syntax, and a trailing catch-all with [`WILDCARD`]
+fn segments(path: &str) -> Vec<String> {
+    path.trim_end_matches('/')
+        .split('/')
+        .skip(1)
+        .map(|segment| {
+            if segment.starts_with(':') {
+                return "{}".to_string();
+            }
+            if segment.starts_with('*') || segment.starts_with("{*") || segment.ends_with("}*") {
+                return WILDCARD.to_string();
+            }
+            // Braces nest in the patterns of actix-web parameters, `{id:\d{3}}`
+            let mut normalized = String::new();
+            let mut depth = 0usize;
+            for c in segment.chars() {
+                match c {
+                    '{' => {
+                        if depth == 0 {
+                            normalized.push_str("{}");
+                        }
+                        depth += 1;
+                    }
+                    '}' => depth = depth.saturating_sub(1),
+                    c if depth == 0 => normalized.push(c),
+                    _ => {}
+                }
+            }
+            normalized
+        })
+        .collect()
+}
+
+/// Whether the route segments `route` match the template segments
+/// `template`, a catch-all matching one or more trailing segments
+fn matches(route: &[String], template: &[String]) -> bool {
+    match route.split_last() {
+        Some((last, prefix)) if last == WILDCARD => {
+            template.len() > prefix.len() && template.starts_with(prefix)
+        }
+        _ => route == template,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info: {title: Users, version: 1.0.0}
+servers: [{url: https://api.example.com/v2}]
+paths:
+  /users:
+    get:
+      responses: {'200': {description: OK}}
+    post:
+      responses: {'201': {description: Created}}
+  /users/{id}:
+    get:
+      responses: {'200': {description: OK}}
+    delete:
+      responses: {'204': {description: Deleted}}
+  /files/{name}.json:
+    get:
+      responses: {'200': {description: OK}}
+  /assets/{folder}/{file}:
+    get:
+      responses: {'200': {description: OK}}
+"#;
+
+    fn route(method: &str, path: &str) -> Route {
+        Route {
+            method: method.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_route_drift() {
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+        let drift = openapi.route_drift([
+            ("get", "/users"),
+            ("GET", "/users/:user_id"),
+            ("DELETE", "/v2/users/{id:\\d{1,9}}"),
+            ("GET", "/files/{name}.json"),
+            ("GET", "/assets/*path"),
+            ("PUT", "/users/{id}"),
+            ("GET", "/health"),
+        ]);
+
+        assert_eq!(
+            drift,
+            RouteDrift {
+                unhandled: vec![route("POST", "/users")],
+                undocumented: vec![route("PUT", "/users/{id}"), route("GET", "/health")],
+            }
+        );
+        assert!(!drift.is_empty());
+        assert_eq!(drift.unhandled[0].to_string(), "POST /users");
+    }
+
+    #[test]
+    fn test_route_drift_in_sync() {
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+        let drift = openapi.route_drift([
+            ("GET", "/v2/users/"),
+            ("POST", "/users"),
+            ("GET", "/users/{id}"),
+            ("DELETE", "/users/{id}"),
+            ("GET", "/files/{file}.json"),
+            ("GET", "/assets/{*rest}"),
+        ]);
+        assert!(drift.is_empty(), "{drift:?}");
+
+        // A catch-all needs at least one segment to match
+        let drift = openapi.route_drift([("GET", "/users/{id}/*rest")]);
+        assert!(drift.unhandled.contains(&route("GET", "/users/{id}")));
+        assert_eq!(drift.undocumented, vec![route("GET", "/users/{id}/*rest")]);
+    }
+}
@@ -0,0 +1,93 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Typed access to `x-` specification extensions.
+
+use crate::model::parse::{
+    ComponentSchemaBase, ContactObject, ExternalDocumentation, Header, InfoObject, LicenseObject,
+    Link, OpenAPI, Parameter, PathBase, PathItem, Properties, Response, Schema, TagObject,
+};
+use anyhow::Context;
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+
+/// The `x-` extensions of an object. Other unrecognized keys kept alongside
+/// them are not exposed.
+#[derive(Debug, Clone, Copy)]
+pub struct Extensions<'a>(&'a IndexMap<String, Value>);
+
+impl<'a> Extensions<'a> {
+    /// Deserializes the extension `name` (e.g. `x-rate-limit`) into `T`,
+    /// returning `None` when the object does not declare it
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> anyhow::Result<Option<T>> {
+        let Some(value) = self.raw(name) else {
+            return Ok(None);
+        };
+        serde_yaml::from_value(value.clone())
+            .map(Some)
+            .with_context(|| format!("Invalid extension '{name}'"))
+    }
+
+    /// The raw value of the extension `name`
+    pub fn raw(&self, name: &str) -> Option<&'a Value> {
+        self.0.get(name).filter(|_| name.starts_with("x-"))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.raw(name).is_some()
+    }
+
+    /// Every extension in document order
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &'a Value)> {
+        self.0
+            .iter()
+            .filter(|(name, _)| name.starts_with("x-"))
+            .map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+macro_rules! impl_extensions {
+    ($($model:ty),* $(,)?) => {
+        $(
+            impl $model {
+                /// The `x-` extensions declared on this object
+                pub fn extensions(&self) -> Extensions<'_> {
+                    Extensions(&self.extra)
+                }
+            }
+        )*
+    };
+}
+
+impl_extensions!(
+    OpenAPI,
+    InfoObject,
+    ContactObject,
+    LicenseObject,
+    TagObject,
+    ExternalDocumentation,
+    PathItem,
+    PathBase,
+    Parameter,
+    Response,
+    Header,
+    Link,
+    Schema,
+    ComponentSchemaBase,
+    Properties,
+);
@@ -0,0 +1,737 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Random requests valid against a document, for property tests of the
+//! handlers behind it: every value is drawn from the declared schemas,
+//! honouring their types, formats, enums and bounds.
+
+use crate::model::parse::{BaseContent, In, OpenAPI, Parameter, Style};
+use base64::engine::general_purpose;
+use base64::Engine;
+use indexmap::IndexMap;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde_json::{Map, Value};
+
+/// Nesting beyond which optional properties and array items are left out,
+/// so that recursive schemas end
+const MAX_DEPTH: usize = 6;
+
+/// Extra items or characters drawn beyond the lower bound when a schema
+/// sets no upper bound
+const SPREAD: u64 = 4;
+
+const ALPHANUMERIC: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// A request for an operation, see [`OpenAPI::generate_request`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedRequest {
+    pub method: String,
+    /// The path template with its parameters filled in and percent-encoded
+    pub path: String,
+    /// Decoded query pairs, repeated for exploded arrays
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    /// The media type of `body`, absent when the operation takes no JSON body
+    pub content_type: Option<String>,
+    pub body: Option<Value>,
+}
+
+impl GeneratedRequest {
+    /// The path followed by the encoded query, if any
+    pub fn uri(&self) -> String {
+        if self.query.is_empty() {
+            return self.path.clone();
+        }
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&self.query)
+            .finish();
+        format!("{}?{}", self.path, query)
+    }
+}
+
+/// Draws values matching JSON Schemas from a seeded pseudo-random sequence:
+/// the same seed always yields the same values.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    state: u64,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// A value matching `schema`, a JSON Schema of `document` whose `$ref`s
+    /// point into `document`. `readOnly` properties are left out, as
+    /// requests may not carry them; `pattern`s are only honoured through
+    /// the schema's `enum`, `const` or examples.
+    pub fn value(&mut self, schema: &Value, document: &Value) -> Value {
+        self.value_at(schema, document, 0)
+    }
+
+    fn value_at(&mut self, schema: &Value, document: &Value, depth: usize) -> Value {
+        let Value::Object(schema) = schema else {
+            return self.string(&Map::new());
+        };
+
+        if let Some(target) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+        {
+            return match document.pointer(target) {
+                Some(target) => self.value_at(target, document, depth + 1),
+                None => Value::Null,
+            };
+        }
+        if let Some(value) = schema.get("const") {
+            return value.clone();
+        }
+        if let Some(Value::Array(values)) = schema.get("enum") {
+            if let Some(value) = self.pick(values) {
+                return value.clone();
+            }
+        }
+        if schema.contains_key("pattern") {
+            let examples = schema
+                .get("examples")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .chain(schema.get("example"))
+                .collect::<Vec<_>>();
+            if let Some(example) = self.pick(&examples) {
+                return (*example).clone();
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("allOf") {
+            let mut merged = Map::new();
+            for schema in schemas {
+                match self.value_at(schema, document, depth + 1) {
+                    Value::Object(part) => merged.extend(part),
+                    other if merged.is_empty() => return other,
+                    _ => {}
+                }
+            }
+            if let Value::Object(own) = self.object(schema, document, depth) {
+                merged.extend(own);
+            }
+            return Value::Object(merged);
+        }
+        if let Some(Value::Array(schemas)) = ["oneOf", "anyOf"]
+            .iter()
+            .find_map(|keyword| schema.get(*keyword))
+        {
+            if let Some(schema) = self.pick(schemas) {
+                return self.value_at(schema, document, depth + 1);
+            }
+        }
+
+        let types: Vec<&str> = match schema.get("type") {
+            Some(Value::String(r#type)) => vec![r#type.as_str()],
+            Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+            _ if schema.contains_key("properties") => vec!["object"],
+            _ if schema.contains_key("items") => vec!["array"],
+            _ => vec!["string"],
+        };
+        if schema.get("nullable") == Some(&Value::Bool(true)) && self.below(4) == 0 {
+            return Value::Null;
+        }
+        match self.pick(&types).copied() {
+            Some("object") => self.object(schema, document, depth),
+            Some("array") => self.array(schema, document, depth),
+            Some("integer") => self.integer(schema),
+            Some("number") => self.number(schema),
+            Some("boolean") => Value::Bool(self.below(2) == 0),
+            Some("null") => Value::Null,
+            _ => self.string(schema),
+        }
+    }
+
+    /// Every required property and, short of [`MAX_DEPTH`], about half of
+    /// the optional ones, leaving out `readOnly` ones
+    fn object(&mut self, schema: &Map<String, Value>, document: &Value, depth: usize) -> Value {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let mut object = Map::new();
+        for (name, property) in schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            if property.get("readOnly") == Some(&Value::Bool(true)) {
+                continue;
+            }
+            let optional = !required.contains(&name.as_str());
+            if optional && (depth >= MAX_DEPTH || self.below(2) == 0) {
+                continue;
+            }
+            object.insert(name.clone(), self.value_at(property, document, depth + 1));
+        }
+        Value::Object(object)
+    }
+
+    fn array(&mut self, schema: &Map<String, Value>, document: &Value, depth: usize) -> Value {
+        let min_items = schema.get("minItems").and_then(Value::as_u64).unwrap_or(0);
+        let max_items = match depth >= MAX_DEPTH {
+            true => min_items,
+            false => self.upper_bound(schema, "maxItems", min_items),
+        };
+        let count = self.between(min_items, max_items);
+        let unique = schema.get("uniqueItems") == Some(&Value::Bool(true));
+        let items = schema.get("items").cloned().unwrap_or_default();
+
+        let mut values: Vec<Value> = Vec::new();
+        // Bounded retries, as an item schema may not allow enough distinct values
+        for _ in 0..count * 8 {
+            if values.len() as u64 >= count {
+                break;
+            }
+            let value = self.value_at(&items, document, depth + 1);
+            if !unique || !values.contains(&value) {
+                values.push(value);
+            }
+        }
+        Value::Array(values)
+    }
+
+    fn integer(&mut self, schema: &Map<String, Value>) -> Value {
+        let (minimum, maximum) = bounds(schema, 1.0);
+        let minimum = minimum.ceil() as i64;
+        let maximum = maximum.floor() as i64;
+        let value = match schema
+            .get("multipleOf")
+            .and_then(Value::as_i64)
+            .filter(|multiple| *multiple > 0)
+        {
+            Some(multiple) => {
+                let lowest = minimum.div_euclid(multiple) + i64::from(minimum % multiple != 0);
+                let highest = maximum.div_euclid(multiple);
+                self.between_signed(lowest, highest.max(lowest)) * multiple
+            }
+            None => self.between_signed(minimum, maximum.max(minimum)),
+        };
+        Value::from(value)
+    }
+
+    fn number(&mut self, schema: &Map<String, Value>) -> Value {
+        let (minimum, maximum) = bounds(schema, 1e-9);
+        if let Some(multiple) = schema
+            .get("multipleOf")
+            .and_then(Value::as_f64)
+            .filter(|multiple| *multiple > 0.0)
+        {
+            let lowest = (minimum / multiple).ceil() as i64;
+            let highest = ((maximum / multiple).floor() as i64).max(lowest);
+            let value = self.between_signed(lowest, highest) as f64 * multiple;
+            // Drop the binary noise of the product, e.g. `0.30000000000000004`
+            let decimals = multiple
+                .to_string()
+                .split_once('.')
+                .map_or(0, |(_, d)| d.len());
+            return Value::from(
+                format!("{value:.decimals$}")
+                    .parse::<f64>()
+                    .unwrap_or(value),
+            );
+        }
+        let unit = (self.next() >> 11) as f64 / (1u64 << 53) as f64;
+        Value::from(minimum + (maximum - minimum) * unit)
+    }
+
+    fn string(&mut self, schema: &Map<String, Value>) -> Value {
+        let value = match schema.get("format").and_then(Value::as_str) {
+            Some("date-time") => format!("{}T{}Z", self.date(), self.time()),
+            Some("date") => self.date(),
+            Some("time") => self.time(),
+            Some("duration") => format!("P{}D", self.between(1, 365)),
+            Some("email") => format!("{}@example.com", self.word(1, 12).to_lowercase()),
+            Some("uuid") => {
+                let bytes = (u128::from(self.next()) << 64 | u128::from(self.next())).to_be_bytes();
+                uuid::Builder::from_random_bytes(bytes)
+                    .into_uuid()
+                    .to_string()
+            }
+            Some("uri" | "url") => format!("https://example.com/{}", self.word(0, 12)),
+            Some("uri-reference") => format!("/{}", self.word(0, 12)),
+            Some("hostname") => format!("{}.example.com", self.word(1, 12).to_lowercase()),
+            Some("ipv4") => {
+                let octets = self.next().to_be_bytes();
+                std::net::Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3]).to_string()
+            }
+            Some("ipv6") => {
+                let bits = u128::from(self.next()) << 64 | u128::from(self.next());
+                std::net::Ipv6Addr::from(bits).to_string()
+            }
+            Some("byte" | "base64") => {
+                general_purpose::STANDARD.encode(self.word(0, 16).as_bytes())
+            }
+            Some("base64url") => {
+                general_purpose::URL_SAFE_NO_PAD.encode(self.word(0, 16).as_bytes())
+            }
+            _ => {
+                let min_length = schema.get("minLength").and_then(Value::as_u64).unwrap_or(0);
+                let max_length = self.upper_bound(schema, "maxLength", min_length);
+                self.word(min_length, max_length)
+            }
+        };
+        Value::String(value)
+    }
+
+    fn date(&mut self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}",
+            self.between(1970, 2099),
+            self.between(1, 12),
+            self.between(1, 28)
+        )
+    }
+
+    fn time(&mut self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}",
+            self.below(24),
+            self.below(60),
+            self.below(60)
+        )
+    }
+
+    /// Alphanumerics, between `min_length` and `max_length` of them
+    fn word(&mut self, min_length: u64, max_length: u64) -> String {
+        let length = self.between(min_length, max_length);
+        (0..length)
+            .map(|_| ALPHANUMERIC[self.below(ALPHANUMERIC.len() as u64) as usize] as char)
+            .collect()
+    }
+
+    /// The `keyword` bound of `schema`, or else [`SPREAD`] above `minimum`
+    fn upper_bound(&self, schema: &Map<String, Value>, keyword: &str, minimum: u64) -> u64 {
+        schema
+            .get(keyword)
+            .and_then(Value::as_u64)
+            .unwrap_or(minimum + SPREAD)
+            .max(minimum)
+    }
+
+    fn pick<'a, T>(&mut self, values: &'a [T]) -> Option<&'a T> {
+        values.get(self.below(values.len() as u64) as usize)
+    }
+
+    fn between(&mut self, minimum: u64, maximum: u64) -> u64 {
+        minimum + self.below((maximum - minimum).saturating_add(1))
+    }
+
+    fn between_signed(&mut self, minimum: i64, maximum: i64) -> i64 {
+        let span = maximum.abs_diff(minimum).saturating_add(1);
+        minimum.wrapping_add(self.below(span) as i64)
+    }
+
+    /// A value below `bound`, or `0` when `bound` is `0`
+    fn below(&mut self, bound: u64) -> u64 {
+        match bound {
+            0 => 0,
+            bound => self.next() % bound,
+        }
+    }
+
+    /// SplitMix64
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// The inclusive range allowed by the numeric bounds of `schema`, exclusive
+/// ones moved inwards by `step`; a missing side lies 1000 from the other
+fn bounds(schema: &Map<String, Value>, step: f64) -> (f64, f64) {
+    let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+    let minimum = bound("minimum").or(bound("exclusiveMinimum").map(|minimum| minimum + step));
+    let maximum = bound("maximum").or(bound("exclusiveMaximum").map(|maximum| maximum - step));
+    match (minimum, maximum) {
+        (Some(minimum), Some(maximum)) => (minimum, maximum.max(minimum)),
+        (Some(minimum), None) => (minimum, minimum + 1000.0),
+        (None, Some(maximum)) => (maximum - 1000.0, maximum),
+        (None, None) => (-1000.0, 1000.0),
+    }
+}
+
+impl OpenAPI {
+    /// A request for the operation `method` of the path template `path`
+    /// drawn from `seed`: path parameters filled in, required query and
+    /// header parameters along with about half of the optional ones, and a
+    /// JSON body when the operation takes one. Parameters are generated
+    /// from their `schema`; those described by `content` are left out.
+    pub fn generate_request(
+        &self,
+        path: &str,
+        method: &str,
+        seed: u64,
+    ) -> Option<GeneratedRequest> {
+        let (_, operation) = self
+            .paths
+            .get(path)?
+            .all_operations()
+            .find(|(known, _)| known.eq_ignore_ascii_case(method))?;
+        let parameters = self
+            .compiled()
+            .operation(path, method)
+            .map(|compiled| compiled.parameters.as_slice())
+            .unwrap_or_default();
+        let document = serde_json::to_value(self).unwrap_or_default();
+        let mut generator = Generator::new(seed);
+
+        let mut request = GeneratedRequest {
+            method: method.to_ascii_uppercase(),
+            path: path.to_string(),
+            query: Vec::new(),
+            headers: Vec::new(),
+            content_type: None,
+            body: None,
+        };
+        for parameter in parameters {
+            let (Some(name), Some(location), Some(schema)) =
+                (&parameter.name, &parameter.r#in, &parameter.schema)
+            else {
+                continue;
+            };
+            if *location != In::Path && !parameter.required && generator.below(2) == 0 {
+                continue;
+            }
+            let mut schema = serde_json::to_value(schema).unwrap_or_default();
+            if let (Value::Object(schema), Some(values)) = (&mut schema, &parameter.r#enum) {
+                schema.insert(
+                    "enum".to_string(),
+                    serde_json::to_value(values).unwrap_or_default(),
+                );
+            }
+            if *location == In::Path {
+                // Path segments cannot be empty
+                if let Value::Object(schema) = &mut schema {
+                    let min_length = schema.entry("minLength").or_insert(Value::from(1));
+                    if min_length.as_u64() == Some(0) {
+                        *min_length = Value::from(1);
+                    }
+                }
+            }
+            let value = generator.value(&schema, &document);
+
+            match location {
+                In::Path => {
+                    let segment =
+                        utf8_percent_encode(&scalar(&value), NON_ALPHANUMERIC).to_string();
+                    request.path = request.path.replace(&format!("{{{name}}}"), &segment);
+                }
+                In::Query => request.query.extend(query_pairs(name, &value, parameter)),
+                In::Header => request.headers.push((name.clone(), scalar(&value))),
+                In::QueryString | In::Cookie => {}
+            }
+        }
+
+        if let Some((content_type, content)) = operation
+            .request
+            .as_ref()
+            .and_then(|body| json_content(&body.content))
+        {
+            let schema = serde_json::to_value(&content.schema).unwrap_or_default();
+            request.content_type = Some(content_type.to_string());
+            request.body = Some(generator.value(&schema, &document));
+        }
+        Some(request)
+    }
+}
+
+/// A [`proptest`] strategy over the requests of the operation `method` of
+/// the path template `path`, or `None` when there is no such operation.
+/// Shrinking works on the seed, not on the request's values.
+#[cfg(feature = "proptest")]
+pub fn strategy(
+    openapi: std::sync::Arc<OpenAPI>,
+    path: &str,
+    method: &str,
+) -> Option<impl proptest::strategy::Strategy<Value = GeneratedRequest>> {
+    use proptest::strategy::Strategy;
+
+    openapi.generate_request(path, method, 0)?;
+    let (path, method) = (path.to_string(), method.to_string());
+    Some(proptest::arbitrary::any::<u64>().prop_map(move |seed| {
+        openapi
+            .generate_request(&path, &method, seed)
+            .expect("operation checked when building the strategy")
+    }))
+}
+
+/// The JSON content of a request body
+fn json_content(content: &IndexMap<String, BaseContent>) -> Option<(&str, &BaseContent)> {
+    content
+        .iter()
+        .find(|(media_type, _)| {
+            let media_type = media_type.split(';').next().unwrap_or_default().trim();
+            media_type == "application/json" || media_type.ends_with("+json")
+        })
+        .map(|(media_type, content)| (media_type.as_str(), content))
+}
+
+/// The query pairs carrying `value` per the parameter's `style` and `explode`
+fn query_pairs(name: &str, value: &Value, parameter: &Parameter) -> Vec<(String, String)> {
+    match value {
+        Value::Array(items) => {
+            let delimiter = match (&parameter.style, parameter.explode) {
+                (Some(Style::PipeDelimited), _) => "|",
+                (Some(Style::SpaceDelimited), _) => " ",
+                (None | Some(Style::Form), Some(false)) => ",",
+                _ => {
+                    return items
+                        .iter()
+                        .map(|item| (name.to_string(), scalar(item)))
+                        .collect()
+                }
+            };
+            let joined = items.iter().map(scalar).collect::<Vec<_>>().join(delimiter);
+            vec![(name.to_string(), joined)]
+        }
+        Value::Object(properties) if parameter.style == Some(Style::DeepObject) => properties
+            .iter()
+            .map(|(property, value)| (format!("{name}[{property}]"), scalar(value)))
+            .collect(),
+        Value::Object(properties) => properties
+            .iter()
+            .map(|(property, value)| (property.clone(), scalar(value)))
+            .collect(),
+        value => vec![(name.to_string(), scalar(value))],
+    }
+}
+
+/// A scalar as it appears in a path, query or header
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Generated
+  version: 1.0.0
+paths:
+  /users/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+        schema:
+          type: string
+          format: email
+    put:
+      parameters:
+        - name: limit
+          in: query
+          required: true
+          schema:
+            type: integer
+            minimum: 1
+            maximum: 50
+        - name: tags
+          in: query
+          schema:
+            type: array
+            items:
+              type: string
+              enum: [a, b, c]
+        - name: ids
+          in: query
+          explode: false
+          schema:
+            type: array
+            minItems: 1
+            items:
+              type: integer
+        - name: X-Trace
+          in: header
+          required: true
+          schema:
+            type: string
+            format: uuid
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/User'
+components:
+  schemas:
+    User:
+      type: object
+      required: [name, age, roles]
+      properties:
+        id:
+          type: integer
+          readOnly: true
+        name:
+          type: string
+          minLength: 2
+          maxLength: 5
+        age:
+          type: integer
+          exclusiveMinimum: 17
+          maximum: 130
+        score:
+          type: number
+          minimum: 0
+          maximum: 1
+          multipleOf: 0.1
+        born:
+          type: string
+          format: date-time
+        roles:
+          type: array
+          minItems: 1
+          maxItems: 3
+          uniqueItems: true
+          items:
+            type: string
+            enum: [admin, member, guest]
+        manager:
+          $ref: '#/components/schemas/User'
+"#;
+
+    #[test]
+    fn test_generator_respects_bounds() {
+        let document = json!({});
+        for seed in 0..200 {
+            let mut generator = Generator::new(seed);
+            let mut value = |schema: Value| generator.value(&schema, &document);
+
+            let integer =
+                value(json!({"type": "integer", "minimum": -3, "maximum": 9, "multipleOf": 3}));
+            assert!([-3, 0, 3, 6, 9].contains(&integer.as_i64().unwrap()));
+            let number =
+                value(json!({"type": "number", "exclusiveMinimum": 0, "exclusiveMaximum": 1}))
+                    .as_f64()
+                    .unwrap();
+            assert!(number > 0.0 && number < 1.0);
+            let word = value(json!({"type": "string", "minLength": 3, "maxLength": 4}));
+            assert!((3..=4).contains(&word.as_str().unwrap().len()));
+            let uuid = value(json!({"type": "string", "format": "uuid"}));
+            assert!(uuid::Uuid::parse_str(uuid.as_str().unwrap()).is_ok());
+            let items = value(json!({"type": "array", "maxItems": 2, "items": {"enum": [1, 2]}}));
+            assert!(items.as_array().unwrap().len() <= 2);
+            let unique =
+                value(json!({"minItems": 2, "uniqueItems": true, "items": {"type": "boolean"}}));
+            assert_eq!(unique.as_array().unwrap().len(), 2);
+            assert_eq!(value(json!({"const": "fixed"})), json!("fixed"));
+        }
+    }
+
+    #[test]
+    fn test_generate_request() {
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+
+        let request = openapi.generate_request("/users/{id}", "put", 7).unwrap();
+        assert_eq!(
+            request,
+            openapi.generate_request("/users/{id}", "PUT", 7).unwrap()
+        );
+        assert_ne!(
+            request,
+            openapi.generate_request("/users/{id}", "put", 8).unwrap()
+        );
+        assert_eq!(request.method, "PUT");
+        assert!(request.path.starts_with("/users/") && request.path.contains("%40example"));
+        assert!(request.uri().starts_with(&format!("{}?", request.path)));
+        assert_eq!(request.headers[0].0, "X-Trace");
+        assert_eq!(request.content_type.as_deref(), Some("application/json"));
+
+        for seed in 0..100 {
+            let request = openapi
+                .generate_request("/users/{id}", "put", seed)
+                .unwrap();
+            let limit = request
+                .query
+                .iter()
+                .find(|(name, _)| name == "limit")
+                .unwrap();
+            assert!((1..=50).contains(&limit.1.parse::<i64>().unwrap()));
+            assert!(
+                request
+                    .query
+                    .iter()
+                    .filter(|(name, _)| name == "ids")
+                    .count()
+                    <= 1
+            );
+            let body = request.body.unwrap();
+            assert!(body.get("id").is_none());
+            assert!((18..=130).contains(&body["age"].as_i64().unwrap()));
+            assert!((2..=5).contains(&body["name"].as_str().unwrap().len()));
+        }
+
+        assert_eq!(openapi.generate_request("/users/{id}", "get", 0), None);
+        assert_eq!(openapi.generate_request("/groups", "get", 0), None);
+    }
+
+    #[cfg(feature = "test-with-axum")]
+    #[test]
+    fn test_generated_requests_pass_validation() {
+        use crate::request::axum::RequestData;
+        use axum::body::{Body, Bytes};
+
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+        for seed in 0..100 {
+            let generated = openapi
+                .generate_request("/users/{id}", "put", seed)
+                .unwrap();
+            let mut request = axum::http::Request::put(generated.uri())
+                .header("content-type", generated.content_type.as_deref().unwrap());
+            for (name, value) in &generated.headers {
+                request = request.header(name, value);
+            }
+            let body = serde_json::to_vec(&generated.body).unwrap();
+            let data = RequestData::new(
+                request.body(Body::empty()).unwrap(),
+                Some(Bytes::from(body)),
+            );
+            assert_eq!(openapi.validator(data), Ok(()), "{generated:?}");
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn test_strategy_generates_requests(
+            request in strategy(std::sync::Arc::new(OpenAPI::yaml(SPEC).unwrap()), "/users/{id}", "put").unwrap()
+        ) {
+            proptest::prop_assert!(request.body.is_some());
+        }
+    }
+}
@@ -0,0 +1,81 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Navigation of the API graph described by response `links`.
+
+use crate::model::lint::unescape;
+use crate::model::parse::{Link, OpenAPI, PathBase};
+
+/// A link declared by one of an operation's responses.
+#[derive(Debug, Clone, Copy)]
+pub struct OperationLink<'a> {
+    /// Status code (or range, or `default`) of the declaring response
+    pub status: &'a str,
+    pub name: &'a str,
+    pub link: &'a Link,
+}
+
+impl OpenAPI {
+    /// The links of every response of `operation`, following response and
+    /// link references into `components`
+    pub fn links<'a>(&'a self, operation: &'a PathBase) -> Vec<OperationLink<'a>> {
+        operation
+            .responses
+            .iter()
+            .flat_map(|(status, response)| {
+                self.resolve_response(response)
+                    .links
+                    .iter()
+                    .map(move |(name, link)| OperationLink {
+                        status,
+                        name,
+                        link: self.resolve_link(link),
+                    })
+            })
+            .collect()
+    }
+
+    /// Follows a `#/components/links/...` reference, returning `link` itself
+    /// when it is not a reference or the target does not exist
+    pub fn resolve_link<'a>(&'a self, link: &'a Link) -> &'a Link {
+        link.r#ref
+            .as_deref()
+            .and_then(|reference| reference.strip_prefix("#/components/links/"))
+            .and_then(|name| self.components.as_ref()?.links.get(name))
+            .unwrap_or(link)
+    }
+
+    /// The operation a link points to as `(path, method, operation)`, found by
+    /// its `operationId` or a local `operationRef` such as
+    /// `#/paths/~1users~1{id}/get`
+    pub fn link_target(&self, link: &Link) -> Option<(&str, &str, &PathBase)> {
+        let link = self.resolve_link(link);
+        if let Some(operation_id) = &link.operation_id {
+            return self.operation_by_id(operation_id);
+        }
+
+        let reference = link.operation_ref.as_deref()?.strip_prefix("#/paths/")?;
+        let (path, method) = reference.rsplit_once('/')?;
+        let path = unescape(path.trim_end_matches("/additionalOperations"));
+        let method = unescape(method);
+        let (path, path_item) = self.paths.get_key_value(path.as_str())?;
+        let (method, operation) = path_item
+            .all_operations()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&method))?;
+        Some((path.as_str(), method, operation))
+    }
+}
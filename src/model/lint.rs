@@ -0,0 +1,451 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Static checks on a parsed document that do not depend on any request.
+
+use crate::model::parse::{
+    BaseContent, Example, Header, In, OpenAPI, Parameter, PathBase, PathItem,
+};
+use crate::model::visit::{walk, SchemaNode, Visitor};
+use crate::validator;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Component sections whose entries are referenced through `$ref`.
+const REFERENCED_SECTIONS: &[&str] = &[
+    "schemas",
+    "parameters",
+    "requestBodies",
+    "responses",
+    "headers",
+    "links",
+    "callbacks",
+    "examples",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found in the document, located by a JSON pointer such as
+/// `/paths/~1users/get/responses`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            self.severity, self.rule, self.pointer, self.message
+        )
+    }
+}
+
+/// The rules reported by [`OpenAPI::lint`]
+pub const RULES: &[&str] = &[
+    "missing-operation-id",
+    "duplicate-operation-id",
+    "missing-responses",
+    "unused-parameter",
+    "unreachable-component",
+    "invalid-example",
+];
+
+/// What a [`Ruleset`] turns a rule into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Warning,
+    Error,
+}
+
+/// Disables rules or overrides their severity, such as
+///
+/// ```yaml
+/// rules:
+///   missing-operation-id: off
+///   unreachable-component: error
+/// ```
+///
+/// Rules it does not mention keep their default severity.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ruleset {
+    #[serde(default)]
+    pub rules: IndexMap<String, RuleLevel>,
+}
+
+impl Ruleset {
+    pub fn yaml(contents: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(contents)
+    }
+
+    pub fn with_rule(mut self, rule: impl Into<String>, level: RuleLevel) -> Self {
+        self.rules.insert(rule.into(), level);
+        self
+    }
+
+    /// Drops the diagnostics of disabled rules and sets the severity of the
+    /// overridden ones
+    pub fn apply(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter_map(|mut diagnostic| {
+                match self.rules.get(diagnostic.rule) {
+                    Some(RuleLevel::Off) => return None,
+                    Some(RuleLevel::Warning) => diagnostic.severity = Severity::Warning,
+                    Some(RuleLevel::Error) => diagnostic.severity = Severity::Error,
+                    None => {}
+                }
+                Some(diagnostic)
+            })
+            .collect()
+    }
+}
+
+impl OpenAPI {
+    /// [`OpenAPI::lint`] with the rules of `ruleset` disabled or re-graded
+    pub fn lint_with(&self, ruleset: &Ruleset) -> Vec<Diagnostic> {
+        ruleset.apply(self.lint())
+    }
+
+    /// Checks the document for likely authoring mistakes: operations without
+    /// an `operationId` or responses, duplicate `operationId`s, path
+    /// parameters missing from the path template, unreachable components and
+    /// examples that contradict their schema
+    pub fn lint(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut operation_ids: HashMap<&str, String> = HashMap::new();
+
+        for (path, path_item) in &self.paths {
+            let path_pointer = format!("/paths/{}", escape(path));
+
+            if let Some(parameters) = &path_item.parameters {
+                unused_path_parameters(path, &path_pointer, parameters, &mut diagnostics);
+            }
+
+            for (pointer, operation) in operations(path_item, &path_pointer) {
+                match operation.operation_id.as_deref() {
+                    None => diagnostics.push(Diagnostic {
+                        rule: "missing-operation-id",
+                        severity: Severity::Warning,
+                        pointer: pointer.clone(),
+                        message: "Operation has no operationId".to_string(),
+                    }),
+                    Some(id) => match operation_ids.get(id) {
+                        Some(first) => diagnostics.push(Diagnostic {
+                            rule: "duplicate-operation-id",
+                            severity: Severity::Error,
+                            pointer: format!("{pointer}/operationId"),
+                            message: format!("operationId '{id}' is already used by {first}"),
+                        }),
+                        None => {
+                            operation_ids.insert(id, pointer.clone());
+                        }
+                    },
+                }
+
+                if operation.responses.is_empty() {
+                    diagnostics.push(Diagnostic {
+                        rule: "missing-responses",
+                        severity: Severity::Error,
+                        pointer: format!("{pointer}/responses"),
+                        message: "Operation declares no responses".to_string(),
+                    });
+                }
+
+                if let Some(parameters) = &operation.parameters {
+                    unused_path_parameters(path, &pointer, parameters, &mut diagnostics);
+                }
+            }
+        }
+
+        diagnostics.extend(self.unreachable_components());
+        diagnostics.extend(self.invalid_examples());
+        diagnostics
+    }
+
+    /// Checks every `example` and `examples` value of the document against
+    /// the schema it illustrates. External examples are not fetched.
+    pub fn invalid_examples(&self) -> Vec<Diagnostic> {
+        let mut check = ExampleCheck {
+            open_api: self,
+            diagnostics: vec![],
+        };
+        walk(self, &mut check);
+        check.diagnostics
+    }
+
+    fn unreachable_components(&self) -> Vec<Diagnostic> {
+        let Some(components) = &self.components else {
+            return vec![];
+        };
+        let Ok(Value::Mapping(mut document)) = serde_yaml::to_value(self) else {
+            return vec![];
+        };
+        let Some(Value::Mapping(sections)) = document.remove("components") else {
+            return vec![];
+        };
+
+        // References from outside `components` are the roots; components are
+        // reachable from there, directly or through other components
+        let mut pending = Vec::new();
+        collect_refs(&Value::Mapping(document), &mut pending);
+        let mut reachable = HashSet::new();
+        while let Some(reference) = pending.pop() {
+            if !reachable.insert(reference.clone()) {
+                continue;
+            }
+            let target = reference
+                .strip_prefix("#/components/")
+                .and_then(|rest| rest.split_once('/'))
+                .and_then(|(section, name)| sections.get(section)?.get(unescape(name).as_str()));
+            if let Some(target) = target {
+                collect_refs(target, &mut pending);
+            }
+        }
+
+        let mut diagnostics = Vec::new();
+        for section in REFERENCED_SECTIONS {
+            let Some(Value::Mapping(entries)) = sections.get(*section) else {
+                continue;
+            };
+            for name in entries.keys().filter_map(Value::as_str) {
+                let pointer = format!("/components/{section}/{}", escape(name));
+                if !reachable.contains(&format!("#{pointer}")) {
+                    diagnostics.push(unreachable(pointer, name));
+                }
+            }
+        }
+
+        let used_schemes: HashSet<&str> = self
+            .security
+            .iter()
+            .flatten()
+            .chain(
+                self.paths
+                    .values()
+                    .flat_map(|path_item| operations(path_item, ""))
+                    .flat_map(|(_, operation)| operation.security.iter().flatten()),
+            )
+            .flat_map(|requirement| requirement.keys().map(String::as_str))
+            .collect();
+        for name in components
+            .security_schemes
+            .keys()
+            .filter(|name| !used_schemes.contains(name.as_str()))
+        {
+            let pointer = format!("/components/securitySchemes/{}", escape(name));
+            diagnostics.push(unreachable(pointer, name));
+        }
+
+        diagnostics
+    }
+}
+
+struct ExampleCheck<'a> {
+    open_api: &'a OpenAPI,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ExampleCheck<'_> {
+    fn check(&mut self, pointer: String, example: &Value, schema: SchemaNode<'_>) {
+        let result = serde_json::to_value(example)
+            .map_err(anyhow::Error::from)
+            .and_then(|value| {
+                validator::schema_value(
+                    "example",
+                    &value,
+                    schema,
+                    self.open_api.components.as_ref(),
+                )
+            });
+        if let Err(err) = result {
+            self.diagnostics.push(Diagnostic {
+                rule: "invalid-example",
+                severity: Severity::Error,
+                pointer,
+                message: format!("Example does not match its schema: {err}"),
+            });
+        }
+    }
+
+    /// Checks the `example` and named `examples` of a parameter, header or media type.
+    fn check_all(
+        &mut self,
+        pointer: &str,
+        example: Option<&Value>,
+        examples: &IndexMap<String, Example>,
+        schema: SchemaNode<'_>,
+    ) {
+        if let Some(example) = example {
+            self.check(format!("{pointer}/example"), example, schema);
+        }
+        for (name, example) in examples {
+            let example = self.open_api.resolve_example(example);
+            if let Some(value) = &example.value {
+                let pointer = format!("{pointer}/examples/{}/value", escape(name));
+                self.check(pointer, value, schema);
+            }
+        }
+    }
+
+    /// Checks the examples a schema lists for itself.
+    fn check_list(&mut self, pointer: &str, examples: Option<&Vec<Value>>, schema: SchemaNode<'_>) {
+        for (index, example) in examples.into_iter().flatten().enumerate() {
+            self.check(format!("{pointer}/examples/{index}"), example, schema);
+        }
+    }
+}
+
+impl Visitor for ExampleCheck<'_> {
+    fn visit_parameter(&mut self, pointer: &str, parameter: &Parameter) {
+        if let Some(schema) = &parameter.schema {
+            let schema = SchemaNode::Schema(schema);
+            self.check_all(
+                pointer,
+                parameter.example.as_ref(),
+                &parameter.examples,
+                schema,
+            );
+        }
+    }
+
+    fn visit_header(&mut self, pointer: &str, header: &Header) {
+        if let Some(schema) = &header.schema {
+            let schema = SchemaNode::Schema(schema);
+            self.check_all(pointer, header.example.as_ref(), &header.examples, schema);
+        }
+    }
+
+    fn visit_media_type(&mut self, pointer: &str, _: &str, media: &BaseContent) {
+        let schema = SchemaNode::Schema(&media.schema);
+        self.check_all(pointer, media.example.as_ref(), &media.examples, schema);
+    }
+
+    fn visit_schema(&mut self, pointer: &str, node: SchemaNode<'_>) {
+        match node {
+            SchemaNode::Schema(schema) => {
+                if let Some(example) = &schema.example {
+                    self.check(format!("{pointer}/example"), example, node);
+                }
+                self.check_list(pointer, schema.examples.as_ref(), node);
+            }
+            SchemaNode::Component(schema) => {
+                self.check_list(pointer, schema.examples.as_ref(), node);
+            }
+            SchemaNode::Property(property) => {
+                if let Some(example) = &property.example {
+                    self.check(format!("{pointer}/example"), example, node);
+                }
+                self.check_list(pointer, property.examples.as_ref(), node);
+            }
+            SchemaNode::Composed(_) => {}
+        }
+    }
+}
+
+/// Operations of a path item with their JSON pointers.
+fn operations<'a>(path_item: &'a PathItem, path_pointer: &str) -> Vec<(String, &'a PathBase)> {
+    path_item
+        .all_operations()
+        .map(|(method, operation)| {
+            let pointer = if path_item.additional_operations.contains_key(method) {
+                format!("{path_pointer}/additionalOperations/{}", escape(method))
+            } else {
+                format!("{path_pointer}/{method}")
+            };
+            (pointer, operation)
+        })
+        .collect()
+}
+
+fn unused_path_parameters(
+    path: &str,
+    pointer: &str,
+    parameters: &[Parameter],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (index, parameter) in parameters.iter().enumerate() {
+        let (Some(name), Some(In::Path)) = (&parameter.name, &parameter.r#in) else {
+            continue;
+        };
+        if !path.contains(&format!("{{{name}}}")) {
+            diagnostics.push(Diagnostic {
+                rule: "unused-parameter",
+                severity: Severity::Warning,
+                pointer: format!("{pointer}/parameters/{index}"),
+                message: format!("Path parameter '{name}' does not appear in '{path}'"),
+            });
+        }
+    }
+}
+
+fn unreachable(pointer: String, name: &str) -> Diagnostic {
+    Diagnostic {
+        rule: "unreachable-component",
+        severity: Severity::Warning,
+        pointer,
+        message: format!("Component '{name}' is never referenced"),
+    }
+}
+
+fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping {
+                match (key.as_str(), value) {
+                    (Some("$ref"), Value::String(reference)) => refs.push(reference.clone()),
+                    _ => collect_refs(value, refs),
+                }
+            }
+        }
+        Value::Sequence(values) => values.iter().for_each(|value| collect_refs(value, refs)),
+        _ => {}
+    }
+}
+
+/// Escapes a JSON pointer token (RFC 6901).
+pub(crate) fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+pub(crate) fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
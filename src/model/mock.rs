@@ -0,0 +1,436 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Responses made up from a document, for mock servers: the examples it
+//! declares, or values generated from the response schemas.
+
+use crate::model::parse::{BaseContent, OpenAPI};
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+
+/// Nesting beyond which generated values are cut short, so that recursive
+/// schemas end
+const MAX_DEPTH: usize = 8;
+
+/// A response an operation could answer with, see [`OpenAPI::mock_response`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+    /// The media type of `body`, absent when the response has no content
+    pub content_type: Option<String>,
+    pub body: Option<Value>,
+}
+
+impl OpenAPI {
+    /// The response of the operation `method` of the path template `path`
+    /// for `status`, declared by its code, its `4XX`-style range or as
+    /// `default`. Without `status`, the lowest 2XX response is chosen, or
+    /// else the `default` one as `200`.
+    ///
+    /// The body is the first example of the JSON content, or else of the
+    /// first content type, and is generated from its schema with [`sample`]
+    /// when there is no example.
+    pub fn mock_response(
+        &self,
+        path: &str,
+        method: &str,
+        status: Option<u16>,
+    ) -> Option<MockResponse> {
+        let (_, operation) = self
+            .paths
+            .get(path)?
+            .all_operations()
+            .find(|(known, _)| known.eq_ignore_ascii_case(method))?;
+
+        let (status, key) = match status {
+            Some(status) => {
+                let key = [status.to_string(), format!("{}XX", status / 100)]
+                    .into_iter()
+                    .chain(["default".to_string()])
+                    .find(|key| operation.responses.contains_key(key))?;
+                (status, key)
+            }
+            None => {
+                let mut statuses: Vec<(u16, &str)> = operation
+                    .responses
+                    .keys()
+                    .filter_map(|status| match status.as_str() {
+                        "default" | "2XX" => Some((200, status.as_str())),
+                        code => code.parse().ok().map(|code| (code, status.as_str())),
+                    })
+                    .collect();
+                // Success responses first, declared ones before `default`
+                statuses.sort_by_key(|(code, status)| {
+                    (!(200..300).contains(code), *status == "default", *code)
+                });
+                let (status, key) = statuses.first().copied()?;
+                (status, key.to_string())
+            }
+        };
+        let response = self.resolve_response(&operation.responses[&key]);
+
+        let Some((content_type, content)) = json_content(&response.content) else {
+            return Some(MockResponse {
+                status,
+                content_type: None,
+                body: None,
+            });
+        };
+        Some(MockResponse {
+            status,
+            content_type: Some(content_type.to_string()),
+            body: Some(self.mock_body(content)),
+        })
+    }
+
+    fn mock_body(&self, content: &BaseContent) -> Value {
+        let example = content.example.clone().or_else(|| {
+            content
+                .examples
+                .values()
+                .find_map(|example| self.resolve_example(example).value.clone())
+        });
+        if let Some(example) = example.and_then(|example| serde_json::to_value(example).ok()) {
+            return example;
+        }
+
+        let document = serde_json::to_value(self).unwrap_or_default();
+        let schema = serde_json::to_value(&content.schema).unwrap_or_default();
+        sample(&schema, &document)
+    }
+}
+
+/// The JSON content of a response, or else its first one
+fn json_content(content: &IndexMap<String, BaseContent>) -> Option<(&str, &BaseContent)> {
+    content
+        .iter()
+        .find(|(media_type, _)| {
+            let media_type = media_type.split(';').next().unwrap_or_default().trim();
+            media_type == "application/json" || media_type.ends_with("+json")
+        })
+        .or_else(|| content.first())
+        .map(|(media_type, content)| (media_type.as_str(), content))
+}
+
+/// A value matching `schema`, a JSON Schema of `document` whose `$ref`s
+/// point into `document`. Examples, defaults and enums are used when the
+/// schema has them; other values are the simplest ones satisfying the
+/// type, format and bounds. `pattern`s are not honoured.
+pub fn sample(schema: &Value, document: &Value) -> Value {
+    sample_at(schema, document, 0)
+}
+
+fn sample_at(schema: &Value, document: &Value, depth: usize) -> Value {
+    let Value::Object(schema) = schema else {
+        return Value::Null;
+    };
+    if depth > MAX_DEPTH {
+        return Value::Null;
+    }
+
+    if let Some(target) = schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix('#'))
+    {
+        return match document.pointer(target) {
+            Some(target) => sample_at(target, document, depth + 1),
+            None => Value::Null,
+        };
+    }
+    if let Some(value) = schema
+        .get("example")
+        .or_else(|| schema.get("examples").and_then(|examples| examples.get(0)))
+        .or_else(|| schema.get("default"))
+        .or_else(|| schema.get("const"))
+        .or_else(|| schema.get("enum").and_then(|values| values.get(0)))
+    {
+        return value.clone();
+    }
+    if let Some(Value::Array(schemas)) = schema.get("allOf") {
+        let mut merged = Map::new();
+        for schema in schemas {
+            if let Value::Object(sampled) = sample_at(schema, document, depth + 1) {
+                merged.extend(sampled);
+            }
+        }
+        if let Value::Object(own) = object(schema, document, depth) {
+            merged.extend(own);
+        }
+        return Value::Object(merged);
+    }
+    if let Some(schema) = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|keyword| schema.get(*keyword)?.get(0))
+    {
+        return sample_at(schema, document, depth + 1);
+    }
+
+    let r#type = match schema.get("type") {
+        Some(Value::String(r#type)) => Some(r#type.as_str()),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|r#type| *r#type != "null"),
+        _ => None,
+    };
+    match r#type {
+        Some("object") => object(schema, document, depth),
+        None if schema.contains_key("properties") => object(schema, document, depth),
+        Some("array") => {
+            let item = schema
+                .get("items")
+                .map(|items| sample_at(items, document, depth + 1))
+                .unwrap_or_default();
+            let count = schema
+                .get("minItems")
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .max(1);
+            Value::Array(vec![item; count as usize])
+        }
+        Some("string") => Value::String(string(schema)),
+        Some("integer") => {
+            let value = number(schema).ceil() as i64;
+            Value::from(value)
+        }
+        Some("number") => Value::from(number(schema)),
+        Some("boolean") => Value::Bool(true),
+        _ => Value::Null,
+    }
+}
+
+/// Every property of an object schema but the `writeOnly` ones
+fn object(schema: &Map<String, Value>, document: &Value, depth: usize) -> Value {
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter(|(_, property)| property.get("writeOnly") != Some(&Value::Bool(true)))
+        .map(|(name, property)| (name.clone(), sample_at(property, document, depth + 1)))
+        .collect();
+    Value::Object(properties)
+}
+
+fn string(schema: &Map<String, Value>) -> String {
+    let value = match schema.get("format").and_then(Value::as_str) {
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("date") => "2024-01-01",
+        Some("time") => "00:00:00Z",
+        Some("duration") => "P1D",
+        Some("email") => "user@example.com",
+        Some("uuid") => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+        Some("uri" | "url") => "https://example.com",
+        Some("uri-reference") => "/example",
+        Some("hostname") => "example.com",
+        Some("ipv4") => "192.0.2.1",
+        Some("ipv6") => "2001:db8::1",
+        Some("byte" | "base64") => "c3RyaW5n",
+        _ => "string",
+    };
+    let min_length = schema.get("minLength").and_then(Value::as_u64).unwrap_or(0) as usize;
+    let max_length = schema.get("maxLength").and_then(Value::as_u64);
+    let mut value = value.to_string();
+    while value.chars().count() < min_length {
+        value.push('x');
+    }
+    if let Some(max_length) = max_length {
+        value = value.chars().take(max_length as usize).collect();
+    }
+    value
+}
+
+/// The value closest to zero within the bounds, rounded up to `multipleOf`
+fn number(schema: &Map<String, Value>) -> f64 {
+    let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+    let value = match (bound("minimum"), bound("exclusiveMinimum")) {
+        (Some(minimum), _) => minimum,
+        (None, Some(minimum)) => minimum + 1.0,
+        (None, None) => match (bound("maximum"), bound("exclusiveMaximum")) {
+            (Some(maximum), _) if maximum < 0.0 => maximum,
+            (None, Some(maximum)) if maximum <= 0.0 => maximum - 1.0,
+            _ => 0.0,
+        },
+    };
+    match bound("multipleOf").filter(|multiple| *multiple > 0.0) {
+        Some(multiple) => (value / multiple).ceil() * multiple,
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Mock
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      responses:
+        '404':
+          description: Missing
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/User'
+    put:
+      responses:
+        '204':
+          description: Updated
+    delete:
+      responses:
+        default:
+          description: Deleted
+          content:
+            text/plain:
+              example: gone
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              examples:
+                two:
+                  value: [{"id": 1}, {"id": 2}]
+components:
+  schemas:
+    User:
+      type: object
+      properties:
+        id:
+          type: integer
+          minimum: 1
+        email:
+          type: string
+          format: email
+        password:
+          type: string
+          writeOnly: true
+        role:
+          type: string
+          enum: [admin, member]
+        tags:
+          type: array
+          items:
+            type: string
+            minLength: 8
+        manager:
+          $ref: '#/components/schemas/User'
+"#;
+
+    #[test]
+    fn test_mock_response() {
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+
+        let response = openapi.mock_response("/users/{id}", "GET", None).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type.as_deref(), Some("application/json"));
+        let body = response.body.unwrap();
+        assert_eq!(body["id"], 1);
+        assert_eq!(body["email"], "user@example.com");
+        assert_eq!(body["role"], "admin");
+        assert_eq!(body["tags"], json!(["stringxx"]));
+        assert_eq!(body["manager"]["manager"]["id"], 1);
+        assert!(body.get("password").is_none());
+
+        assert_eq!(
+            openapi.mock_response("/users/{id}", "put", None),
+            Some(MockResponse {
+                status: 204,
+                content_type: None,
+                body: None,
+            })
+        );
+        assert_eq!(
+            openapi.mock_response("/users/{id}", "delete", None),
+            Some(MockResponse {
+                status: 200,
+                content_type: Some("text/plain".to_string()),
+                body: Some(json!("gone")),
+            })
+        );
+        assert_eq!(
+            openapi.mock_response("/users", "get", None).unwrap().body,
+            Some(json!([{"id": 1}, {"id": 2}]))
+        );
+        assert_eq!(openapi.mock_response("/users", "post", None), None);
+        assert_eq!(openapi.mock_response("/groups", "get", None), None);
+
+        let missing = openapi.mock_response("/users/{id}", "get", Some(404));
+        assert_eq!(
+            missing,
+            Some(MockResponse {
+                status: 404,
+                content_type: None,
+                body: None,
+            })
+        );
+        assert_eq!(openapi.mock_response("/users/{id}", "get", Some(500)), None);
+        let deleted = openapi.mock_response("/users/{id}", "delete", Some(410));
+        assert_eq!(deleted.map(|response| response.status), Some(410));
+    }
+
+    #[test]
+    fn test_sample() {
+        let document = json!({});
+        let sample = |schema: Value| sample(&schema, &document);
+
+        assert_eq!(
+            sample(json!({"type": "integer", "exclusiveMinimum": 4})),
+            json!(5)
+        );
+        assert_eq!(
+            sample(json!({"type": "number", "maximum": -2.5})),
+            json!(-2.5)
+        );
+        assert_eq!(
+            sample(json!({"type": "integer", "minimum": 3, "multipleOf": 5})),
+            json!(5)
+        );
+        assert_eq!(sample(json!({"type": ["null", "boolean"]})), json!(true));
+        assert_eq!(
+            sample(json!({"type": "string", "maxLength": 3})),
+            json!("str")
+        );
+        assert_eq!(
+            sample(json!({"type": "array", "minItems": 2, "items": {"const": 1}})),
+            json!([1, 1])
+        );
+        assert_eq!(
+            sample(json!({
+                "allOf": [{"properties": {"a": {"default": 1}}}],
+                "properties": {"b": {"type": "string", "format": "date"}}
+            })),
+            json!({"a": 1, "b": "2024-01-01"})
+        );
+        assert_eq!(
+            sample(json!({"oneOf": [{"type": "string", "format": "uuid"}, {"type": "integer"}]})),
+            json!("3fa85f64-5717-4562-b3fc-2c963f66afa6")
+        );
+        assert_eq!(sample(json!({"$ref": "#/missing"})), Value::Null);
+    }
+}
@@ -15,4 +15,23 @@
  * limitations under the License.
  */
 
+pub mod bundle;
+pub mod drift;
+pub mod extensions;
+pub mod generate;
+pub mod link;
+pub mod lint;
+pub mod mock;
+pub mod normalize;
 pub mod parse;
+pub mod registry;
+pub mod router;
+pub mod server;
+pub mod shared;
+pub mod strict;
+#[cfg(feature = "swagger")]
+pub mod swagger;
+pub mod version;
+pub mod visit;
+#[cfg(feature = "hot-reload")]
+pub mod watch;
@@ -0,0 +1,221 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Rewrites OpenAPI 3.0 schema idioms into their OpenAPI 3.1 (JSON Schema) form.
+
+use serde_yaml::{Mapping, Value};
+
+/// Keywords holding a single subschema.
+const SUBSCHEMA: &[&str] = &["items", "additionalProperties", "not", "contentSchema"];
+
+/// Keywords holding a list of subschemas.
+const SUBSCHEMA_LIST: &[&str] = &["allOf", "oneOf", "anyOf", "prefixItems"];
+
+/// Keywords holding a map of named subschemas.
+const SUBSCHEMA_MAP: &[&str] = &["properties", "patternProperties", "$defs"];
+
+/// Check if `document` declares an OpenAPI 3.0 version (3.0.x)
+pub fn is_30(document: &Value) -> bool {
+    document
+        .get("openapi")
+        .and_then(Value::as_str)
+        .is_some_and(|version| version.starts_with("3.0"))
+}
+
+/// Methods a path item holds operations under.
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace", "query",
+];
+
+/// Rewrites every schema of an OpenAPI 3.0 document into its 3.1 form:
+///
+/// - `nullable: true` adds `null` to `type` (and to `enum` when present)
+/// - boolean `exclusiveMinimum`/`exclusiveMaximum` take over the value of
+///   `minimum`/`maximum`
+/// - `example` becomes a single-entry `examples` list
+///
+/// Only schema positions of the document are visited, so examples and
+/// `x-` extensions holding `schema` keys are left as written. The declared
+/// `openapi` version is left untouched.
+pub fn to_31(document: &mut Value) {
+    if let Some(paths) = document.get_mut("paths") {
+        each_value(paths, path_item);
+    }
+    if let Some(webhooks) = document.get_mut("webhooks") {
+        each_value(webhooks, path_item);
+    }
+
+    let Some(components) = document.get_mut("components") else {
+        return;
+    };
+    for (key, f) in [
+        ("schemas", schema as fn(&mut Value)),
+        ("parameters", parameter),
+        ("headers", parameter),
+        ("requestBodies", content),
+        ("responses", response),
+        ("callbacks", callback),
+        ("pathItems", path_item),
+    ] {
+        if let Some(value) = components.get_mut(key) {
+            each_value(value, f);
+        }
+    }
+}
+
+fn path_item(value: &mut Value) {
+    let Value::Mapping(path_item) = value else {
+        return;
+    };
+
+    for (key, value) in path_item.iter_mut() {
+        match key.as_str() {
+            Some("parameters") => each_value(value, parameter),
+            Some("additionalOperations") => each_value(value, operation),
+            Some(method) if METHODS.contains(&method) => operation(value),
+            _ => {}
+        }
+    }
+}
+
+fn operation(value: &mut Value) {
+    let Value::Mapping(operation) = value else {
+        return;
+    };
+
+    for (key, value) in operation.iter_mut() {
+        match key.as_str() {
+            Some("parameters") => each_value(value, parameter),
+            Some("requestBody") => content(value),
+            Some("responses") => each_value(value, response),
+            Some("callbacks") => each_value(value, callback),
+            _ => {}
+        }
+    }
+}
+
+fn callback(value: &mut Value) {
+    each_value(value, path_item);
+}
+
+/// A parameter or header, whose schema is under `schema` or `content`
+fn parameter(value: &mut Value) {
+    if let Some(schema) = value.get_mut("schema") {
+        self::schema(schema);
+    }
+    content(value);
+}
+
+fn response(value: &mut Value) {
+    if let Some(headers) = value.get_mut("headers") {
+        each_value(headers, parameter);
+    }
+    content(value);
+}
+
+/// The media types under the `content` of a request body, response,
+/// parameter or header
+fn content(value: &mut Value) {
+    if let Some(content) = value.get_mut("content") {
+        each_value(content, media_type);
+    }
+}
+
+fn media_type(value: &mut Value) {
+    for key in ["schema", "itemSchema"] {
+        if let Some(schema) = value.get_mut(key) {
+            self::schema(schema);
+        }
+    }
+    if let Some(encoding) = value.get_mut("encoding") {
+        each_value(encoding, |encoding| {
+            if let Some(headers) = encoding.get_mut("headers") {
+                each_value(headers, parameter);
+            }
+        });
+    }
+}
+
+fn each_value(value: &mut Value, f: fn(&mut Value)) {
+    match value {
+        Value::Mapping(mapping) => mapping.iter_mut().for_each(|(_, value)| f(value)),
+        Value::Sequence(values) => values.iter_mut().for_each(f),
+        _ => {}
+    }
+}
+
+fn schema(value: &mut Value) {
+    let Value::Mapping(schema) = value else {
+        return;
+    };
+
+    nullable(schema);
+    exclusive_bound(schema, "exclusiveMinimum", "minimum");
+    exclusive_bound(schema, "exclusiveMaximum", "maximum");
+    if let Some(example) = schema.remove("example") {
+        if !schema.contains_key("examples") {
+            schema.insert("examples".into(), Value::Sequence(vec![example]));
+        }
+    }
+
+    for (key, value) in schema.iter_mut() {
+        match key.as_str() {
+            Some(keyword) if SUBSCHEMA.contains(&keyword) => self::schema(value),
+            Some(keyword) if SUBSCHEMA_LIST.contains(&keyword) => each_value(value, self::schema),
+            Some(keyword) if SUBSCHEMA_MAP.contains(&keyword) => each_value(value, self::schema),
+            _ => {}
+        }
+    }
+}
+
+fn nullable(schema: &mut Mapping) {
+    if schema.remove("nullable").and_then(|value| value.as_bool()) != Some(true) {
+        return;
+    }
+
+    let null = Value::from("null");
+    if let Some(r#type) = schema.get_mut("type") {
+        match r#type {
+            Value::Sequence(types) if !types.contains(&null) => types.push(null),
+            Value::String(_) => *r#type = Value::Sequence(vec![r#type.clone(), null]),
+            _ => {}
+        }
+    }
+
+    if let Some(Value::Sequence(values)) = schema.get_mut("enum") {
+        if !values.contains(&Value::Null) {
+            values.push(Value::Null);
+        }
+    }
+}
+
+fn exclusive_bound(schema: &mut Mapping, exclusive: &str, bound: &str) {
+    match schema.get(exclusive) {
+        Some(Value::Bool(true)) => match schema.remove(bound) {
+            Some(limit) => {
+                schema.insert(exclusive.into(), limit);
+            }
+            None => {
+                schema.remove(exclusive);
+            }
+        },
+        Some(Value::Bool(false)) => {
+            schema.remove(exclusive);
+        }
+        _ => {}
+    }
+}
@@ -15,44 +15,164 @@
  * limitations under the License.
  */
 
-use crate::observability::ValidationMetrics;
-use crate::validator::ValidateRequest;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::model::normalize;
+use crate::model::router::RouteMatch;
+use crate::observability::{self, RequestContext, ValidationMetrics};
+use crate::validator::compiled::{self, CompiledOperation, CompiledSpec};
+use crate::validator::report::{Issue, MatchedOperation, ValidationReport};
+use crate::validator::{
+    redact, MethodNotAllowed, Unauthorized, ValidateRequest, ValidationOptions,
+};
+use indexmap::IndexMap;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::hash::Hash;
+use std::sync::OnceLock;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OpenAPI {
     pub openapi: String,
     pub info: InfoObject,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<ServerObject>,
-    pub paths: HashMap<String, PathItem>,
-    pub components: Option<ComponentsObject>,
+    /// Read with [`OpenAPI::paths`]; edits go through [`OpenAPI::paths_mut`]
+    /// so that the validation plan follows them
+    pub(crate) paths: IndexMap<String, PathItem>,
+    /// Read with [`OpenAPI::components`]; edits go through
+    /// [`OpenAPI::components_mut`] so that the validation plan follows them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) components: Option<ComponentsObject>,
+    /// Default security requirements, overridable per operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirementObject>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TagObject>,
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
 
     // === OpenAPI 3.1 fields ===
-    #[serde(rename = "jsonSchemaDialect")]
+    #[serde(rename = "jsonSchemaDialect", skip_serializing_if = "Option::is_none")]
     pub json_schema_dialect: Option<String>,
-    pub webhooks: Option<HashMap<String, PathItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<IndexMap<String, PathItem>>,
 
     // === OpenAPI 3.2 fields ===
-    #[serde(rename = "$self")]
+    #[serde(rename = "$self", skip_serializing_if = "Option::is_none")]
     pub self_ref: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+
+    #[serde(skip)]
+    pub(crate) options: ValidationOptions,
+    /// Validation plan, built on first use; see [`OpenAPI::compiled`]
+    #[serde(skip)]
+    compiled: OnceLock<CompiledSpec>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A path item; keys other than the known fields and HTTP methods (such as
+/// `x-` extensions) are kept in `extra`.
+#[derive(Debug, Clone, Serialize)]
 pub struct PathItem {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<Parameter>>, // Path-level parameters
     #[serde(flatten)]
-    pub operations: HashMap<String, PathBase>, // For HTTP methods (get, post, etc.)
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub servers: Vec<ServerObject>, // Will be ignored during deserialization
+    pub operations: IndexMap<String, PathBase>, // For HTTP methods (get, post, etc.)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<ServerObject>,
 
     // === OpenAPI 3.2 HTTP method ===
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub query: Option<PathBase>, // QUERY method (3.2)
+    #[serde(
+        rename = "additionalOperations",
+        skip_serializing_if = "IndexMap::is_empty"
+    )]
+    pub additional_operations: IndexMap<String, PathBase>, // Custom HTTP methods (3.2)
 
     #[serde(flatten)]
-    pub extra: serde_yaml::Value, // Catches any other fields
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+/// Deserialized by hand so that only HTTP method keys become operations while
+/// every other unknown key lands in `extra`.
+impl<'de> Deserialize<'de> for PathItem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PathItemVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for PathItemVisitor {
+            type Value = PathItem;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a path item object")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<PathItem, A::Error> {
+                let mut item = PathItem {
+                    r#ref: None,
+                    summary: None,
+                    description: None,
+                    parameters: None,
+                    operations: IndexMap::new(),
+                    servers: vec![],
+                    query: None,
+                    additional_operations: IndexMap::new(),
+                    extra: IndexMap::new(),
+                };
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "$ref" => item.r#ref = map.next_value()?,
+                        "summary" => item.summary = map.next_value()?,
+                        "description" => item.description = map.next_value()?,
+                        "parameters" => item.parameters = map.next_value()?,
+                        "servers" => item.servers = map.next_value()?,
+                        "query" => item.query = map.next_value()?,
+                        "additionalOperations" => item.additional_operations = map.next_value()?,
+                        method if METHODS.contains(&method) => {
+                            item.operations.insert(key, map.next_value()?);
+                        }
+                        _ => {
+                            item.extra.insert(key, map.next_value()?);
+                        }
+                    }
+                }
+
+                Ok(item)
+            }
+        }
+
+        deserializer.deserialize_map(PathItemVisitor)
+    }
+}
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+impl PathItem {
+    /// Operations of this path item keyed by method: the standard methods in
+    /// their conventional order, then QUERY and the additional methods
+    pub fn all_operations(&self) -> impl Iterator<Item = (&str, &PathBase)> {
+        METHODS
+            .iter()
+            .filter_map(|method| Some((*method, self.operations.get(*method)?)))
+            .chain(self.query.iter().map(|operation| ("query", operation)))
+            .chain(
+                self.additional_operations
+                    .iter()
+                    .map(|(method, operation)| (method.as_str(), operation)),
+            )
+    }
 }
 
 macro_rules! require_non_empty {
@@ -64,8 +184,82 @@ macro_rules! require_non_empty {
 }
 
 impl OpenAPI {
+    /// Parses a YAML (or JSON) document; OpenAPI 3.0 schemas are normalized
+    /// into their 3.1 form so validation sees a single representation
     pub fn yaml(contents: &str) -> Result<Self, serde_yaml::Error> {
-        serde_yaml::from_str(contents)
+        let open_api = Self::parse_yaml(contents)?;
+        open_api.compiled();
+        Ok(open_api)
+    }
+
+    /// Parses a JSON document produced by [`OpenAPI::to_json`], skipping the
+    /// merge key expansion and 3.0 normalization [`OpenAPI::yaml`] applies to
+    /// authored documents
+    pub fn json(contents: &str) -> Result<Self, serde_json::Error> {
+        let open_api: Self = serde_json::from_str(contents)?;
+        open_api.compiled();
+        Ok(open_api)
+    }
+
+    fn parse_yaml(contents: &str) -> Result<Self, serde_yaml::Error> {
+        let mut document: serde_yaml::Value = serde_yaml::from_str(contents)?;
+        let merged = expand_merge_keys(&mut document)?;
+        let is_30 = normalize::is_30(&document);
+        if !merged && !is_30 {
+            // Deserialize from the source text to keep error locations
+            return serde_yaml::from_str(contents);
+        }
+        if is_30 {
+            normalize::to_31(&mut document);
+        }
+        serde_yaml::from_value(document)
+    }
+
+    /// Serializes the document back to YAML, keeping the authored key order,
+    /// `x-` extensions and unrecognized fields. OpenAPI 3.0 input is emitted
+    /// in its normalized 3.1 schema form
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Serializes the document to pretty-printed JSON, see [`OpenAPI::to_yaml`]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Replace the options controlling how requests are validated
+    pub fn with_options(mut self, options: ValidationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The options controlling how requests are validated
+    pub fn options(&self) -> &ValidationOptions {
+        &self.options
+    }
+
+    /// The paths of the document and their operations
+    pub fn paths(&self) -> &IndexMap<String, PathItem> {
+        &self.paths
+    }
+
+    /// Edits the paths of the document; the validation plan is rebuilt on
+    /// its next use
+    pub fn paths_mut(&mut self) -> &mut IndexMap<String, PathItem> {
+        self.compiled = OnceLock::new();
+        &mut self.paths
+    }
+
+    /// The reusable components of the document
+    pub fn components(&self) -> Option<&ComponentsObject> {
+        self.components.as_ref()
+    }
+
+    /// Edits the components of the document; the validation plan is rebuilt
+    /// on its next use
+    pub fn components_mut(&mut self) -> &mut Option<ComponentsObject> {
+        self.compiled = OnceLock::new();
+        &mut self.components
     }
 
     /// Check if this is an OpenAPI 3.1 spec (3.1.x)
@@ -78,140 +272,818 @@ impl OpenAPI {
         self.openapi.starts_with("3.2")
     }
 
+    /// Declares the document as OpenAPI `version`, `3.1` or `3.2`. Documents
+    /// are parsed into the 3.1 schema form, so 3.0 ones are upgraded by
+    /// declaring the later version. A document already at `version` keeps
+    /// its patch version; later ones are not downgraded
+    pub fn upgrade(mut self, version: &str) -> anyhow::Result<Self> {
+        let target = match minor_version(version) {
+            Some(target @ ((3, 1) | (3, 2))) => target,
+            _ => anyhow::bail!("Unsupported target version '{version}', expected 3.1 or 3.2"),
+        };
+        match minor_version(&self.openapi) {
+            Some(current) if current > target => {
+                anyhow::bail!("Cannot downgrade OpenAPI {} to {version}", self.openapi)
+            }
+            Some(current) if current == target => {}
+            Some(_) => self.openapi = format!("{}.{}.0", target.0, target.1),
+            None => anyhow::bail!("Invalid OpenAPI version '{}'", self.openapi),
+        }
+        Ok(self)
+    }
+
+    /// Every operation of the document as `(path, method, operation)`, in
+    /// document order, including QUERY and `additionalOperations` methods
+    pub fn operations(&self) -> impl Iterator<Item = (&str, &str, &PathBase)> {
+        self.paths.iter().flat_map(|(path, path_item)| {
+            path_item
+                .all_operations()
+                .map(move |(method, operation)| (path.as_str(), method, operation))
+        })
+    }
+
+    /// Resolves the path item for a request path, either a path of the
+    /// document such as `/users/{id}` or a concrete one like `/users/42`. With
+    /// [`ValidationOptions::strip_base_path`], a path that matches no entry
+    /// as-is is retried with each server's base path removed, so `/v2/users`
+    /// resolves to `/users` under `servers: [{url: https://api.example.com/v2}]`.
+    /// Templated base paths such as `/{version}` match through their variables
+    pub fn path_item(&self, path: &str) -> Option<&PathItem> {
+        self.resolve_path(path).map(|(_, path_item)| path_item)
+    }
+
+    /// Like [`OpenAPI::path_item`], also returning the path as declared in the
+    /// document
+    pub fn resolve_path(&self, path: &str) -> Option<(&str, &PathItem)> {
+        let route = match self.paths.get_key_value(path) {
+            Some((path, _)) => path.as_str(),
+            None => self.match_path(path).map(|route| route.path)?,
+        };
+        let (path, path_item) = self.paths.get_key_value(route)?;
+        Some((path.as_str(), path_item))
+    }
+
+    /// Matches a concrete request path such as `/users/42` against the
+    /// templated paths of the document, binding the template variables.
+    /// Server base paths are stripped as in [`OpenAPI::path_item`].
+    pub fn match_path(&self, path: &str) -> Option<RouteMatch<'_>> {
+        let router = self.compiled().router();
+        if let Some(route) = router.at(path) {
+            return Some(route);
+        }
+        if !self.options.strip_base_path {
+            return None;
+        }
+
+        self.servers
+            .iter()
+            .filter_map(|server| server.matches(None, path))
+            .find_map(|server| router.at(&server.path))
+    }
+
+    /// The validation plan of the document: resolved and merged parameters
+    /// per operation, resolved component schemas and precompiled patterns. [`OpenAPI::yaml`] builds it
+    /// while loading, and [`OpenAPI::paths_mut`] and
+    /// [`OpenAPI::components_mut`] discard it for the next use to rebuild.
+    pub fn compiled(&self) -> &CompiledSpec {
+        self.compiled.get_or_init(|| CompiledSpec::build(self))
+    }
+
+    /// The validation plan of the `method` operation on `path`
+    pub fn compiled_operation(&self, path: &str, method: &str) -> Option<&CompiledOperation> {
+        let (path, _) = self.resolve_path(path)?;
+        self.compiled().operation(path, method)
+    }
+
+    /// Follows a `#/components/responses/...` reference, returning `response`
+    /// itself when it is not a reference or the target does not exist
+    pub fn resolve_response<'a>(&'a self, response: &'a Response) -> &'a Response {
+        response
+            .r#ref
+            .as_deref()
+            .and_then(|reference| reference.strip_prefix("#/components/responses/"))
+            .and_then(|name| self.components.as_ref()?.responses.get(name))
+            .unwrap_or(response)
+    }
+
+    /// Follows a `#/components/callbacks/...` reference, returning `callback`
+    /// itself when it is not a reference or the target does not exist
+    pub fn resolve_callback<'a>(&'a self, callback: &'a Callback) -> &'a Callback {
+        callback
+            .r#ref
+            .as_deref()
+            .and_then(|reference| reference.strip_prefix("#/components/callbacks/"))
+            .and_then(|name| self.components.as_ref()?.callbacks.get(name))
+            .unwrap_or(callback)
+    }
+
+    /// Follows a `#/components/examples/...` reference, returning `example`
+    /// itself when it is not a reference or the target does not exist
+    pub fn resolve_example<'a>(&'a self, example: &'a Example) -> &'a Example {
+        example
+            .r#ref
+            .as_deref()
+            .and_then(|reference| reference.strip_prefix("#/components/examples/"))
+            .and_then(|name| self.components.as_ref()?.examples.get(name))
+            .unwrap_or(example)
+    }
+
+    /// Looks up an operation by its `operationId`, returning its path and method too
+    pub fn operation_by_id(&self, operation_id: &str) -> Option<(&str, &str, &PathBase)> {
+        self.operations()
+            .find(|(_, _, operation)| operation.operation_id.as_deref() == Some(operation_id))
+    }
+
     pub fn validator(&self, valid: impl ValidateRequest) -> Result<(), String> {
-        let metrics = ValidationMetrics::from_context(&valid.context());
+        self.validate(valid).map_err(|error| error.to_string())
+    }
+
+    /// Validates a request like [`OpenAPI::validator`], keeping the
+    /// [`MethodNotAllowed`] and [`Unauthorized`] errors recoverable with
+    /// `anyhow::Error::downcast_ref`, for middlewares to answer with `405` or
+    /// `401`. Other errors carry the redacted message only.
+    ///
+    /// [`MethodNotAllowed`]: crate::validator::MethodNotAllowed
+    /// [`Unauthorized`]: crate::validator::Unauthorized
+    pub fn validate(&self, valid: impl ValidateRequest) -> anyhow::Result<()> {
+        let context = valid.context();
+        let mut metrics = ValidationMetrics::from_context(&context);
+        match self.matched_operation(&context) {
+            Some((template, Some(operation))) => {
+                metrics.record_operation(template, operation.operation_id.as_deref())
+            }
+            Some((template, None)) => metrics.record_path(template),
+            None => {}
+        }
 
-        let result = self.perform_validation(valid);
+        let secrets = valid.secrets(&self.options);
+        let result = self.perform_validation(valid).map_err(|error| {
+            let message = redact::redact(&error.to_string(), &secrets);
+            match error.downcast::<MethodNotAllowed>() {
+                Ok(not_allowed) => anyhow::Error::new(not_allowed).context(message),
+                Err(error) => match error.downcast::<Unauthorized>() {
+                    Ok(unauthorized) => anyhow::Error::new(unauthorized).context(message),
+                    Err(_) => anyhow::Error::msg(message),
+                },
+            }
+        });
 
         match &result {
             Ok(_) => metrics.record_success(),
-            Err(err) => metrics.record_failure(err.clone()),
+            Err(err) => metrics.record_failure(err.to_string()),
         }
 
         result
     }
 
-    fn perform_validation(&self, valid: impl ValidateRequest) -> Result<(), String> {
+    /// Validates a request like [`OpenAPI::validator`], but runs every check
+    /// instead of stopping at the first failed one and reports what it found
+    /// rather than logging it
+    pub fn validation_report(&self, valid: impl ValidateRequest) -> ValidationReport {
+        #[cfg(feature = "clock")]
+        let start = std::time::Instant::now();
+        let context = valid.context();
+        let secrets = valid.secrets(&self.options);
+
+        let (errors, warnings) = observability::collect_warnings(|| {
+            compiled::with_patterns(self, || {
+                if let Err(error) = self.require_document() {
+                    return vec![Issue::new("", error)];
+                }
+                let mut errors = Vec::new();
+                for (phase, label, check) in checks() {
+                    if let Err(e) = check(&valid, self) {
+                        errors.push(Issue::new(
+                            format!("/{phase}"),
+                            format!("{label} validation failed: {e}"),
+                        ));
+                        // Without an operation, every other check fails as well
+                        if phase == "method" {
+                            break;
+                        }
+                    }
+                }
+                errors
+            })
+        });
+        let redacted = |issues: Vec<Issue>| {
+            issues
+                .into_iter()
+                .map(|issue| Issue::new(issue.pointer, redact::redact(&issue.message, &secrets)))
+                .collect::<Vec<_>>()
+        };
+
+        let operation = self
+            .matched_operation(&context)
+            .and_then(|(template, operation)| {
+                operation.map(|operation| MatchedOperation {
+                    path: template.to_string(),
+                    method: context.method.to_ascii_uppercase(),
+                    operation_id: operation.operation_id.clone(),
+                })
+            });
+        ValidationReport {
+            valid: errors.is_empty(),
+            method: context.method,
+            path: context.path,
+            request_id: context.request_id,
+            operation,
+            errors: redacted(errors),
+            warnings: redacted(warnings),
+            #[cfg(feature = "clock")]
+            duration_us: Some(start.elapsed().as_micros() as u64),
+            #[cfg(not(feature = "clock"))]
+            duration_us: None,
+        }
+    }
+
+    /// The path template `context` resolves to, with the operation declared
+    /// for its method if there is one
+    fn matched_operation(&self, context: &RequestContext) -> Option<(&str, Option<&PathBase>)> {
+        let path = context.path.split('?').next().unwrap_or_default();
+        let (template, path_item) = self.resolve_path(path)?;
+        let method = context.method.to_ascii_lowercase();
+        Some((
+            template,
+            crate::validator::find_operation(path_item, &method),
+        ))
+    }
+
+    fn require_document(&self) -> Result<(), String> {
         require_non_empty!(self.openapi, "OpenAPI version is required");
         require_non_empty!(self.info.title, "Title is required");
         require_non_empty!(self.info.version, "Version is required");
         require_non_empty!(self.paths, "Paths are required");
-        valid
-            .method(self)
-            .map_err(|e| format!("Method validation failed: {e}"))?;
-        valid
-            .path(self)
-            .map_err(|e| format!("Path validation failed: {e}"))?;
-        valid
-            .query(self)
-            .map_err(|e| format!("Query validation failed: {e}"))?;
-        valid
-            .body(self)
-            .map_err(|e| format!("Body validation failed: {e}"))?;
         Ok(())
     }
+
+    fn perform_validation<V: ValidateRequest>(&self, valid: V) -> anyhow::Result<()> {
+        self.require_document().map_err(anyhow::Error::msg)?;
+        compiled::with_patterns(self, || {
+            for (phase, label, check) in checks() {
+                observability::phase(phase, || check(&valid, self)).map_err(|e| {
+                    let message = format!("{label} validation failed: {e}");
+                    e.context(message)
+                })?;
+            }
+            Ok(())
+        })
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SecurityRequirementObject {
-    #[serde(rename = "type", default)]
-    pub _type: String,
-    pub scheme: Option<String>,
+type Check<V> = fn(&V, &OpenAPI) -> anyhow::Result<()>;
+
+/// The checks a request goes through, in order, with the phase naming their
+/// span and report pointer, and the label their errors are prefixed with
+fn checks<V: ValidateRequest>() -> [(&'static str, &'static str, Check<V>); 5] {
+    [
+        ("method", "Method", V::method),
+        ("security", "Security", V::security),
+        ("path", "Path", V::path),
+        ("query", "Query", V::query),
+        ("body", "Body", V::body),
+    ]
+}
+
+/// The major and minor parts of a version such as `3.1.0` or `3.2`
+fn minor_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split('.');
+    Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+}
+
+/// Expands YAML merge keys (`<<: *anchor` or `<<: [*a, *b]`) into the
+/// surrounding mapping, where explicit keys win over merged ones and earlier
+/// sources over later ones. Merged mappings may use merge keys themselves.
+/// Returns whether any merge key was found.
+pub fn expand_merge_keys(value: &mut serde_yaml::Value) -> Result<bool, serde_yaml::Error> {
+    use serde_yaml::Value;
+
+    let mut merged = false;
+    match value {
+        Value::Mapping(mapping) => {
+            if let Some(mut merge) = mapping.remove("<<") {
+                merged = true;
+                expand_merge_keys(&mut merge)?;
+                let sources = match merge {
+                    Value::Sequence(sources) => sources,
+                    source => vec![source],
+                };
+                for source in sources {
+                    let Value::Mapping(source) = source else {
+                        return Err(serde_yaml::Error::custom(
+                            "merge key '<<' must refer to a mapping or a list of mappings",
+                        ));
+                    };
+                    for (key, value) in source {
+                        mapping.entry(key).or_insert(value);
+                    }
+                }
+            }
+            for value in mapping.values_mut() {
+                merged |= expand_merge_keys(value)?;
+            }
+        }
+        Value::Sequence(values) => {
+            for value in values {
+                merged |= expand_merge_keys(value)?;
+            }
+        }
+        Value::Tagged(tagged) => merged |= expand_merge_keys(&mut tagged.value)?,
+        _ => {}
+    }
+    Ok(merged)
+}
+
+/// Names of security schemes that must all be satisfied, each mapped to the
+/// scopes it requires. An empty object makes security optional.
+pub type SecurityRequirementObject = IndexMap<String, Vec<String>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityScheme {
+    #[serde(rename = "type")]
+    pub r#type: SecuritySchemeType,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    // apiKey
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "in", skip_serializing_if = "Option::is_none")]
+    pub r#in: Option<In>,
+    // http
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
+    #[serde(rename = "bearerFormat", skip_serializing_if = "Option::is_none")]
+    pub bearer_format: Option<String>,
+    // oauth2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flows: Option<OAuthFlows>,
+    // openIdConnect
+    #[serde(rename = "openIdConnectUrl", skip_serializing_if = "Option::is_none")]
+    pub open_id_connect_url: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SecuritySchemeType {
+    #[serde(rename = "apiKey")]
+    ApiKey,
+    #[serde(rename = "http")]
+    Http,
+    #[serde(rename = "oauth2")]
+    OAuth2,
+    #[serde(rename = "openIdConnect")]
+    OpenIdConnect,
+    #[serde(rename = "mutualTLS")]
+    MutualTLS,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthFlows {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implicit: Option<OAuthFlow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<OAuthFlow>,
+    #[serde(rename = "clientCredentials", skip_serializing_if = "Option::is_none")]
+    pub client_credentials: Option<OAuthFlow>,
+    #[serde(rename = "authorizationCode", skip_serializing_if = "Option::is_none")]
+    pub authorization_code: Option<OAuthFlow>,
+
+    // === OpenAPI 3.2 field ===
+    #[serde(
+        rename = "deviceAuthorization",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub device_authorization: Option<OAuthFlow>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthFlow {
+    #[serde(rename = "authorizationUrl", skip_serializing_if = "Option::is_none")]
+    pub authorization_url: Option<String>,
+    #[serde(rename = "tokenUrl", skip_serializing_if = "Option::is_none")]
+    pub token_url: Option<String>,
+    #[serde(rename = "refreshUrl", skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<String>,
+    #[serde(
+        rename = "deviceAuthorizationUrl",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub device_authorization_url: Option<String>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub scopes: IndexMap<String, String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InfoObject {
     pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub version: String,
+    #[serde(rename = "termsOfService", skip_serializing_if = "Option::is_none")]
+    pub terms_of_service: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<ContactObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<LicenseObject>,
 
     // === OpenAPI 3.2 field ===
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactObject {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseObject {
+    pub name: String,
+    /// An SPDX license expression, mutually exclusive with `url` (3.1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+/// A tag used to group operations, see the document-level `tags`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagObject {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+
+    // === OpenAPI 3.2 fields ===
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Name of the tag this one is nested under
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Free-form category such as `nav`, `badge` or `audience`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalDocumentation {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerObject {
     pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Substitutions for the `{name}` placeholders of `url`
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub variables: IndexMap<String, ServerVariable>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerVariable {
+    /// Restricts the substitution to these values
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub r#enum: Option<Vec<String>>,
+    pub default: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PathBase {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[serde(rename = "operationId")]
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+    #[serde(rename = "operationId", skip_serializing_if = "Option::is_none")]
     pub operation_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<Parameter>>,
-    #[serde(rename = "requestBody")]
+    #[serde(rename = "requestBody", skip_serializing_if = "Option::is_none")]
     pub request: Option<Request>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<ServerObject>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deprecated: bool,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_responses",
+        skip_serializing_if = "IndexMap::is_empty"
+    )]
+    pub responses: IndexMap<String, Response>,
+    /// Overrides the document-level `security`; an empty list disables it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirementObject>>,
+    /// Out-of-band requests the API makes in response to this operation
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub callbacks: IndexMap<String, Callback>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A callback object: path items keyed by a runtime expression such as
+/// `{$request.body#/callbackUrl}`, or a reference to `components.callbacks`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Callback {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
+    #[serde(flatten)]
+    pub expressions: IndexMap<String, PathItem>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// Response keys are status codes, which YAML happily parses as integers
+/// when left unquoted (`200:`), so normalise them back to strings.
+fn deserialize_responses<'de, D>(deserializer: D) -> Result<IndexMap<String, Response>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    IndexMap::<serde_yaml::Value, Response>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(code, response)| match code {
+            serde_yaml::Value::String(code) => Ok((code, response)),
+            serde_yaml::Value::Number(code) => Ok((code.to_string(), response)),
+            other => Err(D::Error::custom(format!(
+                "invalid response status code: {other:?}"
+            ))),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Parameter {
-    #[serde(rename = "$ref")]
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
     pub r#ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
-    #[serde(rename = "in")]
+    #[serde(rename = "in", skip_serializing_if = "Option::is_none")]
     pub r#in: Option<In>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
     pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_yaml::Value>,
-    #[serde(rename = "type")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub examples: IndexMap<String, Example>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub r#type: Option<TypeOrUnion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub r#enum: Option<Vec<serde_yaml::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<Box<Schema>>,
+    /// Media type and schema of the value, used instead of `schema` (e.g. `in: querystring`)
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub content: IndexMap<String, BaseContent>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deprecated: bool,
+    #[serde(rename = "allowEmptyValue", default, skip_serializing_if = "is_false")]
+    pub allow_empty_value: bool,
+    #[serde(rename = "allowReserved", default, skip_serializing_if = "is_false")]
+    pub allow_reserved: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<Style>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
     #[serde(flatten)]
-    pub extra: HashMap<String, serde_yaml::Value>,
+    pub extra: IndexMap<String, serde_yaml::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Schema {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub r#type: Option<TypeOrUnion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<Format>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub r#enum: Option<Vec<serde_yaml::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
-    pub properties: Option<HashMap<String, Properties>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<IndexMap<String, Properties>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_yaml::Value>,
-    pub examples: Option<Vec<String>>,
-    #[serde(rename = "$ref")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<serde_yaml::Value>>,
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
     pub r#ref: Option<String>,
-    #[serde(rename = "allOf")]
+    #[serde(rename = "allOf", skip_serializing_if = "Option::is_none")]
     pub all_of: Option<Vec<ComponentProperties>>,
-    #[serde(rename = "oneOf")]
+    #[serde(rename = "oneOf", skip_serializing_if = "Option::is_none")]
     pub one_of: Option<Vec<ComponentProperties>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<Schema>>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub required: Vec<String>,
-    #[serde(rename = "minItems")]
+    #[serde(rename = "minItems", skip_serializing_if = "Option::is_none")]
     pub min_items: Option<u64>,
-    #[serde(rename = "maxItems")]
+    #[serde(rename = "maxItems", skip_serializing_if = "Option::is_none")]
     pub max_items: Option<u64>,
-    #[serde(rename = "minLength")]
+    #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
     pub min_length: Option<u64>,
-    #[serde(rename = "maxLength")]
+    #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
     pub max_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub maximum: Option<f64>,
+    #[serde(rename = "exclusiveMinimum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<f64>,
+    #[serde(rename = "exclusiveMaximum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<f64>,
+    #[serde(rename = "contentEncoding", skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+    #[serde(rename = "contentMediaType", skip_serializing_if = "Option::is_none")]
+    pub content_media_type: Option<String>,
+    #[serde(rename = "contentSchema", skip_serializing_if = "Option::is_none")]
+    pub content_schema: Option<Box<Schema>>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BaseContent {
+    #[serde(default)]
     pub schema: Schema,
+    /// Schema of each item of a sequential media type such as `application/jsonl` (3.2)
+    #[serde(rename = "itemSchema", skip_serializing_if = "Option::is_none")]
+    pub item_schema: Option<Schema>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub encoding: IndexMap<String, Encoding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_yaml::Value>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub examples: IndexMap<String, Example>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Encoding {
+    /// Comma-separated media types accepted for the property, e.g. `image/png, image/*`
+    #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Request {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub required: bool,
+    pub content: IndexMap<String, BaseContent>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Response headers keyed by name; `Content-Type` is ignored when present
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<String, Header>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub content: IndexMap<String, BaseContent>,
+    /// Operations reachable from this response, keyed by link name
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub links: IndexMap<String, Link>,
+
+    // === OpenAPI 3.2 field ===
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+/// A header object: a parameter without `name` and `in`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Header {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
     pub required: bool,
-    pub content: HashMap<String, BaseContent>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deprecated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Box<Schema>>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub content: IndexMap<String, BaseContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<Style>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_yaml::Value>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub examples: IndexMap<String, Example>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+/// A named example of a parameter, header or media type value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Example {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The literal example, mutually exclusive with `externalValue`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_yaml::Value>,
+    /// URL of an example that cannot be embedded, such as a binary file
+    #[serde(rename = "externalValue", skip_serializing_if = "Option::is_none")]
+    pub external_value: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
+}
+
+/// A design-time link from a response to another operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Link {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
+    #[serde(rename = "operationRef", skip_serializing_if = "Option::is_none")]
+    pub operation_ref: Option<String>,
+    #[serde(rename = "operationId", skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// Parameter values or runtime expressions such as `$response.body#/id`
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub parameters: IndexMap<String, serde_yaml::Value>,
+    #[serde(rename = "requestBody", skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<serde_yaml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<ServerObject>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -220,74 +1092,160 @@ pub enum SchemaOption {
     AllOf,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentSchemaBase {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<serde_yaml::Value>>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub r#type: Option<TypeOrUnion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<ComponentSchemaBase>>,
-    pub properties: Option<HashMap<String, Properties>>,
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<IndexMap<String, Properties>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub required: Vec<String>,
-    #[serde(rename = "allOf")]
+    #[serde(rename = "allOf", skip_serializing_if = "Option::is_none")]
     pub all_of: Option<Vec<ComponentProperties>>,
-    #[serde(rename = "oneOf")]
+    #[serde(rename = "oneOf", skip_serializing_if = "Option::is_none")]
     pub one_of: Option<Vec<ComponentProperties>>,
-    #[serde(rename = "minItems")]
+    #[serde(rename = "minItems", skip_serializing_if = "Option::is_none")]
     pub min_items: Option<u64>,
-    #[serde(rename = "maxItems")]
+    #[serde(rename = "maxItems", skip_serializing_if = "Option::is_none")]
     pub max_items: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Format>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#enum: Option<Vec<serde_yaml::Value>>,
+    #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u64>,
+    #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+    #[serde(rename = "exclusiveMinimum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<f64>,
+    #[serde(rename = "exclusiveMaximum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<f64>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentProperties {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub r#type: Option<TypeOrUnion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[serde(default)]
-    pub properties: HashMap<String, Properties>,
-    #[serde(rename = "$ref")]
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub properties: IndexMap<String, Properties>,
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
     pub r#ref: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Properties {
-    #[serde(rename = "type")]
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub r#ref: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub r#type: Option<TypeOrUnion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<Format>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_yaml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<serde_yaml::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
-    #[serde(rename = "minLength")]
+    #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
     pub min_length: Option<u64>,
-    #[serde(rename = "maxLength")]
+    #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
     pub max_length: Option<u64>,
-    #[serde(rename = "minItems")]
+    #[serde(rename = "minItems", skip_serializing_if = "Option::is_none")]
     pub min_items: Option<u64>,
-    #[serde(rename = "maxItems")]
+    #[serde(rename = "maxItems", skip_serializing_if = "Option::is_none")]
     pub max_items: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub maximum: Option<f64>,
+    #[serde(rename = "exclusiveMinimum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<f64>,
+    #[serde(rename = "exclusiveMaximum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<Properties>>,
-    pub properties: Option<HashMap<String, Properties>>,
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<IndexMap<String, Properties>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub required: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub r#enum: Option<Vec<serde_yaml::Value>>,
+    #[serde(rename = "readOnly", default, skip_serializing_if = "is_false")]
+    pub read_only: bool,
+    #[serde(rename = "writeOnly", default, skip_serializing_if = "is_false")]
+    pub write_only: bool,
+    #[serde(rename = "contentEncoding", skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+    #[serde(rename = "contentMediaType", skip_serializing_if = "Option::is_none")]
+    pub content_media_type: Option<String>,
+    #[serde(rename = "contentSchema", skip_serializing_if = "Option::is_none")]
+    pub content_schema: Option<Box<Schema>>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentsObject {
-    #[serde(default)]
-    pub schemas: HashMap<String, ComponentSchemaBase>,
-    #[serde(default)]
-    pub parameters: HashMap<String, Parameter>,
-    #[serde(rename = "requestBodies", default)]
-    pub request_bodies: HashMap<String, Request>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub schemas: IndexMap<String, ComponentSchemaBase>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub parameters: IndexMap<String, Parameter>,
+    #[serde(
+        rename = "requestBodies",
+        default,
+        skip_serializing_if = "IndexMap::is_empty"
+    )]
+    pub request_bodies: IndexMap<String, Request>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub responses: IndexMap<String, Response>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<String, Header>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub links: IndexMap<String, Link>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub callbacks: IndexMap<String, Callback>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub examples: IndexMap<String, Example>,
+    #[serde(
+        rename = "securitySchemes",
+        default,
+        skip_serializing_if = "IndexMap::is_empty"
+    )]
+    pub security_schemes: IndexMap<String, SecurityScheme>,
+
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>, // Catches any other fields
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all(deserialize = "lowercase"))]
+#[serde(rename_all = "lowercase")]
 pub enum Type {
     Object,
     String,
@@ -307,8 +1265,22 @@ pub enum TypeOrUnion {
     Union(Vec<Type>),
 }
 
+/// Parameter serialization style, see the OpenAPI `style` field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Style {
+    Matrix,
+    Label,
+    Simple,
+    Form,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+    Cookie,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all(deserialize = "lowercase"))]
+#[serde(rename_all = "lowercase")]
 pub enum In {
     Query,
     #[serde(rename = "querystring")]
@@ -319,7 +1291,7 @@ pub enum In {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all(deserialize = "lowercase"))]
+#[serde(rename_all = "lowercase")]
 pub enum Format {
     URI,
     #[serde(rename = "uri-reference")]
@@ -330,6 +1302,7 @@ pub enum Format {
     Date,
     #[serde(rename = "date-time")]
     DateTime,
+    Duration,
     UUID,
     Hostname,
     IPV4,
@@ -347,6 +1320,9 @@ pub enum Format {
     Svg,
     #[serde(rename = "url")]
     Url,
-    #[serde(other)]
-    Unknown,
+    Byte,
+    #[serde(rename = "base64url")]
+    Base64Url,
+    #[serde(untagged)]
+    Unknown(String),
 }
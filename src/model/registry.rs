@@ -0,0 +1,105 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Several documents served from one middleware, each mounted at a path
+//! prefix.
+
+use crate::model::shared::SharedOpenAPI;
+
+/// The document a request path was routed to, see [`SpecRegistry::route`].
+#[derive(Debug)]
+pub struct SpecMatch<'a> {
+    /// The prefix the document is mounted at, empty for the root
+    pub prefix: &'a str,
+    pub spec: &'a SharedOpenAPI,
+    /// The request path relative to `prefix`, always starting with `/`
+    pub path: String,
+}
+
+/// Documents keyed by the path prefix they are mounted at, such as
+/// `/billing` and `/users`, so that a gateway validates the requests of
+/// several upstream services. Requests go to the longest prefix matching
+/// whole segments of their path.
+#[derive(Debug, Clone, Default)]
+pub struct SpecRegistry {
+    /// Sorted by decreasing prefix length
+    specs: Vec<(String, SharedOpenAPI)>,
+}
+
+impl SpecRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts `spec` at `prefix`, see [`SpecRegistry::insert`]
+    pub fn with(mut self, prefix: &str, spec: impl Into<SharedOpenAPI>) -> Self {
+        self.insert(prefix, spec);
+        self
+    }
+
+    /// Mounts `spec` at `prefix`; `""` or `/` mount it at the root. Replaces
+    /// the document previously mounted at the same prefix.
+    pub fn insert(&mut self, prefix: &str, spec: impl Into<SharedOpenAPI>) {
+        let prefix = normalize(prefix);
+        self.specs.retain(|(known, _)| *known != prefix);
+        let index = self
+            .specs
+            .partition_point(|(known, _)| known.len() >= prefix.len());
+        self.specs.insert(index, (prefix, spec.into()));
+    }
+
+    /// The document mounted at exactly `prefix`
+    pub fn get(&self, prefix: &str) -> Option<&SharedOpenAPI> {
+        let prefix = normalize(prefix);
+        self.specs
+            .iter()
+            .find(|(known, _)| *known == prefix)
+            .map(|(_, spec)| spec)
+    }
+
+    /// Finds the document a request for `path` belongs to
+    pub fn route(&self, path: &str) -> Option<SpecMatch<'_>> {
+        self.specs.iter().find_map(|(prefix, spec)| {
+            let rest = path.strip_prefix(prefix.as_str())?;
+            if !rest.is_empty() && !rest.starts_with('/') {
+                return None;
+            }
+            Some(SpecMatch {
+                prefix,
+                spec,
+                path: if rest.is_empty() { "/" } else { rest }.to_string(),
+            })
+        })
+    }
+
+    /// Every mounted prefix and document, longest prefix first
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SharedOpenAPI)> {
+        self.specs
+            .iter()
+            .map(|(prefix, spec)| (prefix.as_str(), spec))
+    }
+}
+
+/// `/billing/` and `billing` both mount at `/billing`
+fn normalize(prefix: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("/{prefix}")
+    }
+}
@@ -0,0 +1,161 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Segment trie matching concrete request paths against the templated paths
+//! of a document.
+
+use crate::model::server::{tokens, Token};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// A templated path matched by [`PathRouter::at`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteMatch<'r> {
+    /// The path as declared in the document, e.g. `/users/{id}`
+    pub path: &'r str,
+    /// Values of the path template variables, e.g. `id` → `42`
+    pub params: IndexMap<String, String>,
+}
+
+/// Routes request paths to document paths one segment at a time. Literal
+/// segments take precedence over templated ones, as the specification
+/// requires for `/users/me` and `/users/{id}`.
+#[derive(Debug, Default)]
+pub struct PathRouter {
+    root: Node,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    literals: HashMap<String, Node>,
+    templates: Vec<(Vec<Part>, Node)>,
+    /// The document path ending at this node
+    path: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Variable(String),
+}
+
+impl PathRouter {
+    pub fn new<'a>(paths: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut router = PathRouter::default();
+        for path in paths {
+            router.insert(path);
+        }
+        router
+    }
+
+    pub fn insert(&mut self, path: &str) {
+        let mut node = &mut self.root;
+        for segment in segments(path) {
+            if !segment.contains('{') {
+                node = node.literals.entry(segment.to_string()).or_default();
+                continue;
+            }
+
+            let parts: Vec<Part> = tokens(segment)
+                .into_iter()
+                .map(|token| match token {
+                    Token::Literal(literal) => Part::Literal(literal.to_string()),
+                    Token::Variable(name) => Part::Variable(name.to_string()),
+                })
+                .collect();
+            let index = match node.templates.iter().position(|(known, _)| *known == parts) {
+                Some(index) => index,
+                None => {
+                    node.templates.push((parts, Node::default()));
+                    node.templates.len() - 1
+                }
+            };
+            node = &mut node.templates[index].1;
+        }
+        node.path = Some(path.to_string());
+    }
+
+    /// Finds the document path matching the concrete request path `path`
+    pub fn at(&self, path: &str) -> Option<RouteMatch<'_>> {
+        let segments: Vec<&str> = segments(path).collect();
+        let mut params = IndexMap::new();
+        let path = self.root.find(&segments, &mut params)?;
+        Some(RouteMatch { path, params })
+    }
+}
+
+impl Node {
+    fn find(&self, segments: &[&str], params: &mut IndexMap<String, String>) -> Option<&str> {
+        let Some((segment, rest)) = segments.split_first() else {
+            return self.path.as_deref();
+        };
+
+        if let Some(found) = self
+            .literals
+            .get(*segment)
+            .and_then(|child| child.find(rest, params))
+        {
+            return Some(found);
+        }
+
+        for (parts, child) in &self.templates {
+            let bound = params.len();
+            if bind(parts, segment, params) {
+                if let Some(found) = child.find(rest, params) {
+                    return Some(found);
+                }
+            }
+            params.truncate(bound);
+        }
+        None
+    }
+}
+
+/// Matches one segment against a template such as `{name}.{ext}`; every
+/// variable takes at least one character, up to the next literal.
+fn bind(parts: &[Part], mut segment: &str, params: &mut IndexMap<String, String>) -> bool {
+    for (index, part) in parts.iter().enumerate() {
+        match part {
+            Part::Literal(literal) => match segment.strip_prefix(literal.as_str()) {
+                Some(rest) => segment = rest,
+                None => return false,
+            },
+            Part::Variable(name) => {
+                let end = match parts.get(index + 1) {
+                    Some(Part::Literal(next)) => {
+                        let first = segment.chars().next().map_or(0, char::len_utf8);
+                        match segment[first..].find(next.as_str()) {
+                            Some(position) => first + position,
+                            None => return false,
+                        }
+                    }
+                    _ => segment.len(),
+                };
+                if end == 0 {
+                    return false;
+                }
+                params.insert(name.clone(), segment[..end].to_string());
+                segment = &segment[end..];
+            }
+        }
+    }
+    segment.is_empty()
+}
+
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.strip_prefix('/').unwrap_or(path).split('/')
+}
@@ -0,0 +1,201 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Server URL templates: variable expansion and matching requests against
+//! the declared servers.
+
+use crate::model::parse::{OpenAPI, ServerObject};
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// The server a request was addressed to, see [`OpenAPI::server_for`].
+#[derive(Debug)]
+pub struct ServerMatch<'a> {
+    pub server: &'a ServerObject,
+    /// Values the request host and path bound to the server variables
+    pub variables: IndexMap<String, String>,
+    /// The request path relative to the server URL, always starting with `/`
+    pub path: String,
+}
+
+pub(crate) enum Token<'a> {
+    Literal(&'a str),
+    Variable(&'a str),
+}
+
+impl ServerObject {
+    /// Expands `url`, taking variable values from `values` and falling back
+    /// to their defaults. Fails on undeclared variables and on values outside
+    /// a variable's `enum`.
+    pub fn expand(&self, values: &HashMap<&str, &str>) -> Result<String> {
+        let mut url = String::with_capacity(self.url.len());
+        for token in tokens(&self.url) {
+            match token {
+                Token::Literal(literal) => url.push_str(literal),
+                Token::Variable(name) => {
+                    let variable = self
+                        .variables
+                        .get(name)
+                        .ok_or_else(|| anyhow!("Server variable '{name}' is not declared"))?;
+                    let value = values.get(name).copied().unwrap_or(&variable.default);
+                    if let Some(allowed) = &variable.r#enum {
+                        if !allowed.iter().any(|allowed| allowed == value) {
+                            return Err(anyhow!(
+                                "Value '{value}' of server variable '{name}' is not one of {allowed:?}"
+                            ));
+                        }
+                    }
+                    url.push_str(value);
+                }
+            }
+        }
+        Ok(url)
+    }
+
+    /// The URL with every variable set to its default
+    pub fn default_url(&self) -> Result<String> {
+        self.expand(&HashMap::new())
+    }
+
+    /// The path component of `url` without a trailing slash, e.g. `/v2` for
+    /// `https://api.example.com/v2/`. `None` when the server is mounted at
+    /// the root or its path uses server variables.
+    pub fn base_path(&self) -> Option<&str> {
+        let path = split_url(&self.url).1;
+        (!path.is_empty() && !path.contains('{')).then_some(path)
+    }
+
+    /// Checks whether a request for `path` on `host` (when known) was
+    /// addressed to this server. Variables match any single host label or
+    /// path segment, or one of their `enum` values.
+    pub fn matches(&self, host: Option<&str>, path: &str) -> Option<ServerMatch<'_>> {
+        let (host_template, path_template) = split_url(&self.url);
+        let mut variables = IndexMap::new();
+
+        if let (Some(mut host), Some(template)) = (host, host_template) {
+            if !template.contains(':') {
+                host = host.split(':').next().unwrap_or(host);
+            }
+            let (regex, names) = self.template_regex(template, "[^./]+", "(?i)^", "$")?;
+            bind(&regex, &names, host, &mut variables)?;
+        }
+
+        let (regex, names) = self.template_regex(path_template, "[^/]+", "^", "(/.*)?$")?;
+        let rest = bind(&regex, &names, path, &mut variables)?;
+        Some(ServerMatch {
+            server: self,
+            variables,
+            path: rest.map_or_else(|| "/".to_string(), str::to_string),
+        })
+    }
+
+    fn template_regex<'t>(
+        &self,
+        template: &'t str,
+        any: &str,
+        prefix: &str,
+        suffix: &str,
+    ) -> Option<(Regex, Vec<&'t str>)> {
+        let mut pattern = prefix.to_string();
+        let mut names = vec![];
+        for token in tokens(template) {
+            match token {
+                Token::Literal(literal) => pattern.push_str(&regex::escape(literal)),
+                Token::Variable(name) => {
+                    let alternatives = match self.variables.get(name)?.r#enum.as_deref() {
+                        Some(values) => values
+                            .iter()
+                            .map(|value| regex::escape(value))
+                            .collect::<Vec<_>>()
+                            .join("|"),
+                        None => any.to_string(),
+                    };
+                    pattern.push_str(&format!("({alternatives})"));
+                    names.push(name);
+                }
+            }
+        }
+        pattern.push_str(suffix);
+        Some((Regex::new(&pattern).ok()?, names))
+    }
+}
+
+impl OpenAPI {
+    /// The first server of the document a request for `path` on `host`
+    /// (when known) was addressed to
+    pub fn server_for(&self, host: Option<&str>, path: &str) -> Option<ServerMatch<'_>> {
+        self.servers
+            .iter()
+            .find_map(|server| server.matches(host, path))
+    }
+}
+
+/// Splits a server URL into its host (absent for relative URLs) and its path
+/// without a trailing slash.
+fn split_url(url: &str) -> (Option<&str>, &str) {
+    let (host, path) = match url.split_once("://") {
+        Some((_, rest)) => match rest.find('/') {
+            Some(index) => (Some(&rest[..index]), &rest[index..]),
+            None => (Some(rest), ""),
+        },
+        None => (None, url),
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    (host, path.trim_end_matches('/'))
+}
+
+pub(crate) fn tokens(template: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Literal(&rest[..start]));
+        }
+        tokens.push(Token::Variable(&rest[start + 1..start + end]));
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Literal(rest));
+    }
+    tokens
+}
+
+/// Matches `input` against a template regex, recording variable values and
+/// returning the unmatched trailing path, if the regex captures one.
+fn bind<'i>(
+    regex: &Regex,
+    names: &[&str],
+    input: &'i str,
+    variables: &mut IndexMap<String, String>,
+) -> Option<Option<&'i str>> {
+    let captures = regex.captures(input)?;
+    for (index, name) in names.iter().enumerate() {
+        let value = captures.get(index + 1)?.as_str();
+        match variables.get(*name) {
+            Some(bound) if bound != value => return None,
+            _ => {
+                variables.insert(name.to_string(), value.to_string());
+            }
+        }
+    }
+    Some(captures.get(names.len() + 1).map(|rest| rest.as_str()))
+}
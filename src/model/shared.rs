@@ -0,0 +1,71 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A document that can be replaced while requests are being validated.
+
+use crate::model::parse::OpenAPI;
+#[cfg(feature = "fs")]
+use anyhow::{Context, Result};
+use arc_swap::ArcSwap;
+#[cfg(feature = "fs")]
+use std::path::Path;
+use std::sync::Arc;
+
+/// A handle on the current document, shared by the middlewares of a server.
+/// Clones see the same document, and [`SharedOpenAPI::replace`] swaps it for
+/// all of them atomically: requests already being validated finish against
+/// the document they started with.
+#[derive(Debug, Clone)]
+pub struct SharedOpenAPI(Arc<ArcSwap<OpenAPI>>);
+
+impl SharedOpenAPI {
+    pub fn new(open_api: OpenAPI) -> Self {
+        Self(Arc::new(ArcSwap::from_pointee(open_api)))
+    }
+
+    /// The current document
+    pub fn load(&self) -> Arc<OpenAPI> {
+        self.0.load_full()
+    }
+
+    /// Makes `open_api` the current document, returning the one it replaces
+    pub fn replace(&self, open_api: OpenAPI) -> Arc<OpenAPI> {
+        self.0.swap(Arc::new(open_api))
+    }
+
+    /// Re-reads the YAML or JSON document at `path` and makes it the current
+    /// one with the current validation options. The current document stays
+    /// in place when `path` cannot be read or parsed.
+    #[cfg(feature = "fs")]
+    pub fn reload(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read '{}'", path.display()))?;
+        let open_api = OpenAPI::yaml(&contents)
+            .with_context(|| format!("Invalid OpenAPI document '{}'", path.display()))?;
+
+        let options = self.load().options.clone();
+        self.replace(open_api.with_options(options));
+        Ok(())
+    }
+}
+
+impl From<OpenAPI> for SharedOpenAPI {
+    fn from(open_api: OpenAPI) -> Self {
+        Self::new(open_api)
+    }
+}
@@ -0,0 +1,482 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Strict parsing: rejects keys that are not part of the OpenAPI specification.
+
+use crate::model::lint::escape;
+use crate::model::parse::{expand_merge_keys, OpenAPI};
+use anyhow::Result;
+use serde_yaml::Value;
+
+/// Keys of a document that the OpenAPI specification does not define, such
+/// as a misspelled `requird:`. Each key is located by its JSON pointer.
+#[derive(Debug)]
+pub struct UnknownKeys {
+    pub pointers: Vec<String>,
+}
+
+impl std::fmt::Display for UnknownKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown keys: {}", self.pointers.join(", "))
+    }
+}
+
+impl std::error::Error for UnknownKeys {}
+
+impl OpenAPI {
+    /// Parses like [`OpenAPI::yaml`], but fails with [`UnknownKeys`] when the
+    /// document contains keys the specification does not define
+    pub fn yaml_strict(contents: &str) -> Result<Self> {
+        let mut document: Value = serde_yaml::from_str(contents)?;
+        expand_merge_keys(&mut document)?;
+        let pointers = unknown_keys(&document);
+        if !pointers.is_empty() {
+            return Err(UnknownKeys { pointers }.into());
+        }
+        Ok(Self::yaml(contents)?)
+    }
+}
+
+/// Lists the JSON pointers of every key in `document` that is neither defined
+/// by the OpenAPI specification nor a `x-` extension.
+pub fn unknown_keys(document: &Value) -> Vec<String> {
+    let mut pointers = Vec::new();
+    walk(document, Kind::Root, "", &mut pointers);
+    pointers
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Root,
+    Info,
+    Contact,
+    License,
+    Server,
+    ServerVariable,
+    Paths,
+    Callback,
+    PathItem,
+    Operation,
+    Parameter,
+    RequestBody,
+    MediaType,
+    Encoding,
+    Responses,
+    Response,
+    Header,
+    Example,
+    Link,
+    Components,
+    SecurityScheme,
+    OAuthFlows,
+    OAuthFlow,
+    Tag,
+    ExternalDocs,
+    Schema,
+    Discriminator,
+    Xml,
+}
+
+/// How the value under a known key is checked.
+enum Child {
+    /// Free-form or scalar content
+    Any,
+    One(Kind),
+    List(Kind),
+    Map(Kind),
+    /// A single object or a list of them, such as schema `items`
+    OneOrList(Kind),
+}
+
+const SCHEMA_FIELDS: &[&str] = &[
+    "$ref",
+    "$id",
+    "$schema",
+    "$anchor",
+    "$dynamicRef",
+    "$dynamicAnchor",
+    "$defs",
+    "$comment",
+    "$vocabulary",
+    "type",
+    "enum",
+    "const",
+    "multipleOf",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "maxContains",
+    "minContains",
+    "maxProperties",
+    "minProperties",
+    "required",
+    "dependentRequired",
+    "properties",
+    "patternProperties",
+    "additionalProperties",
+    "propertyNames",
+    "items",
+    "additionalItems",
+    "prefixItems",
+    "contains",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "not",
+    "if",
+    "then",
+    "else",
+    "dependentSchemas",
+    "definitions",
+    "dependencies",
+    "format",
+    "title",
+    "description",
+    "default",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "examples",
+    "example",
+    "nullable",
+    "discriminator",
+    "xml",
+    "externalDocs",
+    "contentEncoding",
+    "contentMediaType",
+    "contentSchema",
+];
+
+fn fields(kind: Kind) -> &'static [&'static str] {
+    match kind {
+        Kind::Root => &[
+            "openapi",
+            "$self",
+            "info",
+            "jsonSchemaDialect",
+            "servers",
+            "paths",
+            "webhooks",
+            "components",
+            "security",
+            "tags",
+            "externalDocs",
+        ],
+        Kind::Info => &[
+            "title",
+            "summary",
+            "description",
+            "termsOfService",
+            "contact",
+            "license",
+            "version",
+        ],
+        Kind::Contact => &["name", "url", "email"],
+        Kind::License => &["name", "identifier", "url"],
+        Kind::Server => &["url", "name", "description", "variables"],
+        Kind::ServerVariable => &["enum", "default", "description"],
+        Kind::Paths | Kind::Callback | Kind::Responses => &[],
+        Kind::PathItem => &[
+            "$ref",
+            "summary",
+            "description",
+            "get",
+            "put",
+            "post",
+            "delete",
+            "options",
+            "head",
+            "patch",
+            "trace",
+            "query",
+            "additionalOperations",
+            "servers",
+            "parameters",
+        ],
+        Kind::Operation => &[
+            "tags",
+            "summary",
+            "description",
+            "externalDocs",
+            "operationId",
+            "parameters",
+            "requestBody",
+            "responses",
+            "callbacks",
+            "deprecated",
+            "security",
+            "servers",
+        ],
+        Kind::Parameter => &[
+            "name",
+            "in",
+            "description",
+            "required",
+            "deprecated",
+            "allowEmptyValue",
+            "style",
+            "explode",
+            "allowReserved",
+            "schema",
+            "example",
+            "examples",
+            "content",
+        ],
+        Kind::RequestBody => &["description", "content", "required"],
+        Kind::MediaType => &[
+            "schema",
+            "itemSchema",
+            "example",
+            "examples",
+            "encoding",
+            "prefixEncoding",
+            "itemEncoding",
+        ],
+        Kind::Encoding => &[
+            "contentType",
+            "headers",
+            "style",
+            "explode",
+            "allowReserved",
+            "encoding",
+            "prefixEncoding",
+            "itemEncoding",
+        ],
+        Kind::Response => &["summary", "description", "headers", "content", "links"],
+        Kind::Header => &[
+            "description",
+            "required",
+            "deprecated",
+            "allowEmptyValue",
+            "style",
+            "explode",
+            "allowReserved",
+            "schema",
+            "example",
+            "examples",
+            "content",
+        ],
+        Kind::Example => &[
+            "summary",
+            "description",
+            "value",
+            "externalValue",
+            "dataValue",
+            "serializedValue",
+        ],
+        Kind::Link => &[
+            "operationRef",
+            "operationId",
+            "parameters",
+            "requestBody",
+            "description",
+            "server",
+        ],
+        Kind::Components => &[
+            "schemas",
+            "responses",
+            "parameters",
+            "examples",
+            "requestBodies",
+            "headers",
+            "securitySchemes",
+            "links",
+            "callbacks",
+            "pathItems",
+            "mediaTypes",
+        ],
+        Kind::SecurityScheme => &[
+            "type",
+            "description",
+            "name",
+            "in",
+            "scheme",
+            "bearerFormat",
+            "flows",
+            "openIdConnectUrl",
+            "oauth2MetadataUrl",
+            "deprecated",
+        ],
+        Kind::OAuthFlows => &[
+            "implicit",
+            "password",
+            "clientCredentials",
+            "authorizationCode",
+            "deviceAuthorization",
+        ],
+        Kind::OAuthFlow => &[
+            "authorizationUrl",
+            "deviceAuthorizationUrl",
+            "tokenUrl",
+            "refreshUrl",
+            "scopes",
+        ],
+        Kind::Tag => &[
+            "name",
+            "summary",
+            "description",
+            "externalDocs",
+            "parent",
+            "kind",
+        ],
+        Kind::ExternalDocs => &["description", "url"],
+        Kind::Schema => SCHEMA_FIELDS,
+        Kind::Discriminator => &["propertyName", "mapping", "defaultMapping"],
+        Kind::Xml => &[
+            "name",
+            "namespace",
+            "prefix",
+            "attribute",
+            "wrapped",
+            "nodeType",
+        ],
+    }
+}
+
+fn child(kind: Kind, key: &str) -> Child {
+    match (kind, key) {
+        (Kind::Root, "info") => Child::One(Kind::Info),
+        (Kind::Root, "paths") => Child::One(Kind::Paths),
+        (Kind::Root, "webhooks") => Child::Map(Kind::PathItem),
+        (Kind::Root, "components") => Child::One(Kind::Components),
+        (Kind::Root, "tags") => Child::List(Kind::Tag),
+        (Kind::Info, "contact") => Child::One(Kind::Contact),
+        (Kind::Info, "license") => Child::One(Kind::License),
+        (Kind::Server, "variables") => Child::Map(Kind::ServerVariable),
+        (Kind::Paths | Kind::Callback, _) => Child::One(Kind::PathItem),
+        (Kind::PathItem, "parameters") | (Kind::Operation, "parameters") => {
+            Child::List(Kind::Parameter)
+        }
+        (Kind::PathItem, "additionalOperations") => Child::Map(Kind::Operation),
+        (Kind::PathItem, "servers") | (Kind::Operation, "servers") => Child::List(Kind::Server),
+        (Kind::PathItem, _) if key != "$ref" && key != "summary" && key != "description" => {
+            Child::One(Kind::Operation)
+        }
+        (Kind::Operation, "requestBody") => Child::One(Kind::RequestBody),
+        (Kind::Operation, "responses") => Child::One(Kind::Responses),
+        (Kind::Operation, "callbacks") => Child::Map(Kind::Callback),
+        (Kind::Responses, _) => Child::One(Kind::Response),
+        (Kind::Parameter | Kind::Header | Kind::MediaType, "schema")
+        | (Kind::MediaType, "itemSchema") => Child::One(Kind::Schema),
+        (Kind::Parameter | Kind::Header | Kind::MediaType, "examples") => Child::Map(Kind::Example),
+        (Kind::Parameter | Kind::Header | Kind::RequestBody | Kind::Response, "content") => {
+            Child::Map(Kind::MediaType)
+        }
+        (Kind::MediaType | Kind::Encoding, "encoding") => Child::Map(Kind::Encoding),
+        (Kind::MediaType | Kind::Encoding, "prefixEncoding") => Child::List(Kind::Encoding),
+        (Kind::MediaType | Kind::Encoding, "itemEncoding") => Child::One(Kind::Encoding),
+        (Kind::Encoding | Kind::Response, "headers") => Child::Map(Kind::Header),
+        (Kind::Response, "links") => Child::Map(Kind::Link),
+        (Kind::Link, "server") => Child::One(Kind::Server),
+        (Kind::Components, "schemas") => Child::Map(Kind::Schema),
+        (Kind::Components, "responses") => Child::Map(Kind::Response),
+        (Kind::Components, "parameters") => Child::Map(Kind::Parameter),
+        (Kind::Components, "examples") => Child::Map(Kind::Example),
+        (Kind::Components, "requestBodies") => Child::Map(Kind::RequestBody),
+        (Kind::Components, "headers") => Child::Map(Kind::Header),
+        (Kind::Components, "securitySchemes") => Child::Map(Kind::SecurityScheme),
+        (Kind::Components, "links") => Child::Map(Kind::Link),
+        (Kind::Components, "callbacks") => Child::Map(Kind::Callback),
+        (Kind::Components, "pathItems") => Child::Map(Kind::PathItem),
+        (Kind::Components, "mediaTypes") => Child::Map(Kind::MediaType),
+        (Kind::SecurityScheme, "flows") => Child::One(Kind::OAuthFlows),
+        (Kind::OAuthFlows, _) => Child::One(Kind::OAuthFlow),
+        (_, "externalDocs") => Child::One(Kind::ExternalDocs),
+        (Kind::Schema, "properties" | "patternProperties" | "$defs" | "definitions")
+        | (Kind::Schema, "dependentSchemas") => Child::Map(Kind::Schema),
+        (Kind::Schema, "allOf" | "anyOf" | "oneOf" | "prefixItems") => Child::List(Kind::Schema),
+        (Kind::Schema, "items" | "additionalItems") => Child::OneOrList(Kind::Schema),
+        (
+            Kind::Schema,
+            "additionalProperties"
+            | "propertyNames"
+            | "contains"
+            | "not"
+            | "if"
+            | "then"
+            | "else"
+            | "unevaluatedItems"
+            | "unevaluatedProperties"
+            | "contentSchema",
+        ) => Child::One(Kind::Schema),
+        (Kind::Schema, "discriminator") => Child::One(Kind::Discriminator),
+        (Kind::Schema, "xml") => Child::One(Kind::Xml),
+        _ => Child::Any,
+    }
+}
+
+fn walk(value: &Value, kind: Kind, pointer: &str, pointers: &mut Vec<String>) {
+    let Value::Mapping(mapping) = value else {
+        return;
+    };
+    // Reference objects are checked where they point to
+    if !matches!(kind, Kind::Schema | Kind::PathItem) && mapping.contains_key("$ref") {
+        return;
+    }
+
+    for (key, value) in mapping {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        if key.starts_with("x-") {
+            continue;
+        }
+
+        let pointer = format!("{pointer}/{}", escape(key));
+        let known = match kind {
+            Kind::Paths => key.starts_with('/'),
+            Kind::Responses | Kind::Callback => true,
+            _ => fields(kind).contains(&key),
+        };
+        if !known {
+            pointers.push(pointer);
+            continue;
+        }
+
+        match child(kind, key) {
+            Child::Any => {}
+            Child::One(kind) => walk(value, kind, &pointer, pointers),
+            Child::List(kind) | Child::OneOrList(kind) if value.is_sequence() => {
+                for (index, item) in value.as_sequence().into_iter().flatten().enumerate() {
+                    walk(item, kind, &format!("{pointer}/{index}"), pointers);
+                }
+            }
+            Child::OneOrList(kind) => walk(value, kind, &pointer, pointers),
+            Child::List(_) => {}
+            Child::Map(kind) => {
+                let Value::Mapping(entries) = value else {
+                    continue;
+                };
+                for (name, item) in entries {
+                    let Some(name) = name.as_str() else {
+                        continue;
+                    };
+                    walk(item, kind, &format!("{pointer}/{}", escape(name)), pointers);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,510 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Conversion of Swagger 2.0 documents into the OpenAPI 3.0 model.
+
+use crate::model::normalize;
+use crate::model::parse::{expand_merge_keys, OpenAPI};
+use anyhow::{anyhow, Context, Result};
+use serde_yaml::{Mapping, Value};
+
+const OPERATIONS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Parameter fields that move into the parameter `schema` in OpenAPI 3.0.
+const SCHEMA_FIELDS: &[&str] = &[
+    "type",
+    "format",
+    "items",
+    "enum",
+    "default",
+    "pattern",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "minLength",
+    "maxLength",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "multipleOf",
+];
+
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+const FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+const MULTIPART_FORM_DATA: &str = "multipart/form-data";
+
+impl OpenAPI {
+    /// Parses a Swagger 2.0 document (YAML or JSON) into the OpenAPI 3.x model
+    pub fn swagger(contents: &str) -> Result<Self> {
+        let mut document: Value = serde_yaml::from_str(contents)?;
+        expand_merge_keys(&mut document)?;
+        let mut converted = convert(&document)?;
+        normalize::to_31(&mut converted);
+        Ok(serde_yaml::from_value(converted)?)
+    }
+}
+
+/// Converts a Swagger 2.0 document into its OpenAPI 3.0 equivalent.
+///
+/// `basePath`, `host` and `schemes` become `servers`, `definitions` and
+/// `securityDefinitions` move under `components`, and `body`/`formData`
+/// parameters become request bodies for the operation's `consumes` media types.
+pub fn convert(document: &Value) -> Result<Value> {
+    let version = match document.get("swagger") {
+        Some(Value::String(version)) => version.clone(),
+        Some(Value::Number(version)) => version.to_string(),
+        _ => return Err(anyhow!("Missing 'swagger' version field")),
+    };
+    if !version.starts_with('2') {
+        return Err(anyhow!("Unsupported Swagger version '{}'", version));
+    }
+
+    let swagger = Swagger {
+        document,
+        consumes: media_types(document.get("consumes")),
+        produces: media_types(document.get("produces")),
+    };
+
+    let mut openapi = Mapping::new();
+    openapi.insert("openapi".into(), "3.0.3".into());
+    openapi.insert(
+        "info".into(),
+        document
+            .get("info")
+            .cloned()
+            .context("Missing 'info' field")?,
+    );
+    if let Some(servers) = servers(document) {
+        openapi.insert("servers".into(), servers);
+    }
+    openapi.insert("paths".into(), swagger.paths()?);
+    openapi.insert("components".into(), swagger.components());
+    if let Some(security) = document.get("security") {
+        openapi.insert("security".into(), security.clone());
+    }
+
+    let mut openapi = Value::Mapping(openapi);
+    rewrite_refs(&mut openapi);
+    Ok(openapi)
+}
+
+struct Swagger<'a> {
+    document: &'a Value,
+    consumes: Vec<String>,
+    produces: Vec<String>,
+}
+
+impl Swagger<'_> {
+    fn paths(&self) -> Result<Value> {
+        let mut paths = Mapping::new();
+        let Some(Value::Mapping(items)) = self.document.get("paths") else {
+            return Ok(Value::Mapping(paths));
+        };
+
+        for (path, item) in items {
+            let shared = self.parameters(item.get("parameters"))?;
+            let mut converted = Mapping::new();
+            for method in OPERATIONS {
+                if let Some(operation) = item.get(*method) {
+                    converted.insert((*method).into(), self.operation(operation, &shared)?);
+                }
+            }
+            paths.insert(path.clone(), Value::Mapping(converted));
+        }
+
+        Ok(Value::Mapping(paths))
+    }
+
+    fn operation(&self, operation: &Value, shared: &[Value]) -> Result<Value> {
+        let mut converted = Mapping::new();
+        for field in [
+            "summary",
+            "description",
+            "operationId",
+            "deprecated",
+            "security",
+        ] {
+            if let Some(value) = operation.get(field) {
+                converted.insert(field.into(), value.clone());
+            }
+        }
+
+        // Operation parameters override path-level ones with the same name and location
+        let own = self.parameters(operation.get("parameters"))?;
+        let key =
+            |parameter: &Value| (parameter.get("name").cloned(), parameter.get("in").cloned());
+        let mut parameters: Vec<Value> = shared
+            .iter()
+            .filter(|parameter| !own.iter().any(|other| key(other) == key(parameter)))
+            .cloned()
+            .collect();
+        parameters.extend(own);
+
+        let location = |parameter: &Value| {
+            parameter
+                .get("in")
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+        };
+        let (body, rest): (Vec<Value>, Vec<Value>) = parameters
+            .into_iter()
+            .partition(|parameter| location(parameter).as_deref() == Some("body"));
+        let (form_data, rest): (Vec<Value>, Vec<Value>) = rest
+            .into_iter()
+            .partition(|parameter| location(parameter).as_deref() == Some("formData"));
+
+        if !rest.is_empty() {
+            converted.insert(
+                "parameters".into(),
+                Value::Sequence(rest.iter().map(parameter).collect()),
+            );
+        }
+
+        let consumes = match operation.get("consumes") {
+            Some(consumes) => media_types(Some(consumes)),
+            None => self.consumes.clone(),
+        };
+        if let Some(body) = body.first() {
+            converted.insert("requestBody".into(), request_body(body, &consumes));
+        } else if !form_data.is_empty() {
+            converted.insert("requestBody".into(), form_body(&form_data, &consumes));
+        }
+
+        let produces = match operation.get("produces") {
+            Some(produces) => media_types(Some(produces)),
+            None => self.produces.clone(),
+        };
+        if let Some(Value::Mapping(responses)) = operation.get("responses") {
+            let mut converted_responses = Mapping::new();
+            for (code, response) in responses {
+                let response = self.resolve(response, "responses")?;
+                converted_responses.insert(code.clone(), self.response(response, &produces));
+            }
+            converted.insert("responses".into(), Value::Mapping(converted_responses));
+        }
+
+        Ok(Value::Mapping(converted))
+    }
+
+    /// Resolves `$ref`s to the document-level `parameters`, which may be
+    /// `body` or `formData` parameters that have no OpenAPI 3.0 counterpart.
+    fn parameters(&self, parameters: Option<&Value>) -> Result<Vec<Value>> {
+        let Some(Value::Sequence(parameters)) = parameters else {
+            return Ok(vec![]);
+        };
+
+        parameters
+            .iter()
+            .map(|parameter| self.resolve(parameter, "parameters").cloned())
+            .collect()
+    }
+
+    fn resolve<'a>(&'a self, value: &'a Value, section: &str) -> Result<&'a Value> {
+        let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+            return Ok(value);
+        };
+        let name = reference
+            .strip_prefix(&format!("#/{section}/"))
+            .with_context(|| format!("Unsupported reference '{reference}'"))?;
+
+        self.document
+            .get(section)
+            .and_then(|definitions| definitions.get(name))
+            .with_context(|| format!("Reference '{reference}' not found"))
+    }
+
+    fn response(&self, response: &Value, produces: &[String]) -> Value {
+        let mut converted = Mapping::new();
+        converted.insert(
+            "description".into(),
+            response
+                .get("description")
+                .cloned()
+                .unwrap_or_else(|| "".into()),
+        );
+        if let Some(schema) = response.get("schema") {
+            converted.insert("content".into(), content(produces, &file_schema(schema)));
+        }
+        Value::Mapping(converted)
+    }
+
+    fn components(&self) -> Value {
+        let mut components = Mapping::new();
+        if let Some(definitions) = self.document.get("definitions") {
+            components.insert("schemas".into(), definitions.clone());
+        }
+        if let Some(Value::Mapping(definitions)) = self.document.get("securityDefinitions") {
+            let schemes = definitions
+                .iter()
+                .map(|(name, scheme)| (name.clone(), security_scheme(scheme)))
+                .collect();
+            components.insert("securitySchemes".into(), Value::Mapping(schemes));
+        }
+        Value::Mapping(components)
+    }
+}
+
+fn media_types(value: Option<&Value>) -> Vec<String> {
+    let media_types: Vec<String> = match value {
+        Some(Value::Sequence(values)) => values
+            .iter()
+            .filter_map(|value| value.as_str().map(str::to_owned))
+            .collect(),
+        _ => vec![],
+    };
+
+    if media_types.is_empty() {
+        vec![DEFAULT_MEDIA_TYPE.to_string()]
+    } else {
+        media_types
+    }
+}
+
+/// Builds `servers` from `schemes`, `host` and `basePath`; without a host the
+/// server URL is relative to the document location.
+fn servers(document: &Value) -> Option<Value> {
+    let base_path = document
+        .get("basePath")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+
+    let urls: Vec<String> = match document.get("host").and_then(Value::as_str) {
+        Some(host) => {
+            let schemes = match document.get("schemes") {
+                Some(Value::Sequence(schemes)) if !schemes.is_empty() => schemes
+                    .iter()
+                    .filter_map(|scheme| scheme.as_str().map(str::to_owned))
+                    .collect(),
+                _ => vec!["https".to_string()],
+            };
+            schemes
+                .iter()
+                .map(|scheme| format!("{scheme}://{host}{base_path}"))
+                .collect()
+        }
+        None if !base_path.is_empty() => vec![base_path.to_string()],
+        None => return None,
+    };
+
+    let servers = urls
+        .into_iter()
+        .map(|url| {
+            let mut server = Mapping::new();
+            server.insert("url".into(), url.into());
+            Value::Mapping(server)
+        })
+        .collect();
+    Some(Value::Sequence(servers))
+}
+
+/// Converts a non-body parameter, moving its type constraints into `schema`
+/// and mapping `collectionFormat` onto `style`/`explode`.
+fn parameter(parameter: &Value) -> Value {
+    let mut converted = Mapping::new();
+    let mut schema = Mapping::new();
+
+    let Value::Mapping(fields) = parameter else {
+        return parameter.clone();
+    };
+    for (key, value) in fields {
+        match key.as_str() {
+            Some(field) if SCHEMA_FIELDS.contains(&field) => {
+                schema.insert(key.clone(), value.clone());
+            }
+            Some("collectionFormat") => {}
+            _ => {
+                converted.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    if schema.get("type").and_then(Value::as_str) == Some("array") {
+        let in_query = parameter.get("in").and_then(Value::as_str) == Some("query");
+        let style = match parameter.get("collectionFormat").and_then(Value::as_str) {
+            Some("ssv") => Some(("spaceDelimited", false)),
+            Some("pipes") => Some(("pipeDelimited", false)),
+            Some("multi") => Some(("form", true)),
+            Some("csv") | None if in_query => Some(("form", false)),
+            _ => None,
+        };
+        if let Some((style, explode)) = style {
+            converted.insert("style".into(), style.into());
+            converted.insert("explode".into(), explode.into());
+        }
+    }
+
+    if !schema.is_empty() {
+        converted.insert("schema".into(), Value::Mapping(schema));
+    }
+    Value::Mapping(converted)
+}
+
+fn request_body(body: &Value, consumes: &[String]) -> Value {
+    let mut converted = Mapping::new();
+    if let Some(description) = body.get("description") {
+        converted.insert("description".into(), description.clone());
+    }
+    if let Some(required) = body.get("required") {
+        converted.insert("required".into(), required.clone());
+    }
+    let schema = body.get("schema").cloned().unwrap_or_default();
+    converted.insert("content".into(), content(consumes, &schema));
+    Value::Mapping(converted)
+}
+
+/// Collects `formData` parameters into the properties of a single object
+/// schema, served as multipart when the operation accepts it or uploads files.
+fn form_body(parameters: &[Value], consumes: &[String]) -> Value {
+    let mut properties = Mapping::new();
+    let mut required = vec![];
+    for form_parameter in parameters {
+        let Some(name) = form_parameter.get("name") else {
+            continue;
+        };
+        if form_parameter.get("required").and_then(Value::as_bool) == Some(true) {
+            required.push(name.clone());
+        }
+        let schema = parameter(form_parameter)
+            .get("schema")
+            .map(file_schema)
+            .unwrap_or_default();
+        properties.insert(name.clone(), schema);
+    }
+
+    let mut schema = Mapping::new();
+    schema.insert("type".into(), "object".into());
+    schema.insert("properties".into(), Value::Mapping(properties));
+    if !required.is_empty() {
+        schema.insert("required".into(), Value::Sequence(required));
+    }
+
+    let has_file = parameters
+        .iter()
+        .any(|parameter| parameter.get("type").and_then(Value::as_str) == Some("file"));
+    let form_types: Vec<String> = consumes
+        .iter()
+        .filter(|media_type| *media_type == FORM_URLENCODED || *media_type == MULTIPART_FORM_DATA)
+        .cloned()
+        .collect();
+    let media_types = match form_types.is_empty() {
+        true if has_file => vec![MULTIPART_FORM_DATA.to_string()],
+        true => vec![FORM_URLENCODED.to_string()],
+        false => form_types,
+    };
+
+    let mut converted = Mapping::new();
+    converted.insert(
+        "content".into(),
+        content(&media_types, &Value::Mapping(schema)),
+    );
+    Value::Mapping(converted)
+}
+
+fn content(media_types: &[String], schema: &Value) -> Value {
+    let content = media_types
+        .iter()
+        .map(|media_type| {
+            let mut media = Mapping::new();
+            media.insert("schema".into(), schema.clone());
+            (media_type.as_str().into(), Value::Mapping(media))
+        })
+        .collect();
+    Value::Mapping(content)
+}
+
+/// Swagger 2.0 `type: file` becomes a binary string in OpenAPI 3.0.
+fn file_schema(schema: &Value) -> Value {
+    if schema.get("type").and_then(Value::as_str) != Some("file") {
+        return schema.clone();
+    }
+
+    let mut converted = Mapping::new();
+    converted.insert("type".into(), "string".into());
+    converted.insert("format".into(), "binary".into());
+    Value::Mapping(converted)
+}
+
+fn security_scheme(scheme: &Value) -> Value {
+    let mut converted = Mapping::new();
+    if let Some(description) = scheme.get("description") {
+        converted.insert("description".into(), description.clone());
+    }
+
+    match scheme.get("type").and_then(Value::as_str) {
+        Some("basic") => {
+            converted.insert("type".into(), "http".into());
+            converted.insert("scheme".into(), "basic".into());
+        }
+        Some("oauth2") => {
+            let flow_name = match scheme.get("flow").and_then(Value::as_str) {
+                Some("application") => "clientCredentials",
+                Some("accessCode") => "authorizationCode",
+                Some("password") => "password",
+                _ => "implicit",
+            };
+            let mut flow = Mapping::new();
+            for field in ["authorizationUrl", "tokenUrl"] {
+                if let Some(url) = scheme.get(field) {
+                    flow.insert(field.into(), url.clone());
+                }
+            }
+            flow.insert(
+                "scopes".into(),
+                scheme
+                    .get("scopes")
+                    .cloned()
+                    .unwrap_or_else(|| Value::Mapping(Mapping::new())),
+            );
+            let mut flows = Mapping::new();
+            flows.insert(flow_name.into(), Value::Mapping(flow));
+            converted.insert("type".into(), "oauth2".into());
+            converted.insert("flows".into(), Value::Mapping(flows));
+        }
+        _ => {
+            for field in ["type", "name", "in"] {
+                if let Some(value) = scheme.get(field) {
+                    converted.insert(field.into(), value.clone());
+                }
+            }
+        }
+    }
+
+    Value::Mapping(converted)
+}
+
+/// Points `#/definitions/...` references at `#/components/schemas/...`.
+fn rewrite_refs(value: &mut Value) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping.iter_mut() {
+                if key.as_str() == Some("$ref") {
+                    if let Some(name) = value
+                        .as_str()
+                        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+                    {
+                        *value = format!("#/components/schemas/{name}").into();
+                        continue;
+                    }
+                }
+                rewrite_refs(value);
+            }
+        }
+        Value::Sequence(values) => values.iter_mut().for_each(rewrite_refs),
+        _ => {}
+    }
+}
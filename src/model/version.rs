@@ -0,0 +1,173 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Several versions of a document validated side by side, selected per
+//! request by a header, an `Accept` media type parameter or a path prefix.
+
+use crate::model::shared::SharedOpenAPI;
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// The version a request was routed to, see [`VersionedSpecs::select`].
+#[derive(Debug)]
+pub struct VersionMatch<'a> {
+    pub version: &'a str,
+    pub spec: &'a SharedOpenAPI,
+    /// The request path, without the version prefix when the version was
+    /// taken from it
+    pub path: String,
+}
+
+/// Documents keyed by API version, such as `v1` and `v2`. A request selects
+/// its version, in order of precedence, through:
+///
+/// 1. the header configured with [`VersionedSpecs::with_header`], e.g.
+///    `X-API-Version: 2`
+/// 2. the `Accept` parameter configured with
+///    [`VersionedSpecs::with_accept_parameter`], e.g.
+///    `Accept: application/json; version=2`
+/// 3. the first path segment when [`VersionedSpecs::with_prefix`] is
+///    enabled, e.g. `/v2/users`
+/// 4. the version configured with [`VersionedSpecs::with_default`]
+///
+/// Versions are compared ignoring case and a leading `v`, so `2`, `v2` and
+/// `V2` all select `v2`.
+#[derive(Debug, Clone, Default)]
+pub struct VersionedSpecs {
+    versions: IndexMap<String, SharedOpenAPI>,
+    header: Option<String>,
+    accept_parameter: Option<String>,
+    prefix: bool,
+    default: Option<String>,
+}
+
+impl VersionedSpecs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `spec` as `version`, replacing the document previously added
+    /// under the same name
+    pub fn with_version(mut self, version: &str, spec: impl Into<SharedOpenAPI>) -> Self {
+        self.versions.insert(version.to_string(), spec.into());
+        self
+    }
+
+    /// Select versions by the value of the header `name`
+    pub fn with_header(mut self, name: &str) -> Self {
+        self.header = Some(name.to_ascii_lowercase());
+        self
+    }
+
+    /// Select versions by the media type parameter `name` of the `Accept` header
+    pub fn with_accept_parameter(mut self, name: &str) -> Self {
+        self.accept_parameter = Some(name.to_string());
+        self
+    }
+
+    /// Enable/disable selecting versions by the first path segment
+    pub fn with_prefix(mut self, enabled: bool) -> Self {
+        self.prefix = enabled;
+        self
+    }
+
+    /// The version of requests that do not select one
+    pub fn with_default(mut self, version: &str) -> Self {
+        self.default = Some(version.to_string());
+        self
+    }
+
+    /// The document of `version`
+    pub fn get(&self, version: &str) -> Option<(&str, &SharedOpenAPI)> {
+        self.versions
+            .iter()
+            .find(|(known, _)| same_version(known, version))
+            .map(|(known, spec)| (known.as_str(), spec))
+    }
+
+    /// Finds the version a request for `path` with `headers`, keyed by
+    /// lower-cased name, asks for. A request naming a version that is not
+    /// loaded matches none, rather than falling back to the default.
+    pub fn select(
+        &self,
+        path: &str,
+        headers: &HashMap<String, String>,
+    ) -> Option<VersionMatch<'_>> {
+        if let Some(requested) = self.header.as_ref().and_then(|name| headers.get(name)) {
+            return matched(self.get(requested.trim())?, path);
+        }
+
+        let accepted = self
+            .accept_parameter
+            .as_deref()
+            .and_then(|parameter| accept_parameter(headers.get("accept")?, parameter));
+        if let Some(requested) = accepted {
+            return matched(self.get(requested)?, path);
+        }
+
+        if self.prefix {
+            let trimmed = path.strip_prefix('/').unwrap_or(path);
+            let (segment, rest) = trimmed.split_once('/').unwrap_or((trimmed, ""));
+            if let Some(found) = self.get(segment) {
+                return matched(found, &format!("/{rest}"));
+            }
+        }
+
+        matched(self.get(self.default.as_deref()?)?, path)
+    }
+
+    /// Every version and its document, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SharedOpenAPI)> {
+        self.versions
+            .iter()
+            .map(|(version, spec)| (version.as_str(), spec))
+    }
+}
+
+fn matched<'a>(
+    (version, spec): (&'a str, &'a SharedOpenAPI),
+    path: &str,
+) -> Option<VersionMatch<'a>> {
+    Some(VersionMatch {
+        version,
+        spec,
+        path: path.to_string(),
+    })
+}
+
+fn same_version(known: &str, requested: &str) -> bool {
+    let strip = |version: &str| {
+        version
+            .strip_prefix(['v', 'V'])
+            .unwrap_or(version)
+            .to_ascii_lowercase()
+    };
+    !requested.is_empty() && strip(known) == strip(requested)
+}
+
+/// The value of the media type parameter `name` in the first media range of
+/// an `Accept` header declaring it
+fn accept_parameter<'a>(accept: &'a str, name: &str) -> Option<&'a str> {
+    accept.split(',').find_map(|range| {
+        range.split(';').skip(1).find_map(|parameter| {
+            let (key, value) = parameter.split_once('=')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim().trim_matches('"'))
+        })
+    })
+}
@@ -0,0 +1,240 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Traversal of the document model for tooling such as linters, code
+//! generators and statistics.
+
+use crate::model::lint::escape;
+use crate::model::parse::{
+    BaseContent, ComponentProperties, ComponentSchemaBase, Header, OpenAPI, Parameter, PathBase,
+    PathItem, Properties, Request, Response, Schema,
+};
+use indexmap::IndexMap;
+
+/// A schema of the document, whichever model type holds it.
+#[derive(Debug, Clone, Copy)]
+pub enum SchemaNode<'a> {
+    /// A parameter or media type schema
+    Schema(&'a Schema),
+    /// A schema under `components.schemas`, or its `items`
+    Component(&'a ComponentSchemaBase),
+    /// An entry of `properties`, or its `items`
+    Property(&'a Properties),
+    /// A member of `allOf` or `oneOf`
+    Composed(&'a ComponentProperties),
+}
+
+/// Callbacks invoked by [`walk`]. Every callback receives the JSON pointer of
+/// the visited node and defaults to doing nothing.
+pub trait Visitor {
+    fn visit_operation(&mut self, _pointer: &str, _path: &str, _method: &str, _: &PathBase) {}
+
+    fn visit_parameter(&mut self, _pointer: &str, _: &Parameter) {}
+
+    fn visit_response(&mut self, _pointer: &str, _status: &str, _: &Response) {}
+
+    fn visit_header(&mut self, _pointer: &str, _: &Header) {}
+
+    fn visit_media_type(&mut self, _pointer: &str, _media_type: &str, _: &BaseContent) {}
+
+    fn visit_schema(&mut self, _pointer: &str, _: SchemaNode<'_>) {}
+}
+
+/// Visits the paths, webhooks and components of `open_api` in document order,
+/// descending into parameters, request bodies, responses, headers, callbacks
+/// and nested schemas.
+pub fn walk(open_api: &OpenAPI, visitor: &mut impl Visitor) {
+    for (path, path_item) in &open_api.paths {
+        let pointer = format!("/paths/{}", escape(path));
+        walk_path_item(&pointer, path, path_item, visitor);
+    }
+
+    if let Some(webhooks) = &open_api.webhooks {
+        for (name, path_item) in webhooks {
+            let pointer = format!("/webhooks/{}", escape(name));
+            walk_path_item(&pointer, name, path_item, visitor);
+        }
+    }
+
+    let Some(components) = &open_api.components else {
+        return;
+    };
+    for (name, schema) in &components.schemas {
+        let pointer = format!("/components/schemas/{}", escape(name));
+        walk_schema(&pointer, SchemaNode::Component(schema), visitor);
+    }
+    for (name, parameter) in &components.parameters {
+        let pointer = format!("/components/parameters/{}", escape(name));
+        walk_parameter(&pointer, parameter, visitor);
+    }
+    for (name, request) in &components.request_bodies {
+        let pointer = format!("/components/requestBodies/{}", escape(name));
+        walk_request_body(&pointer, request, visitor);
+    }
+    for (name, response) in &components.responses {
+        let pointer = format!("/components/responses/{}", escape(name));
+        walk_response(&pointer, name, response, visitor);
+    }
+    for (name, header) in &components.headers {
+        let pointer = format!("/components/headers/{}", escape(name));
+        walk_header(&pointer, header, visitor);
+    }
+}
+
+fn walk_path_item(pointer: &str, path: &str, path_item: &PathItem, visitor: &mut impl Visitor) {
+    walk_parameters(pointer, path_item.parameters.as_deref(), visitor);
+
+    for (method, operation) in path_item.all_operations() {
+        let pointer = if path_item.additional_operations.contains_key(method) {
+            format!("{pointer}/additionalOperations/{}", escape(method))
+        } else {
+            format!("{pointer}/{method}")
+        };
+        visitor.visit_operation(&pointer, path, method, operation);
+
+        walk_parameters(&pointer, operation.parameters.as_deref(), visitor);
+        if let Some(request) = &operation.request {
+            walk_request_body(&format!("{pointer}/requestBody"), request, visitor);
+        }
+        for (status, response) in &operation.responses {
+            let pointer = format!("{pointer}/responses/{}", escape(status));
+            walk_response(&pointer, status, response, visitor);
+        }
+        for (name, callback) in &operation.callbacks {
+            for (expression, path_item) in &callback.expressions {
+                let pointer = format!(
+                    "{pointer}/callbacks/{}/{}",
+                    escape(name),
+                    escape(expression)
+                );
+                walk_path_item(&pointer, expression, path_item, visitor);
+            }
+        }
+    }
+}
+
+fn walk_parameters(pointer: &str, parameters: Option<&[Parameter]>, visitor: &mut impl Visitor) {
+    for (index, parameter) in parameters.into_iter().flatten().enumerate() {
+        walk_parameter(&format!("{pointer}/parameters/{index}"), parameter, visitor);
+    }
+}
+
+fn walk_parameter(pointer: &str, parameter: &Parameter, visitor: &mut impl Visitor) {
+    visitor.visit_parameter(pointer, parameter);
+    if let Some(schema) = &parameter.schema {
+        let pointer = format!("{pointer}/schema");
+        walk_schema(&pointer, SchemaNode::Schema(schema), visitor);
+    }
+    walk_content(pointer, &parameter.content, visitor);
+}
+
+/// Responses under `components.responses` are visited with their name as `status`.
+fn walk_response(pointer: &str, status: &str, response: &Response, visitor: &mut impl Visitor) {
+    visitor.visit_response(pointer, status, response);
+    for (name, header) in &response.headers {
+        walk_header(
+            &format!("{pointer}/headers/{}", escape(name)),
+            header,
+            visitor,
+        );
+    }
+    walk_content(pointer, &response.content, visitor);
+}
+
+fn walk_header(pointer: &str, header: &Header, visitor: &mut impl Visitor) {
+    visitor.visit_header(pointer, header);
+    if let Some(schema) = &header.schema {
+        let pointer = format!("{pointer}/schema");
+        walk_schema(&pointer, SchemaNode::Schema(schema), visitor);
+    }
+    walk_content(pointer, &header.content, visitor);
+}
+
+fn walk_request_body(pointer: &str, request: &Request, visitor: &mut impl Visitor) {
+    walk_content(pointer, &request.content, visitor);
+}
+
+fn walk_content(
+    pointer: &str,
+    content: &IndexMap<String, BaseContent>,
+    visitor: &mut impl Visitor,
+) {
+    for (media_type, media) in content {
+        let pointer = format!("{pointer}/content/{}", escape(media_type));
+        visitor.visit_media_type(&pointer, media_type, media);
+        let schema_pointer = format!("{pointer}/schema");
+        walk_schema(&schema_pointer, SchemaNode::Schema(&media.schema), visitor);
+        if let Some(item_schema) = &media.item_schema {
+            let pointer = format!("{pointer}/itemSchema");
+            walk_schema(&pointer, SchemaNode::Schema(item_schema), visitor);
+        }
+    }
+}
+
+fn walk_schema(pointer: &str, node: SchemaNode<'_>, visitor: &mut impl Visitor) {
+    visitor.visit_schema(pointer, node);
+
+    let (properties, composed) = match node {
+        SchemaNode::Schema(schema) => {
+            if let Some(items) = &schema.items {
+                let pointer = format!("{pointer}/items");
+                walk_schema(&pointer, SchemaNode::Schema(items), visitor);
+            }
+            if let Some(content_schema) = &schema.content_schema {
+                let pointer = format!("{pointer}/contentSchema");
+                walk_schema(&pointer, SchemaNode::Schema(content_schema), visitor);
+            }
+            (
+                schema.properties.as_ref(),
+                [schema.all_of.as_deref(), schema.one_of.as_deref()],
+            )
+        }
+        SchemaNode::Component(schema) => {
+            if let Some(items) = &schema.items {
+                let pointer = format!("{pointer}/items");
+                walk_schema(&pointer, SchemaNode::Component(items), visitor);
+            }
+            (
+                schema.properties.as_ref(),
+                [schema.all_of.as_deref(), schema.one_of.as_deref()],
+            )
+        }
+        SchemaNode::Property(property) => {
+            if let Some(items) = &property.items {
+                let pointer = format!("{pointer}/items");
+                walk_schema(&pointer, SchemaNode::Property(items), visitor);
+            }
+            if let Some(content_schema) = &property.content_schema {
+                let pointer = format!("{pointer}/contentSchema");
+                walk_schema(&pointer, SchemaNode::Schema(content_schema), visitor);
+            }
+            (property.properties.as_ref(), [None, None])
+        }
+        SchemaNode::Composed(schema) => (Some(&schema.properties), [None, None]),
+    };
+
+    for (name, property) in properties.into_iter().flatten() {
+        let pointer = format!("{pointer}/properties/{}", escape(name));
+        walk_schema(&pointer, SchemaNode::Property(property), visitor);
+    }
+    for (keyword, members) in ["allOf", "oneOf"].into_iter().zip(composed) {
+        for (index, member) in members.into_iter().flatten().enumerate() {
+            let pointer = format!("{pointer}/{keyword}/{index}");
+            walk_schema(&pointer, SchemaNode::Composed(member), visitor);
+        }
+    }
+}
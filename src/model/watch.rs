@@ -0,0 +1,115 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Reloading a [`SharedOpenAPI`] whenever its document changes on disk.
+
+use crate::model::shared::SharedOpenAPI;
+use anyhow::{Context, Result};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+
+/// Outcome of a reload triggered by a change of the watched document.
+#[derive(Debug)]
+pub enum ReloadEvent<'a> {
+    /// The document was parsed and is now the current one
+    Reloaded { path: &'a Path },
+    /// The document could not be read or parsed; the previous one is kept
+    Failed {
+        path: &'a Path,
+        error: &'a anyhow::Error,
+    },
+}
+
+/// Watches a document for [`SharedOpenAPI::watch`]; dropping it stops the
+/// reloads.
+pub struct SpecWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl SharedOpenAPI {
+    /// Reloads the document at `path` (see [`SharedOpenAPI::reload`]) every
+    /// time it is written or replaced, reporting each attempt to `on_reload`.
+    /// The directory containing `path` is watched, so editors saving through
+    /// a temporary file and renaming it over `path` are picked up too.
+    ///
+    /// ```no_run
+    /// use openapi_rs::model::parse::OpenAPI;
+    /// use openapi_rs::model::shared::SharedOpenAPI;
+    /// use openapi_rs::model::watch::ReloadEvent;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let content = std::fs::read_to_string("api.yaml")?;
+    /// let shared = SharedOpenAPI::new(OpenAPI::yaml(&content)?);
+    /// let _watcher = shared.watch("api.yaml", |event| {
+    ///     if let ReloadEvent::Failed { path, error } = event {
+    ///         eprintln!("Keeping the previous '{}': {error:#}", path.display());
+    ///     }
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch<F>(&self, path: impl Into<PathBuf>, on_reload: F) -> Result<SpecWatcher>
+    where
+        F: Fn(ReloadEvent<'_>) + Send + 'static,
+    {
+        let path = path.into();
+        let file_name = path
+            .file_name()
+            .with_context(|| format!("'{}' does not name a file", path.display()))?
+            .to_owned();
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let shared = self.clone();
+        let reload_path = path.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            let written = match event.kind {
+                EventKind::Create(_) => true,
+                EventKind::Modify(kind) => !matches!(kind, ModifyKind::Metadata(_)),
+                _ => false,
+            };
+            let ours = event
+                .paths
+                .iter()
+                .any(|changed| changed.file_name() == Some(file_name.as_os_str()));
+            if !written || !ours {
+                return;
+            }
+
+            let path = reload_path.as_path();
+            match shared.reload(path) {
+                Ok(()) => on_reload(ReloadEvent::Reloaded { path }),
+                Err(error) => on_reload(ReloadEvent::Failed {
+                    path,
+                    error: &error,
+                }),
+            }
+        })
+        .context("Failed to create the file watcher")?;
+
+        watcher
+            .watch(&directory, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Failed to watch '{}'", directory.display()))?;
+        Ok(SpecWatcher { _watcher: watcher })
+    }
+}
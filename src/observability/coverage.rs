@@ -0,0 +1,302 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Which operations and responses of a document requests have exercised,
+//! to find the endpoints a test run left untested.
+
+use crate::model::shared::SharedOpenAPI;
+use crate::observability::{ObservabilityHook, RequestContext};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// An [`ObservabilityHook`] counting the requests of every operation of a
+/// document and the statuses of their responses. Clones share their counts,
+/// so one clone can be registered on the middleware and another read with
+/// [`CoverageTracker::report`].
+///
+/// Request paths are resolved against the current document, so with a
+/// [`SpecRegistry`] each mounted document needs a tracker of its own.
+///
+/// [`SpecRegistry`]: crate::model::registry::SpecRegistry
+#[derive(Debug, Clone)]
+pub struct CoverageTracker {
+    openapi: SharedOpenAPI,
+    hits: Arc<Mutex<Hits>>,
+}
+
+#[derive(Debug, Default)]
+struct Hits {
+    /// Keyed by path template and lower-cased method
+    operations: HashMap<(String, String), OperationHits>,
+    undocumented: u64,
+}
+
+#[derive(Debug, Default)]
+struct OperationHits {
+    requests: u64,
+    rejected: u64,
+    statuses: BTreeMap<u16, u64>,
+}
+
+impl CoverageTracker {
+    pub fn new(openapi: SharedOpenAPI) -> Self {
+        Self {
+            openapi,
+            hits: Arc::default(),
+        }
+    }
+
+    /// Every operation of the current document with what was recorded for it
+    pub fn report(&self) -> CoverageReport {
+        let openapi = self.openapi.load();
+        let hits = self.lock();
+        let unhit = OperationHits::default();
+
+        let operations = openapi
+            .operations()
+            .map(|(path, method, operation)| {
+                let recorded = hits
+                    .operations
+                    .get(&(path.to_string(), method.to_string()))
+                    .unwrap_or(&unhit);
+                let mut responses: Vec<ResponseCoverage> = operation
+                    .responses
+                    .keys()
+                    .map(|status| ResponseCoverage {
+                        status: status.clone(),
+                        hits: 0,
+                    })
+                    .collect();
+                let mut undeclared = BTreeMap::new();
+                for (&code, &count) in &recorded.statuses {
+                    let declared = [code.to_string(), format!("{}XX", code / 100)]
+                        .into_iter()
+                        .chain(["default".to_string()])
+                        .find_map(|key| responses.iter().position(|r| r.status == key));
+                    match declared {
+                        Some(index) => responses[index].hits += count,
+                        None => {
+                            undeclared.insert(code, count);
+                        }
+                    }
+                }
+                OperationCoverage {
+                    path: path.to_string(),
+                    method: method.to_ascii_uppercase(),
+                    operation_id: operation.operation_id.clone(),
+                    requests: recorded.requests,
+                    rejected: recorded.rejected,
+                    responses,
+                    undeclared,
+                }
+            })
+            .collect();
+
+        CoverageReport {
+            operations,
+            undocumented: hits.undocumented,
+        }
+    }
+
+    /// Forget everything recorded so far, e.g. between test suites
+    pub fn reset(&self) {
+        *self.lock() = Hits::default();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Hits> {
+        self.hits.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Applies `record` to the hits of the operation `ctx` is for, returning
+    /// whether the document declares its path
+    fn record(&self, ctx: &RequestContext, record: impl FnOnce(&mut OperationHits)) -> bool {
+        let openapi = self.openapi.load();
+        let Some((path, _)) = openapi.resolve_path(&ctx.path) else {
+            return false;
+        };
+        let key = (path.to_string(), ctx.method.to_ascii_lowercase());
+        record(self.lock().operations.entry(key).or_default());
+        true
+    }
+
+    fn record_request(&self, ctx: &RequestContext, rejected: bool) {
+        let documented = self.record(ctx, |hits| {
+            hits.requests += 1;
+            hits.rejected += u64::from(rejected);
+        });
+        if !documented {
+            self.lock().undocumented += 1;
+        }
+    }
+}
+
+impl ObservabilityHook for CoverageTracker {
+    fn on_success(&self, ctx: &RequestContext, _: Duration) {
+        self.record_request(ctx, false);
+    }
+
+    fn on_failure(&self, ctx: &RequestContext, _: &str, _: Duration) {
+        self.record_request(ctx, true);
+    }
+
+    fn on_response(&self, ctx: &RequestContext, status: u16) {
+        self.record(ctx, |hits| *hits.statuses.entry(status).or_default() += 1);
+    }
+}
+
+/// What a [`CoverageTracker`] recorded, see [`CoverageTracker::report`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoverageReport {
+    /// Every operation of the document, in document order
+    pub operations: Vec<OperationCoverage>,
+    /// Requests for paths the document does not declare
+    pub undocumented: u64,
+}
+
+impl CoverageReport {
+    /// The operations no request reached
+    pub fn untested(&self) -> impl Iterator<Item = &OperationCoverage> {
+        self.operations
+            .iter()
+            .filter(|operation| operation.requests == 0)
+    }
+
+    /// The share of operations requests reached, from 0 to 1; 1 for a
+    /// document without operations
+    pub fn operation_ratio(&self) -> f64 {
+        ratio(
+            self.operations.len() - self.untested().count(),
+            self.operations.len(),
+        )
+    }
+
+    /// The share of declared responses that were sent, from 0 to 1; 1 for a
+    /// document without responses
+    pub fn response_ratio(&self) -> f64 {
+        let responses = || self.operations.iter().flat_map(|o| &o.responses);
+        ratio(
+            responses().filter(|response| response.hits > 0).count(),
+            responses().count(),
+        )
+    }
+}
+
+fn ratio(covered: usize, total: usize) -> f64 {
+    match total {
+        0 => 1.0,
+        total => covered as f64 / total as f64,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OperationCoverage {
+    /// The path template, such as `/users/{id}`
+    pub path: String,
+    /// The upper-cased HTTP method
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// Requests validated, whether they passed or not
+    pub requests: u64,
+    /// Requests rejected by validation
+    pub rejected: u64,
+    /// Every declared response, by its status key
+    pub responses: Vec<ResponseCoverage>,
+    /// Counts of the statuses sent that no response declares
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub undeclared: BTreeMap<u16, u64>,
+}
+
+impl OperationCoverage {
+    /// The status keys of the declared responses never sent
+    pub fn untested_responses(&self) -> impl Iterator<Item = &str> {
+        self.responses
+            .iter()
+            .filter(|response| response.hits == 0)
+            .map(|response| response.status.as_str())
+    }
+}
+
+/// A declared response and the responses sent under it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResponseCoverage {
+    /// The key of the response: a code, a range such as `4XX` or `default`
+    pub status: String,
+    pub hits: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::parse::OpenAPI;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Covered
+  version: 1.0.0
+paths:
+  /users:
+    get:
+      operationId: listUsers
+      responses:
+        '200':
+          description: OK
+  /users/{id}:
+    get:
+      responses:
+        '200':
+          description: OK
+        4XX:
+          description: Client error
+        default:
+          description: Error
+    delete:
+      responses:
+        '204':
+          description: Deleted
+"#;
+
+    #[test]
+    fn test_report_counts_operations_and_responses() {
+        let tracker = CoverageTracker::new(SharedOpenAPI::new(OpenAPI::yaml(SPEC).unwrap()));
+        let get = |path: &str| RequestContext::new("GET".to_string(), path.to_string());
+
+        tracker.on_success(&get("/users/7"), Duration::ZERO);
+        tracker.on_response(&get("/users/7"), 404);
+        tracker.on_failure(&get("/users/x"), "bad id", Duration::ZERO);
+        tracker.on_response(&get("/users/x"), 503);
+        tracker.on_failure(&get("/groups"), "Path not found", Duration::ZERO);
+
+        let report = tracker.report();
+        assert_eq!(report.undocumented, 1);
+        let untested: Vec<_> = report.untested().map(|o| (&*o.method, &*o.path)).collect();
+        assert_eq!(untested, [("GET", "/users"), ("DELETE", "/users/{id}")]);
+        assert_eq!(report.operation_ratio(), 1.0 / 3.0);
+        assert_eq!(report.response_ratio(), 2.0 / 5.0);
+
+        let user = &report.operations[1];
+        assert_eq!((user.requests, user.rejected), (2, 1));
+        assert_eq!(user.untested_responses().collect::<Vec<_>>(), ["200"]);
+        assert!(user.undeclared.is_empty());
+
+        tracker.reset();
+        assert_eq!(tracker.report().untested().count(), 3);
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! User supplied sinks for the outcome of middleware validations.
+
+use crate::observability::RequestContext;
+#[cfg(any(feature = "actix-web", feature = "tower"))]
+use crate::validator::report::Issue;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(all(feature = "clock", any(feature = "actix-web", feature = "tower")))]
+use std::time::Instant;
+
+/// Receives the outcome of every request a validation middleware checks,
+/// to forward it to StatsD, Sentry or any other sink.
+///
+/// `duration` is the time spent validating, including reading the body. It
+/// is always zero without the `clock` feature.
+pub trait ObservabilityHook: Send + Sync {
+    /// Called when the request passed validation and is forwarded
+    fn on_success(&self, ctx: &RequestContext, duration: Duration) {
+        let _ = (ctx, duration);
+    }
+
+    /// Called when the request is rejected, with the rejection message
+    fn on_failure(&self, ctx: &RequestContext, error: &str, duration: Duration) {
+        let _ = (ctx, error, duration);
+    }
+
+    /// Called with the status of the response sent for the request, the
+    /// wrapped service's one or the rejection's
+    fn on_response(&self, ctx: &RequestContext, status: u16) {
+        let _ = (ctx, status);
+    }
+
+    /// Called for each deprecated operation or parameter the request uses,
+    /// with the warning describing it
+    fn on_deprecated(&self, ctx: &RequestContext, warning: &str) {
+        let _ = (ctx, warning);
+    }
+}
+
+/// The hooks registered on a middleware
+#[derive(Clone, Default)]
+pub struct Hooks(Vec<Arc<dyn ObservabilityHook>>);
+
+impl Hooks {
+    pub fn push(&mut self, hook: Arc<dyn ObservabilityHook>) {
+        self.0.push(hook);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Start timing the validation of the request described by `context`
+    #[cfg(any(feature = "actix-web", feature = "tower"))]
+    pub(crate) fn start(&self, context: RequestContext) -> Observation {
+        Observation {
+            hooks: self.0.clone(),
+            context,
+            #[cfg(feature = "clock")]
+            start: Instant::now(),
+        }
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Hooks").field(&self.0.len()).finish()
+    }
+}
+
+/// One validation being timed for [`Hooks`]
+#[cfg(any(feature = "actix-web", feature = "tower"))]
+pub(crate) struct Observation {
+    hooks: Vec<Arc<dyn ObservabilityHook>>,
+    context: RequestContext,
+    #[cfg(feature = "clock")]
+    start: Instant,
+}
+
+#[cfg(any(feature = "actix-web", feature = "tower"))]
+impl Observation {
+    pub(crate) fn context(&self) -> &RequestContext {
+        &self.context
+    }
+
+    pub(crate) fn success(&self) {
+        let duration = self.elapsed();
+        for hook in &self.hooks {
+            hook.on_success(&self.context, duration);
+        }
+    }
+
+    pub(crate) fn failure(&self, error: &str) {
+        let duration = self.elapsed();
+        for hook in &self.hooks {
+            hook.on_failure(&self.context, error, duration);
+        }
+    }
+
+    pub(crate) fn deprecated(&self, warnings: &[Issue]) {
+        for warning in warnings {
+            for hook in &self.hooks {
+                hook.on_deprecated(&self.context, &warning.message);
+            }
+        }
+    }
+
+    pub(crate) fn response(&self, status: u16) {
+        for hook in &self.hooks {
+            hook.on_response(&self.context, status);
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        #[cfg(feature = "clock")]
+        return self.start.elapsed();
+        #[cfg(not(feature = "clock"))]
+        Duration::ZERO
+    }
+}
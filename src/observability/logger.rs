@@ -0,0 +1,313 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use log::kv::{self, Key, VisitSource, VisitValue};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::Path;
+
+/// Log configuration structure
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    /// Log level (trace, debug, info, warn, error)
+    pub level: String,
+    /// Log file path (optional)
+    pub log_file: Option<String>,
+    /// Enable console output
+    pub console_output: bool,
+    /// Show timestamp
+    pub show_timestamp: bool,
+    /// Show code location information
+    pub show_target: bool,
+    /// Show thread information
+    pub show_thread: bool,
+    /// Write each record as a single-line JSON object, with the fields of
+    /// validation events as keys, instead of text
+    pub json_format: bool,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            log_file: None,
+            console_output: true,
+            show_timestamp: true,
+            show_target: false,
+            show_thread: false,
+            json_format: false,
+        }
+    }
+}
+
+impl LogConfig {
+    /// Create new log configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set log level
+    pub fn with_level(mut self, level: &str) -> Self {
+        self.level = level.to_string();
+        self
+    }
+
+    /// Set log file path
+    pub fn with_log_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.log_file = Some(file.as_ref().to_string_lossy().to_string());
+        self
+    }
+
+    /// Enable/disable console output
+    pub fn with_console_output(mut self, enabled: bool) -> Self {
+        self.console_output = enabled;
+        self
+    }
+
+    /// Enable/disable timestamp display
+    pub fn with_timestamp(mut self, enabled: bool) -> Self {
+        self.show_timestamp = enabled;
+        self
+    }
+
+    /// Enable/disable target information display
+    pub fn with_target(mut self, enabled: bool) -> Self {
+        self.show_target = enabled;
+        self
+    }
+
+    /// Enable/disable thread information display
+    pub fn with_thread(mut self, enabled: bool) -> Self {
+        self.show_thread = enabled;
+        self
+    }
+
+    /// Enable/disable JSON output
+    pub fn with_json_format(mut self, enabled: bool) -> Self {
+        self.json_format = enabled;
+        self
+    }
+}
+
+/// Initialize logger with default configuration
+pub fn init_logger() {
+    init_logger_with_config(LogConfig::default());
+}
+
+/// Initialize logger with specified configuration
+pub fn init_logger_with_config(config: LogConfig) {
+    let log_level = match config.level.as_str() {
+        "trace" => log::LevelFilter::Trace,
+        "debug" => log::LevelFilter::Debug,
+        "info" => log::LevelFilter::Info,
+        "warn" => log::LevelFilter::Warn,
+        "error" => log::LevelFilter::Error,
+        _ => log::LevelFilter::Info,
+    };
+
+    let json_config = config.clone();
+    let mut dispatch = fern::Dispatch::new()
+        .format(move |out, message, record| {
+            if config.json_format {
+                return out.finish(format_args!("{}", json_line(&json_config, message, record)));
+            }
+
+            let mut format_str = String::new();
+
+            if config.show_timestamp {
+                format_str.push_str(&format!(
+                    "{} ",
+                    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S%.3f")
+                ));
+            }
+
+            format_str.push_str(&format!("[{}]", record.level()));
+
+            if config.show_thread {
+                format_str.push_str(&format!(
+                    " [{}]",
+                    std::thread::current().name().unwrap_or("main")
+                ));
+            }
+
+            if config.show_target {
+                format_str.push_str(&format!(" {}", record.target()));
+            }
+
+            format_str.push_str(&format!(" - {message}"));
+
+            out.finish(format_args!("{format_str}"))
+        })
+        .level(log_level);
+
+    // Console output
+    if config.console_output {
+        dispatch = dispatch.chain(std::io::stdout());
+    }
+
+    // File output
+    if let Some(log_file) = &config.log_file {
+        // Ensure log file directory exists
+        if let Some(parent) = Path::new(log_file).parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                eprintln!("Failed to create log directory {parent:?}: {e}");
+                return;
+            }
+        }
+
+        match fern::log_file(log_file) {
+            Ok(file) => {
+                dispatch = dispatch.chain(file);
+            }
+            Err(e) => {
+                eprintln!("Failed to create log file {log_file}: {e}");
+                return;
+            }
+        }
+    }
+
+    // Apply configuration
+    if let Err(e) = dispatch.apply() {
+        eprintln!("Failed to initialize logger: {e}");
+    } else {
+        log::info!("Logger initialized with config: {config:?}");
+    }
+}
+
+/// Formats `record` as a JSON object. Records carrying key-values, such as
+/// validation events, are written as those key-values; other records as
+/// their message.
+fn json_line(config: &LogConfig, message: &fmt::Arguments, record: &log::Record) -> String {
+    let mut line = Map::new();
+    if config.show_timestamp {
+        let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        line.insert("timestamp".to_string(), timestamp.into());
+    }
+    line.insert("level".to_string(), record.level().as_str().into());
+    if config.show_thread {
+        let thread = std::thread::current().name().unwrap_or("main").to_string();
+        line.insert("thread".to_string(), thread.into());
+    }
+    if config.show_target {
+        line.insert("target".to_string(), record.target().into());
+    }
+
+    let mut fields = Fields(Map::new());
+    let _ = record.key_values().visit(&mut fields);
+    if fields.0.is_empty() {
+        line.insert("message".to_string(), message.to_string().into());
+    }
+    line.extend(fields.0);
+    Value::Object(line).to_string()
+}
+
+/// Collects the key-values of a record, leaving out missing ones
+struct Fields(Map<String, Value>);
+
+impl<'kvs> VisitSource<'kvs> for Fields {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        let mut field = Field(None);
+        value.visit(&mut field)?;
+        if let Some(value) = field.0 {
+            self.0.insert(key.to_string(), value);
+        }
+        Ok(())
+    }
+}
+
+struct Field(Option<Value>);
+
+impl<'v> VisitValue<'v> for Field {
+    fn visit_any(&mut self, value: kv::Value) -> Result<(), kv::Error> {
+        self.0 = Some(value.to_string().into());
+        Ok(())
+    }
+
+    fn visit_null(&mut self) -> Result<(), kv::Error> {
+        Ok(())
+    }
+
+    fn visit_u64(&mut self, value: u64) -> Result<(), kv::Error> {
+        self.0 = Some(value.into());
+        Ok(())
+    }
+
+    fn visit_i64(&mut self, value: i64) -> Result<(), kv::Error> {
+        self.0 = Some(value.into());
+        Ok(())
+    }
+
+    fn visit_bool(&mut self, value: bool) -> Result<(), kv::Error> {
+        self.0 = Some(value.into());
+        Ok(())
+    }
+
+    fn visit_str(&mut self, value: &str) -> Result<(), kv::Error> {
+        self.0 = Some(value.into());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_line() {
+        let config = LogConfig::new()
+            .with_timestamp(false)
+            .with_json_format(true);
+        let fields: &[(&str, kv::Value)] = &[
+            ("event", "openapi_validation".into()),
+            ("method", "GET".into()),
+            ("operation_id", kv::Value::null()),
+            ("success", false.into()),
+            ("duration_ms", 3u64.into()),
+            ("error_code", "invalid_query".into()),
+        ];
+        let line = json_line(
+            &config,
+            &format_args!("openapi_validation method=\"GET\""),
+            &log::Record::builder()
+                .level(log::Level::Warn)
+                .args(format_args!("openapi_validation method=\"GET\""))
+                .key_values(&fields)
+                .build(),
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&line).unwrap(),
+            serde_json::json!({
+                "level": "WARN",
+                "event": "openapi_validation",
+                "method": "GET",
+                "success": false,
+                "duration_ms": 3,
+                "error_code": "invalid_query"
+            })
+        );
+
+        let line = json_line(
+            &config,
+            &format_args!("Logger initialized"),
+            &log::Record::builder()
+                .level(log::Level::Info)
+                .args(format_args!("Logger initialized"))
+                .build(),
+        );
+        assert_eq!(line, r#"{"level":"INFO","message":"Logger initialized"}"#);
+    }
+}
@@ -0,0 +1,117 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Prometheus metrics for validations, served in the text exposition format
+//! by `request::axum::metrics` and `request::actix_web::metrics`.
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// The `Content-Type` of [`render`]
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// The `operation` label of validations that matched no documented operation
+const UNMATCHED: &str = "unmatched";
+
+struct Metrics {
+    registry: Registry,
+    total: IntCounterVec,
+    duration: HistogramVec,
+    deprecated: IntCounterVec,
+}
+
+fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(|| {
+        let registry = Registry::new();
+        let total = IntCounterVec::new(
+            Opts::new("openapi_validation_total", "OpenAPI request validations"),
+            &["outcome", "operation"],
+        )
+        .expect("valid counter options");
+        let duration = HistogramVec::new(
+            HistogramOpts::new(
+                "openapi_validation_duration_seconds",
+                "Time spent validating requests against the OpenAPI document",
+            ),
+            &["operation"],
+        )
+        .expect("valid histogram options");
+        let deprecated = IntCounterVec::new(
+            Opts::new(
+                "openapi_deprecated_total",
+                "Requests using deprecated operations or parameters",
+            ),
+            &["method", "path", "parameter"],
+        )
+        .expect("valid counter options");
+        registry
+            .register(Box::new(total.clone()))
+            .expect("metric registered once");
+        registry
+            .register(Box::new(duration.clone()))
+            .expect("metric registered once");
+        registry
+            .register(Box::new(deprecated.clone()))
+            .expect("metric registered once");
+        Metrics {
+            registry,
+            total,
+            duration,
+            deprecated,
+        }
+    })
+}
+
+/// The registry holding the validation metrics, which applications may
+/// register their own collectors with to serve them from the same handler
+pub fn registry() -> &'static Registry {
+    &metrics().registry
+}
+
+/// Encode every metric of [`registry`] in the Prometheus text format
+pub fn render() -> String {
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&registry().gather(), &mut buffer) {
+        log::error!("Failed to encode metrics: {e}");
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+/// Count a validation of `operation`, an `operationId` or `METHOD /template`,
+/// and observe how long it took
+pub(crate) fn observe(operation: Option<&str>, outcome: &str, elapsed: Duration) {
+    let metrics = metrics();
+    let operation = operation.unwrap_or(UNMATCHED);
+    metrics.total.with_label_values(&[outcome, operation]).inc();
+    metrics
+        .duration
+        .with_label_values(&[operation])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// Count a use of the deprecated `method` operation of the `path` template,
+/// or of its deprecated query `parameter` when not empty
+pub(crate) fn deprecated(method: &str, path: &str, parameter: &str) {
+    metrics()
+        .deprecated
+        .with_label_values(&[&method.to_ascii_uppercase(), path, parameter])
+        .inc();
+}
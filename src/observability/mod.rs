@@ -15,217 +15,410 @@
  * limitations under the License.
  */
 
-use std::path::Path;
+mod coverage;
+mod hook;
+#[cfg(feature = "logging")]
+mod logger;
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+#[cfg(feature = "opentelemetry")]
+mod otel;
+
+pub use coverage::{CoverageReport, CoverageTracker, OperationCoverage, ResponseCoverage};
+pub use hook::{Hooks, ObservabilityHook};
+#[cfg(feature = "logging")]
+pub use logger::{init_logger, init_logger_with_config, LogConfig};
+
+use crate::model::lint::escape;
+use crate::validator::report::Issue;
+use std::cell::RefCell;
+use std::fmt;
+#[cfg(feature = "clock")]
 use std::time::Instant;
 
+thread_local! {
+    /// Warnings recorded on this thread while [`collect_warnings`] runs
+    static WARNINGS: RefCell<Option<Vec<Issue>>> = const { RefCell::new(None) };
+}
+
 #[derive(Debug, Clone)]
 pub struct RequestContext {
     pub method: String,
     pub path: String,
+    /// The W3C `traceparent` header of the request, which validation spans
+    /// are parented to
+    pub traceparent: Option<String>,
+    /// Correlates the validation with other systems' logs, named in
+    /// validation logs and rejection messages
+    pub request_id: Option<String>,
 }
 
 impl RequestContext {
     pub fn new(method: String, path: String) -> Self {
-        Self { method, path }
+        Self {
+            method,
+            path,
+            traceparent: None,
+            request_id: None,
+        }
+    }
+
+    pub fn with_traceparent(mut self, traceparent: Option<String>) -> Self {
+        self.traceparent = traceparent;
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
+    /// Set `traceparent` and the request ID from the `X-Request-Id` and
+    /// `traceparent` headers, using the trace ID as request ID when there is
+    /// no `X-Request-Id`
+    pub fn with_trace_headers(self, request_id: Option<&str>, traceparent: Option<&str>) -> Self {
+        let request_id = request_id.or_else(|| {
+            traceparent
+                .and_then(|traceparent| traceparent.split('-').nth(1))
+                .filter(|trace_id| trace_id.len() == 32)
+        });
+        self.with_request_id(request_id.map(str::to_string))
+            .with_traceparent(traceparent.map(str::to_string))
+    }
+
+    /// `error` followed by the request ID, for rejection messages
+    pub fn error_message(&self, error: &str) -> String {
+        match &self.request_id {
+            Some(request_id) => format!("{error} (request_id={request_id})"),
+            None => error.to_string(),
+        }
     }
 }
 
 pub struct ValidationMetrics {
+    #[cfg(feature = "clock")]
     start_time: Instant,
     method: String,
     path: String,
+    request_id: Option<String>,
+    operation_id: Option<String>,
+    /// The entered `openapi.validate` span, closed once the outcome is recorded
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    /// The `operation` label of the Prometheus metrics
+    #[cfg(feature = "prometheus")]
+    operation: Option<String>,
+    #[cfg(feature = "opentelemetry")]
+    otel: otel::Validation,
 }
 
 impl ValidationMetrics {
     pub fn new(method: &str, path: &str) -> Self {
+        Self::from_context(&RequestContext::new(method.to_string(), path.to_string()))
+    }
+
+    pub fn from_context(context: &RequestContext) -> Self {
+        let (method, path) = (context.method.as_str(), context.path.as_str());
         Self {
+            #[cfg(feature = "clock")]
             start_time: Instant::now(),
             method: method.to_string(),
             path: path.to_string(),
+            request_id: context.request_id.clone(),
+            operation_id: None,
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "openapi.validate",
+                method,
+                path,
+                request_id = context.request_id,
+                operation_id = tracing::field::Empty,
+                outcome = tracing::field::Empty,
+                error = tracing::field::Empty,
+            )
+            .entered(),
+            #[cfg(feature = "prometheus")]
+            operation: None,
+            #[cfg(feature = "opentelemetry")]
+            otel: otel::Validation::start(method, path, context.traceparent.as_deref()),
         }
     }
 
-    pub fn from_context(context: &RequestContext) -> Self {
-        Self::new(&context.method, &context.path)
+    /// Record the path template and `operationId` of the operation the
+    /// request resolved to, for the logs, the `openapi.validate` span and the
+    /// `operation` metrics label
+    #[cfg_attr(
+        not(any(feature = "tracing", feature = "prometheus", feature = "opentelemetry")),
+        allow(unused_variables)
+    )]
+    pub fn record_operation(&mut self, path_template: &str, operation_id: Option<&str>) {
+        self.operation_id = operation_id.map(str::to_string);
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("path", path_template);
+            if let Some(operation_id) = operation_id {
+                self.span.record("operation_id", operation_id);
+            }
+        }
+        #[cfg(feature = "prometheus")]
+        {
+            self.operation = Some(match operation_id {
+                Some(operation_id) => operation_id.to_string(),
+                None => format!("{} {path_template}", self.method.to_ascii_uppercase()),
+            });
+        }
+        #[cfg(feature = "opentelemetry")]
+        self.otel
+            .record_operation(&self.method, path_template, operation_id);
     }
 
-    pub fn record_success(self) {
-        let duration_ms = self.start_time.elapsed().as_millis();
-        let timestamp = chrono::Utc::now().timestamp_millis();
+    /// Record the path template of a request whose method has no operation
+    /// there, for the `openapi.validate` span. The metrics keep the
+    /// `unmatched` label, as the method is whatever the client sent.
+    #[cfg_attr(
+        not(any(feature = "tracing", feature = "opentelemetry")),
+        allow(unused_variables)
+    )]
+    pub fn record_path(&mut self, path_template: &str) {
+        #[cfg(feature = "tracing")]
+        self.span.record("path", path_template);
+        #[cfg(feature = "opentelemetry")]
+        self.otel.record_route(path_template);
+    }
 
+    pub fn record_success(self) {
+        #[cfg(feature = "tracing")]
+        self.span.record("outcome", "success");
+        #[cfg(feature = "prometheus")]
+        metrics::observe(
+            self.operation.as_deref(),
+            "success",
+            self.start_time.elapsed(),
+        );
         log::info!(
-            "openapi_validation method=\"{}\" path=\"{}\" success=true duration_ms={} timestamp={}",
+            event = "openapi_validation",
+            method = self.method.as_str(),
+            path = self.path.as_str(),
+            operation_id = self.operation_id.as_deref(),
+            request_id = self.request_id.as_deref(),
+            success = true,
+            duration_ms = self.duration_ms();
+            "openapi_validation method=\"{}\" path=\"{}\"{}{} success=true{}{}",
             self.method,
             self.path,
-            duration_ms,
-            timestamp
+            Quoted("operation_id", self.operation_id.as_deref()),
+            Quoted("request_id", self.request_id.as_deref()),
+            self.duration(),
+            Timestamp
         );
+        #[cfg(feature = "opentelemetry")]
+        self.otel.finish(None, self.start_time.elapsed());
     }
 
     pub fn record_failure(self, error: String) {
-        let duration_ms = self.start_time.elapsed().as_millis();
-        let timestamp = chrono::Utc::now().timestamp_millis();
-
+        #[cfg(feature = "tracing")]
+        self.span
+            .record("outcome", "failure")
+            .record("error", error.as_str());
+        #[cfg(feature = "prometheus")]
+        metrics::observe(
+            self.operation.as_deref(),
+            "failure",
+            self.start_time.elapsed(),
+        );
         log::warn!(
-            "openapi_validation method=\"{}\" path=\"{}\" success=false duration_ms={} error=\"{}\" timestamp={}",
+            event = "openapi_validation",
+            method = self.method.as_str(),
+            path = self.path.as_str(),
+            operation_id = self.operation_id.as_deref(),
+            request_id = self.request_id.as_deref(),
+            success = false,
+            duration_ms = self.duration_ms(),
+            error_code = error_code(&error),
+            error = error.as_str();
+            "openapi_validation method=\"{}\" path=\"{}\"{}{} success=false{} error=\"{}\"{}",
             self.method,
             self.path,
-            duration_ms,
+            Quoted("operation_id", self.operation_id.as_deref()),
+            Quoted("request_id", self.request_id.as_deref()),
+            self.duration(),
             error,
-            timestamp
+            Timestamp
         );
+        #[cfg(feature = "opentelemetry")]
+        self.otel.finish(Some(&error), self.start_time.elapsed());
     }
-}
 
-/// Log configuration structure
-#[derive(Debug, Clone)]
-pub struct LogConfig {
-    /// Log level (trace, debug, info, warn, error)
-    pub level: String,
-    /// Log file path (optional)
-    pub log_file: Option<String>,
-    /// Enable console output
-    pub console_output: bool,
-    /// Show timestamp
-    pub show_timestamp: bool,
-    /// Show code location information
-    pub show_target: bool,
-    /// Show thread information
-    pub show_thread: bool,
-}
-
-impl Default for LogConfig {
-    fn default() -> Self {
-        Self {
-            level: "info".to_string(),
-            log_file: None,
-            console_output: true,
-            show_timestamp: true,
-            show_target: false,
-            show_thread: false,
-        }
+    fn duration(&self) -> Duration {
+        Duration(self.duration_ms())
     }
-}
 
-impl LogConfig {
-    /// Create new log configuration
-    pub fn new() -> Self {
-        Self::default()
+    fn duration_ms(&self) -> Option<u64> {
+        #[cfg(feature = "clock")]
+        return Some(self.start_time.elapsed().as_millis() as u64);
+        #[cfg(not(feature = "clock"))]
+        None
     }
+}
 
-    /// Set log level
-    pub fn with_level(mut self, level: &str) -> Self {
-        self.level = level.to_string();
-        self
+/// A stable code for the check an error of `OpenAPI::validator` comes from
+fn error_code(error: &str) -> &'static str {
+    let phase = error.split(" validation failed").next().unwrap_or_default();
+    match phase {
+        "Method" => "invalid_method",
+        "Security" => "unauthorized",
+        "Path" => "invalid_path",
+        "Query" => "invalid_query",
+        "Body" => "invalid_body",
+        _ => "invalid_document",
     }
+}
 
-    /// Set log file path
-    pub fn with_log_file<P: AsRef<Path>>(mut self, file: P) -> Self {
-        self.log_file = Some(file.as_ref().to_string_lossy().to_string());
-        self
-    }
+/// Run one phase of a validation, such as `method` or `body`, inside an
+/// `openapi.validate.phase` span nested in the `openapi.validate` one
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub fn phase<T>(name: &'static str, check: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("openapi.validate.phase", phase = name).entered();
+    check()
+}
 
-    /// Enable/disable console output
-    pub fn with_console_output(mut self, enabled: bool) -> Self {
-        self.console_output = enabled;
-        self
-    }
+/// A quoted field of a log line, such as ` request_id="..."`, left out when
+/// the request has no value for it
+struct Quoted<'a>(&'static str, Option<&'a str>);
 
-    /// Enable/disable timestamp display
-    pub fn with_timestamp(mut self, enabled: bool) -> Self {
-        self.show_timestamp = enabled;
-        self
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.1 {
+            Some(value) => write!(f, " {}=\"{value}\"", self.0),
+            None => Ok(()),
+        }
     }
+}
 
-    /// Enable/disable target information display
-    pub fn with_target(mut self, enabled: bool) -> Self {
-        self.show_target = enabled;
-        self
-    }
+/// The ` duration_ms=` field of a log line, left out without the `clock`
+/// feature
+struct Duration(Option<u64>);
 
-    /// Enable/disable thread information display
-    pub fn with_thread(mut self, enabled: bool) -> Self {
-        self.show_thread = enabled;
-        self
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(duration_ms) => write!(f, " duration_ms={duration_ms}"),
+            None => Ok(()),
+        }
     }
 }
 
-/// Initialize logger with default configuration
-pub fn init_logger() {
-    init_logger_with_config(LogConfig::default());
-}
-
-/// Initialize logger with specified configuration
-pub fn init_logger_with_config(config: LogConfig) {
-    let log_level = match config.level.as_str() {
-        "trace" => log::LevelFilter::Trace,
-        "debug" => log::LevelFilter::Debug,
-        "info" => log::LevelFilter::Info,
-        "warn" => log::LevelFilter::Warn,
-        "error" => log::LevelFilter::Error,
-        _ => log::LevelFilter::Info,
-    };
+/// The ` timestamp=` field of a log line, left out without the `clock`
+/// feature as targets such as `wasm32-unknown-unknown` have no system clock
+struct Timestamp;
 
-    let mut dispatch = fern::Dispatch::new()
-        .format(move |out, message, record| {
-            let mut format_str = String::new();
-
-            if config.show_timestamp {
-                format_str.push_str(&format!(
-                    "{} ",
-                    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S%.3f")
-                ));
-            }
-
-            format_str.push_str(&format!("[{}]", record.level()));
-
-            if config.show_thread {
-                format_str.push_str(&format!(
-                    " [{}]",
-                    std::thread::current().name().unwrap_or("main")
-                ));
-            }
+impl fmt::Display for Timestamp {
+    #[cfg(feature = "clock")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " timestamp={}", chrono::Utc::now().timestamp_millis())
+    }
 
-            if config.show_target {
-                format_str.push_str(&format!(" {}", record.target()));
-            }
+    #[cfg(not(feature = "clock"))]
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
 
-            format_str.push_str(&format!(" - {message}"));
+/// Run `f`, returning along with its result the warnings it records, such as
+/// uses of deprecated operations and parameters
+pub(crate) fn collect_warnings<T>(f: impl FnOnce() -> T) -> (T, Vec<Issue>) {
+    let outer = WARNINGS.with(|warnings| warnings.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let collected = WARNINGS.with(|warnings| std::mem::replace(&mut *warnings.borrow_mut(), outer));
+    (result, collected.unwrap_or_default())
+}
 
-            out.finish(format_args!("{format_str}"))
-        })
-        .level(log_level);
+fn warn(issue: impl FnOnce() -> Issue) {
+    WARNINGS.with(|warnings| {
+        if let Some(warnings) = warnings.borrow_mut().as_mut() {
+            warnings.push(issue());
+        }
+    });
+}
 
-    // Console output
-    if config.console_output {
-        dispatch = dispatch.chain(std::io::stdout());
-    }
+/// Record a request made against an operation marked `deprecated: true`,
+/// `path` being the path template of the document
+pub fn record_deprecated_operation(method: &str, path: &str) {
+    #[cfg(feature = "prometheus")]
+    metrics::deprecated(method, path, "");
+    warn(|| {
+        let method = method.to_ascii_uppercase();
+        Issue::new("", format!("Operation '{method} {path}' is deprecated"))
+    });
+    log::warn!(
+        event = "openapi_deprecated",
+        method,
+        path;
+        "openapi_deprecated method=\"{}\" path=\"{}\"{}",
+        method,
+        path,
+        Timestamp
+    );
+}
 
-    // File output
-    if let Some(log_file) = &config.log_file {
-        // Ensure log file directory exists
-        if let Some(parent) = Path::new(log_file).parent() {
-            if let Err(e) = std::fs::create_dir_all(parent) {
-                eprintln!("Failed to create log directory {parent:?}: {e}");
-                return;
-            }
-        }
+/// Record a request carrying a parameter marked `deprecated: true` of the
+/// `method` operation of the `path` template
+pub fn record_deprecated_parameter(method: &str, path: &str, parameter: &str) {
+    #[cfg(feature = "prometheus")]
+    metrics::deprecated(method, path, parameter);
+    warn(|| {
+        Issue::new(
+            format!("/query/{}", escape(parameter)),
+            format!("Query parameter '{parameter}' is deprecated"),
+        )
+    });
+    log::warn!(
+        event = "openapi_deprecated",
+        method,
+        path,
+        parameter;
+        "openapi_deprecated method=\"{}\" path=\"{}\" parameter=\"{}\"{}",
+        method,
+        path,
+        parameter,
+        Timestamp
+    );
+}
 
-        match fern::log_file(log_file) {
-            Ok(file) => {
-                dispatch = dispatch.chain(file);
-            }
-            Err(e) => {
-                eprintln!("Failed to create log file {log_file}: {e}");
-                return;
-            }
-        }
-    }
+/// Record a response that does not match the responses modeled for its
+/// operation
+pub fn record_response_violation(method: &str, path: &str, status: u16, error: &str) {
+    log::warn!(
+        event = "openapi_response_violation",
+        method,
+        path,
+        status,
+        error;
+        "openapi_response_violation method=\"{}\" path=\"{}\" status={} error=\"{}\"{}",
+        method,
+        path,
+        status,
+        error,
+        Timestamp
+    );
+}
 
-    // Apply configuration
-    if let Err(e) = dispatch.apply() {
-        eprintln!("Failed to initialize logger: {e}");
-    } else {
-        log::info!("Logger initialized with config: {config:?}");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_line_fields() {
+        let line = format!(
+            "openapi_validation{}{}{}",
+            Quoted("operation_id", Some("getUser")),
+            Quoted("request_id", None),
+            Duration(Some(3))
+        );
+        assert_eq!(
+            line,
+            "openapi_validation operation_id=\"getUser\" duration_ms=3"
+        );
     }
 }
@@ -0,0 +1,264 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! OpenTelemetry spans and metrics for validations, reported through the
+//! global tracer and meter providers installed by the application.
+
+use opentelemetry::global::{self, BoxedSpan};
+use opentelemetry::trace::{
+    Span, SpanContext, SpanId, SpanKind, Status, TraceContextExt, TraceFlags, TraceId, TraceState,
+    Tracer,
+};
+use opentelemetry::{Context, KeyValue};
+use std::time::Duration;
+
+/// The instrumentation scope of the spans and metrics
+const SCOPE: &str = "openapi-rs";
+
+/// The `operation` attribute of validations that matched no documented operation
+const UNMATCHED: &str = "unmatched";
+
+/// The `openapi.validate` span of one validation
+pub(crate) struct Validation {
+    span: BoxedSpan,
+    operation: Option<String>,
+}
+
+impl Validation {
+    /// Start the span as a child of the request's `traceparent`, or of the
+    /// current context when it has none or it is malformed
+    pub(crate) fn start(method: &str, path: &str, traceparent: Option<&str>) -> Self {
+        let parent = match traceparent.and_then(parse_traceparent) {
+            Some(span_context) => Context::new().with_remote_span_context(span_context),
+            None => Context::current(),
+        };
+        let tracer = global::tracer(SCOPE);
+        let span = tracer
+            .span_builder("openapi.validate")
+            .with_kind(SpanKind::Internal)
+            .with_attributes([
+                KeyValue::new("http.request.method", method.to_ascii_uppercase()),
+                KeyValue::new(
+                    "url.path",
+                    path.split('?').next().unwrap_or_default().to_string(),
+                ),
+            ])
+            .start_with_context(&tracer, &parent);
+        Self {
+            span,
+            operation: None,
+        }
+    }
+
+    pub(crate) fn record_operation(
+        &mut self,
+        method: &str,
+        path_template: &str,
+        operation_id: Option<&str>,
+    ) {
+        self.record_route(path_template);
+        if let Some(operation_id) = operation_id {
+            self.span.set_attribute(KeyValue::new(
+                "openapi.operation_id",
+                operation_id.to_string(),
+            ));
+        }
+        self.operation = Some(match operation_id {
+            Some(operation_id) => operation_id.to_string(),
+            None => format!("{} {path_template}", method.to_ascii_uppercase()),
+        });
+    }
+
+    pub(crate) fn record_route(&mut self, path_template: &str) {
+        self.span
+            .set_attribute(KeyValue::new("http.route", path_template.to_string()));
+    }
+
+    /// End the span and record the validation, failed when `error` is set
+    pub(crate) fn finish(mut self, error: Option<&str>, elapsed: Duration) {
+        let outcome = if error.is_some() {
+            "failure"
+        } else {
+            "success"
+        };
+        self.span
+            .set_attribute(KeyValue::new("openapi.validation.outcome", outcome));
+        if let Some(error) = error {
+            self.span.set_status(Status::error(error.to_string()));
+        }
+        self.span.end();
+
+        let operation = KeyValue::new(
+            "operation",
+            self.operation.unwrap_or_else(|| UNMATCHED.to_string()),
+        );
+        let meter = global::meter(SCOPE);
+        meter
+            .u64_counter("openapi.validations")
+            .with_description("OpenAPI request validations")
+            .build()
+            .add(1, &[KeyValue::new("outcome", outcome), operation.clone()]);
+        meter
+            .f64_histogram("openapi.validation.duration")
+            .with_description("Time spent validating requests against the OpenAPI document")
+            .with_unit("s")
+            .build()
+            .record(elapsed.as_secs_f64(), &[operation]);
+    }
+}
+
+/// Parse a W3C `traceparent` header, `{version}-{trace-id}-{parent-id}-{flags}`
+fn parse_traceparent(value: &str) -> Option<SpanContext> {
+    let mut parts = value.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let span_id = parts.next()?;
+    let flags = parts.next()?;
+    if version.len() != 2
+        || version.eq_ignore_ascii_case("ff")
+        || (version == "00" && parts.next().is_some())
+        || trace_id.len() != 32
+        || span_id.len() != 16
+        || flags.len() != 2
+    {
+        return None;
+    }
+
+    let trace_id = TraceId::from_hex(trace_id).ok()?;
+    let span_id = SpanId::from_hex(span_id).ok()?;
+    let flags = u8::from_str_radix(flags, 16).ok()?;
+    if trace_id == TraceId::INVALID || span_id == SpanId::INVALID {
+        return None;
+    }
+    Some(SpanContext::new(
+        trace_id,
+        span_id,
+        TraceFlags::new(flags),
+        true,
+        TraceState::default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::{RequestContext, ValidationMetrics};
+    use opentelemetry::Value;
+    use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+    use opentelemetry_sdk::metrics::{InMemoryMetricExporter, PeriodicReader, SdkMeterProvider};
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+
+    #[test]
+    fn test_parse_traceparent() {
+        let span_context =
+            parse_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+        assert_eq!(
+            span_context.trace_id(),
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap()
+        );
+        assert_eq!(
+            span_context.span_id(),
+            SpanId::from_hex("00f067aa0ba902b7").unwrap()
+        );
+        assert!(span_context.is_sampled());
+        assert!(span_context.is_remote());
+
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473z-00f067aa0ba902b7-01",
+        ] {
+            assert!(parse_traceparent(invalid).is_none(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_validation_span_and_metrics() {
+        let spans = InMemorySpanExporter::default();
+        global::set_tracer_provider(
+            SdkTracerProvider::builder()
+                .with_simple_exporter(spans.clone())
+                .build(),
+        );
+        let metrics = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(metrics.clone()).build())
+            .build();
+        global::set_meter_provider(meter_provider.clone());
+
+        let context = RequestContext::new("get".to_string(), "/users/7?page=x".to_string())
+            .with_traceparent(Some(
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+            ));
+        let mut validation = ValidationMetrics::from_context(&context);
+        validation.record_operation("/users/{id}", Some("getUser"));
+        validation.record_failure("Query validation failed: page".to_string());
+
+        let trace_id = TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap();
+        let finished = spans.get_finished_spans().unwrap();
+        let span = finished
+            .iter()
+            .find(|span| span.span_context.trace_id() == trace_id)
+            .unwrap();
+        assert_eq!(span.name, "openapi.validate");
+        assert_eq!(
+            span.parent_span_id,
+            SpanId::from_hex("00f067aa0ba902b7").unwrap()
+        );
+        let attribute = |key: &str| {
+            span.attributes
+                .iter()
+                .find(|attribute| attribute.key.as_str() == key)
+                .map(|attribute| attribute.value.clone())
+        };
+        assert_eq!(attribute("http.request.method"), Some(Value::from("GET")));
+        assert_eq!(attribute("url.path"), Some(Value::from("/users/7")));
+        assert_eq!(attribute("http.route"), Some(Value::from("/users/{id}")));
+        assert_eq!(
+            attribute("openapi.operation_id"),
+            Some(Value::from("getUser"))
+        );
+        assert_eq!(
+            attribute("openapi.validation.outcome"),
+            Some(Value::from("failure"))
+        );
+        assert_eq!(span.status, Status::error("Query validation failed: page"));
+
+        meter_provider.force_flush().unwrap();
+        let exported = metrics.get_finished_metrics().unwrap();
+        let counter = exported
+            .iter()
+            .flat_map(|resource| resource.scope_metrics())
+            .filter(|scope| scope.scope().name() == SCOPE)
+            .flat_map(|scope| scope.metrics())
+            .find(|metric| metric.name() == "openapi.validations")
+            .unwrap();
+        let AggregatedMetrics::U64(MetricData::Sum(sum)) = counter.data() else {
+            panic!("openapi.validations is not a u64 sum");
+        };
+        assert!(sum.data_points().any(|point| {
+            point.value() >= 1
+                && point
+                    .attributes()
+                    .any(|kv| kv.key.as_str() == "operation" && kv.value.as_str() == "getUser")
+        }));
+    }
+}
@@ -15,88 +15,41 @@
  * limitations under the License.
  */
 
+use crate::model::drift::RouteDrift;
 use crate::model::parse::OpenAPI;
-use crate::observability::RequestContext;
-use crate::validator::{body, method, path, query, ValidateRequest};
+use crate::model::registry::SpecRegistry;
+use crate::model::shared::SharedOpenAPI;
+use crate::model::version::VersionedSpecs;
+use crate::observability::{self, RequestContext};
+use crate::request::config::{MiddlewareConfig, ViolationMode};
+use crate::request::validation::Specs;
+use crate::validator::stream::{BodyLimit, JsonLines};
+use crate::validator::{self, MethodNotAllowed, Unauthorized};
 use actix_web::{
-    body::{EitherBody, MessageBody},
+    body::{self as actix_body, BoxBody, EitherBody, MessageBody},
     dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
-    web::{Bytes, BytesMut},
-    Error, HttpMessage, HttpRequest,
+    http::{header, Method, StatusCode},
+    web::{self, Bytes, BytesMut, Data, ServiceConfig},
+    Error, FromRequest, Handler, HttpMessage, HttpRequest, HttpResponse, HttpResponseBuilder,
+    Responder,
 };
 use anyhow::Result;
 use futures_util::{future::LocalBoxFuture, StreamExt};
-use serde_json::Value;
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
 use std::collections::HashMap;
 use std::future::{ready, Ready};
 use std::rc::Rc;
-use std::sync::Arc;
-
-#[allow(dead_code)]
-pub struct RequestData {
-    pub path: String,
-    pub method: String,
-    pub query_string: String,
-    pub body: Option<Bytes>,
-}
-
-impl ValidateRequest for RequestData {
-    fn header(&self, _: &OpenAPI) -> Result<()> {
-        Ok(())
-    }
-
-    fn method(&self, open_api: &OpenAPI) -> Result<()> {
-        method(self.path.as_str(), self.method.as_str(), open_api)
-    }
-
-    fn query(&self, open_api: &OpenAPI) -> Result<()> {
-        let query_pairs: HashMap<String, String> = if !self.query_string.is_empty() {
-            self.query_string
-                .split('&')
-                .filter_map(|pair| {
-                    let mut split = pair.split('=');
-                    match (split.next(), split.next()) {
-                        (Some(key), Some(value)) => Some((key.to_string(), value.to_string())),
-                        _ => None,
-                    }
-                })
-                .collect()
-        } else {
-            HashMap::new()
-        };
-
-        query(self.path.as_str(), &query_pairs, open_api)
-    }
 
-    fn path(&self, open_api: &OpenAPI) -> Result<()> {
-        if let Some(last_segment) = self.path.rsplit('/').find(|s| !s.is_empty()) {
-            path(self.path.as_str(), last_segment, open_api)?
-        }
-
-        Ok(())
-    }
-
-    fn body(&self, open_api: &OpenAPI) -> Result<()> {
-        if self.body.is_none() {
-            return Ok(());
-        }
-        let self_body = self
-            .body
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Missing body"))?;
-        let request_fields: Value = serde_json::from_slice(self_body)?;
-        body(self.path.as_str(), request_fields, open_api)
-    }
-
-    fn context(&self) -> RequestContext {
-        RequestContext::new(self.method.clone(), self.path.clone())
-    }
-}
+pub use crate::request::validation::RequestData;
 
 /// OpenAPI validates middleware
 ///
 /// Provides request validation based on OpenAPI specifications, supporting path, method, query parameters, and request body validation.
 ///
+/// Wrapping a `web::scope` or `web::resource` instead of the `App` validates
+/// only the routes it contains.
+///
 /// # example
 ///
 /// ```rust
@@ -124,13 +77,48 @@ impl ValidateRequest for RequestData {
 /// ```
 #[derive(Debug, Clone)]
 pub struct OpenApiValidation {
-    openapi: Arc<OpenAPI>,
+    specs: Specs,
+    config: MiddlewareConfig,
 }
 
 impl OpenApiValidation {
     pub fn new(openapi: OpenAPI) -> Self {
+        Self::from_shared(SharedOpenAPI::new(openapi))
+    }
+
+    /// Validates against the current document of `openapi`, so replacing it
+    /// takes effect without rebuilding the middleware stack
+    pub fn from_shared(openapi: SharedOpenAPI) -> Self {
+        Self::from_registry(SpecRegistry::new().with("/", openapi))
+    }
+
+    /// Validates each request against the document mounted at the longest
+    /// prefix of its path, with that prefix removed. Requests outside every
+    /// prefix are answered with `404 Not Found`.
+    pub fn from_registry(registry: SpecRegistry) -> Self {
+        Self {
+            specs: Specs::Prefixed(registry),
+            config: MiddlewareConfig::default(),
+        }
+    }
+
+    /// Validates each request against the version of the document it selects,
+    /// see [`VersionedSpecs`]. Requests selecting no loaded version are
+    /// answered with `404 Not Found`.
+    pub fn from_versions(versions: VersionedSpecs) -> Self {
         Self {
-            openapi: Arc::new(openapi),
+            specs: Specs::Versioned(versions),
+            config: MiddlewareConfig::default(),
+        }
+    }
+
+    /// The document mounted at the root, which is the only one unless the
+    /// middleware was built [`from_registry`](Self::from_registry) or
+    /// [`from_versions`](Self::from_versions)
+    pub fn shared(&self) -> Option<&SharedOpenAPI> {
+        match &self.specs {
+            Specs::Prefixed(registry) => registry.get("/"),
+            Specs::Versioned(_) => None,
         }
     }
 
@@ -138,6 +126,21 @@ impl OpenApiValidation {
         let openapi: OpenAPI = serde_yaml::from_str(yaml_content)?;
         Ok(Self::new(openapi))
     }
+
+    /// Forward requests for paths the document does not declare to the
+    /// wrapped service unvalidated, instead of failing them with "Path not
+    /// found". Useful when the middleware wraps a whole `App` serving routes
+    /// such as `/health` outside the specification.
+    pub fn with_skip_undocumented(mut self, enabled: bool) -> Self {
+        self.config.skip_undocumented = enabled;
+        self
+    }
+
+    /// Replace the configuration of which routes are validated
+    pub fn with_config(mut self, config: MiddlewareConfig) -> Self {
+        self.config = config;
+        self
+    }
 }
 
 impl<S, B> Transform<S, ServiceRequest> for OpenApiValidation
@@ -155,14 +158,16 @@ where
     fn new_transform(&self, service: S) -> Self::Future {
         ready(Ok(OpenApiValidationMiddleware {
             service: Rc::new(service),
-            openapi: self.openapi.clone(),
+            specs: self.specs.clone(),
+            config: self.config.clone(),
         }))
     }
 }
 
 pub struct OpenApiValidationMiddleware<S> {
     service: Rc<S>,
-    openapi: Arc<OpenAPI>,
+    specs: Specs,
+    config: MiddlewareConfig,
 }
 
 impl<S, B> Service<ServiceRequest> for OpenApiValidationMiddleware<S>
@@ -179,24 +184,66 @@ where
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let service = Rc::clone(&self.service);
-        let openapi = Arc::clone(&self.openapi);
+        let headers: HashMap<String, String> = req
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let selected = self.specs.select(req.path(), &headers);
+        let undocumented =
+            !matches!(&selected, Some((openapi, path)) if openapi.path_item(path).is_some());
+        let skip =
+            !self.config.validates(req.path()) || (self.config.skip_undocumented && undocumented);
+        let hooks = self.config.hooks.clone();
 
         Box::pin(async move {
-            let path = req.path().to_string();
+            if skip {
+                return service.call(req).await.map(|res| res.map_into_left_body());
+            }
+            let observation = hooks.start(
+                RequestContext::new(req.method().to_string(), req.path().to_string())
+                    .with_trace_headers(
+                        headers.get("x-request-id").map(String::as_str),
+                        headers.get("traceparent").map(String::as_str),
+                    ),
+            );
+            let reject = |mut response: HttpResponseBuilder, message: String| {
+                observation.failure(&message);
+                let message = observation.context().error_message(&message);
+                let response = response
+                    .content_type("text/plain; charset=utf-8")
+                    .body(format!("OpenAPI validation failed: {message}"));
+                observation.response(response.status().as_u16());
+                response
+            };
+            let Some((openapi, path)) = selected else {
+                let message = format!("no document matches '{}'", req.path());
+                let response = reject(HttpResponse::NotFound(), message);
+                return Ok(req.into_response(response).map_into_right_body());
+            };
             let method = req.method().as_str().to_lowercase();
             let query_string = req.query_string().to_string();
+            let content_type = req
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
 
             let (http_req, payload) = req.into_parts();
 
             let mut req_body = None;
+            let mut body_validated = false;
 
             if Self::should_extract_body(&http_req) {
-                match Self::extract_body_safely(payload, &http_req).await {
-                    Ok(body) => req_body = body,
-                    Err(e) => {
+                let extracted =
+                    Self::extract_body_safely(payload, &http_req, &openapi, &path, &method).await;
+                match extracted {
+                    Ok((body, validated)) => (req_body, body_validated) = (body, validated),
+                    Err((status, message)) => {
+                        let response = reject(HttpResponse::build(status), message);
                         let error_req =
                             ServiceRequest::from_parts(http_req, Payload::from(Vec::<u8>::new()));
-                        return Ok(error_req.error_response(e).map_into_right_body());
+                        return Ok(error_req.into_response(response).map_into_right_body());
                     }
                 }
             }
@@ -205,36 +252,48 @@ where
                 path: path.clone(),
                 method,
                 query_string,
+                content_type,
+                headers,
                 body: req_body.clone(),
+                body_validated,
             };
 
             let rebuild_service_request = |http_req: HttpRequest, req_body: &Option<Bytes>| {
                 if let Some(ref body_bytes) = req_body {
                     let req =
                         ServiceRequest::from_parts(http_req, Payload::from(body_bytes.clone()));
-                    req.extensions_mut().insert(body_bytes.clone());
+                    req.extensions_mut()
+                        .insert(PreExtractedBody(body_bytes.clone()));
                     req
                 } else {
                     ServiceRequest::from_parts(http_req, Payload::from(Vec::<u8>::new()))
                 }
             };
 
-            if let Err(e) = openapi.validator(request_data) {
-                let validation_error =
-                    actix_web::error::ErrorBadRequest(format!("OpenAPI validation failed: {e}"));
-
+            let (result, warnings) =
+                observability::collect_warnings(|| openapi.validate(request_data));
+            observation.deprecated(&warnings);
+            if let Err(e) = result {
+                let response = if let Some(not_allowed) = e.downcast_ref::<MethodNotAllowed>() {
+                    let mut response = HttpResponse::MethodNotAllowed();
+                    response.insert_header((header::ALLOW, not_allowed.allow_header()));
+                    reject(response, e.to_string())
+                } else if e.is::<Unauthorized>() {
+                    reject(HttpResponse::Unauthorized(), e.to_string())
+                } else {
+                    reject(HttpResponse::BadRequest(), e.to_string())
+                };
                 let service_req = rebuild_service_request(http_req, &req_body);
-                return Ok(service_req
-                    .error_response(validation_error)
-                    .map_into_right_body());
+                return Ok(service_req.into_response(response).map_into_right_body());
             }
+            observation.success();
 
             let service_req = rebuild_service_request(http_req, &req_body);
 
-            service
-                .call(service_req)
-                .await
-                .map(|res| res.map_into_left_body())
+            service.call(service_req).await.map(|res| {
+                observation.response(res.status().as_u16());
+                res.map_into_left_body()
+            })
         })
     }
 }
@@ -245,28 +304,367 @@ impl<S> OpenApiValidationMiddleware<S> {
             || req.headers().contains_key("transfer-encoding")
     }
 
+    /// Reads the body up to [`ValidationOptions::max_body_size`], failing
+    /// oversized bodies with `413 Payload Too Large` as soon as the limit is
+    /// crossed. JSON Lines bodies are validated as their lines arrive; the
+    /// returned flag tells whether that happened.
+    ///
+    /// [`ValidationOptions::max_body_size`]: crate::validator::ValidationOptions::max_body_size
     async fn extract_body_safely(
         mut payload: Payload,
-        _req: &HttpRequest,
-    ) -> Result<Option<Bytes>, Error> {
+        req: &HttpRequest,
+        openapi: &OpenAPI,
+        path: &str,
+        method: &str,
+    ) -> Result<(Option<Bytes>, bool), (StatusCode, String)> {
+        let too_large = |e: anyhow::Error| (StatusCode::PAYLOAD_TOO_LARGE, e.to_string());
+        let invalid = |e: anyhow::Error| (StatusCode::BAD_REQUEST, e.to_string());
+
+        let mut limit = BodyLimit::new(openapi);
+        let content_length = req
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok());
+        limit.check_declared(content_length).map_err(too_large)?;
+
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let mut lines = validator::is_json_sequence(content_type)
+            .then(|| JsonLines::new(path, method, content_type, openapi).ok())
+            .flatten();
+
         let mut body = BytesMut::new();
 
         while let Some(chunk_result) = payload.next().await {
             let chunk = chunk_result.map_err(|e| {
-                actix_web::error::ErrorBadRequest(format!("Error reading request chunk: {e}"))
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Error reading request chunk: {e}"),
+                )
             })?;
 
+            limit.take(chunk.len()).map_err(too_large)?;
+            if let Some(lines) = &mut lines {
+                lines.push(&chunk).map_err(invalid)?;
+            }
             body.extend_from_slice(&chunk);
         }
 
+        let validated = match lines {
+            Some(lines) => {
+                lines.finish().map_err(invalid)?;
+                true
+            }
+            None => false,
+        };
+
         if body.is_empty() {
-            Ok(None)
+            Ok((None, validated))
         } else {
-            Ok(Some(body.freeze()))
+            Ok((Some(body.freeze()), validated))
         }
     }
 }
 
+/// Response validation middleware
+///
+/// Checks each response against the `responses` of its operation: the status
+/// must be declared, the `Content-Type` must be one of its media types and
+/// JSON bodies must match the schema. Violations are logged and, depending on
+/// the [`ViolationMode`], replaced with a `500 Internal Server Error` or sent
+/// unchanged.
+#[derive(Debug, Clone)]
+pub struct ResponseValidation {
+    specs: Specs,
+    config: MiddlewareConfig,
+    mode: ViolationMode,
+}
+
+impl ResponseValidation {
+    pub fn new(openapi: OpenAPI) -> Self {
+        Self::from_shared(SharedOpenAPI::new(openapi))
+    }
+
+    /// Validates against the current document of `openapi`
+    pub fn from_shared(openapi: SharedOpenAPI) -> Self {
+        Self::from_registry(SpecRegistry::new().with("/", openapi))
+    }
+
+    /// Validates each response against the document mounted at the longest
+    /// prefix of the request path. Responses to requests outside every
+    /// prefix are sent unchecked.
+    pub fn from_registry(registry: SpecRegistry) -> Self {
+        Self {
+            specs: Specs::Prefixed(registry),
+            config: MiddlewareConfig::default(),
+            mode: ViolationMode::default(),
+        }
+    }
+
+    /// Validates each response against the version of the document its
+    /// request selects, see [`VersionedSpecs`]
+    pub fn from_versions(versions: VersionedSpecs) -> Self {
+        Self {
+            specs: Specs::Versioned(versions),
+            config: MiddlewareConfig::default(),
+            mode: ViolationMode::default(),
+        }
+    }
+
+    /// Replace the configuration of which routes are validated
+    pub fn with_config(mut self, config: MiddlewareConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Choose whether violations replace the response or are only logged
+    pub fn with_mode(mut self, mode: ViolationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ResponseValidation
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = ResponseValidationMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ResponseValidationMiddleware {
+            service: Rc::new(service),
+            validation: self.clone(),
+        }))
+    }
+}
+
+pub struct ResponseValidationMiddleware<S> {
+    service: Rc<S>,
+    validation: ResponseValidation,
+}
+
+impl<S, B> Service<ServiceRequest> for ResponseValidationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let ResponseValidation {
+            specs,
+            config,
+            mode,
+        } = &self.validation;
+        let headers: HashMap<String, String> = req
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let selected = specs
+            .select(req.path(), &headers)
+            .filter(|(openapi, path)| {
+                !config.skip_undocumented || openapi.path_item(path).is_some()
+            })
+            .filter(|_| config.validates(req.path()));
+        let method = req.method().as_str().to_lowercase();
+        let mode = *mode;
+
+        Box::pin(async move {
+            let res = service.call(req).await?;
+            let Some((openapi, path)) = selected else {
+                return Ok(res.map_into_boxed_body());
+            };
+
+            let (http_req, http_res) = res.into_parts();
+            let (head, body) = http_res.into_parts();
+            let status = head.status().as_u16();
+            let checked = match actix_body::to_bytes(body).await {
+                Ok(body) => {
+                    let content_type = head
+                        .headers()
+                        .get(header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok());
+                    match validator::response_message(
+                        &path,
+                        &method,
+                        status,
+                        content_type,
+                        &body,
+                        &openapi,
+                    ) {
+                        Ok(()) => Ok(body),
+                        Err(e) => Err((e, Some(body))),
+                    }
+                }
+                Err(e) => {
+                    let e: Box<dyn std::error::Error> = e.into();
+                    Err((anyhow::anyhow!("Error reading response body: {e}"), None))
+                }
+            };
+
+            let body = match checked {
+                Ok(body) => body,
+                Err((e, body)) => {
+                    let error = e.to_string();
+                    observability::record_response_violation(&method, &path, status, &error);
+                    match (mode, body) {
+                        (ViolationMode::Shadow, Some(body)) => body,
+                        _ => {
+                            let response = HttpResponse::InternalServerError()
+                                .body(format!("OpenAPI validation failed: {error}"));
+                            return Ok(ServiceResponse::new(http_req, response));
+                        }
+                    }
+                }
+            };
+            let response = head.set_body(body).map_into_boxed_body();
+            Ok(ServiceResponse::new(http_req, response))
+        })
+    }
+}
+
+/// The request body read by [`OpenApiValidation`], stored in the request
+/// extensions for handlers and extractors such as [`ValidatedJson`]
+#[derive(Debug, Clone)]
+pub struct PreExtractedBody(pub Bytes);
+
+/// Extracts a JSON body after validating it against the request body schema
+/// of the operation, deserializing it into `T`.
+///
+/// The document is the [`SharedOpenAPI`] registered as `web::Data`, and the
+/// operation is found by the route pattern the request matched. The body is
+/// taken from [`PreExtractedBody`] when the middleware already read it.
+/// Invalid bodies are answered with `400 Bad Request`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> ValidatedJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for ValidatedJson<T> {
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let extracted = req
+            .extensions()
+            .get::<PreExtractedBody>()
+            .map(|body| body.0.clone());
+        let read = extracted
+            .is_none()
+            .then(|| Bytes::from_request(&req, payload));
+
+        Box::pin(async move {
+            let body = match (extracted, read) {
+                (Some(body), _) => body,
+                (None, Some(read)) => read.await?,
+                (None, None) => Bytes::new(),
+            };
+            let openapi = req
+                .app_data::<Data<SharedOpenAPI>>()
+                .ok_or_else(|| {
+                    actix_web::error::ErrorInternalServerError(
+                        "SharedOpenAPI is not registered as app data",
+                    )
+                })?
+                .load();
+            let path = req
+                .match_pattern()
+                .unwrap_or_else(|| req.path().to_string());
+            let method = req.method().as_str().to_lowercase();
+            let invalid = |e: anyhow::Error| {
+                actix_web::error::ErrorBadRequest(format!("OpenAPI validation failed: {e}"))
+            };
+
+            let raw: &RawValue = serde_json::from_slice(&body).map_err(|e| invalid(e.into()))?;
+            validator::raw_body(&path, &method, raw, &openapi).map_err(invalid)?;
+            let value = serde_json::from_slice(&body)
+                .map_err(|e| invalid(anyhow::anyhow!("Failed to deserialize body: {e}")))?;
+            Ok(ValidatedJson(value))
+        })
+    }
+}
+
+/// An actix-web `ServiceConfig` recording the method and path of each route
+/// added through it, since an `App` cannot list them, to compare its routes
+/// with the document at startup. The `App` factory runs once per worker, so
+/// the check too; check from one of them or log only once.
+///
+/// # example
+///
+/// ```rust,ignore
+/// use actix_web::http::Method;
+/// use openapi_rs::request::actix_web::CheckedConfig;
+///
+/// App::new().configure(|config| {
+///     CheckedConfig::new(config)
+///         .route("/users", Method::GET, list_users)
+///         .route("/users/{id}", Method::GET, get_user)
+///         .check(&openapi)
+/// })
+/// ```
+pub struct CheckedConfig<'a> {
+    config: &'a mut ServiceConfig,
+    routes: Vec<(Method, String)>,
+}
+
+impl<'a> CheckedConfig<'a> {
+    pub fn new(config: &'a mut ServiceConfig) -> Self {
+        Self {
+            config,
+            routes: Vec::new(),
+        }
+    }
+
+    /// Route `method` requests for `path` to `handler`, as
+    /// `ServiceConfig::route(path, web::method(method).to(handler))` does
+    pub fn route<F, Args>(mut self, path: &str, method: Method, handler: F) -> Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.config
+            .route(path, web::method(method.clone()).to(handler));
+        self.routes.push((method, path.to_string()));
+        self
+    }
+
+    /// How the routes added so far and the operations of `openapi` differ
+    pub fn route_drift(&self, openapi: &OpenAPI) -> RouteDrift {
+        openapi.route_drift(
+            self.routes
+                .iter()
+                .map(|(method, path)| (method.as_str(), path)),
+        )
+    }
+
+    /// Logs how the routes added and the operations of `openapi` differ
+    pub fn check(self, openapi: &OpenAPI) {
+        self.route_drift(openapi).log();
+    }
+}
+
 pub mod middleware {
     use super::OpenApiValidation;
 
@@ -280,9 +678,30 @@ pub mod middleware {
         pub fn from_openapi(openapi: crate::model::parse::OpenAPI) -> OpenApiValidation {
             OpenApiValidation::new(openapi)
         }
+
+        pub fn from_shared(openapi: crate::model::shared::SharedOpenAPI) -> OpenApiValidation {
+            OpenApiValidation::from_shared(openapi)
+        }
+
+        pub fn from_registry(registry: crate::model::registry::SpecRegistry) -> OpenApiValidation {
+            OpenApiValidation::from_registry(registry)
+        }
+
+        pub fn from_versions(versions: crate::model::version::VersionedSpecs) -> OpenApiValidation {
+            OpenApiValidation::from_versions(versions)
+        }
     }
 }
 
+/// Serves the validation metrics in the Prometheus text format, for
+/// `App::route("/metrics", web::get().to(metrics))`
+#[cfg(feature = "prometheus")]
+pub async fn metrics() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(observability::metrics::CONTENT_TYPE)
+        .body(observability::metrics::render())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -356,17 +775,252 @@ paths:
 
         let req = TestRequest::post()
             .uri("/test")
-            .set_json(&serde_json::json!({"test": "value"}))
+            .set_json(serde_json::json!({"test": "value"}))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+    }
+
+    #[actix_web::test]
+    async fn test_middleware_with_undefined_method() {
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /test:
+    get:
+      responses:
+        '200':
+          description: Success
+    post:
+      responses:
+        '200':
+          description: Success
+"#;
+
+        let validation = OpenApiValidation::from_yaml(yaml_content).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .wrap(validation)
+                .route("/test", web::delete().to(dummy_handler)),
+        )
+        .await;
+
+        let req = TestRequest::delete().uri("/test").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(
+            resp.status(),
+            actix_web::http::StatusCode::METHOD_NOT_ALLOWED
+        );
+        assert_eq!(resp.headers().get(header::ALLOW).unwrap(), "GET, POST");
+    }
+
+    #[actix_web::test]
+    async fn test_middleware_with_missing_api_key() {
+        use crate::observability::ObservabilityHook;
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+
+        #[derive(Default, Clone)]
+        struct Failures(Arc<Mutex<Vec<String>>>);
+
+        impl ObservabilityHook for Failures {
+            fn on_failure(&self, _: &RequestContext, error: &str, _: Duration) {
+                self.0.lock().unwrap().push(error.to_string());
+            }
+        }
+
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+security:
+  - api_key: []
+paths:
+  /test:
+    get:
+      responses:
+        '200':
+          description: Success
+components:
+  securitySchemes:
+    api_key:
+      type: apiKey
+      name: X-API-Key
+      in: header
+"#;
+
+        let failures = Failures::default();
+        let validation = OpenApiValidation::from_yaml(yaml_content)
+            .unwrap()
+            .with_config(MiddlewareConfig::new().with_hook(failures.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .wrap(validation)
+                .route("/test", web::get().to(dummy_handler)),
+        )
+        .await;
+
+        let req = TestRequest::get().uri("/test").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+        let failures = failures.0.lock().unwrap().clone();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with("Security validation failed"));
+
+        let req = TestRequest::get()
+            .uri("/test")
+            .insert_header(("X-API-Key", "secret"))
             .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+    }
+
+    #[actix_web::test]
+    async fn test_middleware_with_replaced_spec() {
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /test:
+    get:
+      responses:
+        '200':
+          description: Success
+"#;
+
+        let validation = OpenApiValidation::from_yaml(yaml_content).unwrap();
+        let shared = validation.shared().unwrap().clone();
+
+        let app = test::init_service(
+            App::new()
+                .wrap(validation)
+                .route("/test", web::delete().to(dummy_handler)),
+        )
+        .await;
 
+        let req = TestRequest::delete().uri("/test").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.status(),
+            actix_web::http::StatusCode::METHOD_NOT_ALLOWED
+        );
+
+        let updated = yaml_content.replace("    get:", "    delete:");
+        shared.replace(OpenAPI::yaml(&updated).unwrap());
+
+        let req = TestRequest::delete().uri("/test").to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
     }
 
+    #[actix_web::test]
+    async fn test_middleware_with_registry() {
+        let billing = r#"
+openapi: 3.0.0
+info:
+  title: Billing API
+  version: 1.0.0
+paths:
+  /invoices:
+    get:
+      responses:
+        '200':
+          description: Success
+"#;
+        let users = billing
+            .replace("Billing", "Users")
+            .replace("/invoices", "/users/{id}");
+
+        let registry = SpecRegistry::new()
+            .with("/billing", OpenAPI::yaml(billing).unwrap())
+            .with("/accounts/", OpenAPI::yaml(&users).unwrap());
+        let validation = OpenApiValidation::from_registry(registry);
+        assert!(validation.shared().is_none());
+
+        let app = test::init_service(
+            App::new()
+                .wrap(validation)
+                .route("/billing/invoices", web::get().to(dummy_handler))
+                .route("/accounts/users/{id}", web::get().to(dummy_handler))
+                .route("/accounts/invoices", web::get().to(dummy_handler))
+                .route("/other", web::get().to(dummy_handler)),
+        )
+        .await;
+
+        for (uri, status) in [
+            ("/billing/invoices", 200),
+            ("/accounts/users/42", 200),
+            ("/accounts/invoices", 400),
+            ("/other", 404),
+        ] {
+            let req = TestRequest::get().uri(uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status().as_u16(), status, "{uri}");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_middleware_with_versions() {
+        let v1 = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: Success
+"#;
+        let v2 = v1.replace("/users", "/accounts");
+
+        let versions = VersionedSpecs::new()
+            .with_version("v1", OpenAPI::yaml(v1).unwrap())
+            .with_version("v2", OpenAPI::yaml(&v2).unwrap())
+            .with_header("X-API-Version")
+            .with_prefix(true)
+            .with_default("v1");
+        let validation = OpenApiValidation::from_versions(versions);
+
+        let app = test::init_service(
+            App::new()
+                .wrap(validation)
+                .route("/users", web::get().to(dummy_handler))
+                .route("/accounts", web::get().to(dummy_handler))
+                .route("/v2/accounts", web::get().to(dummy_handler)),
+        )
+        .await;
+
+        for (uri, version, status) in [
+            ("/users", None, 200),
+            ("/accounts", None, 400),
+            ("/accounts", Some("2"), 200),
+            ("/users", Some("v2"), 400),
+            ("/v2/accounts", None, 200),
+            ("/users", Some("v3"), 404),
+        ] {
+            let mut req = TestRequest::get().uri(uri);
+            if let Some(version) = version {
+                req = req.insert_header(("X-API-Version", version));
+            }
+            let resp = test::call_service(&app, req.to_request()).await;
+            assert_eq!(resp.status().as_u16(), status, "{uri} {version:?}");
+        }
+    }
+
     #[test]
     fn test_should_extract_body() {
-        use actix_web::http::header;
-
         let req = TestRequest::post()
             .append_header((header::CONTENT_LENGTH, "100"))
             .to_http_request();
@@ -384,4 +1038,297 @@ paths:
 
         assert!(OpenApiValidationMiddleware::<()>::should_extract_body(&req));
     }
+
+    #[actix_web::test]
+    async fn test_validated_json_extractor() {
+        #[derive(serde::Deserialize)]
+        struct User {
+            name: String,
+        }
+
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /teams/{team}/users:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+              properties:
+                name:
+                  type: string
+                  minLength: 2
+      responses:
+        '200':
+          description: Success
+"#;
+        let openapi = SharedOpenAPI::new(OpenAPI::yaml(yaml_content).unwrap());
+
+        async fn create(user: ValidatedJson<User>) -> HttpResponse {
+            HttpResponse::Ok().body(user.into_inner().name)
+        }
+
+        for wrapped in [false, true] {
+            let validation = OpenApiValidation::from_shared(openapi.clone());
+            let app = test::init_service(
+                App::new()
+                    .app_data(web::Data::new(openapi.clone()))
+                    .wrap(actix_web::middleware::Condition::new(wrapped, validation))
+                    .route("/teams/{team}/users", web::post().to(create)),
+            )
+            .await;
+
+            let req = TestRequest::post()
+                .uri("/teams/a/users")
+                .set_json(serde_json::json!({"name": "alice"}))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert!(resp.status().is_success());
+            assert_eq!(test::read_body(resp).await, "alice");
+
+            let req = TestRequest::post()
+                .uri("/teams/a/users")
+                .set_json(serde_json::json!({"name": "a"}))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_middleware_on_scope_and_skipping_undocumented() {
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /api/users:
+    get:
+      parameters:
+        - name: page
+          in: query
+          required: true
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: Success
+"#;
+        let validation = OpenApiValidation::from_yaml(yaml_content).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .service(
+                    web::scope("/api")
+                        .wrap(validation.clone())
+                        .route("/users", web::get().to(dummy_handler)),
+                )
+                .route("/health", web::get().to(dummy_handler)),
+        )
+        .await;
+        let call = |uri: &str| TestRequest::get().uri(uri).to_request();
+
+        let resp = test::call_service(&app, call("/health")).await;
+        assert!(resp.status().is_success());
+        let resp = test::call_service(&app, call("/api/users?page=1")).await;
+        assert!(resp.status().is_success());
+        let resp = test::call_service(&app, call("/api/users")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+        let app = test::init_service(
+            App::new()
+                .wrap(validation.with_skip_undocumented(true))
+                .route("/api/users", web::get().to(dummy_handler))
+                .route("/health", web::get().to(dummy_handler)),
+        )
+        .await;
+
+        let resp = test::call_service(&app, call("/health")).await;
+        assert!(resp.status().is_success());
+        let resp = test::call_service(&app, call("/api/users")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_response_validation_modes() {
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      responses:
+        '200':
+          description: Success
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [name]
+                properties:
+                  name:
+                    type: string
+"#;
+        let openapi = SharedOpenAPI::new(OpenAPI::yaml(yaml_content).unwrap());
+
+        async fn user(id: web::Path<u32>) -> HttpResponse {
+            match id.into_inner() {
+                1 => HttpResponse::Ok().json(serde_json::json!({"name": "alice"})),
+                2 => HttpResponse::Ok().json(serde_json::json!({"name": 2})),
+                _ => HttpResponse::NotFound().finish(),
+            }
+        }
+
+        for (mode, violation) in [
+            (
+                ViolationMode::Enforce,
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (ViolationMode::Shadow, actix_web::http::StatusCode::OK),
+        ] {
+            let validation = ResponseValidation::from_shared(openapi.clone()).with_mode(mode);
+            let app = test::init_service(
+                App::new()
+                    .wrap(validation)
+                    .route("/users/{id}", web::get().to(user)),
+            )
+            .await;
+            let call = |uri: &str| TestRequest::get().uri(uri).to_request();
+
+            let resp = test::call_service(&app, call("/users/1")).await;
+            assert!(resp.status().is_success());
+            assert_eq!(test::read_body(resp).await, r#"{"name":"alice"}"#);
+
+            let resp = test::call_service(&app, call("/users/2")).await;
+            assert_eq!(resp.status(), violation);
+        }
+
+        let app = test::init_service(
+            App::new()
+                .wrap(ResponseValidation::from_shared(openapi))
+                .route("/users/{id}", web::get().to(user)),
+        )
+        .await;
+        let resp = test::call_service(&app, TestRequest::get().uri("/users/3").to_request()).await;
+        assert_eq!(
+            resp.status(),
+            actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[actix_web::test]
+    async fn test_metrics_handler() {
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /widgets:
+    get:
+      operationId: listMetricsWidgets
+      parameters:
+        - name: limit
+          in: query
+          deprecated: true
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: Success
+  /gadgets:
+    get:
+      responses:
+        '200':
+          description: Success
+"#;
+        let openapi = OpenAPI::yaml(yaml_content).unwrap();
+        let app = test::init_service(
+            App::new()
+                .wrap(OpenApiValidation::new(openapi).with_skip_undocumented(true))
+                .route("/widgets", web::get().to(dummy_handler))
+                .route("/gadgets", web::get().to(dummy_handler))
+                .route("/metrics", web::get().to(metrics)),
+        )
+        .await;
+
+        for uri in ["/widgets?limit=1", "/widgets?limit=x", "/gadgets"] {
+            test::call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+        }
+        let purge = actix_web::http::Method::from_bytes(b"PURGE").unwrap();
+        let req = TestRequest::default().method(purge).uri("/widgets");
+        let resp = test::call_service(&app, req.to_request()).await;
+        assert_eq!(
+            resp.status(),
+            actix_web::http::StatusCode::METHOD_NOT_ALLOWED
+        );
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/metrics").to_request()).await;
+        assert!(resp.status().is_success());
+        let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+        for line in [
+            r#"openapi_validation_total{operation="listMetricsWidgets",outcome="success"} 1"#,
+            r#"openapi_validation_total{operation="listMetricsWidgets",outcome="failure"} 1"#,
+            r#"openapi_validation_total{operation="GET /gadgets",outcome="success"} 1"#,
+            r#"openapi_validation_duration_seconds_count{operation="listMetricsWidgets"} 2"#,
+            r#"openapi_deprecated_total{method="GET",parameter="limit",path="/widgets"} 2"#,
+        ] {
+            assert!(body.contains(line), "missing {line} in {body}");
+        }
+        assert!(
+            body.contains(r#"openapi_validation_total{operation="unmatched",outcome="failure"}"#)
+        );
+        assert!(!body.contains("PURGE"), "{body}");
+    }
+
+    #[actix_web::test]
+    async fn test_checked_config() {
+        let openapi = OpenAPI::yaml(
+            r#"
+openapi: 3.1.0
+info: {title: Users, version: 1.0.0}
+paths:
+  /users:
+    get:
+      responses: {'200': {description: OK}}
+  /users/{id}:
+    get:
+      responses: {'200': {description: OK}}
+    delete:
+      responses: {'204': {description: Deleted}}
+"#,
+        )
+        .unwrap();
+
+        let mut drift = None;
+        let app = test::init_service(App::new().configure(|config| {
+            let checked = CheckedConfig::new(config)
+                .route("/users", Method::GET, dummy_handler)
+                .route("/users/{id:\\d+}", Method::GET, dummy_handler)
+                .route("/health", Method::GET, dummy_handler);
+            drift = Some(checked.route_drift(&openapi));
+            checked.check(&openapi);
+        }))
+        .await;
+
+        let drift = drift.unwrap();
+        assert_eq!(drift.unhandled.len(), 1);
+        assert_eq!(drift.unhandled[0].to_string(), "DELETE /users/{id}");
+        assert_eq!(drift.undocumented.len(), 1);
+        assert_eq!(drift.undocumented[0].to_string(), "GET /health");
+
+        let req = TestRequest::get().uri("/users/7").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+    }
 }
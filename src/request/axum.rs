@@ -15,14 +15,199 @@
  * limitations under the License.
  */
 
+use crate::model::drift::RouteDrift;
 use crate::model::parse::OpenAPI;
+use crate::model::registry::SpecRegistry;
+use crate::model::shared::SharedOpenAPI;
+use crate::model::version::VersionedSpecs;
 use crate::observability::RequestContext;
-use crate::validator::{body, method, path, query, ValidateRequest};
+use crate::request::config::MiddlewareConfig;
+use crate::request::multipart;
+use crate::request::tower::{self, OpenApiValidationService};
+use crate::validator::stream::BodyLimit;
+use crate::validator::{
+    self, decode_query, method, parse_query, path, query, raw_body, redact, security, typed_path,
+    typed_query, ValidateRequest, ValidationOptions,
+};
 use anyhow::Result;
+use axum::async_trait;
 use axum::body::{Body, Bytes};
-use axum::http::Request;
+use axum::extract::{FromRef, FromRequestParts, MatchedPath};
+use axum::handler::Handler;
+use axum::http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    request::Parts,
+    Extensions, Method, Request, StatusCode,
+};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{on, MethodFilter};
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tower_layer::Layer;
+
+pub use crate::request::tower::ResponseValidationLayer;
+
+/// OpenAPI validation layer for axum routers
+///
+/// Buffers the request body, validates the request against the document and
+/// answers rejected requests itself, the same way the actix-web
+/// `OpenApiValidation` transform does.
+///
+/// # example
+///
+/// ```rust,ignore
+/// use axum::{routing::post, Router};
+/// use openapi_rs::request::axum::OpenApiValidationLayer;
+///
+/// let app = Router::new()
+///     .route("/api/users", post(create_user))
+///     .layer(OpenApiValidationLayer::from_yaml(include_str!("api.yaml"))?);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApiValidationLayer(tower::OpenApiValidationLayer<Body>);
+
+impl OpenApiValidationLayer {
+    pub fn new(openapi: OpenAPI) -> Self {
+        Self::from_shared(SharedOpenAPI::new(openapi))
+    }
+
+    /// Validates against the current document of `openapi`, so replacing it
+    /// takes effect without rebuilding the router
+    pub fn from_shared(openapi: SharedOpenAPI) -> Self {
+        Self(tower::OpenApiValidationLayer::from_shared(openapi).with_body())
+    }
+
+    /// See [`tower::OpenApiValidationLayer::from_registry`]
+    pub fn from_registry(registry: SpecRegistry) -> Self {
+        Self(tower::OpenApiValidationLayer::from_registry(registry).with_body())
+    }
+
+    /// See [`tower::OpenApiValidationLayer::from_versions`]
+    pub fn from_versions(versions: VersionedSpecs) -> Self {
+        Self(tower::OpenApiValidationLayer::from_versions(versions).with_body())
+    }
+
+    pub fn from_yaml(yaml_content: &str) -> Result<Self> {
+        Ok(Self::new(OpenAPI::yaml(yaml_content)?))
+    }
+
+    /// Replace the configuration of which routes are validated
+    pub fn with_config(self, config: MiddlewareConfig) -> Self {
+        Self(self.0.with_config(config))
+    }
+}
+
+impl<S> Layer<S> for OpenApiValidationLayer {
+    type Service = OpenApiValidationService<S, Body>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        self.0.layer(inner)
+    }
+}
+
+/// Reads a request body for [`RequestData::body`], failing with
+/// [`PayloadTooLarge`](crate::validator::PayloadTooLarge) as soon as it
+/// crosses [`ValidationOptions::max_body_size`](crate::validator::ValidationOptions::max_body_size),
+/// or before reading anything when `Content-Length` already does.
+pub async fn read_body(parts: &Parts, body: Body, open_api: &OpenAPI) -> Result<Bytes> {
+    let mut limit = BodyLimit::new(open_api);
+    let content_length = parts
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok());
+    limit.check_declared(content_length)?;
+
+    let mut stream = body.into_data_stream();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| anyhow::anyhow!("Error reading request chunk: {e}"))?;
+        limit.take(chunk.len())?;
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(Bytes::from(bytes))
+}
+
+/// Extracts the query of a request after validating it against the
+/// [`SharedOpenAPI`] of the router state, deserializing it into `T`.
+///
+/// Values are converted to the types the document declares for them before
+/// `T` is deserialized, so `page: u32` works for `?page=2`, unless
+/// [`ValidationOptions::query_as_strings`](crate::validator::ValidationOptions::query_as_strings)
+/// is set. Invalid queries are answered with `400 Bad Request`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+    SharedOpenAPI: FromRef<S>,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let openapi = SharedOpenAPI::from_ref(state).load();
+        let path = &spec_path(parts.uri.path(), &parts.extensions);
+        let rejected = |e: anyhow::Error| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("OpenAPI validation failed: {e}"),
+            )
+        };
+
+        let query_string = parts.uri.query().unwrap_or_default();
+        let method = parts.method.as_str();
+        let query_pairs = decode_query(path, method, query_string, &openapi).map_err(rejected)?;
+        query(path, method, &query_pairs, &openapi).map_err(rejected)?;
+
+        let fields = typed_query(path, method, &query_pairs, &openapi);
+        let value = serde_json::from_value(Value::Object(fields))
+            .map_err(|e| rejected(anyhow::anyhow!("Failed to deserialize query: {e}")))?;
+        Ok(ValidatedQuery(value))
+    }
+}
+
+/// Extracts the template variables of the request path after validating
+/// them against the schemas of their `in: path` parameters in the
+/// [`SharedOpenAPI`] of the router state, deserializing them into `T`.
+///
+/// The variables are bound by the document's own path template rather than
+/// the axum route, and converted to their declared types, so `id: u64`
+/// works for `/users/{id}`. Invalid values are answered with
+/// `400 Bad Request`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedPath<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ValidatedPath<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+    SharedOpenAPI: FromRef<S>,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let openapi = SharedOpenAPI::from_ref(state).load();
+        let rejected = |e: anyhow::Error| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("OpenAPI validation failed: {e}"),
+            )
+        };
+
+        let fields =
+            typed_path(parts.uri.path(), parts.method.as_str(), &openapi).map_err(rejected)?;
+        let value = serde_json::from_value(Value::Object(fields))
+            .map_err(|e| rejected(anyhow::anyhow!("Failed to deserialize path: {e}")))?;
+        Ok(ValidatedPath(value))
+    }
+}
 
 #[allow(dead_code)]
 pub struct RequestData {
@@ -31,6 +216,36 @@ pub struct RequestData {
     pub body: Option<Bytes>,
 }
 
+impl RequestData {
+    /// Wraps a request routed by axum, taking [`RequestData::path`] from the
+    /// route template in [`MatchedPath`], or from the request path when the
+    /// request did not go through a router
+    pub fn new(inner: Request<Body>, body: Option<Bytes>) -> Self {
+        Self {
+            path: spec_path(inner.uri().path(), inner.extensions()),
+            inner,
+            body,
+        }
+    }
+}
+
+/// The route template axum matched, with its `:name` and `*name` captures
+/// written as the `{name}` variables of a document path
+fn spec_path(path: &str, extensions: &Extensions) -> String {
+    let Some(matched) = extensions.get::<MatchedPath>() else {
+        return path.to_string();
+    };
+    matched
+        .as_str()
+        .split('/')
+        .map(|segment| match segment.strip_prefix([':', '*']) {
+            Some(name) => format!("{{{name}}}"),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 impl ValidateRequest for RequestData {
     fn header(&self, _: &OpenAPI) -> Result<()> {
         Ok(())
@@ -44,32 +259,34 @@ impl ValidateRequest for RequestData {
         )
     }
 
-    fn query(&self, open_api: &OpenAPI) -> Result<()> {
-        let uri_parts: Vec<&str> = self
+    fn security(&self, open_api: &OpenAPI) -> Result<()> {
+        let headers: HashMap<String, String> = self
             .inner
-            .uri()
-            .path_and_query()
-            .map(|pq| pq.as_str())
-            .unwrap_or("")
-            .split('?')
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
             .collect();
+        let query_pairs = parse_query(self.inner.uri().query().unwrap_or_default());
 
-        let query_pairs = if uri_parts.len() > 1 {
-            uri_parts[1]
-                .split('&')
-                .filter_map(|pair| {
-                    let mut split = pair.split('=');
-                    match (split.next(), split.next()) {
-                        (Some(key), Some(value)) => Some((key.to_string(), value.to_string())),
-                        _ => None,
-                    }
-                })
-                .collect()
-        } else {
-            HashMap::new()
-        };
+        security(
+            self.path.as_str(),
+            self.inner.method().to_string().to_lowercase().as_str(),
+            &headers,
+            &query_pairs,
+            open_api,
+        )
+    }
+
+    fn query(&self, open_api: &OpenAPI) -> Result<()> {
+        let method = self.inner.method().as_str();
+        let query_pairs = decode_query(
+            self.path.as_str(),
+            method,
+            self.inner.uri().query().unwrap_or_default(),
+            open_api,
+        )?;
 
-        query(self.path.as_str(), &query_pairs, open_api)
+        query(self.path.as_str(), method, &query_pairs, open_api)
     }
 
     fn path(&self, open_api: &OpenAPI) -> Result<()> {
@@ -88,23 +305,625 @@ impl ValidateRequest for RequestData {
             .body
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Missing body"))?;
-        let request_fields: Value = serde_json::from_slice(self_body)?;
-        body(self.path.as_str(), request_fields, open_api)
+        let method = self.inner.method().to_string().to_lowercase();
+
+        let content_type = self
+            .inner
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        if let Some(boundary) = multipart::boundary(content_type) {
+            let parts = multipart::parse(self_body, &boundary)?;
+            return validator::multipart(self.path.as_str(), &method, &parts, open_api);
+        }
+        if validator::is_json_sequence(content_type) {
+            let path = self.path.as_str();
+            return validator::json_lines(path, &method, content_type, &self_body[..], open_api);
+        }
+
+        let request_body: &RawValue = serde_json::from_slice(self_body)?;
+        raw_body(self.path.as_str(), &method, request_body, open_api)
     }
 
     fn context(&self) -> RequestContext {
+        let header = |name| {
+            self.inner
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
         RequestContext::new(
-            match *self.inner.method() {
-                axum::http::Method::GET => "GET".to_string(),
-                axum::http::Method::POST => "POST".to_string(),
-                axum::http::Method::PUT => "PUT".to_string(),
-                axum::http::Method::DELETE => "DELETE".to_string(),
-                axum::http::Method::PATCH => "PATCH".to_string(),
-                axum::http::Method::HEAD => "HEAD".to_string(),
-                axum::http::Method::OPTIONS => "OPTIONS".to_string(),
-                _ => "UNKNOWN".to_string(),
-            },
+            self.inner.method().as_str().to_string(),
             self.inner.uri().to_string(),
         )
+        .with_trace_headers(header("x-request-id"), header("traceparent"))
+    }
+
+    fn secrets(&self, options: &ValidationOptions) -> Vec<String> {
+        let headers = self
+            .inner
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)));
+        redact::secrets(
+            headers,
+            self.inner.uri().query().unwrap_or_default(),
+            self.body.as_deref(),
+            options,
+        )
+    }
+}
+
+/// Serves the validation metrics in the Prometheus text format, for
+/// `Router::route("/metrics", get(metrics))`
+#[cfg(feature = "prometheus")]
+pub async fn metrics() -> impl axum::response::IntoResponse {
+    use crate::observability::metrics;
+
+    ([(CONTENT_TYPE, metrics::CONTENT_TYPE)], metrics::render())
+}
+
+/// A server answering every operation of `openapi` with its
+/// [`OpenAPI::mock_response`] once the request passed validation, for
+/// clients to develop against before the service exists. A `Prefer: code=404`
+/// request header picks the response of another declared status.
+///
+/// # example
+///
+/// ```rust,ignore
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+/// axum::serve(listener, openapi_rs::request::axum::mock(openapi)).await?;
+/// ```
+pub fn mock(openapi: OpenAPI) -> axum::Router {
+    let openapi = SharedOpenAPI::new(openapi);
+    let layer = OpenApiValidationLayer::from_shared(openapi.clone());
+    axum::Router::new()
+        .fallback(move |request: Request<Body>| mock_handler(openapi.load(), request))
+        .layer(layer)
+}
+
+async fn mock_handler(openapi: Arc<OpenAPI>, request: Request<Body>) -> Response {
+    let Some(route) = openapi.match_path(request.uri().path()) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let method = request.method().as_str();
+    let status = preferred_status(request.headers());
+    let Some(response) = openapi.mock_response(route.path, method, status) else {
+        let message = format!(
+            "No response declared for status '{}' of '{method} {}'",
+            status.unwrap_or_default(),
+            route.path
+        );
+        return (StatusCode::BAD_REQUEST, message).into_response();
+    };
+
+    let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::OK);
+    match (response.content_type, response.body) {
+        (Some(content_type), Some(body)) => {
+            let body = match body {
+                Value::String(text) if !content_type.contains("json") => text,
+                body => body.to_string(),
+            };
+            (status, [(CONTENT_TYPE, content_type)], body).into_response()
+        }
+        _ => status.into_response(),
+    }
+}
+
+/// The status asked for with a `Prefer: code=404` header
+fn preferred_status(headers: &axum::http::HeaderMap) -> Option<u16> {
+    headers
+        .get_all("prefer")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split([',', ';']))
+        .find_map(|preference| preference.trim().strip_prefix("code=")?.parse().ok())
+}
+
+/// An axum `Router` recording the method and path of each route added
+/// through it, since a `Router` cannot list them, to compare its routes
+/// with the document at startup.
+///
+/// # example
+///
+/// ```rust,ignore
+/// use axum::http::Method;
+/// use openapi_rs::request::axum::CheckedRouter;
+///
+/// let app = CheckedRouter::new()
+///     .route("/users", Method::GET, list_users)
+///     .route("/users/:id", Method::GET, get_user)
+///     .check(&openapi)
+///     .layer(OpenApiValidationLayer::new(openapi));
+/// ```
+pub struct CheckedRouter<S = ()> {
+    router: axum::Router<S>,
+    routes: Vec<(Method, String)>,
+}
+
+impl<S: Clone + Send + Sync + 'static> Default for CheckedRouter<S> {
+    fn default() -> Self {
+        Self {
+            router: axum::Router::new(),
+            routes: Vec::new(),
+        }
+    }
+}
+
+impl<S: Clone + Send + Sync + 'static> CheckedRouter<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route `method` requests for `path` to `handler`, as
+    /// `Router::route(path, on(method, handler))` does. Panics on methods
+    /// axum cannot route, as axum does on invalid routes.
+    pub fn route<H, T>(mut self, path: &str, method: Method, handler: H) -> Self
+    where
+        H: Handler<T, S>,
+        T: 'static,
+    {
+        let filter = MethodFilter::try_from(method.clone())
+            .unwrap_or_else(|error| panic!("Cannot route '{method} {path}': {error}"));
+        self.router = self.router.route(path, on(filter, handler));
+        self.routes.push((method, path.to_string()));
+        self
+    }
+
+    /// How the routes added so far and the operations of `openapi` differ
+    pub fn route_drift(&self, openapi: &OpenAPI) -> RouteDrift {
+        openapi.route_drift(
+            self.routes
+                .iter()
+                .map(|(method, path)| (method.as_str(), path)),
+        )
+    }
+
+    /// The router, after logging how its routes and the operations of
+    /// `openapi` differ
+    pub fn check(self, openapi: &OpenAPI) -> axum::Router<S> {
+        self.route_drift(openapi).log();
+        self.router
+    }
+
+    /// The router, unchecked
+    pub fn into_router(self) -> axum::Router<S> {
+        self.router
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::tower::ServiceExt;
+    use axum::extract::Path;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use axum::routing::post;
+    use axum::Router;
+
+    #[tokio::test]
+    async fn test_layer_on_router() {
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /users/{id}:
+    post:
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: integer
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+      responses:
+        '200':
+          description: Success
+"#;
+
+        let app = Router::new()
+            .route("/users/:id", post(|body: Bytes| async move { body }))
+            .layer(OpenApiValidationLayer::from_yaml(yaml_content).unwrap());
+        let request = |uri: &str, body: &'static str| {
+            Request::post(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let ok = app
+            .clone()
+            .oneshot(request("/users/1", r#"{"name":"alice"}"#))
+            .await
+            .unwrap();
+        assert_eq!(ok.status(), StatusCode::OK);
+        let echoed = axum::body::to_bytes(ok.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(echoed, r#"{"name":"alice"}"#);
+
+        let missing = app
+            .clone()
+            .oneshot(request("/users/1", "{}"))
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::BAD_REQUEST);
+
+        let get = Request::get("/users/1").body(Body::empty()).unwrap();
+        let not_allowed = app.oneshot(get).await.unwrap();
+        assert_eq!(not_allowed.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(not_allowed.headers()["allow"], "POST");
+    }
+
+    #[tokio::test]
+    async fn test_validated_query() {
+        #[derive(serde::Deserialize)]
+        struct Search {
+            page: u32,
+            tags: Vec<String>,
+            sort: String,
+        }
+
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /search:
+    get:
+      parameters:
+        - name: page
+          in: query
+          required: true
+          schema:
+            type: integer
+        - name: tags
+          in: query
+          explode: false
+          schema:
+            type: array
+            items:
+              type: string
+        - name: sort
+          in: query
+          schema:
+            type: string
+            enum: [asc, desc]
+      responses:
+        '200':
+          description: Success
+"#;
+
+        let handler = |ValidatedQuery(search): ValidatedQuery<Search>| async move {
+            format!("{}:{}:{}", search.page, search.tags.join("+"), search.sort)
+        };
+        let app = Router::new()
+            .route("/search", axum::routing::get(handler))
+            .with_state(SharedOpenAPI::new(OpenAPI::yaml(yaml_content).unwrap()));
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let ok = app
+            .clone()
+            .oneshot(get("/search?page=2&tags=a,b&sort=asc"))
+            .await
+            .unwrap();
+        assert_eq!(ok.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(ok.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "2:a+b:asc");
+
+        let bad_enum = app
+            .clone()
+            .oneshot(get("/search?page=2&tags=a&sort=up"))
+            .await
+            .unwrap();
+        assert_eq!(bad_enum.status(), StatusCode::BAD_REQUEST);
+
+        let missing = app.oneshot(get("/search?tags=a&sort=asc")).await.unwrap();
+        assert_eq!(missing.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_validated_path() {
+        #[derive(serde::Deserialize)]
+        struct Item {
+            owner: String,
+            id: u64,
+        }
+
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /users/{owner}/items/{id}:
+    get:
+      parameters:
+        - name: owner
+          in: path
+          required: true
+          schema:
+            type: string
+            pattern: '^[a-z ]+$'
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: integer
+            minimum: 1
+      responses:
+        '200':
+          description: Success
+"#;
+
+        let handler = |ValidatedPath(item): ValidatedPath<Item>| async move {
+            format!("{}:{}", item.owner, item.id)
+        };
+        let app = Router::new()
+            .route("/users/:owner/items/:id", axum::routing::get(handler))
+            .with_state(SharedOpenAPI::new(OpenAPI::yaml(yaml_content).unwrap()));
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        let ok = app
+            .clone()
+            .oneshot(get("/users/jane%20doe/items/7"))
+            .await
+            .unwrap();
+        assert_eq!(ok.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(ok.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "jane doe:7");
+
+        let not_integer = app
+            .clone()
+            .oneshot(get("/users/jane/items/x"))
+            .await
+            .unwrap();
+        assert_eq!(not_integer.status(), StatusCode::BAD_REQUEST);
+
+        let below_minimum = app.oneshot(get("/users/jane/items/0")).await.unwrap();
+        assert_eq!(below_minimum.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_request_data_uses_matched_path() {
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: Success
+"#;
+        let openapi = SharedOpenAPI::new(OpenAPI::yaml(yaml_content).unwrap());
+
+        let spec = openapi.clone();
+        let handler = |request: Request<Body>| async move {
+            let request_data = RequestData::new(request, None);
+            let path = request_data.path.clone();
+            format!("{path} {}", spec.load().validator(request_data).is_ok())
+        };
+        let app = Router::new().route("/users/:id", axum::routing::get(handler));
+        let response = app
+            .oneshot(Request::get("/users/7").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let path = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(path, "/users/{id} true");
+
+        let outside = Request::get("/users/7").body(Body::empty()).unwrap();
+        let request_data = RequestData::new(outside, None);
+        assert_eq!(request_data.path, "/users/7");
+        assert!(openapi.load().validator(request_data).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_response_validation_layer() {
+        let yaml_content = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      responses:
+        '200':
+          description: Success
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [name]
+                properties:
+                  name:
+                    type: string
+"#;
+
+        let app = Router::new()
+            .route(
+                "/users/:id",
+                axum::routing::get(|Path(id): Path<String>| async move {
+                    match id.as_str() {
+                        "1" => axum::Json(serde_json::json!({"name": "alice"})).into_response(),
+                        "2" => axum::Json(serde_json::json!({"name": 2})).into_response(),
+                        "3" => "alice".into_response(),
+                        _ => StatusCode::NOT_FOUND.into_response(),
+                    }
+                }),
+            )
+            .layer(ResponseValidationLayer::new(
+                OpenAPI::yaml(yaml_content).unwrap(),
+            ));
+        let status = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let request = Request::get(uri).body(Body::empty()).unwrap();
+                app.oneshot(request).await.unwrap().status()
+            }
+        };
+
+        assert_eq!(status("/users/1").await, StatusCode::OK);
+        assert_eq!(status("/users/2").await, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(status("/users/3").await, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(status("/users/4").await, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_mock_answers_valid_requests() {
+        let yaml_content = r#"
+openapi: 3.1.0
+info:
+  title: Mock API
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: integer
+        - name: limit
+          in: query
+          schema:
+            type: integer
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  id:
+                    type: integer
+                  name:
+                    type: string
+        '404':
+          description: Not found
+    delete:
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: integer
+      responses:
+        '204':
+          description: Deleted
+"#;
+
+        let app = mock(OpenAPI::yaml(yaml_content).unwrap());
+        let call = |method: &str, uri: &str| {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+
+        let response = call("GET", "/users/7").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            serde_json::json!({"id": 0, "name": "string"})
+        );
+
+        let response = call("DELETE", "/users/7").await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let preferring = |code: &str| {
+            let request = Request::get("/users/7")
+                .header("prefer", format!("code={code}"))
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+        let response = preferring("404").await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = preferring("500").await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = call("GET", "/users/7?limit=many").await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = call("GET", "/groups").await.unwrap();
+        assert!(response.status().is_client_error());
+    }
+
+    #[tokio::test]
+    async fn test_checked_router() {
+        let openapi = OpenAPI::yaml(
+            r#"
+openapi: 3.1.0
+info: {title: Users, version: 1.0.0}
+paths:
+  /users:
+    get:
+      responses: {'200': {description: OK}}
+    post:
+      responses: {'201': {description: Created}}
+  /users/{id}:
+    get:
+      responses: {'200': {description: OK}}
+"#,
+        )
+        .unwrap();
+
+        let router = CheckedRouter::new()
+            .route("/users", Method::GET, || async { "users" })
+            .route("/users", Method::PUT, || async { StatusCode::OK })
+            .route(
+                "/users/:id",
+                Method::GET,
+                |Path(id): Path<u32>| async move { id.to_string() },
+            );
+        let drift = router.route_drift(&openapi);
+        assert_eq!(drift.unhandled.len(), 1);
+        assert_eq!(drift.unhandled[0].to_string(), "POST /users");
+        assert_eq!(drift.undocumented.len(), 1);
+        assert_eq!(drift.undocumented[0].to_string(), "PUT /users");
+
+        let app = router.check(&openapi);
+        let request = Request::get("/users/7").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "7");
+        let request = Request::get("/users").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
     }
 }
@@ -0,0 +1,133 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Which requests the validation middlewares check.
+
+use crate::observability::{Hooks, ObservabilityHook};
+use crate::validator::redact::wildcard_matches;
+use std::sync::Arc;
+
+/// Routes the validation middlewares leave alone and hooks they report to,
+/// shared by every framework adapter.
+///
+/// Patterns are matched against the request path segment by segment: `*`
+/// matches any characters within a segment, and a final `*` segment matches
+/// the rest of the path, so `/internal/*` covers `/internal/jobs/42`.
+#[derive(Debug, Clone, Default)]
+pub struct MiddlewareConfig {
+    /// Paths exempt from validation
+    pub exclude: Vec<String>,
+    /// When not empty, only these paths are validated
+    pub include: Vec<String>,
+    /// Forward requests for paths the document does not declare unvalidated,
+    /// instead of failing them with "Path not found"
+    pub skip_undocumented: bool,
+    /// Notified of the outcome of every validated request
+    pub hooks: Hooks,
+}
+
+impl MiddlewareConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Exempt paths matching `pattern` from validation
+    pub fn with_exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Validate only paths matching `pattern` and the other included ones
+    pub fn with_include(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Enable/disable forwarding of undocumented paths
+    pub fn with_skip_undocumented(mut self, enabled: bool) -> Self {
+        self.skip_undocumented = enabled;
+        self
+    }
+
+    /// Report the outcome of validations to `hook`, after the hooks already
+    /// registered
+    pub fn with_hook(mut self, hook: impl ObservabilityHook + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Whether requests for `path` are validated at all
+    pub fn validates(&self, path: &str) -> bool {
+        let matches = |pattern: &String| glob(pattern, path);
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+/// What the response validation middlewares do with a response that does
+/// not match the responses modeled for its operation. Violations are logged
+/// either way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ViolationMode {
+    /// Replace the response with `500 Internal Server Error`
+    #[default]
+    Enforce,
+    /// Send the response unchanged
+    Shadow,
+}
+
+fn glob(pattern: &str, path: &str) -> bool {
+    let mut patterns = pattern.trim_matches('/').split('/').peekable();
+    let mut segments = path.trim_matches('/').split('/');
+
+    while let Some(pattern) = patterns.next() {
+        if pattern == "*" && patterns.peek().is_none() {
+            return true;
+        }
+        match segments.next() {
+            Some(segment) if wildcard_matches(pattern, segment) => {}
+            _ => return false,
+        }
+    }
+    segments.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_include_and_exclude_patterns() {
+        let config = MiddlewareConfig::new()
+            .with_exclude("/health")
+            .with_exclude("/internal/*")
+            .with_exclude("/files/*.tmp");
+        assert!(!config.validates("/health"));
+        assert!(!config.validates("/internal/jobs/42"));
+        assert!(!config.validates("/files/upload.tmp"));
+        assert!(config.validates("/files/upload.txt"));
+        assert!(config.validates("/healthz"));
+        assert!(config.validates("/users"));
+
+        let config = MiddlewareConfig::new()
+            .with_include("/api/*")
+            .with_exclude("/api/internal/*");
+        assert!(config.validates("/api/users"));
+        assert!(!config.validates("/api/internal/jobs"));
+        assert!(!config.validates("/metrics"));
+    }
+}
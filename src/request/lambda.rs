@@ -0,0 +1,170 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Validation of requests received by AWS Lambda functions through API
+//! Gateway, an Application Load Balancer or a function URL.
+
+use crate::request::validation;
+use bytes::Bytes;
+use lambda_http::request::RequestContext;
+use lambda_http::{Body, Request, RequestExt};
+use std::collections::HashMap;
+
+pub use crate::request::validation::RequestData;
+
+/// Converts `request` into [`RequestData`] for the document of the API.
+///
+/// The `/{stage}` prefix `lambda_http` keeps on paths of REST APIs and named
+/// HTTP API stages is removed, and so is `base_path`, the base path mapping
+/// of a custom domain, when given.
+pub fn request_data(request: &Request, base_path: Option<&str>) -> RequestData {
+    let mut path = request.uri().path();
+    if let Some(stage) = stage(request) {
+        path = strip_segment(path, stage);
+    }
+    if let Some(base_path) = base_path {
+        path = strip_segment(path, base_path.trim_matches('/'));
+    }
+
+    let headers: HashMap<String, String> = request
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+    let body = match request.body() {
+        Body::Empty => None,
+        Body::Text(text) => Some(Bytes::copy_from_slice(text.as_bytes())),
+        Body::Binary(binary) => Some(Bytes::copy_from_slice(binary)),
+    };
+
+    validation::RequestData {
+        path: path.to_string(),
+        method: request.method().as_str().to_lowercase(),
+        query_string: request.uri().query().unwrap_or_default().to_string(),
+        content_type: headers.get("content-type").cloned(),
+        headers,
+        body: body.filter(|body| !body.is_empty()),
+        body_validated: false,
+    }
+}
+
+/// The stage `lambda_http` prefixed the path with, unless told not to by
+/// `AWS_LAMBDA_HTTP_IGNORE_STAGE_IN_PATH`
+fn stage(request: &Request) -> Option<&str> {
+    if std::env::var_os("AWS_LAMBDA_HTTP_IGNORE_STAGE_IN_PATH").is_some() {
+        return None;
+    }
+    let stage = match request.request_context_ref()? {
+        RequestContext::ApiGatewayV1(context) => context.stage.as_deref(),
+        RequestContext::ApiGatewayV2(context) => context.stage.as_deref(),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    };
+    stage.filter(|stage| *stage != "$default")
+}
+
+/// Removes the leading segments `prefix` from `path`, keeping the root
+fn strip_segment<'a>(path: &'a str, prefix: &str) -> &'a str {
+    if prefix.is_empty() {
+        return path;
+    }
+    match path
+        .strip_prefix('/')
+        .and_then(|path| path.strip_prefix(prefix))
+    {
+        Some("") => "/",
+        Some(rest) if rest.starts_with('/') => rest,
+        _ => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::parse::OpenAPI;
+
+    const SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Users API
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      parameters:
+        - name: notify
+          in: query
+          schema:
+            type: boolean
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+      responses:
+        '201':
+          description: Created
+"#;
+
+    fn rest_request(path: &str, body: &str) -> Request {
+        let event = serde_json::json!({
+            "resource": "/{proxy+}",
+            "path": path,
+            "httpMethod": "POST",
+            "headers": {"Content-Type": "application/json", "Host": "api.example.com"},
+            "multiValueHeaders": {},
+            "queryStringParameters": {"notify": "true"},
+            "multiValueQueryStringParameters": {"notify": ["true"]},
+            "pathParameters": {},
+            "stageVariables": {},
+            "requestContext": {
+                "accountId": "123456789012",
+                "resourceId": "abc123",
+                "stage": "prod",
+                "requestId": "id",
+                "identity": {},
+                "resourcePath": "/{proxy+}",
+                "httpMethod": "POST",
+                "apiId": "api"
+            },
+            "body": body,
+            "isBase64Encoded": false
+        });
+        lambda_http::request::from_str(&event.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_request_data_strips_stage_and_base_path() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+
+        let request = rest_request("/users", r#"{"name": "alice"}"#);
+        let data = request_data(&request, None);
+        assert_eq!(data.path, "/users");
+        assert_eq!(data.method, "post");
+        assert_eq!(data.query_string, "notify=true");
+        assert!(open_api.validator(data).is_ok());
+
+        let request = rest_request("/v1/users", "{}");
+        let data = request_data(&request, Some("/v1/"));
+        assert_eq!(data.path, "/users");
+        assert!(open_api.validator(data).is_err());
+
+        assert_eq!(strip_segment("/prod", "prod"), "/");
+        assert_eq!(strip_segment("/products", "prod"), "/products");
+    }
+}
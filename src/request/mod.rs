@@ -15,8 +15,36 @@
  * limitations under the License.
  */
 
+pub mod config;
+pub mod multipart;
+
 #[cfg(feature = "axum")]
 pub mod axum;
 
 #[cfg(feature = "actix-web")]
 pub mod actix_web;
+
+#[cfg(feature = "tower")]
+pub mod tower;
+
+#[cfg(feature = "lambda")]
+pub mod lambda;
+
+#[cfg(feature = "worker")]
+pub mod worker;
+
+#[cfg(any(
+    feature = "actix-web",
+    feature = "tower",
+    feature = "lambda",
+    feature = "worker",
+    feature = "ffi",
+    feature = "test-util",
+    feature = "client"
+))]
+pub(crate) mod validation;
+
+/// Requests validated outside of a middleware, such as the recorded traffic
+/// `openapi-rs replay` checks or the requests of generated clients
+#[cfg(any(feature = "cli", feature = "client"))]
+pub use validation::RequestData;
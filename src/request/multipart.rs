@@ -0,0 +1,124 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::{anyhow, Result};
+
+/// A single part of a `multipart/form-data` body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    pub name: String,
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl Part {
+    /// Whether the part was sent as a file upload
+    pub fn is_file(&self) -> bool {
+        self.filename.is_some()
+    }
+}
+
+/// Returns the boundary of a `multipart/form-data` content type, or `None`
+/// for any other media type.
+pub fn boundary(content_type: &str) -> Option<String> {
+    let mut params = content_type.split(';');
+    let media_type = params.next()?.trim();
+    if !media_type.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+
+    params.find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("boundary")
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Splits a `multipart/form-data` body into its parts.
+pub fn parse(body: &[u8], boundary: &str) -> Result<Vec<Part>> {
+    let delimiter = format!("--{boundary}");
+    let delimiter = delimiter.as_bytes();
+
+    let mut rest = match find(body, delimiter) {
+        Some(start) => &body[start + delimiter.len()..],
+        None => return Err(anyhow!("Multipart body is missing boundary '{}'", boundary)),
+    };
+
+    let mut parts = Vec::new();
+    loop {
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+        rest = rest
+            .strip_prefix(b"\r\n")
+            .ok_or_else(|| anyhow!("Malformed multipart boundary line"))?;
+
+        let end = find(rest, delimiter)
+            .ok_or_else(|| anyhow!("Multipart body is missing its closing boundary"))?;
+        let content = rest[..end]
+            .strip_suffix(b"\r\n")
+            .ok_or_else(|| anyhow!("Malformed multipart part"))?;
+        parts.push(parse_part(content)?);
+        rest = &rest[end + delimiter.len()..];
+    }
+}
+
+fn parse_part(content: &[u8]) -> Result<Part> {
+    let header_end =
+        find(content, b"\r\n\r\n").ok_or_else(|| anyhow!("Multipart part has no headers"))?;
+    let headers = std::str::from_utf8(&content[..header_end])?;
+
+    let mut name = None;
+    let mut filename = None;
+    let mut content_type = None;
+
+    for line in headers.split("\r\n") {
+        let Some((header, value)) = line.split_once(':') else {
+            continue;
+        };
+        if header.trim().eq_ignore_ascii_case("content-disposition") {
+            for param in value.split(';').skip(1) {
+                match param.split_once('=') {
+                    Some((key, value)) if key.trim() == "name" => {
+                        name = Some(value.trim().trim_matches('"').to_string());
+                    }
+                    Some((key, value)) if key.trim() == "filename" => {
+                        filename = Some(value.trim().trim_matches('"').to_string());
+                    }
+                    _ => {}
+                }
+            }
+        } else if header.trim().eq_ignore_ascii_case("content-type") {
+            content_type = Some(value.trim().to_string());
+        }
+    }
+
+    Ok(Part {
+        name: name.ok_or_else(|| anyhow!("Multipart part is missing a field name"))?,
+        filename,
+        content_type,
+        data: content[header_end + 4..].to_vec(),
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
@@ -0,0 +1,810 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Validation middleware for any [`tower_service::Service`] over
+//! [`http::Request`], e.g. hyper, tonic-web or hand-built stacks.
+
+use crate::model::parse::OpenAPI;
+use crate::model::registry::SpecRegistry;
+use crate::model::shared::SharedOpenAPI;
+use crate::model::version::VersionedSpecs;
+use crate::observability::{self, RequestContext};
+use crate::request::config::{MiddlewareConfig, ViolationMode};
+use crate::request::validation::{RequestData, Specs};
+use crate::validator::stream::{BodyLimit, JsonLines};
+use crate::validator::{self, response_message, MethodNotAllowed, Unauthorized};
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::future::BoxFuture;
+use http::{header, Request, Response, StatusCode};
+use http_body::Body;
+use http_body_util::{BodyExt, Full};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Validates requests before they reach the wrapped service.
+///
+/// The request body is buffered, up to
+/// [`ValidationOptions::max_body_size`](crate::validator::ValidationOptions::max_body_size),
+/// and handed on as `B`, which defaults to [`Full<Bytes>`]; the buffered
+/// bytes are also stored in the request extensions. Rejected requests are
+/// answered without calling the wrapped service, with a response body built
+/// from the error message.
+///
+/// # example
+///
+/// ```rust,ignore
+/// use openapi_rs::request::tower::OpenApiValidationLayer;
+/// use tower::ServiceBuilder;
+///
+/// let service = ServiceBuilder::new()
+///     .layer(OpenApiValidationLayer::new(openapi))
+///     .service(handler);
+/// ```
+#[derive(Debug)]
+pub struct OpenApiValidationLayer<B = Full<Bytes>> {
+    specs: Specs,
+    config: MiddlewareConfig,
+    body: PhantomData<fn() -> B>,
+}
+
+impl<B> Clone for OpenApiValidationLayer<B> {
+    fn clone(&self) -> Self {
+        Self::with_specs(self.specs.clone()).with_config(self.config.clone())
+    }
+}
+
+impl OpenApiValidationLayer {
+    pub fn new(openapi: OpenAPI) -> Self {
+        Self::from_shared(SharedOpenAPI::new(openapi))
+    }
+
+    /// Validates against the current document of `openapi`, so replacing it
+    /// takes effect without rebuilding the service stack
+    pub fn from_shared(openapi: SharedOpenAPI) -> Self {
+        Self::from_registry(SpecRegistry::new().with("/", openapi))
+    }
+
+    /// Validates each request against the document mounted at the longest
+    /// prefix of its path, with that prefix removed. Requests outside every
+    /// prefix are answered with `404 Not Found`.
+    pub fn from_registry(registry: SpecRegistry) -> Self {
+        Self::with_specs(Specs::Prefixed(registry))
+    }
+
+    /// Validates each request against the version of the document it selects,
+    /// see [`VersionedSpecs`]. Requests selecting no loaded version are
+    /// answered with `404 Not Found`.
+    pub fn from_versions(versions: VersionedSpecs) -> Self {
+        Self::with_specs(Specs::Versioned(versions))
+    }
+}
+
+impl<B> OpenApiValidationLayer<B> {
+    fn with_specs(specs: Specs) -> Self {
+        Self {
+            specs,
+            config: MiddlewareConfig::default(),
+            body: PhantomData,
+        }
+    }
+
+    /// Replace the configuration of which routes are validated
+    pub fn with_config(mut self, config: MiddlewareConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Hands the buffered body on as `T` instead, for services expecting
+    /// their own body type such as `axum::body::Body`
+    pub fn with_body<T: From<Bytes>>(self) -> OpenApiValidationLayer<T> {
+        OpenApiValidationLayer::with_specs(self.specs).with_config(self.config)
+    }
+}
+
+impl<S, B> Layer<S> for OpenApiValidationLayer<B> {
+    type Service = OpenApiValidationService<S, B>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        OpenApiValidationService {
+            inner,
+            specs: self.specs.clone(),
+            config: self.config.clone(),
+            body: PhantomData,
+        }
+    }
+}
+
+/// The service produced by [`OpenApiValidationLayer`]
+#[derive(Debug)]
+pub struct OpenApiValidationService<S, B = Full<Bytes>> {
+    inner: S,
+    specs: Specs,
+    config: MiddlewareConfig,
+    body: PhantomData<fn() -> B>,
+}
+
+impl<S: Clone, B> Clone for OpenApiValidationService<S, B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            specs: self.specs.clone(),
+            config: self.config.clone(),
+            body: PhantomData,
+        }
+    }
+}
+
+impl<S, B, ReqBody, ResBody> Service<Request<ReqBody>> for OpenApiValidationService<S, B>
+where
+    S: Service<Request<B>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    B: From<Bytes>,
+    ReqBody: Body + Send + 'static,
+    ReqBody::Data: Send,
+    ReqBody::Error: Display,
+    ResBody: From<String>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        // The clone may not be ready, so keep the service that was polled
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let headers: HashMap<String, String> = req
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let selected = self.specs.select(req.uri().path(), &headers);
+        let undocumented =
+            !matches!(&selected, Some((openapi, path)) if openapi.path_item(path).is_some());
+        let skip = !self.config.validates(req.uri().path())
+            || (self.config.skip_undocumented && undocumented);
+        let hooks = self.config.hooks.clone();
+
+        Box::pin(async move {
+            if skip {
+                // Unvalidated requests still honour the limit of their document
+                let limit = selected
+                    .as_ref()
+                    .map_or_else(BodyLimit::default, |(openapi, _)| BodyLimit::new(openapi));
+                let (parts, body) = req.into_parts();
+                let body = match buffer_body(&parts, body, limit, None).await {
+                    Ok((body, _)) => body,
+                    Err((status, message)) => return Ok(rejection(status, message)),
+                };
+                return inner.call(Request::from_parts(parts, B::from(body))).await;
+            }
+            let observation = hooks.start(
+                RequestContext::new(req.method().to_string(), req.uri().path().to_string())
+                    .with_trace_headers(
+                        headers.get("x-request-id").map(String::as_str),
+                        headers.get("traceparent").map(String::as_str),
+                    ),
+            );
+            let reject = |status: StatusCode, message: String| {
+                observation.failure(&message);
+                observation.response(status.as_u16());
+                rejection(status, observation.context().error_message(&message))
+            };
+            let Some((openapi, path)) = selected else {
+                let message = format!("no document matches '{}'", req.uri().path());
+                return Ok(reject(StatusCode::NOT_FOUND, message));
+            };
+            let method = req.method().as_str().to_lowercase();
+            let query_string = req.uri().query().unwrap_or_default().to_string();
+            let content_type = req
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+
+            let (mut parts, body) = req.into_parts();
+            let (body, body_validated) =
+                match read_body(&parts, body, &openapi, &path, &method).await {
+                    Ok(read) => read,
+                    Err((status, message)) => return Ok(reject(status, message)),
+                };
+
+            let request_data = RequestData {
+                path,
+                method,
+                query_string,
+                content_type,
+                headers,
+                body: (!body.is_empty()).then(|| body.clone()),
+                body_validated,
+            };
+
+            let (result, warnings) =
+                observability::collect_warnings(|| openapi.validate(request_data));
+            observation.deprecated(&warnings);
+            if let Err(e) = result {
+                let response = if let Some(not_allowed) = e.downcast_ref::<MethodNotAllowed>() {
+                    let mut response = reject(StatusCode::METHOD_NOT_ALLOWED, e.to_string());
+                    if let Ok(allow) = not_allowed.allow_header().parse() {
+                        response.headers_mut().insert(header::ALLOW, allow);
+                    }
+                    response
+                } else if e.is::<Unauthorized>() {
+                    reject(StatusCode::UNAUTHORIZED, e.to_string())
+                } else {
+                    reject(StatusCode::BAD_REQUEST, e.to_string())
+                };
+                return Ok(response);
+            }
+            observation.success();
+
+            if !body.is_empty() {
+                parts.extensions.insert(body.clone());
+            }
+            let response = inner
+                .call(Request::from_parts(parts, B::from(body)))
+                .await?;
+            observation.response(response.status().as_u16());
+            Ok(response)
+        })
+    }
+}
+
+/// Validates responses of the wrapped service against the `responses` of
+/// their operation: the status must be declared, the `Content-Type` must be
+/// one of its media types and JSON bodies must match the schema.
+///
+/// Responses are buffered to be checked, and violations are logged and, in
+/// the default [`ViolationMode::Enforce`], replaced with
+/// `500 Internal Server Error`, so this is meant for test and staging
+/// environments catching drift between the server and its contract.
+#[derive(Debug, Clone)]
+pub struct ResponseValidationLayer {
+    specs: Specs,
+    config: MiddlewareConfig,
+    mode: ViolationMode,
+}
+
+impl ResponseValidationLayer {
+    pub fn new(openapi: OpenAPI) -> Self {
+        Self::from_shared(SharedOpenAPI::new(openapi))
+    }
+
+    /// Validates against the current document of `openapi`
+    pub fn from_shared(openapi: SharedOpenAPI) -> Self {
+        Self::from_registry(SpecRegistry::new().with("/", openapi))
+    }
+
+    /// Validates each response against the document mounted at the longest
+    /// prefix of the request path. Responses to requests outside every
+    /// prefix are passed on unchecked.
+    pub fn from_registry(registry: SpecRegistry) -> Self {
+        Self {
+            specs: Specs::Prefixed(registry),
+            config: MiddlewareConfig::default(),
+            mode: ViolationMode::default(),
+        }
+    }
+
+    /// Validates each response against the version of the document its
+    /// request selects, see [`VersionedSpecs`]
+    pub fn from_versions(versions: VersionedSpecs) -> Self {
+        Self {
+            specs: Specs::Versioned(versions),
+            config: MiddlewareConfig::default(),
+            mode: ViolationMode::default(),
+        }
+    }
+
+    /// Replace the configuration of which routes are validated
+    pub fn with_config(mut self, config: MiddlewareConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Choose whether violations replace the response or are only logged
+    pub fn with_mode(mut self, mode: ViolationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+impl<S> Layer<S> for ResponseValidationLayer {
+    type Service = ResponseValidationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseValidationService {
+            inner,
+            specs: self.specs.clone(),
+            config: self.config.clone(),
+            mode: self.mode,
+        }
+    }
+}
+
+/// The service produced by [`ResponseValidationLayer`]
+#[derive(Debug, Clone)]
+pub struct ResponseValidationService<S> {
+    inner: S,
+    specs: Specs,
+    config: MiddlewareConfig,
+    mode: ViolationMode,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ResponseValidationService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+    ResBody: Body + From<Bytes> + From<String> + Send + 'static,
+    ResBody::Data: Send,
+    ResBody::Error: Display,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let headers: HashMap<String, String> = req
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let selected = self
+            .specs
+            .select(req.uri().path(), &headers)
+            .filter(|(openapi, path)| {
+                !self.config.skip_undocumented || openapi.path_item(path).is_some()
+            })
+            .filter(|_| self.config.validates(req.uri().path()));
+        let method = req.method().as_str().to_lowercase();
+        let mode = self.mode;
+
+        Box::pin(async move {
+            let response = inner.call(req).await?;
+            let Some((openapi, path)) = selected else {
+                return Ok(response);
+            };
+
+            let (parts, body) = response.into_parts();
+            let status = parts.status.as_u16();
+            let checked = match body.collect().await {
+                Ok(collected) => {
+                    let body = collected.to_bytes();
+                    let content_type = parts
+                        .headers
+                        .get(header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok());
+                    match response_message(&path, &method, status, content_type, &body, &openapi) {
+                        Ok(()) => Ok(body),
+                        Err(e) => Err((e, Some(body))),
+                    }
+                }
+                Err(e) => Err((anyhow::anyhow!("Error reading response body: {e}"), None)),
+            };
+
+            let body = match checked {
+                Ok(body) => body,
+                Err((e, body)) => {
+                    let error = e.to_string();
+                    observability::record_response_violation(&method, &path, status, &error);
+                    match (mode, body) {
+                        (ViolationMode::Shadow, Some(body)) => body,
+                        _ => return Ok(rejection(StatusCode::INTERNAL_SERVER_ERROR, error)),
+                    }
+                }
+            };
+            Ok(Response::from_parts(parts, ResBody::from(body)))
+        })
+    }
+}
+
+/// Buffers `body` up to [`ValidationOptions::max_body_size`], failing
+/// oversized bodies with `413 Payload Too Large` as soon as the limit is
+/// crossed. JSON Lines bodies are validated as their lines arrive; the
+/// returned flag tells whether that happened.
+///
+/// [`ValidationOptions::max_body_size`]: crate::validator::ValidationOptions::max_body_size
+async fn read_body<ReqBody>(
+    parts: &http::request::Parts,
+    body: ReqBody,
+    openapi: &OpenAPI,
+    path: &str,
+    method: &str,
+) -> Result<(Bytes, bool), (StatusCode, String)>
+where
+    ReqBody: Body,
+    ReqBody::Error: Display,
+{
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let lines = validator::is_json_sequence(content_type)
+        .then(|| JsonLines::new(path, method, content_type, openapi).ok())
+        .flatten();
+
+    buffer_body(parts, body, BodyLimit::new(openapi), lines).await
+}
+
+/// Buffers `body` within `limit`, pushing its chunks to `lines` when given
+async fn buffer_body<ReqBody>(
+    parts: &http::request::Parts,
+    body: ReqBody,
+    mut limit: BodyLimit,
+    mut lines: Option<JsonLines<'_>>,
+) -> Result<(Bytes, bool), (StatusCode, String)>
+where
+    ReqBody: Body,
+    ReqBody::Error: Display,
+{
+    let too_large = |e: anyhow::Error| (StatusCode::PAYLOAD_TOO_LARGE, e.to_string());
+    let invalid = |e: anyhow::Error| (StatusCode::BAD_REQUEST, e.to_string());
+
+    let content_length = parts
+        .headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok());
+    limit.check_declared(content_length).map_err(too_large)?;
+
+    let mut buffered = BytesMut::new();
+    let mut body = std::pin::pin!(body);
+    while let Some(frame) = body.frame().await {
+        let frame = frame.map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Error reading request chunk: {e}"),
+            )
+        })?;
+        let Ok(mut chunk) = frame.into_data() else {
+            continue;
+        };
+        let chunk = chunk.copy_to_bytes(chunk.remaining());
+
+        limit.take(chunk.len()).map_err(too_large)?;
+        if let Some(lines) = &mut lines {
+            lines.push(&chunk).map_err(invalid)?;
+        }
+        buffered.extend_from_slice(&chunk);
+    }
+
+    let validated = match lines {
+        Some(lines) => {
+            lines.finish().map_err(invalid)?;
+            true
+        }
+        None => false,
+    };
+    Ok((buffered.freeze(), validated))
+}
+
+fn rejection<ResBody: From<String>>(status: StatusCode, message: String) -> Response<ResBody> {
+    let mut response = Response::new(ResBody::from(format!(
+        "OpenAPI validation failed: {message}"
+    )));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("text/plain; charset=utf-8"),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::ValidationOptions;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceBuilder, ServiceExt};
+
+    const YAML: &str = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+              properties:
+                name:
+                  type: string
+      responses:
+        '200':
+          description: Success
+"#;
+
+    async fn echo(req: Request<Full<Bytes>>) -> Result<Response<String>, Infallible> {
+        let seen = req.extensions().get::<Bytes>().map(|body| body.len());
+        let body = req.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(seen, (!body.is_empty()).then_some(body.len()));
+        Ok(Response::new(String::from_utf8(body.to_vec()).unwrap()))
+    }
+
+    fn post(uri: &str, body: &str) -> Request<Full<Bytes>> {
+        Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Full::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_layer_validates_requests() {
+        let openapi: OpenAPI = serde_yaml::from_str(YAML).unwrap();
+        let service = ServiceBuilder::new()
+            .layer(OpenApiValidationLayer::new(openapi))
+            .service(service_fn(echo));
+
+        let ok = service
+            .clone()
+            .oneshot(post("/users", r#"{"name":"alice"}"#))
+            .await
+            .unwrap();
+        assert_eq!(ok.status(), StatusCode::OK);
+        assert_eq!(ok.body(), r#"{"name":"alice"}"#);
+
+        let invalid = service
+            .clone()
+            .oneshot(post("/users", r#"{"age":1}"#))
+            .await
+            .unwrap();
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+        assert!(invalid.body().starts_with("OpenAPI validation failed"));
+
+        let request = Request::get("/users")
+            .body(Full::new(Bytes::new()))
+            .unwrap();
+        let not_allowed = service.oneshot(request).await.unwrap();
+        assert_eq!(not_allowed.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(not_allowed.headers()[header::ALLOW], "POST");
+    }
+
+    #[tokio::test]
+    async fn test_layer_limits_body_and_routes_prefixes() {
+        let openapi = serde_yaml::from_str::<OpenAPI>(YAML)
+            .unwrap()
+            .with_options(ValidationOptions::default().with_max_body_size(8));
+        let registry = SpecRegistry::new().with("/api", SharedOpenAPI::new(openapi));
+        let service = ServiceBuilder::new()
+            .layer(OpenApiValidationLayer::from_registry(registry))
+            .service(service_fn(echo));
+
+        let too_large = service
+            .clone()
+            .oneshot(post("/api/users", r#"{"name":"alice"}"#))
+            .await
+            .unwrap();
+        assert_eq!(too_large.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let unknown = service
+            .oneshot(post("/users", r#"{"name":"a"}"#))
+            .await
+            .unwrap();
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_layer_skips_excluded_routes() {
+        let openapi = serde_yaml::from_str::<OpenAPI>(YAML)
+            .unwrap()
+            .with_options(ValidationOptions::default().with_max_body_size(16));
+        let config = MiddlewareConfig::new().with_exclude("/internal/*");
+        let service = ServiceBuilder::new()
+            .layer(OpenApiValidationLayer::new(openapi).with_config(config))
+            .service(service_fn(|req: Request<Full<Bytes>>| async move {
+                let body = req.into_body().collect().await.unwrap().to_bytes();
+                Ok::<_, Infallible>(Response::new(String::from_utf8(body.to_vec()).unwrap()))
+            }));
+
+        let excluded = service
+            .clone()
+            .oneshot(post("/internal/jobs", "not json"))
+            .await
+            .unwrap();
+        assert_eq!(excluded.status(), StatusCode::OK);
+        assert_eq!(excluded.body(), "not json");
+
+        let too_large = service
+            .clone()
+            .oneshot(post("/internal/jobs", "not json, and too long"))
+            .await
+            .unwrap();
+        assert_eq!(too_large.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let validated = service.oneshot(post("/users", "not json")).await.unwrap();
+        assert_eq!(validated.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_layer_reports_to_hooks() {
+        use crate::observability::ObservabilityHook;
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+
+        #[derive(Default, Clone)]
+        struct Outcomes(Arc<Mutex<Vec<String>>>);
+
+        impl ObservabilityHook for Outcomes {
+            fn on_success(&self, ctx: &RequestContext, _: Duration) {
+                let outcome = format!("{} {} ok", ctx.method, ctx.path);
+                self.0.lock().unwrap().push(outcome);
+            }
+
+            fn on_failure(&self, ctx: &RequestContext, error: &str, _: Duration) {
+                let outcome = format!("{} {} {error}", ctx.method, ctx.path);
+                self.0.lock().unwrap().push(outcome);
+            }
+        }
+
+        let outcomes = Outcomes::default();
+        let openapi: OpenAPI = serde_yaml::from_str(YAML).unwrap();
+        let config = MiddlewareConfig::new()
+            .with_exclude("/internal/*")
+            .with_hook(outcomes.clone());
+        let service = ServiceBuilder::new()
+            .layer(OpenApiValidationLayer::new(openapi).with_config(config))
+            .service(service_fn(|_: Request<Full<Bytes>>| async {
+                Ok::<_, Infallible>(Response::new(String::new()))
+            }));
+
+        for request in [
+            post("/users", r#"{"name": "alice"}"#),
+            post("/users", "{}"),
+            Request::get("/users").body(Full::default()).unwrap(),
+            post("/internal/jobs", "{}"),
+        ] {
+            service.clone().oneshot(request).await.unwrap();
+        }
+
+        let outcomes = outcomes.0.lock().unwrap();
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes[0], "POST /users ok");
+        assert!(outcomes[1].starts_with("POST /users Body validation failed"));
+        assert!(outcomes[2].starts_with("GET /users Method"));
+    }
+
+    #[tokio::test]
+    async fn test_layer_reports_deprecations_to_hooks() {
+        use crate::observability::ObservabilityHook;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Default, Clone)]
+        struct Deprecations(Arc<Mutex<Vec<String>>>);
+
+        impl ObservabilityHook for Deprecations {
+            fn on_deprecated(&self, ctx: &RequestContext, warning: &str) {
+                let deprecation = format!("{} {} {warning}", ctx.method, ctx.path);
+                self.0.lock().unwrap().push(deprecation);
+            }
+        }
+
+        let yaml = r#"
+openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      deprecated: true
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: string
+        - name: legacy
+          in: query
+          deprecated: true
+          schema:
+            type: string
+      responses:
+        '200':
+          description: Success
+"#;
+        let deprecations = Deprecations::default();
+        let openapi: OpenAPI = serde_yaml::from_str(yaml).unwrap();
+        let config = MiddlewareConfig::new().with_hook(deprecations.clone());
+        let service = ServiceBuilder::new()
+            .layer(OpenApiValidationLayer::new(openapi).with_config(config))
+            .service(service_fn(echo));
+
+        let request: Request<Full<Bytes>> = Request::get("/users/7?legacy=1")
+            .body(Full::default())
+            .unwrap();
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            *deprecations.0.lock().unwrap(),
+            [
+                "GET /users/7 Operation 'GET /users/{id}' is deprecated",
+                "GET /users/7 Query parameter 'legacy' is deprecated",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_layer_records_coverage() {
+        use crate::observability::CoverageTracker;
+
+        let openapi = SharedOpenAPI::new(serde_yaml::from_str(YAML).unwrap());
+        let coverage = CoverageTracker::new(openapi.clone());
+        let config = MiddlewareConfig::new().with_hook(coverage.clone());
+        let service = ServiceBuilder::new()
+            .layer(OpenApiValidationLayer::from_shared(openapi).with_config(config))
+            .service(service_fn(echo));
+
+        for request in [post("/users", r#"{"name": "alice"}"#), post("/users", "{}")] {
+            service.clone().oneshot(request).await.unwrap();
+        }
+
+        let report = coverage.report();
+        let users = &report.operations[0];
+        assert_eq!((users.requests, users.rejected), (2, 1));
+        assert_eq!(users.responses[0].hits, 1);
+        assert_eq!(users.undeclared.get(&400), Some(&1));
+        assert_eq!(report.untested().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejections_name_request_id() {
+        let openapi: OpenAPI = serde_yaml::from_str(YAML).unwrap();
+        let service = ServiceBuilder::new()
+            .layer(OpenApiValidationLayer::new(openapi))
+            .service(service_fn(echo));
+
+        let mut request = post("/users", "{}");
+        request
+            .headers_mut()
+            .insert("x-request-id", "req-42".parse().unwrap());
+        let response = service.clone().oneshot(request).await.unwrap();
+        assert!(response.body().ends_with("(request_id=req-42)"));
+
+        let mut request = post("/users", "{}");
+        request.headers_mut().insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+        let response = service.oneshot(request).await.unwrap();
+        assert!(response
+            .body()
+            .ends_with("(request_id=4bf92f3577b34da6a3ce929d0e0e4736)"));
+    }
+}
@@ -0,0 +1,160 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Request data and document selection shared by the middlewares.
+
+use crate::model::parse::OpenAPI;
+#[cfg(any(feature = "actix-web", feature = "tower"))]
+use crate::model::registry::SpecRegistry;
+#[cfg(any(feature = "actix-web", feature = "tower"))]
+use crate::model::version::VersionedSpecs;
+use crate::observability::RequestContext;
+use crate::request::multipart;
+use crate::validator::{
+    self, decode_query, method, parse_query, path, query, raw_body, redact, security,
+    ValidateRequest, ValidationOptions,
+};
+use anyhow::Result;
+use bytes::Bytes;
+use serde_json::value::RawValue;
+use std::collections::HashMap;
+#[cfg(any(feature = "actix-web", feature = "tower"))]
+use std::sync::Arc;
+
+#[allow(dead_code)]
+pub struct RequestData {
+    pub path: String,
+    pub method: String,
+    pub query_string: String,
+    pub content_type: Option<String>,
+    /// Request headers keyed by lower-cased name
+    pub headers: HashMap<String, String>,
+    pub body: Option<Bytes>,
+    /// Set when a JSON Lines body was already validated line by line while
+    /// it was received
+    pub body_validated: bool,
+}
+
+impl ValidateRequest for RequestData {
+    fn header(&self, _: &OpenAPI) -> Result<()> {
+        Ok(())
+    }
+
+    fn method(&self, open_api: &OpenAPI) -> Result<()> {
+        method(self.path.as_str(), self.method.as_str(), open_api)
+    }
+
+    fn security(&self, open_api: &OpenAPI) -> Result<()> {
+        let query_pairs = parse_query(&self.query_string);
+
+        security(
+            self.path.as_str(),
+            self.method.as_str(),
+            &self.headers,
+            &query_pairs,
+            open_api,
+        )
+    }
+
+    fn query(&self, open_api: &OpenAPI) -> Result<()> {
+        let (path, method) = (self.path.as_str(), self.method.as_str());
+        let query_pairs = decode_query(path, method, &self.query_string, open_api)?;
+
+        query(path, method, &query_pairs, open_api)
+    }
+
+    fn path(&self, open_api: &OpenAPI) -> Result<()> {
+        if let Some(last_segment) = self.path.rsplit('/').find(|s| !s.is_empty()) {
+            path(self.path.as_str(), last_segment, open_api)?
+        }
+
+        Ok(())
+    }
+
+    fn body(&self, open_api: &OpenAPI) -> Result<()> {
+        if self.body.is_none() {
+            return Ok(());
+        }
+        let self_body = self
+            .body
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Missing body"))?;
+
+        let content_type = self.content_type.as_deref().unwrap_or_default();
+        if let Some(boundary) = multipart::boundary(content_type) {
+            let parts = multipart::parse(self_body, &boundary)?;
+            return validator::multipart(self.path.as_str(), &self.method, &parts, open_api);
+        }
+        if validator::is_json_sequence(content_type) {
+            if self.body_validated {
+                return Ok(());
+            }
+            let (path, method) = (self.path.as_str(), self.method.as_str());
+            return validator::json_lines(path, method, content_type, &self_body[..], open_api);
+        }
+
+        let request_body: &RawValue = serde_json::from_slice(self_body)?;
+        raw_body(
+            self.path.as_str(),
+            self.method.as_str(),
+            request_body,
+            open_api,
+        )
+    }
+
+    fn context(&self) -> RequestContext {
+        RequestContext::new(self.method.clone(), self.path.clone()).with_trace_headers(
+            self.headers.get("x-request-id").map(String::as_str),
+            self.headers.get("traceparent").map(String::as_str),
+        )
+    }
+
+    fn secrets(&self, options: &ValidationOptions) -> Vec<String> {
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()));
+        redact::secrets(headers, &self.query_string, self.body.as_deref(), options)
+    }
+}
+
+/// How a middleware picks the document a request is validated against
+#[cfg(any(feature = "actix-web", feature = "tower"))]
+#[derive(Debug, Clone)]
+pub(crate) enum Specs {
+    Prefixed(SpecRegistry),
+    Versioned(VersionedSpecs),
+}
+
+#[cfg(any(feature = "actix-web", feature = "tower"))]
+impl Specs {
+    /// The current document for a request and the request path relative to it
+    pub(crate) fn select(
+        &self,
+        path: &str,
+        headers: &HashMap<String, String>,
+    ) -> Option<(Arc<OpenAPI>, String)> {
+        match self {
+            Specs::Prefixed(registry) => registry
+                .route(path)
+                .map(|routed| (routed.spec.load(), routed.path)),
+            Specs::Versioned(versions) => versions
+                .select(path, headers)
+                .map(|selected| (selected.spec.load(), selected.path)),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Validation of requests received by Cloudflare Workers, e.g. before they
+//! are proxied to the origin.
+
+use crate::request::validation;
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use std::collections::HashMap;
+use worker::Request;
+
+pub use crate::request::validation::RequestData;
+
+/// Converts `request` into [`RequestData`]. The body is read from a clone of
+/// the request, so `request` can still be forwarded afterwards.
+pub async fn request_data(request: &Request) -> Result<RequestData> {
+    let url = request
+        .url()
+        .map_err(|e| anyhow!("Invalid request URL: {e}"))?;
+    let headers: HashMap<String, String> = request
+        .headers()
+        .entries()
+        .map(|(name, value)| (name.to_lowercase(), value))
+        .collect();
+
+    let body = request
+        .clone()
+        .map_err(|e| anyhow!("Error cloning request: {e}"))?
+        .bytes()
+        .await
+        .map_err(|e| anyhow!("Error reading request body: {e}"))?;
+
+    Ok(validation::RequestData {
+        path: url.path().to_string(),
+        method: request.method().to_string().to_lowercase(),
+        query_string: url.query().unwrap_or_default().to_string(),
+        content_type: headers.get("content-type").cloned(),
+        headers,
+        body: (!body.is_empty()).then(|| Bytes::from(body)),
+        body_validated: false,
+    })
+}
@@ -0,0 +1,177 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Helpers for validating requests against a document in tests, without a
+//! web framework:
+//!
+//! ```
+//! use openapi_rs::model::parse::OpenAPI;
+//! use openapi_rs::test::RequestBuilder;
+//! use openapi_rs::{assert_invalid, assert_valid};
+//!
+//! let openapi = OpenAPI::yaml(r#"
+//! openapi: 3.1.0
+//! info: {title: Users, version: 1.0.0}
+//! paths:
+//!   /users:
+//!     get:
+//!       parameters:
+//!         - {name: limit, in: query, schema: {type: integer}}
+//!       responses: {'200': {description: OK}}
+//! "#).unwrap();
+//!
+//! assert_valid!(openapi, RequestBuilder::get("/users").with_query("limit", "10"));
+//! assert_invalid!(openapi, RequestBuilder::get("/users").with_query("limit", "ten"), "limit");
+//! ```
+
+pub use crate::request::validation::RequestData;
+
+use crate::model::parse::OpenAPI;
+use bytes::Bytes;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Builds the [`RequestData`] of a request from its parts
+#[derive(Debug, Clone)]
+pub struct RequestBuilder {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    headers: HashMap<String, String>,
+    body: Option<Bytes>,
+}
+
+impl RequestBuilder {
+    pub fn new(method: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            method: method.into(),
+            path: path.into(),
+            query: Vec::new(),
+            headers: HashMap::new(),
+            body: None,
+        }
+    }
+
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new("GET", path)
+    }
+
+    pub fn post(path: impl Into<String>) -> Self {
+        Self::new("POST", path)
+    }
+
+    pub fn put(path: impl Into<String>) -> Self {
+        Self::new("PUT", path)
+    }
+
+    pub fn patch(path: impl Into<String>) -> Self {
+        Self::new("PATCH", path)
+    }
+
+    pub fn delete(path: impl Into<String>) -> Self {
+        Self::new("DELETE", path)
+    }
+
+    /// Append a query pair, percent-encoded when the request is built
+    pub fn with_query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set a header, replacing any previous value; names are case-insensitive
+    pub fn with_header(mut self, name: impl AsRef<str>, value: impl Into<String>) -> Self {
+        self.headers
+            .insert(name.as_ref().to_ascii_lowercase(), value.into());
+        self
+    }
+
+    /// Send `body` serialized as JSON, with an `application/json` content type
+    pub fn with_json(self, body: &impl Serialize) -> Self {
+        let body = serde_json::to_vec(body).expect("request body serializes to JSON");
+        self.with_body("application/json", body)
+    }
+
+    /// Send `body` as is, with the content type `content_type`
+    pub fn with_body(mut self, content_type: impl Into<String>, body: impl Into<Bytes>) -> Self {
+        self.body = Some(body.into());
+        self.with_header("content-type", content_type)
+    }
+
+    pub fn build(self) -> RequestData {
+        let query_string = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&self.query)
+            .finish();
+        RequestData {
+            path: self.path,
+            method: self.method.to_ascii_lowercase(),
+            query_string,
+            content_type: self.headers.get("content-type").cloned(),
+            headers: self.headers,
+            body: self.body,
+            body_validated: false,
+        }
+    }
+}
+
+impl From<RequestBuilder> for RequestData {
+    fn from(builder: RequestBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Validates `request` against `openapi` as the middlewares do, returning
+/// the rejection message of an invalid request
+pub fn validate(openapi: &OpenAPI, request: impl Into<RequestData>) -> Result<(), String> {
+    openapi.validator(request.into())
+}
+
+/// Panics unless a [`RequestBuilder`](crate::test::RequestBuilder) or
+/// [`RequestData`](crate::test::RequestData) is valid against an
+/// [`OpenAPI`](crate::model::parse::OpenAPI) document, naming the validation
+/// error.
+#[macro_export]
+macro_rules! assert_valid {
+    ($openapi:expr, $request:expr $(,)?) => {
+        if let Err(error) = $crate::test::validate(&$openapi, $request) {
+            panic!("expected a valid request, validation failed: {}", error);
+        }
+    };
+}
+
+/// Panics if a [`RequestBuilder`](crate::test::RequestBuilder) or
+/// [`RequestData`](crate::test::RequestData) is valid against an
+/// [`OpenAPI`](crate::model::parse::OpenAPI) document or, given a third
+/// argument, if the validation error does not contain it.
+#[macro_export]
+macro_rules! assert_invalid {
+    ($openapi:expr, $request:expr $(,)?) => {
+        if $crate::test::validate(&$openapi, $request).is_ok() {
+            panic!("expected an invalid request, validation passed");
+        }
+    };
+    ($openapi:expr, $request:expr, $expected:expr $(,)?) => {
+        match $crate::test::validate(&$openapi, $request) {
+            Ok(()) => panic!("expected an invalid request, validation passed"),
+            Err(error) => assert!(
+                error.contains($expected),
+                "expected a validation error containing {:?}, got: {}",
+                $expected,
+                error
+            ),
+        }
+    };
+}
@@ -0,0 +1,947 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::model::parse::OpenAPI;
+    use crate::validator::stream::{BodyLimit, JsonLines};
+    use crate::validator::{
+        body, callback, is_json_sequence, json_lines, raw_body, PayloadTooLarge, ValidationOptions,
+    };
+    use serde_json::json;
+    use serde_json::value::RawValue;
+
+    #[test]
+    fn test_nested_ref_validation() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Orders API
+  version: 1.0.0
+paths:
+  /orders:
+    post:
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Order'
+components:
+  schemas:
+    Order:
+      type: object
+      required: [id, customer]
+      properties:
+        id:
+          type: integer
+        customer:
+          $ref: '#/components/schemas/Customer'
+    Customer:
+      type: object
+      required: [email, address]
+      properties:
+        email:
+          type: string
+          format: email
+        address:
+          type: object
+          required: [city]
+          properties:
+            city:
+              type: string
+              minLength: 2
+            geo:
+              $ref: '#/components/schemas/Geo'
+    Geo:
+      type: object
+      properties:
+        lat:
+          type: number
+          minimum: -90
+          maximum: 90
+    Purchase:
+      $ref: '#/components/schemas/Order'
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        let compiled = open_api.compiled();
+        let order = compiled.schema("#/components/schemas/Order").unwrap();
+        assert_eq!(order.required, ["id", "customer"]);
+        assert_eq!(order.properties[0].len(), 2);
+        let purchase = compiled.schema("#/components/schemas/Purchase").unwrap();
+        assert_eq!(purchase.required, order.required);
+        assert!(compiled
+            .schema("#/components/schemas/Geo")
+            .unwrap()
+            .required
+            .is_empty());
+        assert!(compiled.schema("#/components/schemas/Missing").is_none());
+
+        let valid = json!({
+            "id": 1,
+            "customer": {
+                "email": "a@example.com",
+                "address": {"city": "Paris", "geo": {"lat": 48.8}}
+            }
+        });
+        assert!(body("/orders", "post", valid, &open_api).is_ok());
+
+        let cases = [
+            (
+                json!({"id": 1, "customer": {"email": "a@example.com"}}),
+                "Missing required field 'address' in 'customer'",
+            ),
+            (
+                json!({"id": 1, "customer": {"email": "nope", "address": {"city": "Paris"}}}),
+                "Invalid Email format",
+            ),
+            (
+                json!({"id": 1, "customer": {"email": "a@example.com", "address": {}}}),
+                "Missing required field 'city' in 'address'",
+            ),
+            (
+                json!({"id": 1, "customer": {"email": "a@example.com", "address": {"city": "P"}}}),
+                "at least 2 characters",
+            ),
+            (
+                json!({"id": 1, "customer": {
+                    "email": "a@example.com",
+                    "address": {"city": "Paris", "geo": {"lat": 120}}
+                }}),
+                "must be <= 90",
+            ),
+            (
+                json!({"id": 1, "customer": "a@example.com"}),
+                "must be an Object",
+            ),
+        ];
+
+        for (value, expected) in cases {
+            let err = body("/orders", "post", value, &open_api).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_array_items_ref_validation() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users API
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: array
+              items:
+                $ref: '#/components/schemas/User'
+  /teams:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Team'
+components:
+  schemas:
+    User:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+        age:
+          type: integer
+          minimum: 0
+    Team:
+      type: object
+      properties:
+        members:
+          type: array
+          items:
+            $ref: '#/components/schemas/User'
+        rotations:
+          type: array
+          items:
+            type: array
+            items:
+              $ref: '#/components/schemas/User'
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(body(
+            "/users",
+            "post",
+            json!([{"name": "a"}, {"name": "b", "age": 3}]),
+            &open_api
+        )
+        .is_ok());
+
+        let err = body(
+            "/users",
+            "post",
+            json!([{"name": "a"}, {"age": 3}]),
+            &open_api,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("name"), "{err}");
+
+        let err = body(
+            "/users",
+            "post",
+            json!([{"name": "a", "age": -1}]),
+            &open_api,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("must be >= 0"), "{err}");
+
+        let team = json!({"members": [{"name": "a"}], "rotations": [[{"name": "b"}]]});
+        assert!(body("/teams", "post", team, &open_api).is_ok());
+
+        let err = body(
+            "/teams",
+            "post",
+            json!({"members": [{"age": 1}]}),
+            &open_api,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Missing required field 'name' in 'members[0]'"),
+            "{err}"
+        );
+
+        let err = body(
+            "/teams",
+            "post",
+            json!({"rotations": [[{"name": 5}]]}),
+            &open_api,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("must be a String"), "{err}");
+    }
+
+    #[test]
+    fn test_nested_and_scalar_array_validation() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Matrix API
+  version: 1.0.0
+paths:
+  /matrix:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: array
+              items:
+                type: array
+                minItems: 2
+                items:
+                  type: integer
+                  minimum: 0
+                  maximum: 9
+  /tags:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Tags'
+components:
+  schemas:
+    Tags:
+      type: array
+      items:
+        type: string
+        format: email
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(body("/matrix", "post", json!([[1, 2], [3, 4, 5]]), &open_api).is_ok());
+
+        let cases = [
+            (json!([[1, 2], [3]]), "at least 2 items"),
+            (json!([[1, 2], [3, 10]]), "must be <= 9"),
+            (json!([[1, 2], ["a", 1]]), "must be an Integer"),
+            (json!([1, 2]), "must be an Array"),
+        ];
+        for (value, expected) in cases {
+            let err = body("/matrix", "post", value, &open_api).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+
+        assert!(body("/tags", "post", json!(["a@example.com"]), &open_api).is_ok());
+        assert!(body("/tags", "post", json!(["not-an-email"]), &open_api).is_err());
+        assert!(body("/tags", "post", json!([{"a": 1}]), &open_api).is_err());
+    }
+
+    #[test]
+    fn test_inline_schema_validation() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Signup API
+  version: 1.0.0
+paths:
+  /signup:
+    post:
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [email, plan]
+              properties:
+                email:
+                  type: string
+                  format: email
+                plan:
+                  type: string
+                  enum: [free, pro]
+                seats:
+                  type: integer
+                  minimum: 1
+                  maximum: 50
+                company:
+                  type: object
+                  required: [name]
+                  properties:
+                    name:
+                      type: string
+                      maxLength: 5
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        let valid = json!({"email": "a@example.com", "plan": "pro", "seats": 3});
+        assert!(body("/signup", "post", valid, &open_api).is_ok());
+
+        let cases = [
+            (
+                json!({"email": "a@example.com"}),
+                "Missing required field 'plan'",
+            ),
+            (
+                json!({"email": "nope", "plan": "pro"}),
+                "Invalid Email format",
+            ),
+            (
+                json!({"email": "a@example.com", "plan": "gold"}),
+                "not in allowed enum values",
+            ),
+            (
+                json!({"email": "a@example.com", "plan": "free", "seats": 0}),
+                "must be >= 1",
+            ),
+            (
+                json!({"email": "a@example.com", "plan": "free", "company": {}}),
+                "Missing required field 'name' in 'company'",
+            ),
+            (
+                json!({"email": "a@example.com", "plan": "free", "company": {"name": "Initech"}}),
+                "at most 5 characters",
+            ),
+            (
+                json!(["a@example.com"]),
+                "Expected request body to be a Array",
+            ),
+        ];
+        for (value, expected) in cases {
+            let err = body("/signup", "post", value, &open_api).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_password_values_are_redacted() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Auth API
+  version: 1.0.0
+paths:
+  /login:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                username:
+                  type: string
+                  pattern: '^[a-z]+$'
+                password:
+                  type: string
+                  format: password
+                  pattern: '^\S{8,}$'
+                pin:
+                  type: string
+                  format: password
+                  enum: ['0000']
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(body(
+            "/login",
+            "post",
+            json!({"password": "correct-horse"}),
+            &open_api
+        )
+        .is_ok());
+
+        let err = body("/login", "post", json!({"password": "hunter2"}), &open_api).unwrap_err();
+        assert!(!err.to_string().contains("hunter2"), "{err}");
+        assert!(err.to_string().contains("******"), "{err}");
+
+        let err = body("/login", "post", json!({"pin": "1234"}), &open_api).unwrap_err();
+        assert!(!err.to_string().contains("1234"), "{err}");
+
+        let err = body("/login", "post", json!({"username": "Bob"}), &open_api).unwrap_err();
+        assert!(err.to_string().contains("Bob"), "{err}");
+    }
+
+    #[test]
+    fn test_encoded_content_validation() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Envelope API
+  version: 1.0.0
+paths:
+  /envelopes:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                payload:
+                  type: string
+                  contentEncoding: base64
+                  contentMediaType: application/json
+                  contentSchema:
+                    type: object
+                    required: [id]
+                    properties:
+                      id:
+                        type: integer
+                raw:
+                  type: string
+                  contentMediaType: application/json
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        // {"id":7}
+        assert!(body(
+            "/envelopes",
+            "post",
+            json!({"payload": "eyJpZCI6N30="}),
+            &open_api
+        )
+        .is_ok());
+        assert!(body("/envelopes", "post", json!({"raw": "[1, 2]"}), &open_api).is_ok());
+
+        let cases = [
+            (json!({"payload": "not base64!"}), "valid base64 content"),
+            // {"id":"x"}
+            (json!({"payload": "eyJpZCI6IngifQ=="}), "must be an Integer"),
+            // {}
+            (json!({"payload": "e30="}), "Missing required field 'id'"),
+            // not json
+            (json!({"payload": "bm90IGpzb24="}), "must be valid JSON"),
+            (json!({"raw": "{"}), "must be valid JSON"),
+        ];
+        for (value, expected) in cases {
+            let err = body("/envelopes", "post", value, &open_api).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_body_selected_by_method() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Items API
+  version: 1.0.0
+paths:
+  /items:
+    get:
+      responses:
+        '200':
+          description: OK
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name, price]
+              properties:
+                name:
+                  type: string
+                price:
+                  type: integer
+    patch:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                name:
+                  type: string
+                price:
+                  type: integer
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(body("/items", "patch", json!({"price": 3}), &open_api).is_ok());
+
+        let err = body("/items", "post", json!({"price": 3}), &open_api).unwrap_err();
+        assert!(err.to_string().contains("name"), "{err}");
+
+        let err = body("/items", "patch", json!({"price": "3"}), &open_api).unwrap_err();
+        assert!(err.to_string().contains("price"), "{err}");
+
+        let err = body("/items", "get", json!({"name": "a"}), &open_api).unwrap_err();
+        assert!(
+            err.to_string().contains("does not accept a request body"),
+            "{err}"
+        );
+        assert!(body("/items", "get", json!(null), &open_api).is_ok());
+    }
+
+    #[test]
+    fn test_json_lines_item_schema() {
+        let content = r#"
+openapi: 3.2.0
+info:
+  title: Events API
+  version: 1.0.0
+paths:
+  /events:
+    post:
+      requestBody:
+        content:
+          application/jsonl:
+            itemSchema:
+              type: object
+              required: [id]
+              properties:
+                id:
+                  type: integer
+                kind:
+                  type: string
+                  enum: [click, view]
+          application/x-ndjson:
+            itemSchema:
+              type: integer
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(is_json_sequence("application/x-ndjson; charset=utf-8"));
+        assert!(!is_json_sequence("application/json"));
+
+        let jsonl = "application/jsonl";
+        let valid = "{\"id\": 1, \"kind\": \"click\"}\n\n{\"id\": 2}\n";
+        assert!(json_lines("/events", "post", jsonl, valid.as_bytes(), &open_api).is_ok());
+
+        let cases = [
+            (
+                "{\"id\": 1}\n{\"kind\": \"view\"}",
+                "Missing required field 'id' in 'line 2'",
+            ),
+            ("{\"id\": 1, \"kind\": \"scroll\"}", "kind"),
+            ("{\"id\": 1}\n{not json", "'line 2' must be valid JSON"),
+        ];
+        for (lines, expected) in cases {
+            let err =
+                json_lines("/events", "post", jsonl, lines.as_bytes(), &open_api).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+
+        let ndjson = "application/x-ndjson";
+        assert!(json_lines("/events", "post", ndjson, "1\n2\n".as_bytes(), &open_api).is_ok());
+        assert!(json_lines("/events", "post", ndjson, "1\n\"a\"".as_bytes(), &open_api).is_err());
+    }
+
+    #[test]
+    fn test_structured_suffix_and_wildcard_media_types() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Media API
+  version: 1.0.0
+paths:
+  /vendor:
+    post:
+      requestBody:
+        content:
+          application/vnd.company.v2+json:
+            schema:
+              type: object
+              required: [id]
+          application/xml:
+            schema:
+              type: string
+  /any:
+    post:
+      requestBody:
+        content:
+          '*/*':
+            schema:
+              type: object
+              required: [name]
+  /xml:
+    post:
+      requestBody:
+        content:
+          application/xml:
+            schema:
+              type: object
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(body("/vendor", "post", json!({"id": 1}), &open_api).is_ok());
+        let err = body("/vendor", "post", json!({"name": "a"}), &open_api).unwrap_err();
+        assert!(err.to_string().contains("id"), "{err}");
+
+        assert!(body("/any", "post", json!({"name": "a"}), &open_api).is_ok());
+        assert!(body("/any", "post", json!({"id": 1}), &open_api).is_err());
+
+        let err = body("/xml", "post", json!({"id": 1}), &open_api).unwrap_err();
+        assert!(err.to_string().contains("does not accept JSON"), "{err}");
+    }
+
+    #[test]
+    fn test_callback_payload() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Subscriptions API
+  version: 1.0.0
+paths:
+  /subscriptions:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              properties:
+                callbackUrl:
+                  type: string
+                  format: uri
+      responses:
+        '201':
+          description: Subscribed
+      callbacks:
+        onEvent:
+          '{$request.body#/callbackUrl}':
+            post:
+              requestBody:
+                required: true
+                content:
+                  application/json:
+                    schema:
+                      $ref: '#/components/schemas/Event'
+              responses:
+                '200':
+                  description: Received
+        onCancel:
+          $ref: '#/components/callbacks/Cancelled'
+components:
+  schemas:
+    Event:
+      type: object
+      required: [id]
+      properties:
+        id:
+          type: integer
+        message:
+          type: string
+  callbacks:
+    Cancelled:
+      '{$request.body#/callbackUrl}':
+        delete:
+          responses:
+            '204':
+              description: Acknowledged
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        let event = json!({"id": 1, "message": "created"});
+        assert!(callback(
+            "/subscriptions",
+            "post",
+            "onEvent",
+            "post",
+            event,
+            &open_api
+        )
+        .is_ok());
+
+        let invalid = json!({"id": "one"});
+        assert!(callback(
+            "/subscriptions",
+            "post",
+            "onEvent",
+            "post",
+            invalid,
+            &open_api
+        )
+        .is_err());
+        let missing = json!({"message": "created"});
+        assert!(callback(
+            "/subscriptions",
+            "post",
+            "onEvent",
+            "post",
+            missing,
+            &open_api
+        )
+        .is_err());
+
+        let cancel = callback(
+            "/subscriptions",
+            "post",
+            "onCancel",
+            "delete",
+            serde_json::Value::Null,
+            &open_api,
+        );
+        assert!(cancel.is_ok());
+
+        let err = callback(
+            "/subscriptions",
+            "post",
+            "onEvent",
+            "put",
+            json!({}),
+            &open_api,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("does not declare method 'put'"),
+            "{err}"
+        );
+        let err = callback(
+            "/subscriptions",
+            "post",
+            "onPing",
+            "post",
+            json!({}),
+            &open_api,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not declared"), "{err}");
+    }
+
+    #[test]
+    fn test_raw_body_validates_items() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Bulk API
+  version: 1.0.0
+paths:
+  /orders:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Orders'
+  /tags:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: array
+              maxItems: 2
+              items:
+                type: string
+                minLength: 2
+components:
+  schemas:
+    Orders:
+      type: array
+      minItems: 1
+      items:
+        type: object
+        required:
+          - id
+        properties:
+          id:
+            type: integer
+"#;
+        let open_api: OpenAPI = OpenAPI::yaml(content).expect("Failed to parse OpenAPI YAML");
+
+        let check = |path: &str, json: &str| {
+            let raw: Box<RawValue> = serde_json::from_str(json).unwrap();
+            let streamed = raw_body(path, "post", &raw, &open_api);
+            let parsed = body(path, "post", serde_json::from_str(json).unwrap(), &open_api);
+            assert_eq!(streamed.is_ok(), parsed.is_ok(), "{json}: {streamed:?}");
+            streamed
+        };
+
+        assert!(check("/orders", r#"[{"id": 1}, {"id": 2}]"#).is_ok());
+        assert!(check("/orders", r#"[]"#).is_err());
+        assert!(check("/orders", r#"[{"id": 1}, {"name": "two"}]"#).is_err());
+        let err = check("/orders", r#"[{"id": 1}, {"id": "two"}]"#).unwrap_err();
+        assert!(err.to_string().contains("id"), "{err}");
+        assert!(check("/orders", r#"{"id": 1}"#).is_err());
+
+        assert!(check("/tags", r#"["ab", "cd"]"#).is_ok());
+        assert!(check("/tags", r#"["ab", "cd", "ef"]"#).is_err());
+        let err = check("/tags", r#"["ab", "c"]"#).unwrap_err();
+        assert!(err.to_string().contains("request_body[1]"), "{err}");
+    }
+
+    #[test]
+    fn test_streamed_json_lines_and_size_limit() {
+        let content = r#"
+openapi: 3.2.0
+info:
+  title: Events API
+  version: 1.0.0
+paths:
+  /events:
+    post:
+      requestBody:
+        content:
+          application/x-ndjson:
+            itemSchema:
+              type: object
+              required: [id]
+              properties:
+                id:
+                  type: integer
+"#;
+        let open_api = OpenAPI::yaml(content)
+            .unwrap()
+            .with_options(ValidationOptions::new().with_max_body_size(16));
+        let ndjson = "application/x-ndjson";
+
+        let mut lines = JsonLines::new("/events", "post", ndjson, &open_api).unwrap();
+        for chunk in ["{\"id\"", ": 1}\r\n{\"i", "d\": 2}\n\n{\"id\": 3}"] {
+            lines.push(chunk.as_bytes()).unwrap();
+        }
+        assert!(lines.finish().is_ok());
+
+        let mut lines = JsonLines::new("/events", "post", ndjson, &open_api).unwrap();
+        lines.push(b"{\"id\": 1}\n{\"id").unwrap();
+        let err = lines.push(b"\": \"two\"}\n").unwrap_err();
+        assert!(err.to_string().contains("must be an Integer"), "{err}");
+
+        let mut lines = JsonLines::new("/events", "post", ndjson, &open_api).unwrap();
+        lines.push(b"{\"id\": 1}\n{").unwrap();
+        assert!(lines.finish().is_err());
+
+        let mut limit = BodyLimit::new(&open_api);
+        assert!(limit.check_declared(Some("16")).is_ok());
+        let err = limit.check_declared(Some("17")).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PayloadTooLarge>(),
+            Some(&PayloadTooLarge { limit: 16 })
+        );
+        assert!(limit.take(10).is_ok());
+        assert!(limit.take(6).is_ok());
+        assert!(limit.take(1).unwrap_err().is::<PayloadTooLarge>());
+
+        let unlimited = OpenAPI::yaml(content).unwrap();
+        assert!(BodyLimit::new(&unlimited).take(usize::MAX).is_ok());
+    }
+
+    #[cfg(feature = "jsonschema")]
+    #[test]
+    fn test_json_schema_backend() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Shipping API
+  version: 1.0.0
+paths:
+  /shipments:
+    post:
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Shipment'
+components:
+  schemas:
+    Shipment:
+      type: object
+      required: [mode]
+      properties:
+        mode:
+          enum: [air, sea]
+        flight:
+          type: string
+        parcels:
+          type: array
+          items:
+            type: integer
+          contains:
+            minimum: 10
+      dependentRequired:
+        flight: [mode]
+      if:
+        properties:
+          mode:
+            const: air
+      then:
+        required: [flight]
+"#;
+        let native = OpenAPI::yaml(content).unwrap();
+        let open_api = OpenAPI::yaml(content)
+            .unwrap()
+            .with_options(ValidationOptions::new().with_json_schema(true));
+        let post = |open_api: &OpenAPI, fields| body("/shipments", "post", fields, open_api);
+
+        assert!(post(&open_api, json!({"mode": "air", "flight": "LH400"})).is_ok());
+        assert!(post(&open_api, json!({"mode": "sea", "parcels": [1, 12]})).is_ok());
+
+        let error = post(&open_api, json!({"mode": "air"})).unwrap_err();
+        assert!(error.to_string().contains("at '/'"), "{error}");
+        assert!(error.to_string().contains("flight"), "{error}");
+        let error = post(&open_api, json!({"mode": "sea", "parcels": [1, 2]})).unwrap_err();
+        assert!(error.to_string().contains("at '/parcels'"), "{error}");
+        assert!(post(&open_api, json!({"mode": "bus"})).is_err());
+        assert!(post(&open_api, json!(null)).is_err());
+
+        // Keywords the native checks do not cover pass without the backend
+        assert!(post(&native, json!({"mode": "air"})).is_ok());
+
+        let raw = RawValue::from_string(r#"[{"mode": "sea"}]"#.to_string()).unwrap();
+        assert!(raw_body("/shipments", "post", &raw, &open_api).is_err());
+    }
+}
@@ -0,0 +1,331 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Validation plans compiled once per document, so that per-request
+//! validation does not re-resolve references, recompile regexes or scan
+//! every path of the document.
+
+#[cfg(feature = "jsonschema")]
+use crate::model::parse::PathBase;
+use crate::model::parse::{ComponentsObject, In, OpenAPI, Parameter, Properties};
+use crate::model::router::PathRouter;
+use crate::model::visit::{walk, SchemaNode, Visitor};
+use indexmap::IndexMap;
+use regex::Regex;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+#[cfg(feature = "jsonschema")]
+use std::sync::OnceLock;
+
+thread_local! {
+    /// Regexes of the document being validated on this thread, see
+    /// [`with_patterns`]
+    static PATTERNS: RefCell<Option<Arc<HashMap<String, Regex>>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with the precompiled regexes of `open_api` at hand for
+/// [`pattern`], which the validators reach without the document.
+pub(crate) fn with_patterns<R>(open_api: &OpenAPI, f: impl FnOnce() -> R) -> R {
+    /// Restores the regexes of an enclosing validation, even on panics
+    struct Restore(Option<Arc<HashMap<String, Regex>>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            PATTERNS.with(|patterns| *patterns.borrow_mut() = self.0.take());
+        }
+    }
+
+    let patterns = Arc::clone(&open_api.compiled().patterns);
+    let _restore = Restore(PATTERNS.with(|current| current.replace(Some(patterns))));
+    f()
+}
+
+/// Returns the compiled form of `pattern`, precompiled with the document
+/// being validated or else compiled on the spot.
+pub(crate) fn pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    let precompiled = PATTERNS.with(|patterns| patterns.borrow().as_ref()?.get(pattern).cloned());
+    match precompiled {
+        Some(regex) => Ok(regex),
+        None => Regex::new(pattern),
+    }
+}
+
+/// Compiles the schema of the operation's JSON request body for the
+/// `jsonschema` crate, as draft 2020-12 with formats asserted. The document's
+/// `components` are set alongside so that `#/components/...` references
+/// resolve; schemas the crate rejects are left to the native checks.
+#[cfg(feature = "jsonschema")]
+fn compile_body_schema(
+    path: &str,
+    method: &str,
+    operation: &PathBase,
+    open_api: &OpenAPI,
+) -> Option<Arc<jsonschema::Validator>> {
+    let content = super::json_content(operation.request.as_ref()?).next()?;
+    let mut schema = serde_json::to_value(&content.schema).ok()?;
+    if let serde_json::Value::Object(schema) = &mut schema {
+        let components = serde_json::to_value(&open_api.components).unwrap_or_default();
+        schema.insert("components".to_string(), components);
+    }
+
+    let compiled = jsonschema::options()
+        .with_draft(jsonschema::Draft::Draft202012)
+        .should_validate_formats(true)
+        .build(&schema);
+    match compiled {
+        Ok(validator) => Some(Arc::new(validator)),
+        Err(e) => {
+            log::warn!("Request body schema of '{method} {path}' left to native validation: {e}");
+            None
+        }
+    }
+}
+
+/// Everything request validation needs to know about one operation.
+#[derive(Debug, Clone)]
+pub struct CompiledOperation {
+    pub path: String,
+    pub method: String,
+    pub deprecated: bool,
+    /// Path-level parameters overridden by the operation's own ones sharing
+    /// their name and location, with `#/components/parameters` references
+    /// resolved
+    pub parameters: Vec<Parameter>,
+    /// Location and name of every required parameter
+    pub required: HashSet<(In, String)>,
+    /// The JSON request body schema compiled for the `jsonschema` crate on
+    /// first use, see [`CompiledOperation::body_schema`]
+    #[cfg(feature = "jsonschema")]
+    body_schema: OnceLock<Option<Arc<jsonschema::Validator>>>,
+}
+
+#[cfg(feature = "jsonschema")]
+impl CompiledOperation {
+    /// The schema of the operation's JSON request body compiled for the
+    /// `jsonschema` crate, which validates bodies in place of the native
+    /// checks with [`ValidationOptions::json_schema`]
+    ///
+    /// [`ValidationOptions::json_schema`]: crate::validator::ValidationOptions::json_schema
+    pub fn body_schema(&self, open_api: &OpenAPI) -> Option<&jsonschema::Validator> {
+        self.body_schema
+            .get_or_init(|| {
+                let (_, operation) = open_api
+                    .paths
+                    .get(&self.path)?
+                    .all_operations()
+                    .find(|(method, _)| method.eq_ignore_ascii_case(&self.method))?;
+                compile_body_schema(&self.path, &self.method, operation, open_api)
+            })
+            .as_deref()
+    }
+}
+
+/// A component schema as request and response bodies referencing it are
+/// checked, with `$ref` aliases to other components followed.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedSchema {
+    /// The `properties` of the schema and of its `items`
+    pub properties: Vec<IndexMap<String, Properties>>,
+    /// Names required by the schema or its `items`
+    pub required: Vec<String>,
+}
+
+/// The validation plan of a document, see [`OpenAPI::compiled`].
+#[derive(Debug, Default)]
+pub struct CompiledSpec {
+    operations: HashMap<(String, String), CompiledOperation>,
+    /// Every component schema resolved, keyed by its
+    /// `#/components/schemas/...` reference
+    schemas: HashMap<String, ResolvedSchema>,
+    router: PathRouter,
+    /// Every valid `pattern` of the document, compiled
+    patterns: Arc<HashMap<String, Regex>>,
+}
+
+impl CompiledSpec {
+    /// Resolves and merges the parameters of every operation, resolves
+    /// every component schema and precompiles every `pattern` of the
+    /// document
+    pub fn build(open_api: &OpenAPI) -> Self {
+        let mut spec = CompiledSpec {
+            router: PathRouter::new(open_api.paths.keys().map(String::as_str)),
+            ..Default::default()
+        };
+
+        for (path, path_item) in &open_api.paths {
+            let resolve = |parameter: &Parameter| resolve_parameter(open_api, parameter).clone();
+
+            for (method, operation) in path_item.all_operations() {
+                let mut parameters: Vec<Parameter> = vec![];
+                for parameter in path_item
+                    .parameters
+                    .iter()
+                    .flatten()
+                    .chain(operation.parameters.iter().flatten())
+                    .map(resolve)
+                {
+                    parameters.retain(|existing| {
+                        existing.r#ref.is_some()
+                            || existing.name != parameter.name
+                            || existing.r#in != parameter.r#in
+                    });
+                    parameters.push(parameter);
+                }
+
+                let required = parameters
+                    .iter()
+                    .filter(|parameter| parameter.required)
+                    .filter_map(|parameter| {
+                        Some((parameter.r#in.clone()?, parameter.name.clone()?))
+                    })
+                    .collect();
+
+                let method = method.to_ascii_lowercase();
+                let compiled = CompiledOperation {
+                    path: path.clone(),
+                    method: method.clone(),
+                    deprecated: operation.deprecated,
+                    parameters,
+                    required,
+                    #[cfg(feature = "jsonschema")]
+                    body_schema: OnceLock::new(),
+                };
+                spec.operations.insert((path.clone(), method), compiled);
+            }
+        }
+
+        if let Some(components) = &open_api.components {
+            for name in components.schemas.keys() {
+                let schema_ref = format!("{SCHEMAS}{name}");
+                if let Some(resolved) = resolve_schema(&schema_ref, components) {
+                    spec.schemas.insert(schema_ref, resolved);
+                }
+            }
+        }
+
+        let mut compiler = PatternCompiler::default();
+        walk(open_api, &mut compiler);
+        spec.patterns = Arc::new(compiler.0);
+        spec
+    }
+
+    /// The plan of the `method` operation on the document path `path`
+    pub fn operation(&self, path: &str, method: &str) -> Option<&CompiledOperation> {
+        self.operations
+            .get(&(path.to_string(), method.to_ascii_lowercase()))
+    }
+
+    /// Router from concrete request paths to the document's templated paths
+    pub fn router(&self) -> &PathRouter {
+        &self.router
+    }
+
+    /// The component schema `schema_ref` points to; references outside
+    /// `#/components/schemas/` are looked up by their last segment
+    pub fn schema(&self, schema_ref: &str) -> Option<&ResolvedSchema> {
+        self.schemas.get(schema_ref).or_else(|| {
+            let name = schema_ref.rsplit('/').next()?;
+            self.schemas.get(&format!("{SCHEMAS}{name}"))
+        })
+    }
+}
+
+const SCHEMAS: &str = "#/components/schemas/";
+
+/// Resolves `schema_ref` through the chain of components aliasing one
+/// another, stopping at a cycle
+fn resolve_schema(schema_ref: &str, components: &ComponentsObject) -> Option<ResolvedSchema> {
+    let mut visited = HashSet::new();
+    let mut schema_ref = schema_ref;
+    let schema = loop {
+        if !visited.insert(schema_ref) {
+            return None;
+        }
+        let schema = components.schemas.get(schema_ref.rsplit('/').next()?)?;
+        match &schema.r#ref {
+            Some(next) => schema_ref = next,
+            None => break schema,
+        }
+    };
+
+    let mut required = schema.required.clone();
+    for name in schema.items.iter().flat_map(|items| &items.required) {
+        if !required.contains(name) {
+            required.push(name.clone());
+        }
+    }
+    let properties = schema
+        .properties
+        .iter()
+        .chain(
+            schema
+                .items
+                .iter()
+                .filter_map(|items| items.properties.as_ref()),
+        )
+        .cloned()
+        .collect();
+    Some(ResolvedSchema {
+        properties,
+        required,
+    })
+}
+
+fn resolve_parameter<'a>(open_api: &'a OpenAPI, parameter: &'a Parameter) -> &'a Parameter {
+    parameter
+        .r#ref
+        .as_deref()
+        .and_then(|reference| reference.strip_prefix("#/components/parameters/"))
+        .and_then(|name| open_api.components.as_ref()?.parameters.get(name))
+        .unwrap_or(parameter)
+}
+
+/// Compiles the patterns of the document. Invalid patterns are left for
+/// validation to report.
+#[derive(Default)]
+struct PatternCompiler(HashMap<String, Regex>);
+
+impl PatternCompiler {
+    fn compile(&mut self, pattern: &str) {
+        if !self.0.contains_key(pattern) {
+            if let Ok(regex) = Regex::new(pattern) {
+                self.0.insert(pattern.to_string(), regex);
+            }
+        }
+    }
+}
+
+impl Visitor for PatternCompiler {
+    fn visit_parameter(&mut self, _: &str, parameter: &Parameter) {
+        if let Some(regex) = &parameter.pattern {
+            self.compile(regex);
+        }
+    }
+
+    fn visit_schema(&mut self, _: &str, node: SchemaNode<'_>) {
+        let regex = match node {
+            SchemaNode::Schema(schema) => schema.pattern.as_ref(),
+            SchemaNode::Component(schema) => schema.pattern.as_ref(),
+            SchemaNode::Property(property) => property.pattern.as_ref(),
+            SchemaNode::Composed(_) => None,
+        };
+        if let Some(regex) = regex {
+            self.compile(regex);
+        }
+    }
+}
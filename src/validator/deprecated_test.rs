@@ -0,0 +1,74 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::model::parse::OpenAPI;
+    use crate::validator::{method, query, ValidationOptions};
+    use std::collections::HashMap;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Legacy API
+  version: 1.0.0
+paths:
+  /legacy:
+    get:
+      deprecated: true
+      parameters:
+        - name: page
+          in: query
+          deprecated: true
+          schema:
+            type: integer
+        - name: limit
+          in: query
+          schema:
+            type: integer
+"#;
+
+    #[test]
+    fn test_deprecated_is_allowed_by_default() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+
+        let operation = &open_api.paths["/legacy"].operations["get"];
+        assert!(operation.deprecated);
+        assert!(operation.parameters.as_ref().unwrap()[0].deprecated);
+
+        assert!(method("/legacy", "get", &open_api).is_ok());
+
+        let pairs = HashMap::from([("page".to_string(), vec!["1".to_string()])]);
+        assert!(query("/legacy", "get", &pairs, &open_api).is_ok());
+    }
+
+    #[test]
+    fn test_deprecated_rejected_when_configured() {
+        let open_api = OpenAPI::yaml(SPEC)
+            .unwrap()
+            .with_options(ValidationOptions::new().with_reject_deprecated(true));
+
+        let err = method("/legacy", "get", &open_api).unwrap_err();
+        assert!(err.to_string().contains("deprecated"));
+
+        let pairs = HashMap::from([("page".to_string(), vec!["1".to_string()])]);
+        assert!(query("/legacy", "get", &pairs, &open_api).is_err());
+
+        let pairs = HashMap::from([("limit".to_string(), vec!["10".to_string()])]);
+        assert!(query("/legacy", "get", &pairs, &open_api).is_ok());
+    }
+}
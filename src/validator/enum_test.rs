@@ -32,19 +32,19 @@ components: {}
         let open_api: OpenAPI = serde_yaml::from_str(yaml_content).unwrap();
 
         let mut valid_query = HashMap::new();
-        valid_query.insert("status".to_string(), "active".to_string());
-        valid_query.insert("priority".to_string(), "2".to_string());
+        valid_query.insert("status".to_string(), vec!["active".to_string()]);
+        valid_query.insert("priority".to_string(), vec!["2".to_string()]);
 
-        let result = query("/test", &valid_query, &open_api);
+        let result = query("/test", "get", &valid_query, &open_api);
         if let Err(ref e) = result {
             println!("Error message: {}", e);
         }
         assert!(result.is_ok(), "Valid enum values should pass validation");
 
         let mut invalid_query = HashMap::new();
-        invalid_query.insert("status".to_string(), "unknown".to_string());
+        invalid_query.insert("status".to_string(), vec!["unknown".to_string()]);
 
-        let result = query("/test", &invalid_query, &open_api);
+        let result = query("/test", "get", &invalid_query, &open_api);
         assert!(result.is_err(), "Invalid enum values should be rejected");
 
         let error_msg = result.unwrap_err().to_string();
@@ -85,18 +85,18 @@ components: {}
         let open_api: OpenAPI = serde_yaml::from_str(yaml_content).unwrap();
 
         let mut query_params = HashMap::new();
-        query_params.insert("active".to_string(), "true".to_string());
+        query_params.insert("active".to_string(), vec!["true".to_string()]);
 
-        let result = query("/test", &query_params, &open_api);
+        let result = query("/test", "get", &query_params, &open_api);
         assert!(
             result.is_ok(),
             "Valid boolean enum values should pass validation"
         );
 
         let mut invalid_query = HashMap::new();
-        invalid_query.insert("active".to_string(), "maybe".to_string());
+        invalid_query.insert("active".to_string(), vec!["maybe".to_string()]);
 
-        let result = query("/test", &invalid_query, &open_api);
+        let result = query("/test", "get", &invalid_query, &open_api);
         assert!(
             result.is_err(),
             "Invalid boolean enum values should be rejected"
@@ -141,7 +141,7 @@ components:
             "priority": 3
         });
 
-        let result = body("/test", valid_body, &open_api);
+        let result = body("/test", "post", valid_body, &open_api);
         assert!(
             result.is_ok(),
             "Valid request body enum values should pass validation"
@@ -152,7 +152,7 @@ components:
             "priority": 3
         });
 
-        let result = body("/test", invalid_body, &open_api);
+        let result = body("/test", "post", invalid_body, &open_api);
         assert!(
             result.is_err(),
             "Invalid request body enum values should be rejected"
@@ -0,0 +1,232 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::model::parse::Format;
+    use crate::validator::validate_field_format;
+    use serde_json::{json, Value};
+
+    struct Tests {
+        f: Format,
+        value: Value,
+        assert: bool,
+    }
+
+    fn check(tests: Vec<Tests>) {
+        for test in tests {
+            assert_eq!(
+                validate_field_format("field", &test.value, Some(&test.f)).is_ok(),
+                test.assert,
+                "{:?} {}",
+                test.f,
+                test.value
+            );
+        }
+    }
+
+    #[test]
+    fn integer_width_formats() {
+        check(vec![
+            Tests {
+                f: Format::Int32,
+                value: json!(2147483647),
+                assert: true,
+            },
+            Tests {
+                f: Format::Int32,
+                value: json!(-2147483648),
+                assert: true,
+            },
+            Tests {
+                f: Format::Int32,
+                value: json!(2147483648_i64),
+                assert: false,
+            },
+            Tests {
+                f: Format::Int32,
+                value: json!("-2147483649"),
+                assert: false,
+            },
+            Tests {
+                f: Format::Int32,
+                value: json!("42"),
+                assert: true,
+            },
+            Tests {
+                f: Format::Int32,
+                value: json!(1.5),
+                assert: false,
+            },
+            Tests {
+                f: Format::Int64,
+                value: json!(i64::MAX),
+                assert: true,
+            },
+            Tests {
+                f: Format::Int64,
+                value: json!(u64::MAX),
+                assert: false,
+            },
+            Tests {
+                f: Format::Int64,
+                value: json!("9223372036854775808"),
+                assert: false,
+            },
+            Tests {
+                f: Format::Int64,
+                value: json!("abc"),
+                assert: false,
+            },
+        ]);
+    }
+
+    #[test]
+    fn base64_formats() {
+        check(vec![
+            Tests {
+                f: Format::Byte,
+                value: json!("aGVsbG8gd29ybGQ="),
+                assert: true,
+            },
+            Tests {
+                f: Format::Byte,
+                value: json!("aGVsbG8gd29ybGQ"),
+                assert: false,
+            },
+            Tests {
+                f: Format::Byte,
+                value: json!("-_8="),
+                assert: false,
+            },
+            Tests {
+                f: Format::Base64Url,
+                value: json!("-_8"),
+                assert: true,
+            },
+            Tests {
+                f: Format::Base64Url,
+                value: json!("-_8="),
+                assert: true,
+            },
+            Tests {
+                f: Format::Base64Url,
+                value: json!("+/8="),
+                assert: false,
+            },
+            Tests {
+                f: Format::Byte,
+                value: json!(42),
+                assert: false,
+            },
+        ]);
+    }
+
+    #[test]
+    fn duration_format() {
+        let cases = [
+            ("P3DT4H", true),
+            ("P1Y2M10DT2H30M", true),
+            ("PT0.5S", true),
+            ("PT1,5H", true),
+            ("P2W", true),
+            ("PT36H", true),
+            ("P", false),
+            ("PT", false),
+            ("P1DT", false),
+            ("3D", false),
+            ("P1H", false),
+            ("PT1D", false),
+            ("P1D2Y", false),
+            ("P1.D", false),
+            ("PD", false),
+        ];
+
+        check(
+            cases
+                .into_iter()
+                .map(|(value, assert)| Tests {
+                    f: Format::Duration,
+                    value: json!(value),
+                    assert,
+                })
+                .collect(),
+        );
+    }
+
+    #[test]
+    fn hostname_format() {
+        let long_label = "a".repeat(64);
+        let long_host = vec!["abcdefghi"; 26].join(".");
+
+        let cases = [
+            ("example.com", true),
+            ("localhost", true),
+            ("api-v2.example.co.uk", true),
+            ("1password.com", true),
+            ("-example.com", false),
+            ("example-.com", false),
+            ("exa_mple.com", false),
+            ("example..com", false),
+            ("example.com.", false),
+            ("", false),
+            (long_label.as_str(), false),
+            (long_host.as_str(), false),
+        ];
+
+        check(
+            cases
+                .into_iter()
+                .map(|(value, assert)| Tests {
+                    f: Format::Hostname,
+                    value: json!(value),
+                    assert,
+                })
+                .collect(),
+        );
+    }
+
+    #[test]
+    fn uri_formats() {
+        let cases = [
+            (Format::URI, "https://example.com/a?b=c#d", true),
+            (Format::URI, "urn:isbn:0451450523", true),
+            (Format::URI, "mailto:a@example.com", true),
+            (Format::URI, "/relative/path", false),
+            (Format::URI, "example.com", false),
+            (Format::URI, "https://exa mple.com", false),
+            (Format::URIReference, "/relative/path?x=1", true),
+            (Format::URIReference, "../up", true),
+            (Format::URIReference, "#fragment", true),
+            (Format::URIReference, "https://example.com", true),
+            (Format::URIReference, "\\\\server\\share", false),
+            (Format::URIReference, "has space", false),
+            (Format::URIReference, "http://[::1", false),
+        ];
+
+        check(
+            cases
+                .into_iter()
+                .map(|(f, value, assert)| Tests {
+                    f,
+                    value: json!(value),
+                    assert,
+                })
+                .collect(),
+        );
+    }
+}
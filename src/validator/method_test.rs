@@ -0,0 +1,170 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::model::parse::OpenAPI;
+    use crate::validator::{body, method, query, MethodNotAllowed, ValidationOptions};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_undefined_method_lists_allowed_methods() {
+        let content = r#"
+openapi: 3.2.0
+info:
+  title: Search API
+  version: 1.0.0
+paths:
+  /search:
+    post:
+      responses:
+        '200':
+          description: OK
+    get:
+      responses:
+        '200':
+          description: OK
+    query:
+      responses:
+        '200':
+          description: OK
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(method("/search", "get", &open_api).is_ok());
+        assert!(method("/search", "query", &open_api).is_ok());
+
+        let err = method("/search", "delete", &open_api).unwrap_err();
+        let not_allowed = err.downcast_ref::<MethodNotAllowed>().unwrap();
+        assert_eq!(not_allowed.method, "delete");
+        assert_eq!(not_allowed.allowed, ["GET", "POST", "QUERY"]);
+        assert_eq!(not_allowed.allow_header(), "GET, POST, QUERY");
+        assert!(err.to_string().contains("not found for path '/search'"));
+
+        let err = method("/missing", "get", &open_api).unwrap_err();
+        assert!(err.downcast_ref::<MethodNotAllowed>().is_none());
+    }
+
+    #[test]
+    fn test_additional_operations() {
+        let content = r#"
+openapi: 3.2.0
+info:
+  title: WebDAV API
+  version: 1.0.0
+paths:
+  /files:
+    get:
+      responses:
+        '200':
+          description: OK
+    additionalOperations:
+      COPY:
+        parameters:
+          - name: depth
+            in: query
+            schema:
+              type: integer
+        requestBody:
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [destination]
+                properties:
+                  destination:
+                    type: string
+        responses:
+          '201':
+            description: Created
+      LOCK:
+        responses:
+          '200':
+            description: OK
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(method("/files", "COPY", &open_api).is_ok());
+        assert!(method("/files", "copy", &open_api).is_ok());
+        assert!(method("/files", "lock", &open_api).is_ok());
+
+        let err = method("/files", "move", &open_api).unwrap_err();
+        let not_allowed = err.downcast_ref::<MethodNotAllowed>().unwrap();
+        assert_eq!(not_allowed.allowed, ["GET", "COPY", "LOCK"]);
+
+        let query_pairs = HashMap::from([("depth".to_string(), vec!["1".to_string()])]);
+        assert!(query("/files", "copy", &query_pairs, &open_api).is_ok());
+        let query_pairs = HashMap::from([("depth".to_string(), vec!["deep".to_string()])]);
+        assert!(query("/files", "copy", &query_pairs, &open_api).is_err());
+
+        assert!(body("/files", "copy", json!({"destination": "/b"}), &open_api).is_ok());
+        assert!(body("/files", "copy", json!({}), &open_api).is_err());
+        assert!(body("/files", "lock", json!({}), &open_api).is_err());
+    }
+
+    #[test]
+    fn test_server_base_path_stripping() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users API
+  version: 1.0.0
+servers:
+  - url: https://{region}.example.com/{version}
+  - url: https://api.example.com/v2/
+  - url: /internal
+paths:
+  /:
+    get:
+      responses:
+        '200':
+          description: OK
+  /users:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+      responses:
+        '201':
+          description: Created
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        let servers: Vec<Option<&str>> = open_api
+            .servers
+            .iter()
+            .map(|server| server.base_path())
+            .collect();
+        assert_eq!(servers, [None, Some("/v2"), Some("/internal")]);
+
+        assert!(method("/v2/users", "post", &open_api).is_err());
+
+        let open_api = open_api.with_options(ValidationOptions::new().with_strip_base_path(true));
+        assert!(method("/users", "post", &open_api).is_ok());
+        assert!(method("/v2/users", "post", &open_api).is_ok());
+        assert!(method("/internal/users", "post", &open_api).is_ok());
+        assert!(method("/v2", "get", &open_api).is_ok());
+        assert!(method("/v2users", "post", &open_api).is_err());
+        assert!(method("/v3/users", "post", &open_api).is_err());
+
+        assert!(body("/v2/users", "post", json!({"name": "Ada"}), &open_api).is_ok());
+        assert!(body("/v2/users", "post", json!({}), &open_api).is_err());
+    }
+}
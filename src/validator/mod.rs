@@ -15,54 +15,588 @@
  * limitations under the License.
  */
 
+mod body_test;
+pub mod compiled;
+mod deprecated_test;
 mod enum_test;
+mod format_test;
+mod method_test;
+mod multipart_test;
 mod pattern_test;
+mod query_test;
+pub mod redact;
+mod redact_test;
+pub mod report;
+mod report_test;
+mod response_test;
+mod security_test;
+pub mod stream;
+mod tracing_test;
 mod validator_test;
 
 use crate::model::parse;
 use crate::model::parse::{
-    ComponentsObject, Format, In, OpenAPI, Properties, Request, Type, TypeOrUnion,
+    BaseContent, ComponentsObject, Format, In, OpenAPI, PathBase, Properties, Request,
+    SecurityScheme, SecuritySchemeType, Style, Type, TypeOrUnion,
 };
-use crate::observability::RequestContext;
+use crate::model::visit::SchemaNode;
+use crate::observability::{self, RequestContext};
+use crate::request::multipart::Part;
 use anyhow::{anyhow, Context, Result};
 use base64::{engine::general_purpose, Engine};
 use chrono::{DateTime, NaiveDate, NaiveTime};
-use regex::Regex;
+use indexmap::IndexMap;
+use redact::REDACTED;
+use serde_json::value::RawValue;
 use serde_json::{Map, Value};
+use std::cell::OnceCell;
 use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
 use std::net::{Ipv4Addr, Ipv6Addr};
 use std::string::String;
+use url::Url;
 use validator::ValidateEmail;
 
 pub trait ValidateRequest {
     fn header(&self, _: &OpenAPI) -> Result<()>;
     fn method(&self, _: &OpenAPI) -> Result<()>;
+    fn security(&self, _: &OpenAPI) -> Result<()>;
     fn query(&self, _: &OpenAPI) -> Result<()>;
     fn path(&self, _: &OpenAPI) -> Result<()>;
     fn body(&self, _: &OpenAPI) -> Result<()>;
     fn context(&self) -> RequestContext;
+
+    /// Values of the request to mask in errors, usually collected with
+    /// [`redact::secrets`]
+    fn secrets(&self, _: &ValidationOptions) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Options controlling how strictly requests are validated
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    /// Reject requests using deprecated operations or parameters instead of only logging them
+    pub reject_deprecated: bool,
+    /// Reject input that is tolerated by default, such as unencoded reserved
+    /// characters in query values of parameters without `allowReserved`
+    pub strict: bool,
+    /// Resolve request paths that carry a server's base path, such as `/v2`
+    /// of `https://api.example.com/v2`, against the paths declared without it
+    pub strip_base_path: bool,
+    /// Largest request body, in bytes, the framework adapters read before
+    /// rejecting the request with [`PayloadTooLarge`]
+    pub max_body_size: Option<usize>,
+    /// Hand query values to extractors such as `ValidatedQuery` as received
+    /// instead of converting them to the types the document declares
+    pub query_as_strings: bool,
+    /// Query parameters and JSON body fields, as case-insensitive patterns
+    /// whose `*` match any characters, whose values are masked in errors
+    pub redacted_fields: Vec<String>,
+    /// Headers masked in errors on top of [`redact::SENSITIVE_HEADERS`]
+    pub redacted_headers: Vec<String>,
+    /// Validate JSON request bodies with the `jsonschema` crate, which
+    /// covers all of JSON Schema draft 2020-12, instead of the native
+    /// checks. OpenAPI rules beyond JSON Schema, such as rejecting
+    /// `readOnly` properties in requests, are not applied then.
+    #[cfg(feature = "jsonschema")]
+    pub json_schema: bool,
+}
+
+impl ValidationOptions {
+    /// Create new validation options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable/disable rejection of deprecated operations and parameters
+    pub fn with_reject_deprecated(mut self, enabled: bool) -> Self {
+        self.reject_deprecated = enabled;
+        self
+    }
+
+    /// Enable/disable strict validation
+    pub fn with_strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Enable/disable stripping of server base paths during path matching
+    pub fn with_strip_base_path(mut self, enabled: bool) -> Self {
+        self.strip_base_path = enabled;
+        self
+    }
+
+    /// Limit the size of request bodies read by the framework adapters
+    pub fn with_max_body_size(mut self, limit: usize) -> Self {
+        self.max_body_size = Some(limit);
+        self
+    }
+
+    /// Enable/disable handing query values to extractors as strings
+    pub fn with_query_as_strings(mut self, enabled: bool) -> Self {
+        self.query_as_strings = enabled;
+        self
+    }
+
+    /// Mask the values of fields matching `pattern`, such as `*token*`, in errors
+    pub fn with_redacted_field(mut self, pattern: impl Into<String>) -> Self {
+        self.redacted_fields.push(pattern.into());
+        self
+    }
+
+    /// Mask the values of the header `name` in errors
+    pub fn with_redacted_header(mut self, name: impl Into<String>) -> Self {
+        self.redacted_headers.push(name.into());
+        self
+    }
+
+    /// Enable/disable validation of JSON request bodies by the `jsonschema` crate
+    #[cfg(feature = "jsonschema")]
+    pub fn with_json_schema(mut self, enabled: bool) -> Self {
+        self.json_schema = enabled;
+        self
+    }
+}
+
+/// Parses a raw query string into decoded key/value pairs, applying
+/// percent-decoding and treating `+` as a space. Repeated keys keep every
+/// value in the order they appear.
+pub fn parse_query(query_string: &str) -> HashMap<String, Vec<String>> {
+    let mut pairs: HashMap<String, Vec<String>> = HashMap::new();
+    for (key, value) in url::form_urlencoded::parse(query_string.as_bytes()).into_owned() {
+        pairs.entry(key).or_default().push(value);
+    }
+    pairs
+}
+
+/// Returned by [`method`] when the path exists but does not define the
+/// requested method. Recover it with `anyhow::Error::downcast_ref` to answer
+/// with `405 Method Not Allowed` and an `Allow` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodNotAllowed {
+    pub method: String,
+    pub path: String,
+    /// Methods defined for the path, upper-cased
+    pub allowed: Vec<String>,
+}
+
+impl MethodNotAllowed {
+    /// Value for the `Allow` response header
+    pub fn allow_header(&self) -> String {
+        self.allowed.join(", ")
+    }
+}
+
+impl std::fmt::Display for MethodNotAllowed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Method '{}' not found for path '{}', allowed: [{}]",
+            self.method,
+            self.path,
+            self.allow_header()
+        )
+    }
+}
+
+impl std::error::Error for MethodNotAllowed {}
+
+/// Returned by [`security`] when the request does not carry the credentials
+/// its security requirements ask for. Recover it with
+/// `anyhow::Error::downcast_ref` to answer with `401 Unauthorized`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unauthorized {
+    pub method: String,
+    pub path: String,
+    /// Why each alternative security requirement was not satisfied, prefixed
+    /// with the schemes it combines, e.g. `api_key + oauth: ...`
+    pub failures: Vec<String>,
+}
+
+impl std::fmt::Display for Unauthorized {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Authentication failed for '{} {}': {}",
+            self.method,
+            self.path,
+            self.failures.join("; ")
+        )
+    }
+}
+
+impl std::error::Error for Unauthorized {}
+
+/// Returned while reading a request body that exceeds
+/// [`ValidationOptions::max_body_size`]. Recover it with
+/// `anyhow::Error::downcast_ref` to answer with `413 Payload Too Large`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadTooLarge {
+    pub limit: usize,
+}
+
+impl std::fmt::Display for PayloadTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Request body exceeds the limit of {} bytes", self.limit)
+    }
+}
+
+impl std::error::Error for PayloadTooLarge {}
+
+/// Checks that the request carries the credentials required by the
+/// operation's `security` requirements, or the document-level ones when the
+/// operation declares none. An operation-level `security: []` disables
+/// authentication for that operation.
+///
+/// The request must satisfy at least one requirement object, and every scheme
+/// within it. An empty requirement object makes authentication optional.
+/// `headers` are keyed by lower-cased header name.
+pub fn security(
+    path: &str,
+    method: &str,
+    headers: &HashMap<String, String>,
+    query_pairs: &HashMap<String, Vec<String>>,
+    open_api: &OpenAPI,
+) -> Result<()> {
+    let operation = open_api
+        .path_item(path)
+        .and_then(|path_item| find_operation(path_item, method));
+
+    let requirements = match operation.and_then(|operation| operation.security.as_ref()) {
+        Some(requirements) => requirements,
+        None => match &open_api.security {
+            Some(requirements) => requirements,
+            None => return Ok(()),
+        },
+    };
+    if requirements.is_empty() {
+        return Ok(());
+    }
+
+    let credentials = Credentials {
+        headers,
+        query_pairs,
+    };
+
+    let mut failures = Vec::new();
+
+    for requirement in requirements {
+        let mut scheme_names: Vec<&String> = requirement.keys().collect();
+        scheme_names.sort();
+
+        let mut failure = None;
+        for scheme_name in &scheme_names {
+            let scheme = open_api
+                .components
+                .as_ref()
+                .and_then(|components| components.security_schemes.get(*scheme_name))
+                .with_context(|| format!("Security scheme '{}' is not defined", scheme_name))?;
+
+            if let Err(reason) = credentials.check(scheme_name, scheme) {
+                failure = Some(reason);
+                break;
+            }
+        }
+
+        match failure {
+            None => return Ok(()),
+            Some(reason) => {
+                let schemes: Vec<&str> = scheme_names.iter().map(|name| name.as_str()).collect();
+                failures.push(format!("{}: {}", schemes.join(" + "), reason));
+            }
+        }
+    }
+
+    Err(Unauthorized {
+        method: method.to_string(),
+        path: path.to_string(),
+        failures,
+    }
+    .into())
+}
+
+/// Where a request may carry credentials
+struct Credentials<'a> {
+    headers: &'a HashMap<String, String>,
+    query_pairs: &'a HashMap<String, Vec<String>>,
+}
+
+impl Credentials<'_> {
+    /// Checks a single security scheme, returning why it is not satisfied.
+    /// Schemes whose credentials never reach the request, such as
+    /// `mutualTLS`, cannot be verified and are never satisfied.
+    fn check(&self, scheme_name: &str, scheme: &SecurityScheme) -> Result<(), String> {
+        match scheme.r#type {
+            SecuritySchemeType::ApiKey => self.check_api_key(scheme_name, scheme),
+            SecuritySchemeType::Http => match scheme.scheme.as_deref() {
+                Some(http) if http.eq_ignore_ascii_case("bearer") => self.check_bearer(scheme),
+                Some(http) if http.eq_ignore_ascii_case("basic") => self.check_basic(),
+                Some(http) => Err(format!("HTTP scheme '{http}' cannot be verified")),
+                None => Err("HTTP security scheme declares no 'scheme'".to_string()),
+            },
+            SecuritySchemeType::OAuth2 | SecuritySchemeType::OpenIdConnect => self
+                .authorization("Bearer")
+                .map(|_| ())
+                .ok_or_else(|| "Authorization header with a Bearer token is missing".to_string()),
+            SecuritySchemeType::MutualTLS => {
+                Err("mutualTLS client certificates cannot be verified".to_string())
+            }
+        }
+    }
+
+    fn check_bearer(&self, scheme: &SecurityScheme) -> Result<(), String> {
+        let token = self
+            .authorization("Bearer")
+            .ok_or("Authorization header with a Bearer token is missing")?;
+
+        let is_jwt = scheme
+            .bearer_format
+            .as_deref()
+            .is_some_and(|format| format.eq_ignore_ascii_case("JWT"));
+        if is_jwt && !is_structurally_valid_jwt(token) {
+            return Err("Bearer token is not a well-formed JWT".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn check_basic(&self) -> Result<(), String> {
+        let credentials = self
+            .authorization("Basic")
+            .ok_or("Authorization header with Basic credentials is missing")?;
+
+        let decoded = general_purpose::STANDARD
+            .decode(credentials)
+            .map_err(|_| "Basic credentials are not valid base64".to_string())?;
+        let decoded = String::from_utf8(decoded)
+            .map_err(|_| "Basic credentials are not valid UTF-8".to_string())?;
+
+        match decoded.split_once(':') {
+            Some((user, _)) if !user.is_empty() => Ok(()),
+            _ => Err("Basic credentials must have the form 'user:password'".to_string()),
+        }
+    }
+
+    /// Credentials of an `Authorization: <scheme> <credentials>` header
+    fn authorization(&self, auth_scheme: &str) -> Option<&str> {
+        let (scheme, credentials) = self.header("authorization")?.split_once(' ')?;
+        let credentials = credentials.trim();
+        (scheme.eq_ignore_ascii_case(auth_scheme) && !credentials.is_empty()).then_some(credentials)
+    }
+
+    fn check_api_key(&self, scheme_name: &str, scheme: &SecurityScheme) -> Result<(), String> {
+        let name = scheme
+            .name
+            .as_deref()
+            .ok_or_else(|| format!("apiKey scheme '{scheme_name}' does not declare a name"))?;
+
+        let (present, location) = match scheme.r#in {
+            Some(In::Header) => (self.header(name).is_some(), "header"),
+            Some(In::Query) => (
+                self.query_pairs
+                    .get(name)
+                    .is_some_and(|values| values.iter().any(|value| !value.is_empty())),
+                "query parameter",
+            ),
+            Some(In::Cookie) => (self.cookie(name).is_some(), "cookie"),
+            _ => {
+                return Err(format!(
+                    "apiKey scheme '{scheme_name}' must be in header, query or cookie"
+                ))
+            }
+        };
+
+        if present {
+            Ok(())
+        } else {
+            Err(format!("API key {location} '{name}' is missing"))
+        }
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn cookie(&self, name: &str) -> Option<&str> {
+        self.header("cookie")?
+            .split(';')
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Checks the compact JWS shape `header.payload.signature`: a header and
+/// payload that are base64url-encoded JSON objects and a base64url signature.
+/// The signature itself is not verified.
+fn is_structurally_valid_jwt(token: &str) -> bool {
+    let decode = |segment: &str| general_purpose::URL_SAFE_NO_PAD.decode(segment).ok();
+    let is_json_object = |segment: &str| {
+        decode(segment)
+            .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+            .is_some_and(|value| value.is_object())
+    };
+
+    match token.split('.').collect::<Vec<_>>()[..] {
+        [header, payload, signature] => {
+            is_json_object(header) && is_json_object(payload) && decode(signature).is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Looks up the operation for `method`, including the OpenAPI 3.2 QUERY method
+/// and the custom methods declared in `additionalOperations`.
+pub(crate) fn find_operation<'a>(
+    path_item: &'a parse::PathItem,
+    method: &str,
+) -> Option<&'a PathBase> {
+    match path_item.operations.get(method) {
+        Some(operation) => Some(operation),
+        None if method.eq_ignore_ascii_case("query") => path_item.query.as_ref(),
+        None => path_item
+            .additional_operations
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(method))
+            .map(|(_, operation)| operation),
+    }
+}
+
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+fn allowed_methods(path_item: &parse::PathItem) -> Vec<String> {
+    let mut additional: Vec<String> = path_item
+        .additional_operations
+        .keys()
+        .map(|method| method.to_uppercase())
+        .collect();
+    additional.sort();
+
+    HTTP_METHODS
+        .iter()
+        .filter(|method| path_item.operations.contains_key(**method))
+        .map(|method| method.to_uppercase())
+        .chain(path_item.query.as_ref().map(|_| "QUERY".to_string()))
+        .chain(additional)
+        .collect()
+}
+
+/// Reserved characters (RFC 3986) that `allowReserved: false` parameters must
+/// percent-encode. `+` is left out as it is the form encoding of a space.
+const RESERVED_CHARS: &[char] = &[
+    ':', '/', '?', '[', ']', '@', '!', '$', '\'', '(', ')', ',', ';', '=',
+];
+
+/// Parses a raw query string using the parameter definitions of the
+/// `method` operation of `path`.
+///
+/// Values of `allowReserved` parameters keep a literal `+` instead of decoding
+/// it to a space. In strict mode, other parameters are rejected when their
+/// raw value contains unencoded reserved characters.
+pub fn decode_query(
+    path: &str,
+    method: &str,
+    query_string: &str,
+    open_api: &OpenAPI,
+) -> Result<HashMap<String, Vec<String>>> {
+    let parameters: HashMap<&str, &parse::Parameter> = operation_parameters(path, method, open_api)
+        .iter()
+        .filter(|parameter| parameter.r#in == Some(In::Query))
+        .filter_map(|parameter| Some((parameter.name.as_deref()?, parameter)))
+        .collect();
+
+    let mut pairs: HashMap<String, Vec<String>> = HashMap::new();
+
+    for segment in query_string
+        .split('&')
+        .filter(|segment| !segment.is_empty())
+    {
+        let Some((key, value)) = url::form_urlencoded::parse(segment.as_bytes()).next() else {
+            continue;
+        };
+        let raw_value = segment.split_once('=').map_or("", |(_, value)| value);
+
+        let value = match parameters.get(key.as_ref()) {
+            Some(parameter) if parameter.allow_reserved => {
+                let escaped = raw_value.replace('+', "%2B");
+                url::form_urlencoded::parse(format!("v={escaped}").as_bytes())
+                    .next()
+                    .map(|(_, value)| value.into_owned())
+                    .unwrap_or_default()
+            }
+            Some(parameter) if open_api.options.strict => {
+                let delimiter = array_delimiter(parameter);
+                if let Some(reserved) = raw_value
+                    .chars()
+                    .find(|c| RESERVED_CHARS.contains(c) && Some(*c) != delimiter)
+                {
+                    return Err(anyhow!(
+                        "Query parameter '{}' contains unencoded reserved character '{}'",
+                        key,
+                        reserved
+                    ));
+                }
+                value.into_owned()
+            }
+            _ => value.into_owned(),
+        };
+
+        pairs.entry(key.into_owned()).or_default().push(value);
+    }
+
+    Ok(pairs)
+}
+
+/// The parameters of the `method` operation of the document path matching
+/// `path`, its own merged with the path-level ones; none without such an
+/// operation
+fn operation_parameters<'a>(
+    path: &str,
+    method: &str,
+    open_api: &'a OpenAPI,
+) -> &'a [parse::Parameter] {
+    open_api
+        .resolve_path(path)
+        .and_then(|(path, _)| open_api.compiled().operation(path, method))
+        .map(|operation| operation.parameters.as_slice())
+        .unwrap_or_default()
 }
 
 pub fn method(path: &str, method: &str, open_api: &OpenAPI) -> Result<()> {
-    let path_item = open_api.paths.get(path).context("Path not found")?;
+    let (spec_path, path_item) = open_api.resolve_path(path).context("Path not found")?;
 
-    // Check operations or QUERY method (OpenAPI 3.2)
-    let exists = path_item.operations.contains_key(method)
-        || (method.eq_ignore_ascii_case("query") && path_item.query.is_some());
+    let Some(operation) = find_operation(path_item, method) else {
+        return Err(MethodNotAllowed {
+            method: method.to_string(),
+            path: path.to_string(),
+            allowed: allowed_methods(path_item),
+        }
+        .into());
+    };
 
-    if !exists {
-        return Err(anyhow::anyhow!(
-            "Method '{}' not found for path '{}'",
-            method,
-            path
-        ));
+    if operation.deprecated {
+        observability::record_deprecated_operation(method, spec_path);
+        if open_api.options.reject_deprecated {
+            return Err(anyhow!(
+                "Method '{}' for path '{}' is deprecated",
+                method,
+                path
+            ));
+        }
     }
 
     Ok(())
 }
 
 pub fn path(path: &str, uri: &str, open_api: &OpenAPI) -> Result<()> {
-    let path_item = open_api.paths.get(path).context("Path not found")?;
+    let path_item = open_api.path_item(path).context("Path not found")?;
     let empty_vec = vec![];
     let parameters = path_item
         .operations
@@ -89,62 +623,122 @@ pub fn path(path: &str, uri: &str, open_api: &OpenAPI) -> Result<()> {
     Ok(())
 }
 
-fn process_schema_refs(
+/// Binds the template variables of the document path matching the concrete
+/// request path `path`, validates each against the schema of its `in: path`
+/// parameter of the `method` operation and converts it to the declared type.
+pub fn typed_path(path: &str, method: &str, open_api: &OpenAPI) -> Result<Map<String, Value>> {
+    compiled::with_patterns(open_api, || bind_path(path, method, open_api))
+}
+
+fn bind_path(path: &str, method: &str, open_api: &OpenAPI) -> Result<Map<String, Value>> {
+    let route = open_api.match_path(path).context("Path not found")?;
+    let parameters: HashMap<&str, &parse::Parameter> = open_api
+        .compiled()
+        .operation(route.path, method)
+        .map(|operation| operation.parameters.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter(|parameter| parameter.r#in == Some(In::Path))
+        .filter_map(|parameter| Some((parameter.name.as_deref()?, parameter)))
+        .collect();
+
+    let mut fields = Map::new();
+    for (name, raw) in &route.params {
+        let decoded = percent_encoding::percent_decode_str(raw).decode_utf8_lossy();
+        let schema = parameters
+            .get(name.as_str())
+            .and_then(|parameter| parameter.schema.as_deref());
+        let declared = schema.and_then(|schema| schema.r#type.as_ref());
+        let value = coerce_query_value(&decoded, declared);
+        if let Some(schema) = schema {
+            let redact = is_secret(schema.format.as_ref());
+            validate_query_scalar(name, &Value::from(decoded.as_ref()), schema, redact)?;
+            // Bounds only apply once the value is a number
+            validate_numeric_constraints(name, &value, schema)?;
+        }
+        fields.insert(name.clone(), value);
+    }
+    Ok(fields)
+}
+
+fn process_schema_refs<'f, 'a>(
     schema: &parse::Schema,
-    fields: &Map<String, Value>,
-    requireds: &mut HashSet<String>,
-    open_api: &OpenAPI,
+    fields: impl Fn() -> &'f Map<String, Value>,
+    requireds: &mut HashSet<&'a str>,
+    open_api: &'a OpenAPI,
 ) -> Result<()> {
-    if let Some(components) = &open_api.components {
-        for schema_ref in collect_refs(schema) {
-            requireds.extend(extract_required_and_validate_props(
-                fields, schema_ref, components,
-            )?);
-        }
+    for schema_ref in collect_refs(schema) {
+        let required = extract_required_and_validate_props(fields(), schema_ref, open_api)?;
+        requireds.extend(required.iter().map(String::as_str));
     }
     Ok(())
 }
 
+/// The query pairs as a JSON object, single values as strings and repeated
+/// ones as arrays
+fn query_fields(query_pairs: &HashMap<String, Vec<String>>) -> Map<String, Value> {
+    query_pairs
+        .iter()
+        .map(|(k, values)| match values.as_slice() {
+            [value] => (k.clone(), Value::from(value.as_str())),
+            _ => (k.clone(), Value::from(values.as_slice())),
+        })
+        .collect()
+}
+
 fn validate_required_fields(
-    requireds: &HashSet<String>,
-    query_pairs: &HashMap<String, String>,
+    requireds: &HashSet<&str>,
+    query_pairs: &HashMap<String, Vec<String>>,
 ) -> Result<()> {
     for key in requireds {
-        if !query_pairs.contains_key(key) {
+        if !query_pairs.contains_key(*key) {
             return Err(anyhow!("Missing required query parameter: '{}'", key));
         }
     }
     Ok(())
 }
 
-pub fn query(path: &str, query_pairs: &HashMap<String, String>, open_api: &OpenAPI) -> Result<()> {
-    let path_base = open_api
-        .paths
-        .get(path)
-        .context("Path not found in OpenAPI specification")?;
-    let empty_vec = vec![];
+/// Validates the query pairs against the parameters of the `method`
+/// operation of `path`, its own merged with the path-level ones
+pub fn query(
+    path: &str,
+    method: &str,
+    query_pairs: &HashMap<String, Vec<String>>,
+    open_api: &OpenAPI,
+) -> Result<()> {
+    compiled::with_patterns(open_api, || {
+        query_parameters(path, method, query_pairs, open_api)
+    })
+}
 
-    let all_parameters: Vec<&parse::Parameter> = path_base
-        .operations
-        .values()
-        .flat_map(|op| op.parameters.as_ref().unwrap_or(&empty_vec))
-        .chain(path_base.parameters.as_ref().unwrap_or(&empty_vec))
-        .collect();
+fn query_parameters(
+    path: &str,
+    method: &str,
+    query_pairs: &HashMap<String, Vec<String>>,
+    open_api: &OpenAPI,
+) -> Result<()> {
+    let (spec_path, _) = open_api
+        .resolve_path(path)
+        .context("Path not found in OpenAPI specification")?;
+    let operation = open_api.compiled().operation(spec_path, method);
+    let all_parameters = operation
+        .map(|operation| operation.parameters.as_slice())
+        .unwrap_or_default();
+    let required = |location: In, name: &str| {
+        operation
+            .is_some_and(|operation| operation.required.contains(&(location, name.to_string())))
+    };
 
-    let fields: Map<String, Value> = query_pairs
-        .iter()
-        .map(|(k, v)| (k.clone(), Value::from(v.clone())))
-        .collect();
+    // Only parameters referencing component schemas need the pairs as a JSON object
+    let fields = OnceCell::new();
+    let fields = || fields.get_or_init(|| query_fields(query_pairs));
 
-    let mut required_fields: HashSet<String> = HashSet::new();
+    let mut required_fields: HashSet<&str> = HashSet::new();
 
-    for parameter in &all_parameters {
+    for parameter in all_parameters {
         if let Some(param_ref) = &parameter.r#ref {
-            if let Some(components) = &open_api.components {
-                required_fields.extend(extract_required_and_validate_props(
-                    &fields, param_ref, components,
-                )?);
-            }
+            let required = extract_required_and_validate_props(fields(), param_ref, open_api)?;
+            required_fields.extend(required.iter().map(String::as_str));
             continue;
         }
 
@@ -152,16 +746,17 @@ pub fn query(path: &str, query_pairs: &HashMap<String, String>, open_api: &OpenA
             continue;
         };
 
-        // Handle OpenAPI 3.2 querystring parameters (JSON in query string)
+        // Handle OpenAPI 3.2 querystring parameters, encoded per their content media type
         if *location == In::QueryString {
-            if let Some(value) = query_pairs.get(name) {
-                // Must be valid JSON
-                if serde_json::from_str::<Value>(value).is_err() {
-                    return Err(anyhow!(
-                        "QueryString parameter '{}' must be valid JSON",
-                        name
-                    ));
+            match query_pairs.get(name).and_then(|values| values.last()) {
+                Some(value) => {
+                    check_deprecated_parameter(method, spec_path, parameter, name, open_api)?;
+                    validate_querystring(name, value, parameter, open_api.components.as_ref())?;
+                }
+                None if required(In::QueryString, name) => {
+                    return Err(anyhow!("Required query parameter '{}' is missing", name));
                 }
+                None => {}
             }
             continue;
         }
@@ -170,49 +765,84 @@ pub fn query(path: &str, query_pairs: &HashMap<String, String>, open_api: &OpenA
             continue;
         }
 
+        if parameter.style == Some(Style::DeepObject) {
+            let components = open_api.components.as_ref();
+            let properties = parameter
+                .schema
+                .as_deref()
+                .and_then(|schema| object_properties(schema, components));
+
+            let object = deep_object(name, query_pairs, properties);
+            if object.is_empty() {
+                if required(In::Query, name) {
+                    return Err(anyhow!("Required query parameter '{}' is missing", name));
+                }
+                continue;
+            }
+
+            check_deprecated_parameter(method, spec_path, parameter, name, open_api)?;
+
+            if let Some(schema) = &parameter.schema {
+                validate_schema_value(name, &Value::Object(object), schema, components)?;
+            }
+            continue;
+        }
+
         match query_pairs.get(name) {
-            Some(value) => {
-                if parameter.required && value.trim().is_empty() {
+            Some(values) => {
+                check_deprecated_parameter(method, spec_path, parameter, name, open_api)?;
+
+                let is_empty = values.iter().all(|value| value.trim().is_empty());
+
+                if is_empty && parameter.allow_empty_value {
+                    // Flag-style parameters such as `?verbose` carry no value
+                    continue;
+                }
+
+                if is_empty && required(In::Query, name) {
                     return Err(anyhow!(
                         "Required query parameter '{}' cannot be empty",
                         name
                     ));
                 }
 
-                let json_value = Value::from(value.as_str());
+                let redact = parameter
+                    .schema
+                    .as_ref()
+                    .is_some_and(|schema| is_secret(schema.format.as_ref()));
 
-                if let Some(enum_values) = &parameter.r#enum {
-                    validate_enum_value(name, &json_value, enum_values)?;
-                }
+                let array_schema = parameter
+                    .schema
+                    .as_deref()
+                    .filter(|schema| is_array_type(schema.r#type.as_ref()));
 
-                if let Some(param_type) = &parameter.r#type {
-                    validate_field_type(name, &json_value, Some(param_type.clone()))?;
+                if let Some(schema) = array_schema {
+                    let items = split_array_values(values, parameter);
+                    validate_query_array(name, &items, schema, redact)?;
+                    continue;
                 }
 
-                if let Some(schema) = &parameter.schema {
-                    validate_field_format(name, &json_value, schema.format.as_ref())?;
+                for value in values {
+                    let json_value = Value::from(value.as_str());
 
-                    if let Some(enum_values) = &schema.r#enum {
-                        validate_enum_value(name, &json_value, enum_values)?;
+                    if let Some(enum_values) = &parameter.r#enum {
+                        validate_enum_value(name, &json_value, enum_values, redact)?;
                     }
 
-                    if let Some(schema_type) = &schema.r#type {
-                        validate_field_type(name, &json_value, Some(schema_type.clone()))?;
+                    if let Some(param_type) = &parameter.r#type {
+                        validate_field_type(name, &json_value, Some(param_type))?;
                     }
 
-                    validate_pattern(name, &json_value, schema.pattern.as_ref())?;
-
-                    process_schema_refs(schema, &fields, &mut required_fields, open_api)?;
-
-                    validate_string_constraints(name, &json_value, schema)?;
+                    if let Some(schema) = &parameter.schema {
+                        validate_query_scalar(name, &json_value, schema, redact)?;
+                        process_schema_refs(schema, fields, &mut required_fields, open_api)?;
+                    }
 
-                    validate_numeric_constraints(name, &json_value, schema)?;
+                    validate_pattern(name, &json_value, parameter.pattern.as_ref(), redact)?;
                 }
-
-                validate_pattern(name, &json_value, parameter.pattern.as_ref())?;
             }
             None => {
-                if parameter.required {
+                if required(In::Query, name) {
                     return Err(anyhow!("Required query parameter '{}' is missing", name));
                 }
             }
@@ -224,65 +854,378 @@ pub fn query(path: &str, query_pairs: &HashMap<String, String>, open_api: &OpenA
     Ok(())
 }
 
-pub fn body(path: &str, fields: Value, open_api: &OpenAPI) -> Result<()> {
-    let path_base = open_api
-        .paths
-        .get(path)
-        .context("Path not found in OpenAPI specification")?;
+/// The query pairs as a JSON object shaped by the query parameters of the
+/// `method` operation of `path`: arrays are split per their `style`, `deepObject` parameters
+/// are rebuilt and values are converted to their declared types unless
+/// [`ValidationOptions::query_as_strings`] is set. Undeclared keys are kept
+/// as strings, or arrays of them when repeated.
+pub fn typed_query(
+    path: &str,
+    method: &str,
+    query_pairs: &HashMap<String, Vec<String>>,
+    open_api: &OpenAPI,
+) -> Map<String, Value> {
+    let parameters: Vec<&parse::Parameter> = operation_parameters(path, method, open_api)
+        .iter()
+        .filter(|parameter| parameter.r#in == Some(In::Query))
+        .collect();
+    let components = open_api.components.as_ref();
+    let coerce =
+        |value: &str, declared: Option<&TypeOrUnion>| match open_api.options.query_as_strings {
+            true => Value::from(value),
+            false => coerce_query_value(value, declared),
+        };
 
-    // Check for request body in traditional methods (post, put, patch, delete)
-    let request = path_base.operations.iter().find_map(|(method, operation)| {
-        if matches!(method.as_str(), "post" | "put" | "patch" | "delete") {
-            operation.request.as_ref()
-        } else {
-            None
+    let mut fields = query_fields(query_pairs);
+    for parameter in parameters {
+        let Some(name) = parameter.name.as_deref() else {
+            continue;
+        };
+        let schema = parameter.schema.as_deref();
+
+        if parameter.style == Some(Style::DeepObject) {
+            let properties = match open_api.options.query_as_strings {
+                true => None,
+                false => schema.and_then(|schema| object_properties(schema, components)),
+            };
+            let object = deep_object(name, query_pairs, properties);
+            if !object.is_empty() {
+                fields.retain(|key, _| !key.starts_with(&format!("{name}[")));
+                fields.insert(name.to_string(), Value::Object(object));
+            }
+            continue;
         }
-    });
 
-    // If no traditional method request body found, check for OpenAPI 3.2 QUERY method
-    let request = match request {
-        Some(r) => Some(r),
-        None => path_base.query.as_ref().and_then(|q| q.request.as_ref()),
-    };
+        let Some(values) = query_pairs.get(name) else {
+            continue;
+        };
+        let value = match schema.filter(|schema| is_array_type(schema.r#type.as_ref())) {
+            Some(schema) => {
+                let declared = schema
+                    .items
+                    .as_ref()
+                    .and_then(|items| items.r#type.as_ref());
+                split_array_values(values, parameter)
+                    .into_iter()
+                    .map(|value| coerce(value, declared))
+                    .collect()
+            }
+            None => {
+                let declared = schema
+                    .and_then(|schema| schema.r#type.as_ref())
+                    .or(parameter.r#type.as_ref());
+                match values.last() {
+                    Some(value) => coerce(value, declared),
+                    None => continue,
+                }
+            }
+        };
+        fields.insert(name.to_string(), value);
+    }
+    fields
+}
 
-    if let Some(request) = request {
-        if request.required && matches!(fields, Value::Null) {
-            return Err(anyhow!("Request body is required but was not provided"));
+fn check_deprecated_parameter(
+    method: &str,
+    path: &str,
+    parameter: &parse::Parameter,
+    name: &str,
+    open_api: &OpenAPI,
+) -> Result<()> {
+    if parameter.deprecated {
+        observability::record_deprecated_parameter(method, path, name);
+        if open_api.options.reject_deprecated {
+            return Err(anyhow!("Query parameter '{}' is deprecated", name));
         }
+    }
+    Ok(())
+}
 
-        let refs: Vec<&str> = request
-            .content
-            .values()
-            .flat_map(|media| collect_refs(&media.schema))
-            .collect();
-
-        let schema_info = get_schema_info(&refs, open_api);
-        let expected_type = schema_info
-            .as_ref()
-            .and_then(|schema| schema.r#type.clone());
+/// Rebuilds a `style: deepObject` parameter from its `name[property]=value`
+/// pairs. The last occurrence wins when a property is repeated, and values
+/// are converted to the declared property type where they parse as one.
+fn deep_object(
+    name: &str,
+    query_pairs: &HashMap<String, Vec<String>>,
+    properties: Option<&IndexMap<String, Properties>>,
+) -> Map<String, Value> {
+    let entries = query_pairs.iter().filter_map(|(key, values)| {
+        let property = key
+            .strip_prefix(name)?
+            .strip_prefix('[')?
+            .strip_suffix(']')?;
+        Some((property, values.last()?.as_str()))
+    });
+    typed_object(entries, properties)
+}
 
-        match fields {
-            Value::Object(ref map) => {
-                ensure_type(&expected_type, Type::Object)?;
-                validate_object_body(map, request, &refs, open_api)?;
-            }
-            Value::Array(ref arr) => {
-                ensure_type(&expected_type, Type::Array)?;
+/// Builds an object from string entries, converting each value to the type
+/// declared for its property.
+fn typed_object<'a>(
+    entries: impl Iterator<Item = (&'a str, &'a str)>,
+    properties: Option<&IndexMap<String, Properties>>,
+) -> Map<String, Value> {
+    entries
+        .map(|(property, value)| {
+            let declared = properties
+                .and_then(|properties| properties.get(property))
+                .and_then(|prop| prop.r#type.as_ref());
+            (property.to_string(), coerce_query_value(value, declared))
+        })
+        .collect()
+}
 
-                if let Some(schema) = &schema_info {
-                    validate_array_length_with_schema(arr.len(), schema)?;
-                }
+/// Properties of an object schema, following a `$ref` to a component schema
+fn object_properties<'a>(
+    schema: &'a parse::Schema,
+    components: Option<&'a ComponentsObject>,
+) -> Option<&'a IndexMap<String, Properties>> {
+    match (&schema.r#ref, components) {
+        (Some(schema_ref), Some(components)) => {
+            resolve_schema(schema_ref, components)?.properties.as_ref()
+        }
+        _ => schema.properties.as_ref(),
+    }
+}
 
-                validate_array_items(arr, request, &refs, open_api)?;
-            }
-            Value::String(_) | Value::Number(_) | Value::Bool(_) => {
-                if let Some(type_or_union) = &expected_type {
-                    validate_field_type("request_body", &fields, Some(type_or_union.clone()))?;
-                }
+/// Decodes an `in: querystring` parameter according to the media type of its
+/// `content` (JSON by default, or `application/x-www-form-urlencoded`) and
+/// validates it against the nested schema.
+fn validate_querystring(
+    name: &str,
+    value: &str,
+    parameter: &parse::Parameter,
+    components: Option<&ComponentsObject>,
+) -> Result<()> {
+    let content = parameter.content.iter().next();
+
+    let (document, schema) = match content {
+        Some((media_type, content))
+            if media_type_matches(media_type, "application/x-www-form-urlencoded") =>
+        {
+            let pairs: Vec<(String, String)> = url::form_urlencoded::parse(value.as_bytes())
+                .into_owned()
+                .collect();
+            let entries = pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()));
+            let properties = object_properties(&content.schema, components);
+            (
+                Value::Object(typed_object(entries, properties)),
+                Some(&content.schema),
+            )
+        }
+        Some((media_type, _)) if !accepts_json(media_type) => return Ok(()),
+        _ => {
+            let document = serde_json::from_str::<Value>(value)
+                .map_err(|_| anyhow!("QueryString parameter '{}' must be valid JSON", name))?;
+            let schema = content
+                .map(|(_, content)| &content.schema)
+                .or(parameter.schema.as_deref());
+            (document, schema)
+        }
+    };
 
-                for media_type in request.content.values() {
+    match schema {
+        Some(schema) => validate_schema_value(name, &document, schema, components),
+        None => Ok(()),
+    }
+}
+
+/// Converts a raw query string value into the JSON type declared for it,
+/// leaving it as a string when it does not parse so type checks still fail.
+fn coerce_query_value(value: &str, declared: Option<&TypeOrUnion>) -> Value {
+    let parsed = match declared {
+        Some(TypeOrUnion::Single(Type::Integer)) => value.parse::<i64>().ok().map(Value::from),
+        Some(TypeOrUnion::Single(Type::Number)) => value.parse::<f64>().ok().map(Value::from),
+        Some(TypeOrUnion::Single(Type::Boolean)) => value.parse::<bool>().ok().map(Value::from),
+        _ => None,
+    };
+    parsed.unwrap_or_else(|| Value::from(value))
+}
+
+fn is_array_type(schema_type: Option<&TypeOrUnion>) -> bool {
+    match schema_type {
+        Some(TypeOrUnion::Single(t)) => *t == Type::Array,
+        Some(TypeOrUnion::Union(types)) => types.contains(&Type::Array),
+        None => false,
+    }
+}
+
+/// Delimiter of array parameter values serialized per the parameter's
+/// `style`. `form` arrays are only delimited when `explode` is false;
+/// otherwise each occurrence of the key is one item.
+fn array_delimiter(parameter: &parse::Parameter) -> Option<char> {
+    match (&parameter.style, parameter.explode) {
+        (Some(Style::PipeDelimited), _) => Some('|'),
+        (Some(Style::SpaceDelimited), _) => Some(' '),
+        (None | Some(Style::Form), Some(false)) => Some(','),
+        _ => None,
+    }
+}
+
+fn split_array_values<'v>(values: &'v [String], parameter: &parse::Parameter) -> Vec<&'v str> {
+    let Some(delimiter) = array_delimiter(parameter) else {
+        return values.iter().map(String::as_str).collect();
+    };
+
+    values
+        .iter()
+        .flat_map(|value| value.split(delimiter))
+        .collect()
+}
+
+/// Validates every occurrence of a repeated (`style: form`, `explode: true`)
+/// array parameter against the schema's `items`.
+fn validate_query_array(
+    name: &str,
+    values: &[&str],
+    schema: &parse::Schema,
+    redact: bool,
+) -> Result<()> {
+    validate_item_count(name, values.len(), schema.min_items, schema.max_items)?;
+
+    if let Some(items) = &schema.items {
+        for (index, value) in values.iter().enumerate() {
+            let item_key = format!("{name}[{index}]");
+            let redact = redact || is_secret(items.format.as_ref());
+            validate_query_scalar(&item_key, &Value::from(*value), items, redact)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_query_scalar(
+    name: &str,
+    value: &Value,
+    schema: &parse::Schema,
+    redact: bool,
+) -> Result<()> {
+    validate_field_format(name, value, schema.format.as_ref())?;
+
+    if let Some(enum_values) = &schema.r#enum {
+        validate_enum_value(name, value, enum_values, redact)?;
+    }
+
+    if let Some(schema_type) = &schema.r#type {
+        validate_field_type(name, value, Some(schema_type))?;
+    }
+
+    validate_pattern(name, value, schema.pattern.as_ref(), redact)?;
+
+    validate_string_constraints(name, value, schema)?;
+
+    validate_numeric_constraints(name, value, schema)
+}
+
+pub fn body(path: &str, method: &str, fields: Value, open_api: &OpenAPI) -> Result<()> {
+    let path_base = open_api
+        .path_item(path)
+        .context("Path not found in OpenAPI specification")?;
+
+    // Only the requestBody of the requested method applies
+    let request =
+        find_operation(path_base, method).and_then(|operation| operation.request.as_ref());
+
+    #[cfg(feature = "jsonschema")]
+    if let Some(schema) = json_schema(path, method, open_api) {
+        check_body_presence(path, method, request, &fields)?;
+        if fields.is_null() {
+            return Ok(());
+        }
+        return match schema.iter_errors(&fields).next() {
+            Some(error) => {
+                let pointer = error.instance_path().to_string();
+                let pointer = if pointer.is_empty() { "/" } else { &pointer };
+                Err(anyhow!(
+                    "Request body does not match its schema at '{pointer}': {error}"
+                ))
+            }
+            None => Ok(()),
+        };
+    }
+
+    request_body(path, method, request, fields, open_api)
+}
+
+/// Validates the payload of an outgoing callback request before it is
+/// dispatched: `name` is a callback of the `method` operation on `path`, and
+/// `fields` must match the request body its `callback_method` operation declares.
+pub fn callback(
+    path: &str,
+    method: &str,
+    name: &str,
+    callback_method: &str,
+    fields: Value,
+    open_api: &OpenAPI,
+) -> Result<()> {
+    let path_item = open_api
+        .path_item(path)
+        .context("Path not found in OpenAPI specification")?;
+
+    let operation = find_operation(path_item, method)
+        .ok_or_else(|| anyhow!("Method '{}' not found for path '{}'", method, path))?;
+
+    let callback = operation
+        .callbacks
+        .get(name)
+        .map(|callback| open_api.resolve_callback(callback))
+        .ok_or_else(|| anyhow!("Callback '{name}' is not declared for '{method} {path}'"))?;
+
+    let (expression, callback_operation) = callback
+        .expressions
+        .iter()
+        .find_map(|(expression, path_item)| {
+            Some((expression, find_operation(path_item, callback_method)?))
+        })
+        .ok_or_else(|| anyhow!("Callback '{name}' does not declare method '{callback_method}'"))?;
+
+    request_body(
+        expression,
+        callback_method,
+        callback_operation.request.as_ref(),
+        fields,
+        open_api,
+    )
+}
+
+fn request_body(
+    path: &str,
+    method: &str,
+    request: Option<&Request>,
+    fields: Value,
+    open_api: &OpenAPI,
+) -> Result<()> {
+    check_body_presence(path, method, request, &fields)?;
+
+    if let Some(request) = request {
+        let refs: Vec<&str> = json_content(request)
+            .flat_map(|media| collect_refs(&media.schema))
+            .collect();
+
+        let schema_info = get_schema_info(&refs, open_api);
+        let expected_type = expected_body_type(request, schema_info);
+
+        match fields {
+            Value::Object(ref map) => {
+                ensure_type(expected_type, Type::Object)?;
+                validate_object_body(map, request, &refs, open_api)?;
+            }
+            Value::Array(ref arr) => {
+                ensure_type(expected_type, Type::Array)?;
+
+                if let Some(schema) = &schema_info {
+                    validate_array_length_with_schema(arr.len(), schema)?;
+                }
+
+                validate_array_items(arr, request, &refs, open_api)?;
+                validate_array_item_schemas(&fields, &refs, open_api)?;
+            }
+            Value::String(_) | Value::Number(_) | Value::Bool(_) => {
+                validate_field_type("request_body", &fields, expected_type)?;
+
+                for media_type in json_content(request) {
                     if let Some(schema_type) = &media_type.schema.r#type {
-                        validate_field_type("request_body", &fields, Some(schema_type.clone()))?;
+                        validate_field_type("request_body", &fields, Some(schema_type))?;
                     }
 
                     if let Some(format) = &media_type.schema.format {
@@ -290,7 +1233,8 @@ pub fn body(path: &str, fields: Value, open_api: &OpenAPI) -> Result<()> {
                     }
 
                     if let Some(enum_values) = &media_type.schema.r#enum {
-                        validate_enum_value("request_body", &fields, enum_values)?;
+                        let redact = is_secret(media_type.schema.format.as_ref());
+                        validate_enum_value("request_body", &fields, enum_values, redact)?;
                     }
                 }
             }
@@ -300,11 +1244,425 @@ pub fn body(path: &str, fields: Value, open_api: &OpenAPI) -> Result<()> {
                 }
             }
         }
+
+        validate_inline_schemas(&fields, request, open_api)?;
     }
 
     Ok(())
 }
 
+/// The compiled request body schema of an operation, when
+/// [`ValidationOptions::json_schema`] hands bodies to the `jsonschema` crate
+#[cfg(feature = "jsonschema")]
+fn json_schema<'a>(
+    path: &str,
+    method: &str,
+    open_api: &'a OpenAPI,
+) -> Option<&'a jsonschema::Validator> {
+    if !open_api.options.json_schema {
+        return None;
+    }
+    open_api
+        .compiled_operation(path, method)?
+        .body_schema(open_api)
+}
+
+/// Checks that a body is sent when, and only when, the operation takes one,
+/// and that the operation accepts it as JSON
+fn check_body_presence(
+    path: &str,
+    method: &str,
+    request: Option<&Request>,
+    fields: &Value,
+) -> Result<()> {
+    let Some(request) = request else {
+        if fields.is_null() {
+            return Ok(());
+        }
+        return Err(anyhow!(
+            "Method '{}' for path '{}' does not accept a request body",
+            method,
+            path
+        ));
+    };
+
+    if request.required && fields.is_null() {
+        return Err(anyhow!("Request body is required but was not provided"));
+    }
+
+    if !fields.is_null() && !request.content.is_empty() && json_content(request).next().is_none() {
+        return Err(anyhow!(
+            "Request body for '{} {}' does not accept JSON content",
+            method,
+            path
+        ));
+    }
+    Ok(())
+}
+
+/// Validates a JSON request body like [`body`], streaming only top-level
+/// arrays: their items are parsed one at a time from their slice of `body`
+/// and dropped before the next, so a large array is never held as a whole.
+///
+/// Objects and scalars, arrays whose schemas constrain more than their
+/// length, and bodies checked by a compiled JSON Schema are parsed in full and
+/// handed to [`body`]; their memory use is that of a `serde_json::Value`.
+pub fn raw_body(path: &str, method: &str, body: &RawValue, open_api: &OpenAPI) -> Result<()> {
+    compiled::with_patterns(open_api, || raw_json_body(path, method, body, open_api))
+}
+
+fn raw_json_body(path: &str, method: &str, body: &RawValue, open_api: &OpenAPI) -> Result<()> {
+    let json = body.get();
+    #[cfg(feature = "jsonschema")]
+    let compiled = json_schema(path, method, open_api).is_some();
+    #[cfg(not(feature = "jsonschema"))]
+    let compiled = false;
+    if compiled || !json.trim_start().starts_with('[') {
+        return self::body(path, method, serde_json::from_str(json)?, open_api);
+    }
+
+    let path_base = open_api
+        .path_item(path)
+        .context("Path not found in OpenAPI specification")?;
+
+    let Some(request) =
+        find_operation(path_base, method).and_then(|operation| operation.request.as_ref())
+    else {
+        return Err(anyhow!(
+            "Method '{}' for path '{}' does not accept a request body",
+            method,
+            path
+        ));
+    };
+
+    let components = open_api.components.as_ref();
+    let refs: Vec<&str> = json_content(request)
+        .flat_map(|media| collect_refs(&media.schema))
+        .collect();
+    let referenced: Option<Vec<&parse::ComponentSchemaBase>> = refs
+        .iter()
+        .map(|schema_ref| components.and_then(|c| resolve_schema(schema_ref, c)))
+        .collect();
+    let inline: Vec<&parse::Schema> = json_content(request)
+        .map(|media_type| &media_type.schema)
+        .filter(|schema| schema.r#ref.is_none())
+        .collect();
+
+    let item_wise = json_content(request).next().is_some()
+        && referenced.as_ref().is_some_and(|referenced| {
+            referenced
+                .iter()
+                .all(|schema| schema.r#ref.is_none() && schema.r#enum.is_none())
+        })
+        && inline.iter().all(|schema| schema.r#enum.is_none());
+    let Some(referenced) = referenced.filter(|_| item_wise) else {
+        return self::body(path, method, serde_json::from_str(json)?, open_api);
+    };
+
+    let items: Vec<&RawValue> = serde_json::from_str(json)?;
+
+    let schema_info = get_schema_info(&refs, open_api);
+    ensure_type(expected_body_type(request, schema_info), Type::Array)?;
+    if let Some(schema) = schema_info {
+        validate_array_length_with_schema(items.len(), schema)?;
+    }
+
+    // Stands in for the array where only its type is checked
+    let array = Value::Array(vec![]);
+    let key = "request_body";
+    for (schema_type, min_items, max_items) in referenced
+        .iter()
+        .map(|s| (s.r#type.as_ref(), s.min_items, s.max_items))
+        .chain(
+            inline
+                .iter()
+                .map(|s| (s.r#type.as_ref(), s.min_items, s.max_items)),
+        )
+    {
+        validate_field_type(key, &array, schema_type)?;
+        validate_item_count(key, items.len(), min_items, max_items)?;
+    }
+
+    let allows_non_objects = item_types(request, &refs, open_api)
+        .into_iter()
+        .any(|t| *t != TypeOrUnion::Single(Type::Object));
+
+    for (index, item) in items.into_iter().enumerate() {
+        let item: Value = serde_json::from_str(item.get())?;
+        match item.as_object() {
+            Some(map) => validate_map(map, request, &refs, open_api)?,
+            None if allows_non_objects => {}
+            None => return Err(anyhow!("Array item at index {index} must be an object")),
+        }
+
+        let item_key = format!("{key}[{index}]");
+        for items in referenced.iter().filter_map(|schema| schema.items.as_ref()) {
+            validate_component_value(&item_key, &item, items, components)?;
+        }
+        for items in inline.iter().filter_map(|schema| schema.items.as_ref()) {
+            validate_schema_value(&item_key, &item, items, components)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The response declared for `status`, falling back to its `2XX`-style range
+/// and then to `default`
+fn declared_response<'a>(
+    path: &str,
+    method: &str,
+    status: u16,
+    open_api: &'a OpenAPI,
+) -> Result<&'a parse::Response> {
+    let path_item = open_api
+        .path_item(path)
+        .context("Path not found in OpenAPI specification")?;
+
+    let operation = find_operation(path_item, method)
+        .ok_or_else(|| anyhow!("Method '{}' not found for path '{}'", method, path))?;
+
+    let range = format!("{}XX", status / 100);
+    let response = operation
+        .responses
+        .get(&status.to_string())
+        .or_else(|| operation.responses.get(&range))
+        .or_else(|| operation.responses.get("default"))
+        .ok_or_else(|| {
+            anyhow!(
+                "Response status '{}' is not declared for '{} {}'",
+                status,
+                method,
+                path
+            )
+        })?;
+    Ok(open_api.resolve_response(response))
+}
+
+/// Validates a complete response as sent: its status must be declared for
+/// the operation, its `Content-Type` must be one of the declared media types
+/// and JSON bodies must match the schema, see [`response`].
+pub fn response_message(
+    path: &str,
+    method: &str,
+    status: u16,
+    content_type: Option<&str>,
+    body: &[u8],
+    open_api: &OpenAPI,
+) -> Result<()> {
+    let declared = declared_response(path, method, status, open_api)?;
+    if body.is_empty() {
+        return Ok(());
+    }
+    if declared.content.is_empty() {
+        return Err(anyhow!(
+            "Response status '{}' of '{} {}' declares no content",
+            status,
+            method,
+            path
+        ));
+    }
+
+    let content_type = content_type.unwrap_or_default();
+    if !declared
+        .content
+        .keys()
+        .any(|range| media_type_matches(range, content_type))
+    {
+        return Err(anyhow!(
+            "Response content type '{}' is not declared for status '{}' of '{} {}'",
+            content_type,
+            status,
+            method,
+            path
+        ));
+    }
+    if !accepts_json(content_type) {
+        return Ok(());
+    }
+
+    let fields: Value = serde_json::from_slice(body)
+        .map_err(|e| anyhow!("Response body is not valid JSON: {e}"))?;
+    response(path, method, status, fields, open_api)
+}
+
+pub fn response(
+    path: &str,
+    method: &str,
+    status: u16,
+    fields: Value,
+    open_api: &OpenAPI,
+) -> Result<()> {
+    let response = declared_response(path, method, status, open_api)?;
+
+    let json_content = response
+        .content
+        .iter()
+        .filter(|(key, _)| accepts_json(key))
+        .map(|(_, media_type)| media_type);
+
+    let components = open_api.components.as_ref();
+    for media_type in json_content {
+        let schema = &media_type.schema;
+        validate_schema_value("response_body", &fields, schema, components)?;
+
+        let mut refs = collect_refs(schema);
+        refs.extend(schema.items.iter().flat_map(|items| collect_refs(items)));
+
+        let mut schemas: Vec<&IndexMap<String, Properties>> = schema
+            .properties
+            .iter()
+            .chain(schema.items.iter().filter_map(|i| i.properties.as_ref()))
+            .collect();
+        schemas.extend(component_properties(&refs, open_api));
+
+        let objects: Vec<&Map<String, Value>> = match &fields {
+            Value::Object(map) => vec![map],
+            Value::Array(arr) => arr.iter().filter_map(Value::as_object).collect(),
+            _ => vec![],
+        };
+
+        for map in objects {
+            for properties in &schemas {
+                reject_restricted_fields(map, properties, components, Direction::Response)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Which side of the exchange a payload belongs to, deciding whether
+/// `readOnly` or `writeOnly` properties are forbidden in it.
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    Request,
+    Response,
+}
+
+impl Direction {
+    fn forbids(self, properties: &Properties) -> bool {
+        match self {
+            Direction::Request => properties.read_only,
+            Direction::Response => properties.write_only,
+        }
+    }
+
+    fn error(self, key: &str) -> anyhow::Error {
+        match self {
+            Direction::Request => {
+                anyhow!("Request body must not contain read-only field '{}'", key)
+            }
+            Direction::Response => {
+                anyhow!("Response body must not contain write-only field '{}'", key)
+            }
+        }
+    }
+}
+
+fn reject_restricted_fields(
+    fields: &Map<String, Value>,
+    properties: &IndexMap<String, Properties>,
+    components: Option<&ComponentsObject>,
+    direction: Direction,
+) -> Result<()> {
+    for (key, prop) in properties {
+        let Some(value) = fields.get(key) else {
+            continue;
+        };
+
+        if direction.forbids(prop) {
+            return Err(direction.error(key));
+        }
+
+        if let (Some(map), Some(nested)) = (value.as_object(), nested_properties(prop, components))
+        {
+            reject_restricted_fields(map, nested, components, direction)?;
+        }
+
+        if let (Some(arr), Some(nested)) = (
+            value.as_array(),
+            prop.items
+                .as_deref()
+                .and_then(|items| nested_properties(items, components)),
+        ) {
+            for map in arr.iter().filter_map(Value::as_object) {
+                reject_restricted_fields(map, nested, components, direction)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The properties of an object property, declared inline or in the component
+/// schema it references. Recursion over them is bounded by the JSON being
+/// checked; only chains of components aliasing one another through `$ref`
+/// could loop, so those are followed with a visited set.
+fn nested_properties<'a>(
+    prop: &'a Properties,
+    components: Option<&'a ComponentsObject>,
+) -> Option<&'a IndexMap<String, Properties>> {
+    let Some(mut schema_ref) = prop.r#ref.as_deref() else {
+        return prop.properties.as_ref();
+    };
+    let components = components?;
+    let mut visited = HashSet::new();
+    loop {
+        if !visited.insert(schema_ref) {
+            return None;
+        }
+        let schema = resolve_schema(schema_ref, components)?;
+        match &schema.r#ref {
+            Some(next) => schema_ref = next,
+            None => return schema.properties.as_ref(),
+        }
+    }
+}
+
+fn component_properties<'a>(
+    refs: &[&str],
+    open_api: &'a OpenAPI,
+) -> Vec<&'a IndexMap<String, Properties>> {
+    let compiled = open_api.compiled();
+    refs.iter()
+        .filter_map(|schema_ref| compiled.schema(schema_ref))
+        .flat_map(|schema| &schema.properties)
+        .collect()
+}
+
+/// Validates the body against request schemas declared inline rather than
+/// through a `$ref`, with the same rules applied to referenced components.
+fn validate_inline_schemas(fields: &Value, request: &Request, open_api: &OpenAPI) -> Result<()> {
+    if fields.is_null() {
+        return Ok(());
+    }
+
+    for media_type in json_content(request) {
+        if media_type.schema.r#ref.is_none() {
+            validate_schema_value(
+                "request_body",
+                fields,
+                &media_type.schema,
+                open_api.components.as_ref(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The declared `type` of a request body: that of its referenced component
+/// schema, falling back to the inline schemas of its JSON media types
+fn expected_body_type<'a>(
+    request: &'a Request,
+    schema_info: Option<&'a parse::ComponentSchemaBase>,
+) -> Option<&'a TypeOrUnion> {
+    schema_info
+        .and_then(|schema| schema.r#type.as_ref())
+        .or_else(|| json_content(request).find_map(|media_type| media_type.schema.r#type.as_ref()))
+}
+
 fn get_schema_info<'a>(
     refs: &[&str],
     open_api: &'a OpenAPI,
@@ -319,6 +1677,210 @@ fn get_schema_info<'a>(
     })
 }
 
+/// Media types carrying one JSON document per line
+const JSON_SEQUENCE_MEDIA_TYPES: &[&str] = &["application/jsonl", "application/x-ndjson"];
+
+/// Whether `content_type` is a line-delimited JSON sequence validated by [`json_lines`]
+pub fn is_json_sequence(content_type: &str) -> bool {
+    JSON_SEQUENCE_MEDIA_TYPES
+        .iter()
+        .any(|media_type| media_type_matches(media_type, content_type))
+}
+
+/// Validates a JSON Lines / NDJSON request body one line at a time against
+/// the OpenAPI 3.2 `itemSchema` of the matching media type, so the body never
+/// has to be held in memory as a whole.
+pub fn json_lines(
+    path: &str,
+    method: &str,
+    content_type: &str,
+    reader: impl BufRead,
+    open_api: &OpenAPI,
+) -> Result<()> {
+    let mut lines = stream::JsonLines::new(path, method, content_type, open_api)?;
+    for line in reader.lines() {
+        lines.line(&line?)?;
+    }
+    Ok(())
+}
+
+/// Validates the parts of a `multipart/form-data` request body against the
+/// operation's multipart schema and `encoding` object.
+pub fn multipart(path: &str, method: &str, parts: &[Part], open_api: &OpenAPI) -> Result<()> {
+    let path_base = open_api
+        .path_item(path)
+        .context("Path not found in OpenAPI specification")?;
+
+    let Some(request) =
+        find_operation(path_base, method).and_then(|operation| operation.request.as_ref())
+    else {
+        return Err(anyhow!(
+            "Method '{}' for path '{}' does not accept a request body",
+            method,
+            path
+        ));
+    };
+
+    let media_type = request
+        .content
+        .iter()
+        .find(|(key, _)| media_type_matches(key, "multipart/form-data"))
+        .map(|(_, media_type)| media_type)
+        .context("Request body does not accept multipart/form-data")?;
+
+    let components = open_api.components.as_ref();
+    let schema = &media_type.schema;
+    let (properties, required) = match (&schema.r#ref, components) {
+        (Some(schema_ref), Some(components)) => {
+            let resolved = resolve_schema(schema_ref, components)
+                .with_context(|| format!("Schema '{}' not found", schema_ref))?;
+            (resolved.properties.as_ref(), &resolved.required)
+        }
+        _ => (schema.properties.as_ref(), &schema.required),
+    };
+
+    for name in required {
+        if !parts.iter().any(|part| part.name == *name) {
+            let is_file = properties
+                .and_then(|properties| properties.get(name))
+                .is_some_and(is_binary_property);
+            let kind = if is_file { "file" } else { "field" };
+            return Err(anyhow!("Missing required {} '{}'", kind, name));
+        }
+    }
+
+    let Some(properties) = properties else {
+        return Ok(());
+    };
+
+    for (name, prop) in properties {
+        let values: Vec<&Part> = parts.iter().filter(|part| part.name == *name).collect();
+        if values.is_empty() {
+            continue;
+        }
+
+        let item = match (&prop.r#type, &prop.items) {
+            (Some(TypeOrUnion::Single(Type::Array)), Some(items)) => {
+                validate_item_count(name, values.len(), prop.min_items, prop.max_items)?;
+                items.as_ref()
+            }
+            _ => prop,
+        };
+
+        let allowed_types = media_type
+            .encoding
+            .get(name)
+            .and_then(|encoding| encoding.content_type.as_deref());
+
+        for part in values {
+            if is_binary_property(item) {
+                validate_file_part(name, part, item, allowed_types)?;
+            } else {
+                let text = String::from_utf8_lossy(&part.data);
+                let value = coerce_query_value(&text, item.r#type.as_ref());
+                validate_property_value(name, &value, item, components)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn is_binary_property(prop: &Properties) -> bool {
+    let binary = |prop: &Properties| {
+        prop.format == Some(Format::Binary)
+            || prop.r#type == Some(TypeOrUnion::Single(Type::Binary))
+    };
+    binary(prop) || prop.items.as_deref().is_some_and(binary)
+}
+
+fn validate_file_part(
+    name: &str,
+    part: &Part,
+    prop: &Properties,
+    allowed_types: Option<&str>,
+) -> Result<()> {
+    if let Some(max_size) = prop.max_length {
+        if part.data.len() as u64 > max_size {
+            return Err(anyhow!(
+                "File '{}' exceeds the maximum size of {} bytes, but got {}",
+                name,
+                max_size,
+                part.data.len()
+            ));
+        }
+    }
+
+    if let Some(allowed_types) = allowed_types {
+        // Parts without a Content-Type header default to application/octet-stream
+        let content_type = part
+            .content_type
+            .as_deref()
+            .unwrap_or("application/octet-stream");
+        let allowed = allowed_types
+            .split(',')
+            .any(|allowed| media_type_matches(allowed, content_type));
+        if !allowed {
+            return Err(anyhow!(
+                "File '{}' has content type '{}', expected one of '{}'",
+                name,
+                content_type,
+                allowed_types
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Media types of a request body that accept a JSON document
+fn json_content(request: &Request) -> impl Iterator<Item = &BaseContent> {
+    request
+        .content
+        .iter()
+        .filter(|(key, _)| accepts_json(key))
+        .map(|(_, media_type)| media_type)
+}
+
+/// Whether a `content` key accepts JSON: `application/json`, structured
+/// `+json` suffixes such as `application/problem+json`, and ranges such as
+/// `application/*` or `*/*`.
+fn accepts_json(key: &str) -> bool {
+    is_json_media_type(key) || media_type_matches(key, "application/json")
+}
+
+fn is_json_media_type(media_type: &str) -> bool {
+    let essence = media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json" || (essence.contains('/') && essence.ends_with("+json"))
+}
+
+/// Whether a media type (range) such as `image/*` accepts `actual`. Media
+/// type parameters are ignored on both sides.
+fn media_type_matches(range: &str, actual: &str) -> bool {
+    let essence = |media_type: &str| {
+        media_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+    };
+    let (range, actual) = (essence(range), essence(actual));
+
+    match range.split_once('/') {
+        Some(("*", "*")) => true,
+        Some((kind, "*")) => actual
+            .split_once('/')
+            .is_some_and(|(actual_kind, _)| actual_kind == kind),
+        _ => range == actual,
+    }
+}
+
 fn validate_object_body(
     fields: &Map<String, Value>,
     request: &Request,
@@ -327,45 +1889,344 @@ fn validate_object_body(
 ) -> Result<()> {
     for (key, media_type) in &request.content {
         if let Some(field) = fields.get(key) {
-            let type_or_union = media_type.schema.r#type.clone();
-            validate_field_type(key, field, type_or_union)?;
+            validate_field_type(key, field, media_type.schema.r#type.as_ref())?;
             if media_type.schema.r#type == Some(TypeOrUnion::Single(Type::String)) {
                 validate_field_format(key, field, media_type.schema.format.as_ref())?;
             }
         }
     }
 
-    let mut requireds = HashSet::new();
+    let mut requireds = HashSet::new();
+
+    for schema_ref in refs {
+        let required = extract_required_and_validate_props(fields, schema_ref, open_api)?;
+        requireds.extend(required.iter().map(String::as_str));
+    }
+
+    for key in &requireds {
+        if !fields.contains_key(*key) {
+            return Err(anyhow!("Missing required request body field: '{}'", key));
+        }
+    }
+
+    for properties in component_properties(refs, open_api) {
+        reject_restricted_fields(
+            fields,
+            properties,
+            open_api.components.as_ref(),
+            Direction::Request,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn validate_array_items(
+    arr: &[Value],
+    request: &Request,
+    refs: &[&str],
+    open_api: &OpenAPI,
+) -> Result<()> {
+    let allows_non_objects = item_types(request, refs, open_api)
+        .into_iter()
+        .any(|t| *t != TypeOrUnion::Single(Type::Object));
+
+    for (index, item) in arr.iter().enumerate() {
+        match item.as_object() {
+            Some(map) => validate_map(map, request, refs, open_api)?,
+            None if allows_non_objects => {}
+            None => return Err(anyhow!("Array item at index {index} must be an object")),
+        }
+    }
+    Ok(())
+}
+
+/// Declared `type`s of the array items a request body may contain, from both
+/// inline `items` schemas and referenced component schemas.
+fn item_types<'a>(
+    request: &'a Request,
+    refs: &[&str],
+    open_api: &'a OpenAPI,
+) -> Vec<&'a TypeOrUnion> {
+    let components = open_api.components.as_ref();
+    let resolve = |schema_ref: &str| components.and_then(|c| resolve_schema(schema_ref, c));
+
+    let inline = json_content(request)
+        .filter_map(|media_type| media_type.schema.items.as_deref())
+        .filter_map(|items| match &items.r#ref {
+            Some(items_ref) => resolve(items_ref).and_then(|s| s.r#type.as_ref()),
+            None => items.r#type.as_ref(),
+        });
+
+    let referenced = refs
+        .iter()
+        .filter_map(|schema_ref| resolve(schema_ref))
+        .filter_map(|schema| schema.items.as_deref())
+        .filter_map(|items| items.r#type.as_ref());
+
+    inline.chain(referenced).collect()
+}
+
+fn validate_array_item_schemas(fields: &Value, refs: &[&str], open_api: &OpenAPI) -> Result<()> {
+    let components = open_api.components.as_ref();
+
+    for schema_ref in refs {
+        validate_referenced_value("request_body", fields, schema_ref, components)?;
+    }
+
+    Ok(())
+}
+
+fn validate_schema_value(
+    key: &str,
+    value: &Value,
+    schema: &parse::Schema,
+    components: Option<&ComponentsObject>,
+) -> Result<()> {
+    if let Some(schema_ref) = &schema.r#ref {
+        return validate_referenced_value(key, value, schema_ref, components);
+    }
+
+    validate_field_type(key, value, schema.r#type.as_ref())?;
+
+    if format_applies(value, schema.format.as_ref()) {
+        validate_field_format(key, value, schema.format.as_ref())?;
+    }
+
+    let redact = is_secret(schema.format.as_ref());
+
+    if let Some(enum_values) = &schema.r#enum {
+        validate_enum_value(key, value, enum_values, redact)?;
+    }
+
+    validate_pattern(key, value, schema.pattern.as_ref(), redact)?;
+
+    validate_value_bounds(
+        key,
+        value,
+        (schema.min_length, schema.max_length),
+        (schema.minimum, schema.maximum),
+    )?;
+
+    validate_exclusive_bounds(
+        key,
+        value,
+        (schema.exclusive_minimum, schema.exclusive_maximum),
+    )?;
+
+    validate_encoded_content(
+        key,
+        value,
+        schema.content_encoding.as_deref(),
+        schema.content_media_type.as_deref(),
+        schema.content_schema.as_deref(),
+        components,
+    )?;
+
+    if let Some(arr) = value.as_array() {
+        validate_item_count(key, arr.len(), schema.min_items, schema.max_items)?;
+        if let Some(items) = &schema.items {
+            for (index, item) in arr.iter().enumerate() {
+                validate_schema_value(&format!("{key}[{index}]"), item, items, components)?;
+            }
+        }
+    }
+
+    if let Some(map) = value.as_object() {
+        validate_nested_object(
+            key,
+            map,
+            &schema.required,
+            schema.properties.as_ref(),
+            components,
+        )?;
+
+        for part in schema.all_of.iter().flatten() {
+            match &part.r#ref {
+                Some(part_ref) => validate_referenced_value(key, value, part_ref, components)?,
+                None => validate_properties(map, Some(&part.properties), components)?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates a standalone value, such as a documented example, against one of
+/// the document's schemas
+pub fn schema_value(
+    key: &str,
+    value: &Value,
+    schema: SchemaNode<'_>,
+    components: Option<&ComponentsObject>,
+) -> Result<()> {
+    match schema {
+        SchemaNode::Schema(schema) => validate_schema_value(key, value, schema, components),
+        SchemaNode::Component(schema) => validate_component_value(key, value, schema, components),
+        SchemaNode::Property(property) => validate_property_value(key, value, property, components),
+        SchemaNode::Composed(part) => match (&part.r#ref, value.as_object()) {
+            (Some(part_ref), _) => validate_referenced_value(key, value, part_ref, components),
+            (None, Some(map)) => validate_properties(map, Some(&part.properties), components),
+            (None, None) => Ok(()),
+        },
+    }
+}
+
+/// Decodes string values declaring `contentEncoding` and, for JSON
+/// `contentMediaType`s, validates the embedded document against `contentSchema`.
+fn validate_encoded_content(
+    key: &str,
+    value: &Value,
+    encoding: Option<&str>,
+    media_type: Option<&str>,
+    content_schema: Option<&parse::Schema>,
+    components: Option<&ComponentsObject>,
+) -> Result<()> {
+    let Some(str_val) = value.as_str() else {
+        return Ok(());
+    };
+
+    let decoded = match encoding.map(str::to_ascii_lowercase).as_deref() {
+        Some("base64") => general_purpose::STANDARD
+            .decode(str_val)
+            .map_err(|_| anyhow!("The value of '{}' must be valid base64 content", key))?,
+        Some("base64url") => general_purpose::URL_SAFE_NO_PAD
+            .decode(str_val.trim_end_matches('='))
+            .map_err(|_| anyhow!("The value of '{}' must be valid base64url content", key))?,
+        _ => str_val.as_bytes().to_vec(),
+    };
+
+    if !media_type.is_some_and(is_json_media_type) {
+        return Ok(());
+    }
+
+    let document: Value = serde_json::from_slice(&decoded)
+        .map_err(|e| anyhow!("The content of '{}' must be valid JSON: {}", key, e))?;
+
+    match content_schema {
+        Some(schema) => validate_schema_value(key, &document, schema, components),
+        None => Ok(()),
+    }
+}
+
+/// Checks the OpenAPI 3.1 numeric `exclusiveMinimum`/`exclusiveMaximum` bounds
+fn validate_exclusive_bounds(
+    key: &str,
+    value: &Value,
+    (exclusive_minimum, exclusive_maximum): (Option<f64>, Option<f64>),
+) -> Result<()> {
+    let Some(num_val) = value.as_f64() else {
+        return Ok(());
+    };
+
+    if let Some(min) = exclusive_minimum {
+        if num_val <= min {
+            return Err(anyhow!(
+                "The value of '{}' must be > {}, but got {}",
+                key,
+                min,
+                num_val
+            ));
+        }
+    }
+    if let Some(max) = exclusive_maximum {
+        if num_val >= max {
+            return Err(anyhow!(
+                "The value of '{}' must be < {}, but got {}",
+                key,
+                max,
+                num_val
+            ));
+        }
+    }
+
+    Ok(())
+}
 
-    if let Some(components) = &open_api.components {
-        for schema_ref in refs {
-            requireds.extend(extract_required_and_validate_props(
-                fields, schema_ref, components,
-            )?);
+fn validate_value_bounds(
+    key: &str,
+    value: &Value,
+    (min_length, max_length): (Option<u64>, Option<u64>),
+    (minimum, maximum): (Option<f64>, Option<f64>),
+) -> Result<()> {
+    if let Some(str_val) = value.as_str() {
+        let length = str_val.len();
+        if let Some(min) = min_length {
+            if length < usize::try_from(min)? {
+                return Err(anyhow!(
+                    "The length of '{}' must be at least {} characters, but got {}",
+                    key,
+                    min,
+                    length
+                ));
+            }
+        }
+        if let Some(max) = max_length {
+            if length > usize::try_from(max)? {
+                return Err(anyhow!(
+                    "The length of '{}' must be at most {} characters, but got {}",
+                    key,
+                    max,
+                    length
+                ));
+            }
         }
     }
 
-    for key in &requireds {
-        if !fields.contains_key(key) {
-            return Err(anyhow!("Missing required request body field: '{}'", key));
+    if let Some(num_val) = value.as_f64() {
+        if let Some(min) = minimum {
+            if num_val < min {
+                return Err(anyhow!(
+                    "The value of '{}' must be >= {}, but got {}",
+                    key,
+                    min,
+                    num_val
+                ));
+            }
+        }
+        if let Some(max) = maximum {
+            if num_val > max {
+                return Err(anyhow!(
+                    "The value of '{}' must be <= {}, but got {}",
+                    key,
+                    max,
+                    num_val
+                ));
+            }
         }
     }
 
     Ok(())
 }
 
-fn validate_array_items(
-    arr: &[Value],
-    request: &Request,
-    refs: &[&str],
-    open_api: &OpenAPI,
+fn validate_item_count(
+    key: &str,
+    length: usize,
+    min_items: Option<u64>,
+    max_items: Option<u64>,
 ) -> Result<()> {
-    for (index, item) in arr.iter().enumerate() {
-        let map = item
-            .as_object()
-            .with_context(|| format!("Array item at index {index} must be an object"))?;
-        validate_map(map, request, refs, open_api)?;
+    if let Some(min) = min_items {
+        if length < usize::try_from(min)? {
+            return Err(anyhow!(
+                "The array '{}' must have at least {} items, but got {}",
+                key,
+                min,
+                length
+            ));
+        }
+    }
+
+    if let Some(max) = max_items {
+        if length > usize::try_from(max)? {
+            return Err(anyhow!(
+                "The array '{}' must have at most {} items, but got {}",
+                key,
+                max,
+                length
+            ));
+        }
     }
+
     Ok(())
 }
 
@@ -396,7 +2257,7 @@ fn validate_array_length_with_schema(
     Ok(())
 }
 
-fn ensure_type(actual: &Option<TypeOrUnion>, expected: Type) -> Result<()> {
+fn ensure_type(actual: Option<&TypeOrUnion>, expected: Type) -> Result<()> {
     if let Some(type_or_union) = actual {
         match type_or_union {
             TypeOrUnion::Single(t) => {
@@ -430,8 +2291,7 @@ fn validate_map(
 ) -> Result<()> {
     for (key, media_type) in &request.content {
         if let Some(field) = fields.get(key) {
-            let type_or_union = media_type.schema.r#type.clone();
-            validate_field_type(key, field, type_or_union)?;
+            validate_field_type(key, field, media_type.schema.r#type.as_ref())?;
             if media_type.schema.r#type == Some(TypeOrUnion::Single(Type::String)) {
                 validate_field_format(key, field, media_type.schema.format.as_ref())?;
             }
@@ -440,24 +2300,40 @@ fn validate_map(
 
     let mut requireds = HashSet::new();
 
-    if let Some(components) = &open_api.components {
-        for schema_ref in refs {
-            requireds.extend(extract_required_and_validate_props(
-                fields, schema_ref, components,
-            )?);
-        }
+    for schema_ref in refs {
+        let required = extract_required_and_validate_props(fields, schema_ref, open_api)?;
+        requireds.extend(required.iter().map(String::as_str));
     }
 
     for key in &requireds {
-        if !fields.contains_key(key) {
+        if !fields.contains_key(*key) {
             return Err(anyhow!("Missing required request body field: '{}'", key));
         }
     }
 
+    for properties in component_properties(refs, open_api) {
+        reject_restricted_fields(
+            fields,
+            properties,
+            open_api.components.as_ref(),
+            Direction::Request,
+        )?;
+    }
+
     Ok(())
 }
 
 fn validate_field_format(key: &str, value: &Value, format: Option<&Format>) -> Result<()> {
+    match format {
+        Some(Format::Int32) => {
+            return validate_integer_format(key, value, "int32", i32::MIN.into(), i32::MAX.into())
+        }
+        Some(Format::Int64) => {
+            return validate_integer_format(key, value, "int64", i64::MIN, i64::MAX)
+        }
+        _ => {}
+    }
+
     let Some(str_val) = value.as_str() else {
         return Err(anyhow::anyhow!("this value must be string '{}'", key));
     };
@@ -480,9 +2356,29 @@ fn validate_field_format(key: &str, value: &Value, format: Option<&Format>) -> R
             DateTime::parse_from_rfc3339(str_val)
                 .map_err(|_| format_error("DateTime", key, str_val))?;
         }
+        Some(Format::Duration) => {
+            if !is_iso8601_duration(str_val) {
+                return Err(format_error("Duration", key, str_val));
+            }
+        }
         Some(Format::UUID) => {
             uuid::Uuid::parse_str(str_val).map_err(|_| format_error("UUID", key, str_val))?;
         }
+        Some(Format::URI) => {
+            if !is_uri_reference(str_val) || Url::parse(str_val).is_err() {
+                return Err(format_error("URI", key, str_val));
+            }
+        }
+        Some(Format::URIReference) => {
+            if !is_uri_reference(str_val) {
+                return Err(format_error("URI-Reference", key, str_val));
+            }
+        }
+        Some(Format::Hostname) => {
+            if !is_rfc1123_hostname(str_val) {
+                return Err(format_error("Hostname", key, str_val));
+            }
+        }
         Some(Format::IPV4) => {
             str_val
                 .parse::<Ipv4Addr>()
@@ -493,6 +2389,17 @@ fn validate_field_format(key: &str, value: &Value, format: Option<&Format>) -> R
                 .parse::<Ipv6Addr>()
                 .map_err(|_| format_error("IPV6", key, str_val))?;
         }
+        Some(Format::Password) => {}
+        Some(Format::Byte) => {
+            general_purpose::STANDARD
+                .decode(str_val)
+                .map_err(|_| format_error("Byte", key, str_val))?;
+        }
+        Some(Format::Base64Url) => {
+            general_purpose::URL_SAFE_NO_PAD
+                .decode(str_val.trim_end_matches('='))
+                .map_err(|_| format_error("Base64Url", key, str_val))?;
+        }
         None => {}
         _ => {
             return Err(anyhow::anyhow!(
@@ -505,7 +2412,118 @@ fn validate_field_format(key: &str, value: &Value, format: Option<&Format>) -> R
     Ok(())
 }
 
-fn validate_enum_value(key: &str, value: &Value, enum_values: &[serde_yaml::Value]) -> Result<()> {
+/// Checks that a value parses as an absolute URI or as a reference relative to one,
+/// rejecting whitespace and backslashes that the lenient URL parser would escape.
+fn is_uri_reference(value: &str) -> bool {
+    !value.chars().any(|c| c.is_whitespace() || c == '\\')
+        && Url::parse("http://example.invalid/")
+            .and_then(|base| base.join(value))
+            .is_ok()
+}
+
+/// Checks an RFC 1123 hostname: at most 253 characters of dot-separated labels,
+/// each 1-63 alphanumerics or hyphens that neither start nor end with a hyphen.
+fn is_rfc1123_hostname(value: &str) -> bool {
+    value.len() <= 253
+        && value.split('.').all(|label| {
+            (1..=63).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// Checks an ISO 8601 duration such as `P3DT4H` or `PT0.5S`: designators must
+/// appear in order, each preceded by a number, and `T` must introduce time units.
+fn is_iso8601_duration(value: &str) -> bool {
+    fn components(part: &str, designators: &[char]) -> bool {
+        let mut next = 0;
+        let mut number = String::new();
+
+        for c in part.chars() {
+            if c.is_ascii_digit() || ((c == '.' || c == ',') && !number.is_empty()) {
+                number.push(c);
+                continue;
+            }
+
+            let Some(position) = designators[next..].iter().position(|d| *d == c) else {
+                return false;
+            };
+            if number.ends_with(['.', ',']) || number.replace(',', ".").parse::<f64>().is_err() {
+                return false;
+            }
+            number.clear();
+            next += position + 1;
+        }
+
+        number.is_empty()
+    }
+
+    let Some(rest) = value.strip_prefix('P') else {
+        return false;
+    };
+
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+
+    if date.is_empty() && time.is_none_or(str::is_empty) {
+        return false;
+    }
+
+    components(date, &['Y', 'M', 'W', 'D'])
+        && time.is_none_or(|time| !time.is_empty() && components(time, &['H', 'M', 'S']))
+}
+
+/// Whether values of this format are secrets that must never appear in errors or logs
+fn is_secret(format: Option<&Format>) -> bool {
+    matches!(format, Some(Format::Password))
+}
+
+/// Whether `format` should be checked for `value`: string formats only apply
+/// to strings, while integer widths apply to numbers and numeric strings alike.
+fn format_applies(value: &Value, format: Option<&Format>) -> bool {
+    value.is_string() || matches!(format, Some(Format::Int32 | Format::Int64))
+}
+
+fn validate_integer_format(key: &str, value: &Value, kind: &str, min: i64, max: i64) -> Result<()> {
+    let parsed = match value {
+        Value::Number(n) if n.is_f64() => None,
+        Value::Number(n) => n
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| n.as_u64().map(i128::from)),
+        Value::String(s) => s.parse::<i128>().ok(),
+        _ => None,
+    };
+
+    let Some(int_val) = parsed else {
+        return Err(anyhow!(
+            "The value of '{}' must be an integer for format '{}'",
+            key,
+            kind
+        ));
+    };
+
+    if int_val < i128::from(min) || int_val > i128::from(max) {
+        return Err(anyhow!(
+            "The value of '{}' is out of range for format '{}': {}",
+            key,
+            kind,
+            int_val
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_enum_value(
+    key: &str,
+    value: &Value,
+    enum_values: &[serde_yaml::Value],
+    redact: bool,
+) -> Result<()> {
     for enum_val in enum_values {
         if values_equal(value, enum_val) {
             return Ok(());
@@ -516,7 +2534,11 @@ fn validate_enum_value(key: &str, value: &Value, enum_values: &[serde_yaml::Valu
 
     Err(anyhow!(
         "Value '{}' for field '{}' is not in allowed enum values: [{}]",
-        format_json_value(value),
+        if redact {
+            REDACTED.to_string()
+        } else {
+            format_json_value(value)
+        },
         key,
         enum_strings.join(", ")
     ))
@@ -591,65 +2613,51 @@ fn format_json_value(value: &Value) -> String {
         _ => format!("{value:?}"),
     }
 }
-fn validate_field_type(key: &str, value: &Value, field_type: Option<TypeOrUnion>) -> Result<()> {
+fn validate_field_type(key: &str, value: &Value, field_type: Option<&TypeOrUnion>) -> Result<()> {
     use Type::*;
 
     match field_type {
-        Some(TypeOrUnion::Single(Object)) => {
-            if !value.is_object() {
-                return Err(anyhow!("the value of '{}' must be an Object", key));
-            }
+        Some(TypeOrUnion::Single(Object)) if !value.is_object() => {
+            return Err(anyhow!("the value of '{}' must be an Object", key));
         }
-        Some(TypeOrUnion::Single(String)) => {
-            if !value.is_string() {
-                return Err(anyhow!("the value of '{}' must be a String", key));
-            }
+        Some(TypeOrUnion::Single(String)) if !value.is_string() => {
+            return Err(anyhow!("the value of '{}' must be a String", key));
         }
-        Some(TypeOrUnion::Single(Integer)) => {
-            if !value.is_i64() {
-                if let Some(str_val) = value.as_str() {
-                    if str_val.parse::<i64>().is_err() {
-                        return Err(anyhow!("the value of '{}' must be an Integer", key));
-                    }
-                } else {
+        Some(TypeOrUnion::Single(Integer)) if !value.is_i64() => {
+            if let Some(str_val) = value.as_str() {
+                if str_val.parse::<i64>().is_err() {
                     return Err(anyhow!("the value of '{}' must be an Integer", key));
                 }
+            } else {
+                return Err(anyhow!("the value of '{}' must be an Integer", key));
             }
         }
-        Some(TypeOrUnion::Single(Number)) => {
-            if !value.is_number() {
-                if let Some(str_val) = value.as_str() {
-                    if str_val.parse::<f64>().is_err() {
-                        return Err(anyhow!("the value of '{}' must be a Number", key));
-                    }
-                } else {
+        Some(TypeOrUnion::Single(Number)) if !value.is_number() => {
+            if let Some(str_val) = value.as_str() {
+                if str_val.parse::<f64>().is_err() {
                     return Err(anyhow!("the value of '{}' must be a Number", key));
                 }
+            } else {
+                return Err(anyhow!("the value of '{}' must be a Number", key));
             }
         }
-        Some(TypeOrUnion::Single(Array)) => {
-            if !value.is_array() {
-                return Err(anyhow!("the value of '{}' must be an Array", key));
-            }
+        Some(TypeOrUnion::Single(Array)) if !value.is_array() => {
+            return Err(anyhow!("the value of '{}' must be an Array", key));
         }
-        Some(TypeOrUnion::Single(Boolean)) => {
-            if !value.is_boolean() {
-                if let Some(str_val) = value.as_str() {
-                    match str_val.to_lowercase().as_str() {
-                        "true" | "false" => {}
-                        _ => {
-                            return Err(anyhow!("the value of '{}' must be a Boolean", key));
-                        }
+        Some(TypeOrUnion::Single(Boolean)) if !value.is_boolean() => {
+            if let Some(str_val) = value.as_str() {
+                match str_val.to_lowercase().as_str() {
+                    "true" | "false" => {}
+                    _ => {
+                        return Err(anyhow!("the value of '{}' must be a Boolean", key));
                     }
-                } else {
-                    return Err(anyhow!("the value of '{}' must be a Boolean", key));
                 }
+            } else {
+                return Err(anyhow!("the value of '{}' must be a Boolean", key));
             }
         }
-        Some(TypeOrUnion::Single(Null)) => {
-            if !value.is_null() {
-                return Err(anyhow!("the value of '{}' must be Null", key));
-            }
+        Some(TypeOrUnion::Single(Null)) if !value.is_null() => {
+            return Err(anyhow!("the value of '{}' must be Null", key));
         }
         Some(TypeOrUnion::Single(Base64)) => {
             let str_val = value
@@ -664,30 +2672,23 @@ fn validate_field_type(key: &str, value: &Value, field_type: Option<TypeOrUnion>
                 return Err(anyhow!("the value of '{}' must be valid Base64", key));
             }
         }
-        Some(TypeOrUnion::Single(Binary)) => {
-            if !value.is_string() {
-                return Err(anyhow!(
-                    "the value of '{}' must be a String for binary data",
-                    key
-                ));
-            }
+        Some(TypeOrUnion::Single(Binary)) if !value.is_string() => {
+            return Err(anyhow!(
+                "the value of '{}' must be a String for binary data",
+                key
+            ));
         }
-        Some(TypeOrUnion::Union(types)) => {
-            let mut valid = false;
-            for single_type in types {
-                if validate_single_type_match(value, &single_type) {
-                    valid = true;
-                    break;
-                }
-            }
-            if !valid {
-                return Err(anyhow!(
-                    "the value of '{}' must match one of the union types",
-                    key
-                ));
-            }
+        Some(TypeOrUnion::Union(types))
+            if !types
+                .iter()
+                .any(|single_type| validate_single_type_match(value, single_type)) =>
+        {
+            return Err(anyhow!(
+                "the value of '{}' must match one of the union types",
+                key
+            ));
         }
-        None => {}
+        _ => {}
     }
 
     Ok(())
@@ -846,6 +2847,12 @@ fn validate_string_length(key: &str, str_val: &str, properties: &Properties) ->
 }
 
 fn validate_numeric_range(key: &str, value: f64, properties: &Properties) -> Result<()> {
+    validate_exclusive_bounds(
+        key,
+        &Value::from(value),
+        (properties.exclusive_minimum, properties.exclusive_maximum),
+    )?;
+
     if let Some(min) = properties.minimum {
         if value < min {
             return Err(anyhow!(
@@ -906,59 +2913,209 @@ fn format_error(kind: &str, key: &str, value: &str) -> anyhow::Error {
     )
 }
 
-fn extract_required_and_validate_props(
+/// Validates `fields` against the properties of the component schema
+/// `schema_ref` points to and of its `items`, returning the names both
+/// require, as resolved by [`compiled::CompiledSpec`]
+fn extract_required_and_validate_props<'a>(
     fields: &Map<String, Value>,
     schema_ref: &str,
-    components: &ComponentsObject,
-) -> Result<HashSet<String>> {
-    let filename = schema_ref
-        .rsplit('/')
-        .next()
-        .ok_or_else(|| anyhow!("Invalid schema reference: '{}'", schema_ref))?;
-
-    let mut requireds = HashSet::new();
-
-    if let Some(schema) = components.schemas.get(filename) {
-        requireds.extend(schema.required.iter().cloned());
-        validate_properties(fields, &schema.properties)?;
+    open_api: &'a OpenAPI,
+) -> Result<&'a [String]> {
+    let Some(schema) = open_api.compiled().schema(schema_ref) else {
+        return Ok(&[]);
+    };
 
-        if let Some(items) = &schema.items {
-            requireds.extend(items.required.iter().cloned());
-            validate_properties(fields, &items.properties)?;
-        }
+    let components = open_api.components.as_ref();
+    for properties in &schema.properties {
+        validate_properties(fields, Some(properties), components)?;
     }
 
-    Ok(requireds)
+    Ok(&schema.required)
 }
 
 fn validate_properties(
     fields: &Map<String, Value>,
-    properties: &Option<HashMap<String, Properties>>,
+    properties: Option<&IndexMap<String, Properties>>,
+    components: Option<&ComponentsObject>,
 ) -> Result<()> {
     if let Some(properties) = properties {
         for (key, prop) in properties {
             if let Some(value) = fields.get(key) {
-                validate_field_type(key, value, prop.r#type.clone())?;
+                validate_property_value(key, value, prop, components)?;
+            }
+        }
+    }
 
-                if let Some(TypeOrUnion::Single(Type::String)) = prop.r#type {
-                    validate_field_format(key, value, prop.format.as_ref())?;
-                }
+    Ok(())
+}
 
-                if let Some(enum_values) = &prop.r#enum {
-                    validate_enum_value(key, value, enum_values)?;
-                }
+fn validate_property_value(
+    key: &str,
+    value: &Value,
+    prop: &Properties,
+    components: Option<&ComponentsObject>,
+) -> Result<()> {
+    if let Some(schema_ref) = &prop.r#ref {
+        return validate_referenced_value(key, value, schema_ref, components);
+    }
+
+    validate_field_type(key, value, prop.r#type.as_ref())?;
+
+    if prop.r#type == Some(TypeOrUnion::Single(Type::String))
+        || matches!(prop.format, Some(Format::Int32 | Format::Int64))
+    {
+        validate_field_format(key, value, prop.format.as_ref())?;
+    }
+
+    let redact = is_secret(prop.format.as_ref());
+
+    if let Some(enum_values) = &prop.r#enum {
+        validate_enum_value(key, value, enum_values, redact)?;
+    }
+
+    validate_pattern(key, value, prop.pattern.as_ref(), redact)?;
+
+    validate_field_length_limit(key, value, prop)?;
 
-                validate_pattern(key, value, prop.pattern.as_ref())?;
+    validate_encoded_content(
+        key,
+        value,
+        prop.content_encoding.as_deref(),
+        prop.content_media_type.as_deref(),
+        prop.content_schema.as_deref(),
+        components,
+    )?;
+
+    if let (Some(arr), Some(items)) = (value.as_array(), &prop.items) {
+        for (index, item) in arr.iter().enumerate() {
+            validate_property_value(&format!("{key}[{index}]"), item, items, components)?;
+        }
+    }
+
+    if let Some(map) = value.as_object() {
+        validate_nested_object(
+            key,
+            map,
+            &prop.required,
+            prop.properties.as_ref(),
+            components,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn validate_referenced_value(
+    key: &str,
+    value: &Value,
+    schema_ref: &str,
+    components: Option<&ComponentsObject>,
+) -> Result<()> {
+    let Some(schema) = components.and_then(|c| resolve_schema(schema_ref, c)) else {
+        return Err(anyhow!(
+            "Unresolved schema reference '{}' for field '{}'",
+            schema_ref,
+            key
+        ));
+    };
+
+    validate_component_value(key, value, schema, components)
+}
+
+fn validate_component_value(
+    key: &str,
+    value: &Value,
+    schema: &parse::ComponentSchemaBase,
+    components: Option<&ComponentsObject>,
+) -> Result<()> {
+    if let Some(schema_ref) = &schema.r#ref {
+        return validate_referenced_value(key, value, schema_ref, components);
+    }
+
+    validate_field_type(key, value, schema.r#type.as_ref())?;
+
+    if format_applies(value, schema.format.as_ref()) {
+        validate_field_format(key, value, schema.format.as_ref())?;
+    }
+
+    let redact = is_secret(schema.format.as_ref());
+
+    if let Some(enum_values) = &schema.r#enum {
+        validate_enum_value(key, value, enum_values, redact)?;
+    }
 
-                validate_field_length_limit(key, value, prop)?;
+    validate_pattern(key, value, schema.pattern.as_ref(), redact)?;
+
+    validate_value_bounds(
+        key,
+        value,
+        (schema.min_length, schema.max_length),
+        (schema.minimum, schema.maximum),
+    )?;
+
+    validate_exclusive_bounds(
+        key,
+        value,
+        (schema.exclusive_minimum, schema.exclusive_maximum),
+    )?;
+
+    if let Some(arr) = value.as_array() {
+        validate_item_count(key, arr.len(), schema.min_items, schema.max_items)?;
+        if let Some(items) = &schema.items {
+            for (index, item) in arr.iter().enumerate() {
+                validate_component_value(&format!("{key}[{index}]"), item, items, components)?;
             }
-            validate_properties(fields, &prop.properties)?;
+        }
+    }
+
+    let Some(map) = value.as_object() else {
+        return Ok(());
+    };
+
+    validate_nested_object(
+        key,
+        map,
+        &schema.required,
+        schema.properties.as_ref(),
+        components,
+    )?;
+
+    for part in schema.all_of.iter().flatten() {
+        match &part.r#ref {
+            Some(part_ref) => validate_referenced_value(key, value, part_ref, components)?,
+            None => validate_properties(map, Some(&part.properties), components)?,
         }
     }
 
     Ok(())
 }
 
+fn validate_nested_object(
+    key: &str,
+    fields: &Map<String, Value>,
+    required: &[String],
+    properties: Option<&IndexMap<String, Properties>>,
+    components: Option<&ComponentsObject>,
+) -> Result<()> {
+    for field in required {
+        if !fields.contains_key(field) {
+            return Err(anyhow!("Missing required field '{}' in '{}'", field, key));
+        }
+    }
+
+    validate_properties(fields, properties, components)
+}
+
+fn resolve_schema<'a>(
+    schema_ref: &str,
+    components: &'a ComponentsObject,
+) -> Option<&'a parse::ComponentSchemaBase> {
+    schema_ref
+        .rsplit('/')
+        .next()
+        .and_then(|name| components.schemas.get(name))
+}
+
 fn collect_refs(schema: &parse::Schema) -> Vec<&str> {
     let mut refs = Vec::new();
     if let Some(r) = &schema.r#ref {
@@ -1009,6 +3166,12 @@ fn validate_string_constraints(key: &str, value: &Value, schema: &parse::Schema)
 }
 
 fn validate_numeric_constraints(key: &str, value: &Value, schema: &parse::Schema) -> Result<()> {
+    validate_exclusive_bounds(
+        key,
+        value,
+        (schema.exclusive_minimum, schema.exclusive_maximum),
+    )?;
+
     if let Some(num_val) = value.as_f64() {
         if let Some(min) = schema.minimum {
             if num_val < min {
@@ -1035,10 +3198,15 @@ fn validate_numeric_constraints(key: &str, value: &Value, schema: &parse::Schema
     Ok(())
 }
 
-fn validate_pattern(key: &str, value: &Value, pattern: Option<&String>) -> Result<()> {
+fn validate_pattern(
+    key: &str,
+    value: &Value,
+    pattern: Option<&String>,
+    redact: bool,
+) -> Result<()> {
     if let Some(pattern_str) = pattern {
         if let Some(str_val) = value.as_str() {
-            let regex = Regex::new(pattern_str).map_err(|e| {
+            let regex = compiled::pattern(pattern_str).map_err(|e| {
                 anyhow!(
                     "Invalid regex pattern '{}' for field '{}': {}",
                     pattern_str,
@@ -1050,7 +3218,7 @@ fn validate_pattern(key: &str, value: &Value, pattern: Option<&String>) -> Resul
             if !regex.is_match(str_val) {
                 return Err(anyhow!(
                     "Value '{}' for field '{}' does not match the required pattern '{}'",
-                    str_val,
+                    if redact { REDACTED } else { str_val },
                     key,
                     pattern_str
                 ));
@@ -0,0 +1,163 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::model::parse::OpenAPI;
+    use crate::request::multipart::{self, Part};
+    use crate::validator::multipart;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Upload API
+  version: 1.0.0
+paths:
+  /avatars:
+    post:
+      requestBody:
+        content:
+          multipart/form-data:
+            schema:
+              type: object
+              required: [avatar, user_id]
+              properties:
+                user_id:
+                  type: integer
+                avatar:
+                  type: string
+                  format: binary
+                  maxLength: 8
+                attachments:
+                  type: array
+                  maxItems: 2
+                  items:
+                    type: string
+                    format: binary
+            encoding:
+              avatar:
+                contentType: image/png, image/jpeg
+              attachments:
+                contentType: text/*
+"#;
+
+    fn file(name: &str, content_type: &str, data: &[u8]) -> Part {
+        Part {
+            name: name.to_string(),
+            filename: Some(format!("{name}.bin")),
+            content_type: Some(content_type.to_string()),
+            data: data.to_vec(),
+        }
+    }
+
+    fn field(name: &str, value: &str) -> Part {
+        Part {
+            name: name.to_string(),
+            filename: None,
+            content_type: None,
+            data: value.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_parse_multipart_body() {
+        let content_type = "multipart/form-data; boundary=\"XyZ\"";
+        let boundary = multipart::boundary(content_type).unwrap();
+        assert_eq!(boundary, "XyZ");
+        assert!(multipart::boundary("application/json").is_none());
+
+        let body = b"--XyZ\r\n\
+Content-Disposition: form-data; name=\"user_id\"\r\n\r\n\
+42\r\n\
+--XyZ\r\n\
+Content-Disposition: form-data; name=\"avatar\"; filename=\"a.png\"\r\n\
+Content-Type: image/png\r\n\r\n\
+\x89PNG\r\n\
+--XyZ--\r\n";
+
+        let parts = multipart::parse(body, &boundary).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0], field("user_id", "42"));
+        assert!(!parts[0].is_file());
+        assert_eq!(parts[1].filename.as_deref(), Some("a.png"));
+        assert_eq!(parts[1].content_type.as_deref(), Some("image/png"));
+        assert_eq!(parts[1].data, b"\x89PNG");
+
+        assert!(multipart::parse(b"--XyZ\r\nno headers", &boundary).is_err());
+    }
+
+    #[test]
+    fn test_file_upload_constraints() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+
+        let valid = [
+            field("user_id", "42"),
+            file("avatar", "image/png", b"png"),
+            file("attachments", "text/plain", b"a"),
+            file("attachments", "text/csv", b"b"),
+        ];
+        assert!(multipart("/avatars", "post", &valid, &open_api).is_ok());
+
+        let cases = [
+            (
+                vec![field("user_id", "42")],
+                "Missing required file 'avatar'",
+            ),
+            (
+                vec![file("avatar", "image/png", b"png")],
+                "Missing required field 'user_id'",
+            ),
+            (
+                vec![
+                    field("user_id", "42"),
+                    file("avatar", "image/png", b"too large!"),
+                ],
+                "exceeds the maximum size of 8 bytes",
+            ),
+            (
+                vec![field("user_id", "42"), file("avatar", "image/gif", b"gif")],
+                "content type 'image/gif'",
+            ),
+            (
+                vec![
+                    field("user_id", "42"),
+                    file("avatar", "image/jpeg", b"jpg"),
+                    file("attachments", "application/pdf", b"pdf"),
+                ],
+                "File 'attachments'",
+            ),
+            (
+                vec![
+                    field("user_id", "42"),
+                    file("avatar", "image/jpeg", b"jpg"),
+                    file("attachments", "text/plain", b"a"),
+                    file("attachments", "text/plain", b"b"),
+                    file("attachments", "text/plain", b"c"),
+                ],
+                "at most 2 items",
+            ),
+            (
+                vec![field("user_id", "abc"), file("avatar", "image/png", b"png")],
+                "user_id",
+            ),
+        ];
+        for (parts, expected) in cases {
+            let err = multipart("/avatars", "post", &parts, &open_api).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+}
@@ -18,9 +18,10 @@
 #[cfg(test)]
 mod tests {
     use crate::model::parse::{
-        In, InfoObject, OpenAPI, Parameter, PathBase, PathItem, Schema, Type, TypeOrUnion,
+        In, OpenAPI, Parameter, PathBase, PathItem, Schema, Type, TypeOrUnion,
     };
     use crate::validator::{query, validate_pattern};
+    use indexmap::IndexMap;
     use serde_json::Value;
     use std::collections::HashMap;
 
@@ -53,21 +54,16 @@ mod tests {
     }
 
     fn create_base_openapi() -> OpenAPI {
-        OpenAPI {
-            openapi: "3.1.0".to_string(),
-            info: InfoObject {
-                title: "Test API".to_string(),
-                description: None,
-                version: "1.0.0".to_string(),
-                summary: None,
-            },
-            servers: vec![],
-            paths: HashMap::new(),
-            components: None,
-            json_schema_dialect: None,
-            webhooks: None,
-            self_ref: None,
-        }
+        OpenAPI::yaml(
+            r#"
+openapi: 3.1.0
+info:
+  title: Test API
+  version: 1.0.0
+paths: {}
+"#,
+        )
+        .unwrap()
     }
 
     fn create_parameter_with_pattern(
@@ -82,11 +78,18 @@ mod tests {
             required,
             description: None,
             example: None,
+            examples: IndexMap::new(),
             r#type: Some(TypeOrUnion::Single(Type::String)),
             r#enum: None,
             pattern,
             schema: None,
-            extra: HashMap::new(),
+            content: IndexMap::new(),
+            deprecated: false,
+            allow_empty_value: false,
+            allow_reserved: false,
+            style: None,
+            explode: None,
+            extra: IndexMap::new(),
         }
     }
 
@@ -116,6 +119,12 @@ mod tests {
             max_length: None,
             minimum: None,
             maximum: None,
+            exclusive_minimum: None,
+            exclusive_maximum: None,
+            content_encoding: None,
+            content_media_type: None,
+            content_schema: None,
+            extra: IndexMap::new(),
         };
 
         Parameter {
@@ -125,11 +134,18 @@ mod tests {
             required,
             description: None,
             example: None,
+            examples: IndexMap::new(),
             r#type: None,
             r#enum: None,
             pattern: None,
             schema: Some(Box::new(schema)),
-            extra: HashMap::new(),
+            content: IndexMap::new(),
+            deprecated: false,
+            allow_empty_value: false,
+            allow_reserved: false,
+            style: None,
+            explode: None,
+            extra: IndexMap::new(),
         }
     }
 
@@ -137,36 +153,47 @@ mod tests {
         let mut openapi = create_base_openapi();
 
         let path_base = PathBase {
+            tags: vec![],
             summary: None,
             description: None,
+            external_docs: None,
             operation_id: None,
             parameters: Some(parameters),
             request: None,
             servers: vec![],
+            deprecated: false,
+            responses: IndexMap::new(),
+            security: None,
+            callbacks: IndexMap::new(),
+            extra: IndexMap::new(),
         };
 
-        let mut operations = HashMap::new();
+        let mut operations = IndexMap::new();
         operations.insert("get".to_string(), path_base);
 
         let path_item = PathItem {
+            r#ref: None,
+            summary: None,
+            description: None,
             parameters: None,
             operations,
             servers: vec![],
             query: None,
-            extra: serde_yaml::Value::Null,
+            additional_operations: IndexMap::new(),
+            extra: IndexMap::new(),
         };
 
-        openapi.paths.insert("/test".to_string(), path_item);
+        openapi.paths_mut().insert("/test".to_string(), path_item);
         openapi
     }
 
     fn test_query_validation(openapi: &OpenAPI, params: &[(&str, &str)], should_succeed: bool) {
-        let query_params: HashMap<String, String> = params
+        let query_params: HashMap<String, Vec<String>> = params
             .iter()
-            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .map(|(k, v)| (k.to_string(), vec![v.to_string()]))
             .collect();
 
-        let result = query("/test", &query_params, openapi);
+        let result = query("/test", "get", &query_params, openapi);
 
         if should_succeed {
             assert!(
@@ -287,9 +314,10 @@ mod tests {
 
         let result = query(
             "/test",
+            "get",
             &[("test", "anything")]
                 .iter()
-                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .map(|(k, v)| (k.to_string(), vec![v.to_string()]))
                 .collect(),
             &openapi,
         );
@@ -317,7 +345,7 @@ mod tests {
         ];
 
         for (name, value) in test_cases.iter() {
-            let result = validate_pattern(name, value, Some(&"^\\d+$".to_string()));
+            let result = validate_pattern(name, value, Some(&"^\\d+$".to_string()), false);
             assert!(
                 result.is_ok(),
                 "Non-string value {} should pass pattern validation",
@@ -379,6 +407,7 @@ mod tests {
                 "test_field",
                 &Value::String(value.to_string()),
                 pattern_string.as_ref(),
+                false,
             );
 
             if *should_succeed {
@@ -421,6 +450,12 @@ mod tests {
             max_length: None,
             minimum: None,
             maximum: None,
+            exclusive_minimum: None,
+            exclusive_maximum: None,
+            content_encoding: None,
+            content_media_type: None,
+            content_schema: None,
+            extra: IndexMap::new(),
         };
 
         let param = Parameter {
@@ -430,11 +465,18 @@ mod tests {
             required: true,
             description: None,
             example: None,
+            examples: IndexMap::new(),
             r#type: None,
             r#enum: None,
             pattern: Some("^param-pattern$".to_string()),
             schema: Some(Box::new(schema)),
-            extra: HashMap::new(),
+            content: IndexMap::new(),
+            deprecated: false,
+            allow_empty_value: false,
+            allow_reserved: false,
+            style: None,
+            explode: None,
+            extra: IndexMap::new(),
         };
 
         let openapi = create_openapi_with_parameters(vec![param]);
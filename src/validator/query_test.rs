@@ -0,0 +1,606 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::model::parse::{In, OpenAPI};
+    use crate::validator::{decode_query, parse_query, query, typed_query, ValidationOptions};
+
+    #[test]
+    fn test_query_values_are_decoded() {
+        let pairs = parse_query("name=hello%20world&q=a+b&email=a%40example.com&empty=");
+        assert_eq!(pairs["name"], ["hello world"]);
+        assert_eq!(pairs["q"], ["a b"]);
+        assert_eq!(pairs["email"], ["a@example.com"]);
+        assert_eq!(pairs["empty"], [""]);
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Search API
+  version: 1.0.0
+paths:
+  /search:
+    get:
+      parameters:
+        - name: q
+          in: query
+          schema:
+            type: string
+            pattern: '^[a-z ]+$'
+        - name: sort
+          in: query
+          schema:
+            type: string
+            enum: [name asc, name desc]
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(query("/search", "get", &parse_query("q=hello+world"), &open_api).is_ok());
+        assert!(query("/search", "get", &parse_query("q=hello%20world"), &open_api).is_ok());
+        assert!(query(
+            "/search",
+            "get",
+            &parse_query("sort=name%20desc"),
+            &open_api
+        )
+        .is_ok());
+        assert!(query("/search", "get", &parse_query("sort=name+asc"), &open_api).is_ok());
+        assert!(query("/search", "get", &parse_query("q=hello%2Bworld"), &open_api).is_err());
+    }
+
+    #[test]
+    fn test_repeated_keys_are_validated_as_array() {
+        let pairs = parse_query("tag=a&tag=b&tag=c");
+        assert_eq!(pairs["tag"], ["a", "b", "c"]);
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Tag API
+  version: 1.0.0
+paths:
+  /items:
+    get:
+      parameters:
+        - name: tag
+          in: query
+          schema:
+            type: array
+            maxItems: 3
+            items:
+              type: string
+              enum: [a, b, c]
+        - name: id
+          in: query
+          schema:
+            type: array
+            items:
+              type: integer
+              format: int32
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(query("/items", "get", &parse_query("tag=a&tag=b"), &open_api).is_ok());
+        assert!(query("/items", "get", &parse_query("tag=c"), &open_api).is_ok());
+        assert!(query("/items", "get", &parse_query("id=1&id=2"), &open_api).is_ok());
+
+        let err = query("/items", "get", &parse_query("tag=a&tag=x"), &open_api).unwrap_err();
+        assert!(err.to_string().contains("tag[1]"));
+
+        let err = query("/items", "get", &parse_query("id=1&id=abc"), &open_api).unwrap_err();
+        assert!(err.to_string().contains("id[1]"));
+
+        assert!(query(
+            "/items",
+            "get",
+            &parse_query("tag=a&tag=b&tag=c&tag=a"),
+            &open_api
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_deep_object_parameter() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Filter API
+  version: 1.0.0
+paths:
+  /users:
+    get:
+      parameters:
+        - name: filter
+          in: query
+          style: deepObject
+          explode: true
+          schema:
+            type: object
+            required: [name]
+            properties:
+              name:
+                type: string
+              age:
+                type: integer
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(query(
+            "/users",
+            "get",
+            &parse_query("filter[name]=x&filter[age]=3"),
+            &open_api
+        )
+        .is_ok());
+        assert!(query(
+            "/users",
+            "get",
+            &parse_query("filter%5Bname%5D=x"),
+            &open_api
+        )
+        .is_ok());
+        assert!(query("/users", "get", &parse_query(""), &open_api).is_ok());
+
+        let err = query("/users", "get", &parse_query("filter[age]=3"), &open_api).unwrap_err();
+        assert!(err.to_string().contains("Missing required field 'name'"));
+
+        let err = query(
+            "/users",
+            "get",
+            &parse_query("filter[name]=x&filter[age]=old"),
+            &open_api,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("age"));
+    }
+
+    #[test]
+    fn test_delimited_array_styles() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Lookup API
+  version: 1.0.0
+paths:
+  /lookup:
+    get:
+      parameters:
+        - name: ids
+          in: query
+          style: pipeDelimited
+          explode: false
+          schema:
+            type: array
+            items:
+              type: integer
+        - name: words
+          in: query
+          style: spaceDelimited
+          explode: false
+          schema:
+            type: array
+            maxItems: 2
+            items:
+              type: string
+              pattern: '^[a-z]+$'
+        - name: tags
+          in: query
+          explode: false
+          schema:
+            type: array
+            items:
+              type: string
+              enum: [a, b]
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        assert!(query("/lookup", "get", &parse_query("ids=1|2|3"), &open_api).is_ok());
+        assert!(query("/lookup", "get", &parse_query("ids=1%7C2"), &open_api).is_ok());
+        assert!(query("/lookup", "get", &parse_query("words=foo%20bar"), &open_api).is_ok());
+        assert!(query("/lookup", "get", &parse_query("tags=a,b"), &open_api).is_ok());
+
+        let err = query("/lookup", "get", &parse_query("ids=1|x|3"), &open_api).unwrap_err();
+        assert!(err.to_string().contains("ids[1]"));
+
+        assert!(query(
+            "/lookup",
+            "get",
+            &parse_query("words=foo%20bar%20baz"),
+            &open_api
+        )
+        .is_err());
+        assert!(query("/lookup", "get", &parse_query("words=foo%20B4r"), &open_api).is_err());
+        assert!(query("/lookup", "get", &parse_query("tags=a,c"), &open_api).is_err());
+    }
+
+    #[test]
+    fn test_allow_empty_value() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Report API
+  version: 1.0.0
+paths:
+  /report:
+    get:
+      parameters:
+        - name: verbose
+          in: query
+          required: true
+          allowEmptyValue: true
+          schema:
+            type: boolean
+        - name: format
+          in: query
+          schema:
+            type: string
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        let parameters = open_api.paths["/report"].operations["get"]
+            .parameters
+            .as_ref()
+            .unwrap();
+        assert!(parameters[0].allow_empty_value);
+        assert!(!parameters[1].allow_empty_value);
+
+        assert!(query("/report", "get", &parse_query("verbose"), &open_api).is_ok());
+        assert!(query("/report", "get", &parse_query("verbose="), &open_api).is_ok());
+        assert!(query("/report", "get", &parse_query("verbose=true"), &open_api).is_ok());
+        assert!(query("/report", "get", &parse_query("verbose=maybe"), &open_api).is_err());
+        assert!(query("/report", "get", &parse_query("format=csv"), &open_api).is_err());
+    }
+
+    #[test]
+    fn test_empty_required_value_rejected_without_allow_empty_value() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Report API
+  version: 1.0.0
+paths:
+  /report:
+    get:
+      parameters:
+        - name: id
+          in: query
+          required: true
+          schema:
+            type: string
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        let err = query("/report", "get", &parse_query("id="), &open_api).unwrap_err();
+        assert!(err.to_string().contains("cannot be empty"));
+        assert!(query("/report", "get", &parse_query("id=1"), &open_api).is_ok());
+    }
+
+    const RESERVED_SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Redirect API
+  version: 1.0.0
+paths:
+  /redirect:
+    get:
+      parameters:
+        - name: target
+          in: query
+          allowReserved: true
+          schema:
+            type: string
+            pattern: '^https://'
+        - name: note
+          in: query
+          schema:
+            type: string
+        - name: ids
+          in: query
+          explode: false
+          schema:
+            type: array
+            items:
+              type: integer
+"#;
+
+    #[test]
+    fn test_allow_reserved_passes_through() {
+        let open_api = OpenAPI::yaml(RESERVED_SPEC).unwrap();
+
+        let pairs = decode_query(
+            "/redirect",
+            "get",
+            "target=https://a.io/x?y=1+2&note=a+b",
+            &open_api,
+        )
+        .unwrap();
+        assert_eq!(pairs["target"], ["https://a.io/x?y=1+2"]);
+        assert_eq!(pairs["note"], ["a b"]);
+        assert!(query("/redirect", "get", &pairs, &open_api).is_ok());
+
+        let pairs =
+            decode_query("/redirect", "get", "target=https%3A%2F%2Fa.io", &open_api).unwrap();
+        assert_eq!(pairs["target"], ["https://a.io"]);
+
+        // Reserved characters are tolerated outside strict mode
+        assert!(decode_query("/redirect", "get", "note=a:b", &open_api).is_ok());
+    }
+
+    #[test]
+    fn test_reserved_characters_rejected_in_strict_mode() {
+        let open_api = OpenAPI::yaml(RESERVED_SPEC)
+            .unwrap()
+            .with_options(ValidationOptions::new().with_strict(true));
+
+        let err = decode_query("/redirect", "get", "note=a:b", &open_api).unwrap_err();
+        assert!(err.to_string().contains("reserved character ':'"));
+
+        assert!(decode_query("/redirect", "get", "note=a%3Ab", &open_api).is_ok());
+        assert!(decode_query("/redirect", "get", "target=https://a.io", &open_api).is_ok());
+        assert!(decode_query("/redirect", "get", "ids=1,2,3", &open_api).is_ok());
+        assert!(decode_query("/redirect", "get", "unknown=a:b", &open_api).is_ok());
+    }
+
+    #[test]
+    fn test_querystring_parameter_content() {
+        let content = r#"
+openapi: 3.2.0
+info:
+  title: Search API
+  version: 1.0.0
+paths:
+  /search:
+    get:
+      parameters:
+        - name: filter
+          in: querystring
+          required: true
+          content:
+            application/json:
+              schema:
+                type: object
+                required: [status]
+                properties:
+                  status:
+                    type: string
+                    enum: [active, archived]
+                  age:
+                    type: object
+                    properties:
+                      gte:
+                        type: integer
+        - name: page
+          in: querystring
+          content:
+            application/x-www-form-urlencoded:
+              schema:
+                type: object
+                properties:
+                  size:
+                    type: integer
+                    maximum: 100
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        let check =
+            |query_string: &str| query("/search", "get", &parse_query(query_string), &open_api);
+
+        let filter = "filter=%7B%22status%22%3A%22active%22%2C%22age%22%3A%7B%22gte%22%3A18%7D%7D";
+        assert!(check(filter).is_ok());
+        assert!(check(&format!("{filter}&page=size%3D20")).is_ok());
+
+        let tests = [
+            ("", "'filter' is missing"),
+            ("filter=not-json", "must be valid JSON"),
+            (
+                r#"filter={"age":{"gte":18}}"#,
+                "Missing required field 'status'",
+            ),
+            (r#"filter={"status":"deleted"}"#, "status"),
+            (r#"filter={"status":"active","age":{"gte":"x"}}"#, "gte"),
+            (r#"filter={"status":"active"}&page=size%3D500"#, "size"),
+            (r#"filter={"status":"active"}&page=size%3Dmany"#, "size"),
+        ];
+        for (query_string, expected) in tests {
+            let err = check(query_string).unwrap_err();
+            assert!(err.to_string().contains(expected), "{query_string}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_compiled_operation_plan() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Search API
+  version: 1.0.0
+paths:
+  /items:
+    parameters:
+      - name: limit
+        in: query
+        schema:
+          type: integer
+      - $ref: '#/components/parameters/Page'
+    get:
+      parameters:
+        - name: limit
+          in: query
+          required: true
+          schema:
+            type: integer
+            maximum: 50
+        - name: code
+          in: query
+          schema:
+            type: string
+            pattern: '^[A-Z]{3}$'
+      responses:
+        '200':
+          description: OK
+components:
+  parameters:
+    Page:
+      name: page
+      in: query
+      required: true
+      schema:
+        type: integer
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        let plan = open_api.compiled_operation("/items", "GET").unwrap();
+        let names: Vec<&str> = plan
+            .parameters
+            .iter()
+            .filter_map(|parameter| parameter.name.as_deref())
+            .collect();
+        assert_eq!(names, ["page", "limit", "code"]);
+        assert!(plan.parameters[1].required);
+        assert!(plan.required.contains(&(In::Query, "page".to_string())));
+        assert!(plan.required.contains(&(In::Query, "limit".to_string())));
+        assert!(open_api.compiled_operation("/items", "post").is_none());
+
+        // Referenced parameters are validated like inline ones
+        assert!(query(
+            "/items",
+            "get",
+            &parse_query("limit=5&page=2&code=ABC"),
+            &open_api
+        )
+        .is_ok());
+        assert!(query("/items", "get", &parse_query("limit=5"), &open_api).is_err());
+        assert!(query("/items", "get", &parse_query("limit=5&page=two"), &open_api).is_err());
+        assert!(query(
+            "/items",
+            "get",
+            &parse_query("limit=5&page=2&code=abc"),
+            &open_api
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_query_follows_the_operation() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Items API
+  version: 1.0.0
+paths:
+  /items:
+    parameters:
+      - name: limit
+        in: query
+        schema:
+          type: integer
+    get:
+      parameters:
+        - name: limit
+          in: query
+          required: true
+          schema:
+            type: string
+            enum: [all]
+      responses:
+        '200':
+          description: OK
+    post:
+      parameters:
+        - name: dryRun
+          in: query
+          required: true
+          schema:
+            type: boolean
+      responses:
+        '201':
+          description: Created
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+
+        // `dryRun` is required by POST only
+        assert!(query("/items", "get", &parse_query("limit=all"), &open_api).is_ok());
+        assert!(query("/items", "post", &parse_query(""), &open_api).is_err());
+        assert!(query("/items", "post", &parse_query("dryRun=true"), &open_api).is_ok());
+
+        // GET overrides the path-level `limit`, POST keeps it
+        assert!(query("/items", "get", &parse_query(""), &open_api).is_err());
+        assert!(query("/items", "get", &parse_query("limit=5"), &open_api).is_err());
+        assert!(query(
+            "/items",
+            "post",
+            &parse_query("dryRun=true&limit=5"),
+            &open_api
+        )
+        .is_ok());
+        assert!(query(
+            "/items",
+            "post",
+            &parse_query("dryRun=true&limit=all"),
+            &open_api
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_typed_query_follows_declared_types() {
+        let content = r#"
+openapi: 3.0.0
+info:
+  title: Users API
+  version: 1.0.0
+paths:
+  /users:
+    get:
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+        - name: ids
+          in: query
+          style: pipeDelimited
+          schema:
+            type: array
+            items:
+              type: integer
+        - name: filter
+          in: query
+          style: deepObject
+          schema:
+            type: object
+            properties:
+              active:
+                type: boolean
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        let pairs = parse_query("limit=5&ids=1|2&filter[active]=true&extra=x&extra=y");
+
+        let fields = typed_query("/users", "get", &pairs, &open_api);
+        assert_eq!(fields["limit"], serde_json::json!(5));
+        assert_eq!(fields["ids"], serde_json::json!([1, 2]));
+        assert_eq!(fields["filter"], serde_json::json!({"active": true}));
+        assert_eq!(fields["extra"], serde_json::json!(["x", "y"]));
+        assert!(!fields.contains_key("filter[active]"));
+
+        let open_api = open_api.with_options(ValidationOptions::new().with_query_as_strings(true));
+        let fields = typed_query("/users", "get", &pairs, &open_api);
+        assert_eq!(fields["limit"], serde_json::json!("5"));
+        assert_eq!(fields["ids"], serde_json::json!(["1", "2"]));
+        assert_eq!(fields["filter"], serde_json::json!({"active": "true"}));
+    }
+}
@@ -0,0 +1,164 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Masks sensitive request values in validation errors before they are
+//! logged or sent back to clients.
+
+use crate::validator::{parse_query, ValidationOptions};
+use serde_json::Value;
+
+/// Placeholder echoed in errors instead of values that must not be logged
+pub(crate) const REDACTED: &str = "******";
+
+/// Headers whose values are always redacted, in addition to
+/// [`ValidationOptions::redacted_headers`]
+pub const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+];
+
+/// Collects the values of a request that must not appear in errors: those
+/// of sensitive headers, and of query parameters and JSON body fields whose
+/// names match [`ValidationOptions::redacted_fields`]
+pub fn secrets<'a>(
+    headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+    query_string: &str,
+    body: Option<&[u8]>,
+    options: &ValidationOptions,
+) -> Vec<String> {
+    let mut secrets = Vec::new();
+    for (name, value) in headers {
+        let sensitive = SENSITIVE_HEADERS
+            .iter()
+            .copied()
+            .chain(options.redacted_headers.iter().map(String::as_str))
+            .any(|header| header.eq_ignore_ascii_case(name));
+        if sensitive {
+            // The credentials of `Bearer <token>` or the values of cookies
+            // may be echoed without the rest of the header
+            secrets.extend(value.split([';', ',']).map(|part| {
+                let part = part.trim();
+                match part.split_once(' ') {
+                    Some((_, credentials)) => credentials.to_string(),
+                    None => part
+                        .split_once('=')
+                        .map_or(part, |(_, value)| value)
+                        .to_string(),
+                }
+            }));
+            secrets.push(value.to_string());
+        }
+    }
+
+    if options.redacted_fields.is_empty() {
+        return clean(secrets);
+    }
+    for (name, values) in parse_query(query_string) {
+        if is_redacted_field(&name, options) {
+            secrets.extend(values);
+        }
+    }
+    if let Some(body) = body.and_then(|body| serde_json::from_slice::<Value>(body).ok()) {
+        body_secrets(&body, false, options, &mut secrets);
+    }
+    clean(secrets)
+}
+
+/// Replaces every occurrence of `secrets` in `message` that is not part of
+/// a longer word with [`REDACTED`]
+pub fn redact(message: &str, secrets: &[String]) -> String {
+    let mut message = message.to_string();
+    for secret in secrets {
+        let mut redacted = String::with_capacity(message.len());
+        let mut rest = message.as_str();
+        while let Some(position) = rest.find(secret.as_str()) {
+            let end = position + secret.len();
+            let (before, after) = (&rest[..position], &rest[end..]);
+            let bounded = !before
+                .chars()
+                .next_back()
+                .is_some_and(char::is_alphanumeric)
+                && !after.chars().next().is_some_and(char::is_alphanumeric);
+            redacted.push_str(before);
+            redacted.push_str(if bounded { REDACTED } else { secret });
+            rest = after;
+        }
+        redacted.push_str(rest);
+        message = redacted;
+    }
+    message
+}
+
+/// Whether values of the query parameter or body field `name` are redacted
+fn is_redacted_field(name: &str, options: &ValidationOptions) -> bool {
+    let name = name.to_ascii_lowercase();
+    options
+        .redacted_fields
+        .iter()
+        .any(|pattern| wildcard_matches(&pattern.to_ascii_lowercase(), &name))
+}
+
+fn body_secrets(value: &Value, redacted: bool, options: &ValidationOptions, out: &mut Vec<String>) {
+    match value {
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                let redacted = redacted || is_redacted_field(name, options);
+                body_secrets(value, redacted, options, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                body_secrets(item, redacted, options, out);
+            }
+        }
+        Value::String(value) if redacted => out.push(value.clone()),
+        Value::Number(value) if redacted => out.push(value.to_string()),
+        _ => {}
+    }
+}
+
+/// Drops empty secrets and puts longer ones first, so that a secret is
+/// redacted whole before any shorter secret it contains
+fn clean(mut secrets: Vec<String>) -> Vec<String> {
+    secrets.retain(|secret| !secret.is_empty());
+    secrets.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    secrets.dedup();
+    secrets
+}
+
+/// Matches `text` against a pattern whose `*` stand for any characters
+pub(crate) fn wildcard_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
@@ -0,0 +1,108 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::validator::redact::{redact, secrets};
+    use crate::validator::ValidationOptions;
+
+    #[test]
+    fn test_secrets_of_headers_and_fields() {
+        let options = ValidationOptions::new()
+            .with_redacted_field("*token*")
+            .with_redacted_field("ssn")
+            .with_redacted_header("X-Session");
+        let headers = [
+            ("authorization", "Bearer abc.def"),
+            ("cookie", "session=s3cr3t; theme=dark"),
+            ("x-session", "xyz"),
+            ("accept", "application/json"),
+        ];
+        let body = br#"{"name": "alice", "SSN": 123456789, "auth": {"refresh_token": ["r1"]}}"#;
+
+        let collected = secrets(headers, "access_token=t0k3n&page=2", Some(body), &options);
+        for secret in [
+            "Bearer abc.def",
+            "abc.def",
+            "s3cr3t",
+            "xyz",
+            "t0k3n",
+            "123456789",
+            "r1",
+        ] {
+            assert!(collected.contains(&secret.to_string()), "{secret}");
+        }
+        for public in ["application/json", "alice", "2"] {
+            assert!(!collected.contains(&public.to_string()), "{public}");
+        }
+
+        let collected = secrets(
+            [("Authorization", "Bearer abc")],
+            "token=t",
+            Some(br#"{"token": "t"}"#),
+            &ValidationOptions::new(),
+        );
+        assert_eq!(collected, ["Bearer abc", "abc"]);
+    }
+
+    #[test]
+    fn test_redact_whole_words() {
+        let secrets = vec!["s3cr3t".to_string(), "12".to_string()];
+        assert_eq!(
+            redact(
+                "Value 's3cr3t' for field 'pin' must be 12, but got 12 or 120",
+                &secrets
+            ),
+            "Value '******' for field 'pin' must be ******, but got ****** or 120"
+        );
+        assert_eq!(redact("no secrets here", &[]), "no secrets here");
+    }
+
+    #[cfg(feature = "test-with-axum")]
+    #[test]
+    fn test_validator_redacts_errors() {
+        use crate::model::parse::OpenAPI;
+        use crate::request::axum::RequestData;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Redacted API
+  version: 1.0.0
+paths:
+  /sessions:
+    get:
+      parameters:
+        - name: api_token
+          in: query
+          schema:
+            type: string
+            pattern: '^[a-f]+$'
+"#;
+        let options = ValidationOptions::new().with_redacted_field("*token");
+        let openapi = OpenAPI::yaml(content).unwrap().with_options(options);
+        let request = axum::http::Request::get("/sessions?api_token=XYZ123")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let error = openapi
+            .validator(RequestData::new(request, None))
+            .unwrap_err();
+        assert!(error.contains("'******'"), "{error}");
+        assert!(!error.contains("XYZ123"), "{error}");
+    }
+}
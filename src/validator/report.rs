@@ -0,0 +1,72 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A serializable account of one validation, for audit events and
+//! debugging headers.
+
+use serde::Serialize;
+
+/// Everything [`OpenAPI::validation_report`] found about a request: the
+/// operation it matched, every failed check and every warning.
+///
+/// Checks run independently, so a request with a bad query and a bad body
+/// reports both. Each check still reports only its first error.
+///
+/// [`OpenAPI::validation_report`]: crate::model::parse::OpenAPI::validation_report
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// The documented operation the request resolved to
+    pub operation: Option<MatchedOperation>,
+    pub errors: Vec<Issue>,
+    pub warnings: Vec<Issue>,
+    /// Time spent validating, left out without the `clock` feature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_us: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchedOperation {
+    /// The path template, such as `/users/{id}`
+    pub path: String,
+    /// The upper-cased HTTP method
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+}
+
+/// One error or warning of a [`ValidationReport`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Issue {
+    /// JSON pointer to the part of the request the issue is about, such as
+    /// `/body` or `/query/page`; empty for the request as a whole
+    pub pointer: String,
+    pub message: String,
+}
+
+impl Issue {
+    pub fn new(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+}
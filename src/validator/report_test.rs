@@ -0,0 +1,117 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(all(test, feature = "test-with-axum"))]
+mod tests {
+    use crate::model::parse::OpenAPI;
+    use crate::request::axum::RequestData;
+    use crate::validator::report::Issue;
+    use crate::validator::ValidationOptions;
+    use axum::body::{Body, Bytes};
+    use serde_json::json;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Reported API
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      operationId: createUser
+      parameters:
+        - name: legacy
+          in: query
+          deprecated: true
+          schema:
+            type: string
+        - name: page
+          in: query
+          schema:
+            type: integer
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+              properties:
+                name:
+                  type: string
+"#;
+
+    fn request(uri: &str, body: &'static str) -> RequestData {
+        let inner = axum::http::Request::post(uri)
+            .header("content-type", "application/json")
+            .header("x-request-id", "req-7")
+            .body(Body::empty())
+            .unwrap();
+        RequestData::new(inner, Some(Bytes::from_static(body.as_bytes())))
+    }
+
+    #[test]
+    fn test_report_collects_every_failed_check() {
+        let openapi = OpenAPI::yaml(SPEC).unwrap();
+
+        let report = openapi.validation_report(request("/users?legacy=1&page=x", "{}"));
+        assert!(!report.valid);
+        assert_eq!(report.request_id.as_deref(), Some("req-7"));
+        let operation = report.operation.as_ref().unwrap();
+        assert_eq!(operation.path, "/users");
+        assert_eq!(operation.method, "POST");
+        assert_eq!(operation.operation_id.as_deref(), Some("createUser"));
+        let pointers: Vec<&str> = report.errors.iter().map(|e| e.pointer.as_str()).collect();
+        assert_eq!(pointers, ["/query", "/body"]);
+        assert!(report.errors[1]
+            .message
+            .starts_with("Body validation failed"));
+        assert_eq!(
+            report.warnings,
+            [Issue::new(
+                "/query/legacy",
+                "Query parameter 'legacy' is deprecated"
+            )]
+        );
+
+        let serialized = serde_json::to_value(&report).unwrap();
+        assert_eq!(serialized["valid"], false);
+        assert_eq!(serialized["operation"]["operation_id"], "createUser");
+        assert_eq!(serialized["errors"][0]["pointer"], "/query");
+        assert!(serialized["duration_us"].is_u64());
+
+        let report = openapi.validation_report(request("/users?page=2", r#"{"name": "a"}"#));
+        assert!(report.valid);
+        assert!(report.errors.is_empty() && report.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_report_stops_after_unknown_method() {
+        let options = ValidationOptions::new().with_reject_deprecated(true);
+        let openapi = OpenAPI::yaml(SPEC).unwrap().with_options(options);
+        let inner = axum::http::Request::get("/users")
+            .body(Body::empty())
+            .unwrap();
+
+        let report = openapi.validation_report(RequestData::new(inner, None));
+        assert_eq!(report.operation.as_ref().map(|_| ()), None);
+        assert_eq!(
+            serde_json::to_value(&report.errors).unwrap()[0]["pointer"],
+            json!("/method")
+        );
+        assert_eq!(report.errors.len(), 1);
+    }
+}
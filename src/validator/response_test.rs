@@ -0,0 +1,226 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::model::parse::OpenAPI;
+    use crate::validator::{body, response, response_message};
+    use serde_json::json;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Users API
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/User'
+      responses:
+        '201':
+          description: Created
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/User'
+    get:
+      responses:
+        2XX:
+          description: Users
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/User'
+        default:
+          $ref: '#/components/responses/Error'
+  /teams:
+    get:
+      responses:
+        '200':
+          description: Team
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Team'
+components:
+  responses:
+    Error:
+      description: Unexpected error
+      headers:
+        X-Request-Id:
+          schema:
+            type: string
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/User'
+  schemas:
+    User:
+      type: object
+      properties:
+        id:
+          type: integer
+          readOnly: true
+        name:
+          type: string
+        password:
+          type: string
+          writeOnly: true
+        profile:
+          type: object
+          properties:
+            secret:
+              type: string
+              writeOnly: true
+        manager:
+          $ref: '#/components/schemas/User'
+        reports:
+          type: array
+          items:
+            $ref: '#/components/schemas/Member'
+    Member:
+      $ref: '#/components/schemas/User'
+    Team:
+      type: object
+      required: [name]
+      properties:
+        name:
+          type: string
+"#;
+
+    #[test]
+    fn test_response_rejects_write_only_fields() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+
+        let ok = json!({"id": 1, "name": "alice"});
+        assert!(response("/users", "post", 201, ok, &open_api).is_ok());
+
+        let leaked = json!({"id": 1, "name": "alice", "password": "hunter2"});
+        let err = response("/users", "post", 201, leaked, &open_api).unwrap_err();
+        assert!(err.to_string().contains("write-only field 'password'"));
+
+        let nested = json!({"id": 1, "profile": {"secret": "s"}});
+        assert!(response("/users", "post", 201, nested, &open_api).is_err());
+    }
+
+    #[test]
+    fn test_restricted_fields_follow_references() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+
+        let ok = json!({"id": 1, "manager": {"id": 2, "manager": {"id": 3}}});
+        assert!(response("/users", "post", 201, ok, &open_api).is_ok());
+
+        let leaked = json!({"id": 1, "manager": {"id": 2, "manager": {"password": "p"}}});
+        let err = response("/users", "post", 201, leaked, &open_api).unwrap_err();
+        assert!(err.to_string().contains("write-only field 'password'"));
+
+        let leaked = json!({"id": 1, "reports": [{"id": 2}, {"password": "p"}]});
+        assert!(response("/users", "post", 201, leaked, &open_api).is_err());
+
+        let err = body("/users", "post", json!({"manager": {"id": 2}}), &open_api).unwrap_err();
+        assert!(err.to_string().contains("read-only field 'id'"));
+    }
+
+    #[test]
+    fn test_response_follows_schema() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+
+        assert!(response("/teams", "get", 200, json!({"name": "core"}), &open_api).is_ok());
+
+        let err = response("/teams", "get", 200, json!({}), &open_api).unwrap_err();
+        assert!(err.to_string().contains("Missing required field 'name'"));
+
+        for body in [json!("core"), json!(null), json!([{"name": "core"}])] {
+            assert!(response("/teams", "get", 200, body, &open_api).is_err());
+        }
+    }
+
+    #[test]
+    fn test_response_status_resolution() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+
+        let list = json!([{"id": 1, "name": "alice"}]);
+        assert!(response("/users", "get", 200, list, &open_api).is_ok());
+
+        let leaked = json!([{"id": 1, "password": "hunter2"}]);
+        assert!(response("/users", "get", 206, leaked, &open_api).is_err());
+
+        let err = response("/users", "post", 500, json!({}), &open_api).unwrap_err();
+        assert!(err.to_string().contains("not declared"));
+    }
+
+    #[test]
+    fn test_response_reference() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+
+        let error = &open_api.paths["/users"].operations["get"].responses["default"];
+        let resolved = open_api.resolve_response(error);
+        assert_eq!(resolved.description.as_deref(), Some("Unexpected error"));
+        assert!(resolved.headers.contains_key("X-Request-Id"));
+
+        assert!(response("/users", "get", 500, json!({"name": "alice"}), &open_api).is_ok());
+        let leaked = json!({"password": "hunter2"});
+        assert!(response("/users", "get", 500, leaked, &open_api).is_err());
+    }
+
+    #[test]
+    fn test_request_rejects_read_only_fields() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+
+        assert!(body(
+            "/users",
+            "post",
+            json!({"name": "alice", "password": "x"}),
+            &open_api
+        )
+        .is_ok());
+
+        let err = body(
+            "/users",
+            "post",
+            json!({"id": 7, "name": "alice"}),
+            &open_api,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("read-only field 'id'"));
+    }
+
+    #[test]
+    fn test_response_message() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+        let json = Some("application/json; charset=utf-8");
+
+        let ok = br#"{"id": 1, "name": "alice"}"#;
+        assert!(response_message("/users", "post", 201, json, ok, &open_api).is_ok());
+        assert!(response_message("/users", "post", 201, None, b"", &open_api).is_ok());
+
+        let err =
+            response_message("/users", "post", 201, Some("text/plain"), ok, &open_api).unwrap_err();
+        assert!(err.to_string().contains("content type 'text/plain'"));
+
+        let leaked = br#"{"password": "hunter2"}"#;
+        assert!(response_message("/users", "post", 201, json, leaked, &open_api).is_err());
+        assert!(response_message("/users", "post", 201, json, b"{", &open_api).is_err());
+        assert!(response_message("/users", "post", 500, json, ok, &open_api).is_err());
+    }
+}
@@ -0,0 +1,459 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::model::parse::OpenAPI;
+    use crate::validator::{parse_query, security, Unauthorized};
+    use std::collections::HashMap;
+
+    const SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Secure API
+  version: 1.0.0
+security:
+  - header_key: []
+    query_key: []
+    cookie_key: []
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  securitySchemes:
+    header_key:
+      type: apiKey
+      name: X-API-Key
+      in: header
+    query_key:
+      type: apiKey
+      name: api_key
+      in: query
+    cookie_key:
+      type: apiKey
+      name: session
+      in: cookie
+"#;
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_api_key_locations() {
+        let open_api = OpenAPI::yaml(SPEC).unwrap();
+        let all_headers = [("x-api-key", "k1"), ("cookie", "theme=dark; session=s1")];
+
+        let check = |header_pairs: &[(&str, &str)], query: &str| {
+            security(
+                "/users",
+                "get",
+                &headers(header_pairs),
+                &parse_query(query),
+                &open_api,
+            )
+        };
+
+        assert!(check(&all_headers, "api_key=q1").is_ok());
+
+        struct Tests {
+            headers: Vec<(&'static str, &'static str)>,
+            query: &'static str,
+            expected: &'static str,
+        }
+
+        let tests = [
+            Tests {
+                headers: vec![("cookie", "session=s1")],
+                query: "api_key=q1",
+                expected: "header 'X-API-Key'",
+            },
+            Tests {
+                headers: all_headers.to_vec(),
+                query: "other=1",
+                expected: "query parameter 'api_key'",
+            },
+            Tests {
+                headers: all_headers.to_vec(),
+                query: "api_key=",
+                expected: "query parameter 'api_key'",
+            },
+            Tests {
+                headers: vec![("x-api-key", "k1"), ("cookie", "theme=dark")],
+                query: "api_key=q1",
+                expected: "cookie 'session'",
+            },
+        ];
+
+        for test in tests {
+            let err = check(&test.headers, test.query).unwrap_err();
+            let unauthorized = err.downcast_ref::<Unauthorized>().unwrap();
+            assert!(unauthorized.failures[0].contains(test.expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_undefined_scheme_is_reported() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Secure API
+  version: 1.0.0
+security:
+  - missing: []
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        let err =
+            security("/users", "get", &HashMap::new(), &HashMap::new(), &open_api).unwrap_err();
+        assert!(err.to_string().contains("'missing' is not defined"));
+        assert!(err.downcast_ref::<Unauthorized>().is_none());
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Secure API
+  version: 1.0.0
+security:
+  - jwt: []
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  securitySchemes:
+    jwt:
+      type: http
+      scheme: bearer
+      bearerFormat: JWT
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        let check = |authorization: Option<&str>| {
+            let header_pairs: Vec<(&str, &str)> = authorization
+                .map(|value| ("authorization", value))
+                .into_iter()
+                .collect();
+            security(
+                "/users",
+                "get",
+                &headers(&header_pairs),
+                &HashMap::new(),
+                &open_api,
+            )
+        };
+
+        // {"alg":"HS256","typ":"JWT"}.{"sub":"1234567890"}.signature
+        let jwt = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxMjM0NTY3ODkwIn0.\
+                   SflKxwRJSMeKKF2QT4fwpMeJf36POk6yJV_adQssw5c";
+        assert!(check(Some(&format!("Bearer {jwt}"))).is_ok());
+        assert!(check(Some(&format!("bearer {jwt}"))).is_ok());
+
+        let tests = [
+            (None, "Bearer token is missing"),
+            (Some("Bearer "), "Bearer token is missing"),
+            (Some("Basic dXNlcjpwYXNz"), "Bearer token is missing"),
+            (Some("Bearer opaque-token"), "not a well-formed JWT"),
+            (Some("Bearer a.b.c"), "not a well-formed JWT"),
+            (
+                Some("Bearer eyJhbGciOiJIUzI1NiJ9.bm90IGpzb24.sig"),
+                "not a well-formed JWT",
+            ),
+        ];
+        for (authorization, expected) in tests {
+            let err = check(authorization).unwrap_err();
+            let unauthorized = err.downcast_ref::<Unauthorized>().unwrap();
+            assert!(unauthorized.failures[0].contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_basic_credentials() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Secure API
+  version: 1.0.0
+security:
+  - basic: []
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  securitySchemes:
+    basic:
+      type: http
+      scheme: Basic
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        let check = |authorization: &str| {
+            let header_pairs = [("authorization", authorization)];
+            security(
+                "/users",
+                "get",
+                &headers(&header_pairs),
+                &HashMap::new(),
+                &open_api,
+            )
+        };
+
+        // user:pass
+        assert!(check("Basic dXNlcjpwYXNz").is_ok());
+        // user:
+        assert!(check("basic dXNlcjo=").is_ok());
+
+        let tests = [
+            ("", "Basic credentials is missing"),
+            ("Bearer dXNlcjpwYXNz", "Basic credentials is missing"),
+            ("Basic not*base64", "not valid base64"),
+            // userpass
+            ("Basic dXNlcnBhc3M=", "'user:password'"),
+            // :pass
+            ("Basic OnBhc3M=", "'user:password'"),
+        ];
+        for (authorization, expected) in tests {
+            let err = check(authorization).unwrap_err();
+            let unauthorized = err.downcast_ref::<Unauthorized>().unwrap();
+            assert!(unauthorized.failures[0].contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_requirement_alternatives() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Secure API
+  version: 1.0.0
+security:
+  - api_key: []
+    tenant: []
+  - basic: []
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  securitySchemes:
+    api_key:
+      type: apiKey
+      name: X-API-Key
+      in: header
+    tenant:
+      type: apiKey
+      name: tenant
+      in: query
+    basic:
+      type: http
+      scheme: basic
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        let check = |header_pairs: &[(&str, &str)], query: &str| {
+            security(
+                "/users",
+                "get",
+                &headers(header_pairs),
+                &parse_query(query),
+                &open_api,
+            )
+        };
+
+        // Both schemes of the first alternative
+        assert!(check(&[("x-api-key", "k")], "tenant=t").is_ok());
+        // The second alternative alone
+        assert!(check(&[("authorization", "Basic dXNlcjpwYXNz")], "").is_ok());
+
+        // Half of the first alternative is not enough
+        let err = check(&[("x-api-key", "k")], "").unwrap_err();
+        let unauthorized = err.downcast_ref::<Unauthorized>().unwrap();
+        assert_eq!(unauthorized.failures.len(), 2);
+        assert!(unauthorized.failures[0].starts_with("api_key + tenant: "));
+        assert!(unauthorized.failures[0].contains("query parameter 'tenant'"));
+        assert!(unauthorized.failures[1].starts_with("basic: "));
+        assert!(err.to_string().contains("; basic: "), "{err}");
+    }
+
+    #[test]
+    fn test_unverifiable_alternatives_are_not_satisfied() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Secure API
+  version: 1.0.0
+security:
+  - oauth: [read]
+  - api_key: []
+  - mtls: []
+  - digest: []
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  securitySchemes:
+    api_key:
+      type: apiKey
+      name: X-API-Key
+      in: header
+    oauth:
+      type: oauth2
+      flows:
+        clientCredentials:
+          tokenUrl: https://auth.example.com/token
+          scopes:
+            read: Read access
+    mtls:
+      type: mutualTLS
+    digest:
+      type: http
+      scheme: digest
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        let check = |header_pairs: &[(&str, &str)]| {
+            security(
+                "/users",
+                "get",
+                &headers(header_pairs),
+                &HashMap::new(),
+                &open_api,
+            )
+        };
+
+        let err = check(&[]).unwrap_err();
+        let unauthorized = err.downcast_ref::<Unauthorized>().unwrap();
+        assert_eq!(unauthorized.failures.len(), 4);
+        assert!(unauthorized.failures[0].starts_with("oauth: "));
+        assert!(unauthorized.failures[2].contains("cannot be verified"));
+        assert!(unauthorized.failures[3].contains("cannot be verified"));
+
+        assert!(check(&[("authorization", "Bearer token")]).is_ok());
+        assert!(check(&[("x-api-key", "k")]).is_ok());
+    }
+
+    #[test]
+    fn test_empty_requirement_makes_security_optional() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Secure API
+  version: 1.0.0
+security:
+  - api_key: []
+  - {}
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  securitySchemes:
+    api_key:
+      type: apiKey
+      name: X-API-Key
+      in: header
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        assert!(security("/users", "get", &HashMap::new(), &HashMap::new(), &open_api).is_ok());
+    }
+
+    #[test]
+    fn test_operation_security_overrides_document_default() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Secure API
+  version: 1.0.0
+security:
+  - api_key: []
+paths:
+  /health:
+    get:
+      security: []
+      responses:
+        '200':
+          description: OK
+  /admin:
+    get:
+      security:
+        - basic: []
+      responses:
+        '200':
+          description: OK
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  securitySchemes:
+    api_key:
+      type: apiKey
+      name: X-API-Key
+      in: header
+    basic:
+      type: http
+      scheme: basic
+"#;
+        let open_api = OpenAPI::yaml(content).unwrap();
+        let check = |path: &str, header_pairs: &[(&str, &str)]| {
+            security(
+                path,
+                "get",
+                &headers(header_pairs),
+                &HashMap::new(),
+                &open_api,
+            )
+        };
+        let api_key = [("x-api-key", "k")];
+        let basic = [("authorization", "Basic dXNlcjpwYXNz")];
+
+        // Public endpoint inside an authenticated API
+        assert!(check("/health", &[]).is_ok());
+
+        // Document default
+        assert!(check("/users", &api_key).is_ok());
+        assert!(check("/users", &basic).is_err());
+
+        // Operation-specific requirements replace the default
+        assert!(check("/admin", &basic).is_ok());
+        let err = check("/admin", &api_key).unwrap_err();
+        assert!(err.to_string().contains("basic: "), "{err}");
+    }
+}
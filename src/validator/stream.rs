@@ -0,0 +1,162 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Validation of request bodies while they are being received, so that
+//! oversized or invalid bodies are rejected before they are fully read.
+
+use crate::model::parse::{self, ComponentsObject, OpenAPI};
+use crate::validator::{
+    find_operation, media_type_matches, validate_schema_value, PayloadTooLarge,
+};
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+
+/// Enforces [`ValidationOptions::max_body_size`](super::ValidationOptions::max_body_size)
+/// on a body received in chunks. The default limit lets any size through.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BodyLimit {
+    limit: Option<usize>,
+    received: usize,
+}
+
+impl BodyLimit {
+    pub fn new(open_api: &OpenAPI) -> Self {
+        Self {
+            limit: open_api.options.max_body_size,
+            received: 0,
+        }
+    }
+
+    /// Rejects a declared `Content-Length` above the limit before any of the
+    /// body is read
+    pub fn check_declared(&self, content_length: Option<&str>) -> Result<()> {
+        let declared = content_length.and_then(|length| length.trim().parse::<usize>().ok());
+        match (self.limit, declared) {
+            (Some(limit), Some(declared)) if declared > limit => {
+                Err(PayloadTooLarge { limit }.into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Accounts for the next `len` bytes of the body
+    pub fn take(&mut self, len: usize) -> Result<()> {
+        self.received = self.received.saturating_add(len);
+        match self.limit {
+            Some(limit) if self.received > limit => Err(PayloadTooLarge { limit }.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Validates a JSON Lines / NDJSON body against the OpenAPI 3.2 `itemSchema`
+/// of its media type one line at a time, holding at most one incomplete line
+/// in memory.
+pub struct JsonLines<'a> {
+    item_schema: Option<&'a parse::Schema>,
+    components: Option<&'a ComponentsObject>,
+    /// Number of lines seen so far
+    lines: usize,
+    pending: Vec<u8>,
+}
+
+impl<'a> JsonLines<'a> {
+    /// Finds the media type of the `method` operation on `path` matching
+    /// `content_type`
+    pub fn new(
+        path: &str,
+        method: &str,
+        content_type: &str,
+        open_api: &'a OpenAPI,
+    ) -> Result<Self> {
+        let path_base = open_api
+            .path_item(path)
+            .context("Path not found in OpenAPI specification")?;
+
+        let Some(request) =
+            find_operation(path_base, method).and_then(|operation| operation.request.as_ref())
+        else {
+            return Err(anyhow!(
+                "Method '{}' for path '{}' does not accept a request body",
+                method,
+                path
+            ));
+        };
+
+        let media_type = request
+            .content
+            .iter()
+            .find(|(key, _)| media_type_matches(key, content_type))
+            .map(|(_, media_type)| media_type)
+            .with_context(|| format!("Request body does not accept {}", content_type))?;
+
+        Ok(Self {
+            item_schema: media_type.item_schema.as_ref(),
+            components: open_api.components.as_ref(),
+            lines: 0,
+            pending: vec![],
+        })
+    }
+
+    /// Validates the next complete line, without its line terminator
+    pub fn line(&mut self, line: &str) -> Result<()> {
+        self.lines += 1;
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+
+        let key = format!("line {}", self.lines);
+        let value: Value = serde_json::from_str(line)
+            .map_err(|e| anyhow!("The value of '{}' must be valid JSON: {}", key, e))?;
+
+        if let Some(item_schema) = self.item_schema {
+            validate_schema_value(&key, &value, item_schema, self.components)?;
+        }
+        Ok(())
+    }
+
+    /// Validates every line `chunk` completes, keeping its trailing partial
+    /// line for the next chunk
+    pub fn push(&mut self, chunk: &[u8]) -> Result<()> {
+        self.pending.extend_from_slice(chunk);
+        let Some(end) = self.pending.iter().rposition(|byte| *byte == b'\n') else {
+            return Ok(());
+        };
+
+        let complete: Vec<u8> = self.pending.drain(..=end).collect();
+        for line in complete[..end].split(|byte| *byte == b'\n') {
+            self.bytes(line)?;
+        }
+        Ok(())
+    }
+
+    /// Validates the last line, which may lack a terminator
+    pub fn finish(mut self) -> Result<()> {
+        let rest = std::mem::take(&mut self.pending);
+        if rest.is_empty() {
+            return Ok(());
+        }
+        self.bytes(&rest)
+    }
+
+    fn bytes(&mut self, line: &[u8]) -> Result<()> {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let line = std::str::from_utf8(line)
+            .map_err(|e| anyhow!("Line {} is not valid UTF-8: {}", self.lines + 1, e))?;
+        self.line(line)
+    }
+}
@@ -0,0 +1,161 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[cfg(all(test, feature = "tracing", feature = "axum"))]
+mod tests {
+    use crate::model::parse::OpenAPI;
+    use crate::request;
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// A span seen by [`Recorder`]: its name, parent and recorded fields
+    #[derive(Debug, Default, Clone)]
+    struct SpanRecord {
+        name: &'static str,
+        parent: Option<u64>,
+        fields: HashMap<&'static str, String>,
+    }
+
+    impl Visit for SpanRecord {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields.insert(field.name(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.fields.insert(field.name(), format!("{value:?}"));
+        }
+    }
+
+    #[derive(Default, Clone)]
+    struct Recorder {
+        next_id: Arc<AtomicU64>,
+        spans: Arc<Mutex<Vec<SpanRecord>>>,
+        stack: Arc<Mutex<Vec<u64>>>,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+            let mut record = SpanRecord {
+                name: span.metadata().name(),
+                parent: self.stack.lock().unwrap().last().copied(),
+                ..Default::default()
+            };
+            span.record(&mut record);
+            self.spans.lock().unwrap().push(record);
+            Id::from_u64(id)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            values.record(&mut spans[span.into_u64() as usize - 1]);
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, span: &Id) {
+            self.stack.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.stack.lock().unwrap().pop();
+        }
+    }
+
+    fn make_request(uri: &str) -> request::axum::RequestData {
+        request::axum::RequestData {
+            path: uri.split('?').next().unwrap().to_string(),
+            inner: axum::http::Request::builder()
+                .method("GET")
+                .uri(uri)
+                .body(axum::body::Body::empty())
+                .unwrap(),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_validation_spans() {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Traced API
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      operationId: getUser
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: integer
+        - name: limit
+          in: query
+          schema:
+            type: integer
+"#;
+        let openapi = OpenAPI::yaml(content).unwrap();
+        let recorder = Recorder::default();
+
+        tracing::subscriber::with_default(recorder.clone(), || {
+            assert!(openapi.validator(make_request("/users/1?limit=5")).is_ok());
+            assert!(openapi.validator(make_request("/users/1?limit=x")).is_err());
+        });
+
+        let spans = recorder.spans.lock().unwrap();
+        let roots: Vec<(usize, &SpanRecord)> = spans
+            .iter()
+            .enumerate()
+            .filter(|(_, span)| span.name == "openapi.validate")
+            .collect();
+        assert_eq!(roots.len(), 2);
+        for (_, root) in &roots {
+            assert_eq!(root.fields["method"], "GET");
+            assert_eq!(root.fields["path"], "/users/{id}");
+            assert_eq!(root.fields["operation_id"], "getUser");
+        }
+        assert_eq!(roots[0].1.fields["outcome"], "success");
+        assert_eq!(roots[1].1.fields["outcome"], "failure");
+        assert!(roots[1].1.fields["error"].starts_with("Query validation failed"));
+
+        let phases = |root: usize| -> Vec<&str> {
+            spans
+                .iter()
+                .filter(|span| span.parent == Some(root as u64 + 1))
+                .map(|span| span.fields["phase"].as_str())
+                .collect()
+        };
+        assert_eq!(
+            phases(roots[0].0),
+            ["method", "security", "path", "query", "body"]
+        );
+        assert_eq!(phases(roots[1].0), ["method", "security", "path", "query"]);
+    }
+}
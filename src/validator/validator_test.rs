@@ -23,6 +23,7 @@ mod tests {
     use axum::body::Bytes;
     use serde_json::Value;
 
+    #[allow(clippy::useless_format)]
     fn make_request_body_with_value(value: &str) -> request::axum::RequestData {
         request::axum::RequestData {
             path: "/example".to_string(),
@@ -1007,4 +1008,101 @@ paths:
             );
         }
     }
+
+    #[test]
+    fn test_query_method_validation() {
+        let content = r#"
+openapi: 3.2.0
+info:
+  title: Search API
+  version: 1.0.0
+paths:
+  /search:
+    query:
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [term]
+              properties:
+                term:
+                  type: string
+      responses:
+        '200':
+          description: OK
+"#;
+        let openapi: OpenAPI = OpenAPI::yaml(content).expect("Failed to parse OpenAPI YAML");
+
+        fn make_request(method: &str, uri: &str, body: &str) -> request::axum::RequestData {
+            request::axum::RequestData {
+                path: "/search".to_string(),
+                inner: axum::http::Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .body(axum::body::Body::from(body.to_string()))
+                    .unwrap(),
+                body: (!body.is_empty()).then(|| Bytes::from(body.to_string())),
+            }
+        }
+
+        struct Tests {
+            method: &'static str,
+            uri: &'static str,
+            body: &'static str,
+            assert: bool,
+        }
+
+        let tests = [
+            Tests {
+                method: "QUERY",
+                uri: "/search?limit=10",
+                body: r#"{"term":"rust"}"#,
+                assert: true,
+            },
+            Tests {
+                method: "QUERY",
+                uri: "/search?limit=ten",
+                body: r#"{"term":"rust"}"#,
+                assert: false,
+            },
+            Tests {
+                method: "QUERY",
+                uri: "/search",
+                body: r#"{"term":1}"#,
+                assert: false,
+            },
+            Tests {
+                method: "QUERY",
+                uri: "/search",
+                body: r#"{}"#,
+                assert: false,
+            },
+            Tests {
+                method: "GET",
+                uri: "/search",
+                body: "",
+                assert: false,
+            },
+        ];
+
+        for test in tests {
+            let result = openapi.validator(make_request(test.method, test.uri, test.body));
+            assert_eq!(
+                result.is_ok(),
+                test.assert,
+                "{} {} {}: {:?}",
+                test.method,
+                test.uri,
+                test.body,
+                result
+            );
+        }
+    }
 }
@@ -32,9 +32,9 @@ mod tests {
         // Validate general OpenAPI properties
         assert_eq!(openapi.openapi, "3.1.0");
         assert_eq!(openapi.info.title, "Example API");
-        assert!(openapi.components.is_some());
+        assert!(openapi.components().is_some());
 
-        let components = openapi.components.as_ref().unwrap();
+        let components = openapi.components().unwrap();
 
         // Validate schemas' presence of "oneOf" and "allOf"
         let schemas_check = [("ExampleRequest", false), ("ExampleResponse", false)];
@@ -57,7 +57,7 @@ mod tests {
 
         // Validate paths
         let example_path = openapi
-            .paths
+            .paths()
             .get("/example/{uuid}")
             .ok_or("Missing path: /example/{uuid}")?;
         let get_value = example_path
@@ -129,7 +129,7 @@ paths:
         assert_eq!(openapi.info.version, "0.0.1");
 
         // Validate components and schemas
-        let components = openapi.components.as_ref().unwrap();
+        let components = openapi.components().unwrap();
         let example_request = components.schemas.get("ExampleRequest").unwrap();
 
         assert!(example_request.one_of.is_none());
@@ -204,7 +204,7 @@ paths:
         assert_eq!(openapi.info.version, "0.0.1");
 
         // Validate components and schemas
-        let components = openapi.components.as_ref().ok_or("Missing components")?;
+        let components = openapi.components().ok_or("Missing components")?;
         let example_response = components
             .schemas
             .get("ExampleResponse")
@@ -306,7 +306,7 @@ paths:
         assert_eq!(openapi.info.version, "0.0.1");
 
         // Validate components and schemas
-        let components = openapi.components.as_ref().ok_or("Missing components")?;
+        let components = openapi.components().ok_or("Missing components")?;
         let example_response = components
             .schemas
             .get("ExampleResponse")
@@ -389,11 +389,11 @@ paths:
             Some("API definitions for example")
         );
         assert_eq!(openapi.info.version, "0.0.1");
-        assert!(openapi.components.is_none());
+        assert!(openapi.components().is_none());
 
         // Validate paths
         let example_path = openapi
-            .paths
+            .paths()
             .get("/example")
             .ok_or("Missing path: /example")?;
 
@@ -447,7 +447,7 @@ paths:
         assert_eq!(openapi.info.version, "0.0.1");
 
         // Validate components and schemas
-        let components = openapi.components.as_ref().unwrap();
+        let components = openapi.components().unwrap();
         let example_response = components.schemas.get("ExampleResponse").unwrap();
         let properties = example_response.properties.as_ref().unwrap();
 
@@ -670,7 +670,7 @@ paths:
 
         assert_eq!(openapi.openapi, "3.2.0");
 
-        let users_path = openapi.paths.get("/users").unwrap();
+        let users_path = openapi.paths().get("/users").unwrap();
         assert!(users_path.query.is_some());
 
         let query_op = users_path.query.as_ref().unwrap();
@@ -714,7 +714,7 @@ paths:
 
         assert_eq!(openapi.openapi, "3.2.0");
 
-        let search_path = openapi.paths.get("/search").unwrap();
+        let search_path = openapi.paths().get("/search").unwrap();
         let get_op = search_path.operations.get("get").unwrap();
 
         let params = get_op.parameters.as_ref().unwrap();
@@ -850,8 +850,8 @@ paths:
         assert!(openapi.info.summary.is_some());
 
         // Both HTTP methods
-        let users_path = openapi.paths.get("/users").unwrap();
-        assert!(users_path.operations.get("get").is_some());
+        let users_path = openapi.paths().get("/users").unwrap();
+        assert!(users_path.operations.contains_key("get"));
         assert!(users_path.query.is_some());
 
         Ok(())
@@ -1371,7 +1371,7 @@ components:
 
         let openapi: OpenAPI = OpenAPI::yaml(content)?;
 
-        let users_path = openapi.paths.get("/users").unwrap();
+        let users_path = openapi.paths().get("/users").unwrap();
         assert!(users_path.query.is_some());
 
         let query_op = users_path.query.as_ref().unwrap();
@@ -1446,11 +1446,11 @@ paths:
     "#;
 
         let openapi: OpenAPI = OpenAPI::yaml(content)?;
-        let users_path = openapi.paths.get("/users").unwrap();
+        let users_path = openapi.paths().get("/users").unwrap();
 
         // All three methods should coexist
-        assert!(users_path.operations.get("get").is_some());
-        assert!(users_path.operations.get("post").is_some());
+        assert!(users_path.operations.contains_key("get"));
+        assert!(users_path.operations.contains_key("post"));
         assert!(users_path.query.is_some());
 
         // Verify each has correct operationId
@@ -1593,7 +1593,7 @@ paths:
     "#;
 
         let openapi: OpenAPI = OpenAPI::yaml(content)?;
-        let search_path = openapi.paths.get("/search").unwrap();
+        let search_path = openapi.paths().get("/search").unwrap();
         let get_op = search_path.operations.get("get").unwrap();
 
         let params = get_op.parameters.as_ref().unwrap();
@@ -1670,7 +1670,7 @@ paths:
     "#;
 
         let openapi: OpenAPI = OpenAPI::yaml(content)?;
-        let search_path = openapi.paths.get("/search").unwrap();
+        let search_path = openapi.paths().get("/search").unwrap();
         let get_op = search_path.operations.get("get").unwrap();
 
         let params = get_op.parameters.as_ref().unwrap();
@@ -1782,35 +1782,35 @@ paths:
         let serialized_obj = serialized.as_mapping().unwrap();
 
         // Verify $self field
-        assert!(serialized_obj.contains_key(&Value::String("$self".to_string())));
+        assert!(serialized_obj.contains_key(Value::String("$self".to_string())));
         assert_eq!(
             serialized_obj
-                .get(&Value::String("$self".to_string()))
+                .get(Value::String("$self".to_string()))
                 .unwrap(),
             &Value::String("https://api.example.com".to_string())
         );
 
         // Verify jsonSchemaDialect
-        assert!(serialized_obj.contains_key(&Value::String("jsonSchemaDialect".to_string())));
+        assert!(serialized_obj.contains_key(Value::String("jsonSchemaDialect".to_string())));
 
         // Verify webhooks
-        assert!(serialized_obj.contains_key(&Value::String("webhooks".to_string())));
+        assert!(serialized_obj.contains_key(Value::String("webhooks".to_string())));
 
         // Verify info.summary
         let info = serialized_obj
-            .get(&Value::String("info".to_string()))
+            .get(Value::String("info".to_string()))
             .unwrap();
         let info_obj = info.as_mapping().unwrap();
-        assert!(info_obj.contains_key(&Value::String("summary".to_string())));
+        assert!(info_obj.contains_key(Value::String("summary".to_string())));
 
         // Verify query method in path item
         let paths = serialized_obj
-            .get(&Value::String("paths".to_string()))
+            .get(Value::String("paths".to_string()))
             .unwrap();
         let paths_obj = paths.as_mapping().unwrap();
-        let test_path = paths_obj.get(&Value::String("/test".to_string())).unwrap();
+        let test_path = paths_obj.get(Value::String("/test".to_string())).unwrap();
         let test_obj = test_path.as_mapping().unwrap();
-        assert!(test_obj.contains_key(&Value::String("query".to_string())));
+        assert!(test_obj.contains_key(Value::String("query".to_string())));
 
         Ok(())
     }
@@ -2249,9 +2249,9 @@ components:
         assert!(webhooks.contains_key("inventoryLow"));
 
         // Paths verification
-        let products_path = openapi.paths.get("/products").unwrap();
-        assert!(products_path.operations.get("get").is_some());
-        assert!(products_path.operations.get("post").is_some());
+        let products_path = openapi.paths().get("/products").unwrap();
+        assert!(products_path.operations.contains_key("get"));
+        assert!(products_path.operations.contains_key("post"));
         assert!(products_path.query.is_some());
 
         // QUERY method verification
@@ -2262,12 +2262,12 @@ components:
         );
         assert!(products_query.request.is_some());
 
-        let orders_path = openapi.paths.get("/orders").unwrap();
-        assert!(orders_path.operations.get("get").is_some());
+        let orders_path = openapi.paths().get("/orders").unwrap();
+        assert!(orders_path.operations.contains_key("get"));
         assert!(orders_path.query.is_some());
 
         // Components verification
-        let components = openapi.components.as_ref().unwrap();
+        let components = openapi.components().unwrap();
         assert!(components.schemas.contains_key("Product"));
         assert!(components.schemas.contains_key("ProductCreate"));
         assert!(components.schemas.contains_key("ProductUpdate"));
@@ -2276,6 +2276,101 @@ components:
         Ok(())
     }
 
+    #[test]
+    fn security_schemes_and_requirements() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::parse::SecuritySchemeType;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Secure API
+  version: '1.0.0'
+security:
+  - api_key: []
+  - bearer: []
+    oauth: [read, write]
+paths:
+  /health:
+    get:
+      security: []
+      responses:
+        '200':
+          description: OK
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  securitySchemes:
+    api_key:
+      type: apiKey
+      name: X-API-Key
+      in: header
+    bearer:
+      type: http
+      scheme: bearer
+      bearerFormat: JWT
+    basic:
+      type: http
+      scheme: basic
+    oauth:
+      type: oauth2
+      flows:
+        authorizationCode:
+          authorizationUrl: https://auth.example.com/authorize
+          tokenUrl: https://auth.example.com/token
+          scopes:
+            read: Read access
+            write: Write access
+    oidc:
+      type: openIdConnect
+      openIdConnectUrl: https://auth.example.com/.well-known/openid-configuration
+    mtls:
+      type: mutualTLS
+    "#;
+
+        let openapi: OpenAPI = OpenAPI::yaml(content)?;
+
+        let security = openapi.security.as_ref().unwrap();
+        assert_eq!(security.len(), 2);
+        assert!(security[0]["api_key"].is_empty());
+        assert_eq!(security[1]["oauth"], ["read", "write"]);
+
+        let health = &openapi.paths()["/health"].operations["get"];
+        assert!(health.security.as_ref().unwrap().is_empty());
+        assert!(openapi.paths()["/users"].operations["get"]
+            .security
+            .is_none());
+
+        let schemes = &openapi.components().unwrap().security_schemes;
+        assert_eq!(schemes.len(), 6);
+
+        let api_key = &schemes["api_key"];
+        assert_eq!(api_key.r#type, SecuritySchemeType::ApiKey);
+        assert_eq!(api_key.name.as_deref(), Some("X-API-Key"));
+        assert_eq!(api_key.r#in, Some(In::Header));
+
+        let bearer = &schemes["bearer"];
+        assert_eq!(bearer.r#type, SecuritySchemeType::Http);
+        assert_eq!(bearer.scheme.as_deref(), Some("bearer"));
+        assert_eq!(bearer.bearer_format.as_deref(), Some("JWT"));
+
+        let flows = schemes["oauth"].flows.as_ref().unwrap();
+        let code = flows.authorization_code.as_ref().unwrap();
+        assert_eq!(
+            code.token_url.as_deref(),
+            Some("https://auth.example.com/token")
+        );
+        assert_eq!(code.scopes.len(), 2);
+
+        assert_eq!(schemes["oidc"].r#type, SecuritySchemeType::OpenIdConnect);
+        assert!(schemes["oidc"].open_id_connect_url.is_some());
+        assert_eq!(schemes["mtls"].r#type, SecuritySchemeType::MutualTLS);
+
+        Ok(())
+    }
+
     // ==================== Validation Tests for New Features ====================
 
     #[test]
@@ -2332,13 +2427,1805 @@ paths:
 
         // Valid JSON should pass
         let mut query_params = HashMap::new();
-        query_params.insert("filter".to_string(), r#"{"status":"active"}"#.to_string());
-        assert!(query("/search", &query_params, &openapi).is_ok());
+        query_params.insert(
+            "filter".to_string(),
+            vec![r#"{"status":"active"}"#.to_string()],
+        );
+        assert!(query("/search", "get", &query_params, &openapi).is_ok());
 
         // Invalid JSON should fail
-        query_params.insert("filter".to_string(), "invalid-json".to_string());
-        assert!(query("/search", &query_params, &openapi).is_err());
+        query_params.insert("filter".to_string(), vec!["invalid-json".to_string()]);
+        assert!(query("/search", "get", &query_params, &openapi).is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "swagger")]
+    #[test]
+    fn convert_swagger_2_document() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::parse::{SecuritySchemeType, Style};
+        use openapi_rs::validator::{body, query};
+        use serde_json::json;
+        use std::collections::HashMap;
+
+        let content = r#"
+swagger: '2.0'
+info:
+  title: Pet Store
+  version: '1.0.0'
+host: api.example.com
+basePath: /v1
+schemes: [https]
+consumes: [application/json]
+produces: [application/json]
+securityDefinitions:
+  api_key:
+    type: apiKey
+    name: X-API-Key
+    in: header
+  basic:
+    type: basic
+  oauth:
+    type: oauth2
+    flow: accessCode
+    authorizationUrl: https://example.com/authorize
+    tokenUrl: https://example.com/token
+    scopes:
+      read: Read pets
+security:
+  - api_key: []
+parameters:
+  PetBody:
+    name: pet
+    in: body
+    required: true
+    schema:
+      $ref: '#/definitions/Pet'
+definitions:
+  Pet:
+    type: object
+    required: [name]
+    properties:
+      name:
+        type: string
+      age:
+        type: integer
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: tags
+          in: query
+          type: array
+          items:
+            type: string
+        - name: limit
+          in: query
+          type: integer
+          maximum: 100
+      responses:
+        200:
+          description: OK
+          schema:
+            type: array
+            items:
+              $ref: '#/definitions/Pet'
+    post:
+      parameters:
+        - $ref: '#/parameters/PetBody'
+      responses:
+        201:
+          description: Created
+  /pets/{id}/photo:
+    post:
+      consumes: [multipart/form-data]
+      parameters:
+        - name: id
+          in: path
+          required: true
+          type: string
+        - name: file
+          in: formData
+          required: true
+          type: file
+        - name: caption
+          in: formData
+          type: string
+      responses:
+        204:
+          description: Uploaded
+"#;
+
+        let openapi = OpenAPI::swagger(content)?;
+        assert!(openapi.openapi.starts_with("3.0"));
+        assert_eq!(openapi.servers[0].url, "https://api.example.com/v1");
+
+        let components = openapi.components().unwrap();
+        assert!(components.schemas.contains_key("Pet"));
+        let schemes = &components.security_schemes;
+        assert_eq!(schemes["api_key"].r#type, SecuritySchemeType::ApiKey);
+        assert_eq!(schemes["basic"].scheme.as_deref(), Some("basic"));
+        let flows = schemes["oauth"].flows.as_ref().unwrap();
+        assert!(flows.authorization_code.is_some());
+
+        let pets = &openapi.paths()["/pets"];
+        let tags = &pets.operations["get"].parameters.as_ref().unwrap()[0];
+        assert_eq!(tags.style, Some(Style::Form));
+        assert_eq!(tags.explode, Some(false));
+        let response = &pets.operations["get"].responses["200"];
+        assert!(response.content.contains_key("application/json"));
+
+        let request = pets.operations["post"].request.as_ref().unwrap();
+        assert!(request.required);
+        assert_eq!(
+            request.content["application/json"].schema.r#ref.as_deref(),
+            Some("#/components/schemas/Pet")
+        );
+
+        let upload = &openapi.paths()["/pets/{id}/photo"].operations["post"];
+        let form = &upload.request.as_ref().unwrap().content["multipart/form-data"];
+        let properties = form.schema.properties.as_ref().unwrap();
+        assert_eq!(properties["file"].format, Some(Format::Binary));
+        assert_eq!(form.schema.required, ["file"]);
+
+        assert!(body("/pets", "post", json!({"name": "Rex", "age": 3}), &openapi).is_ok());
+        assert!(body("/pets", "post", json!({"age": 3}), &openapi).is_err());
+
+        let query_pairs = HashMap::from([("limit".to_string(), vec!["many".to_string()])]);
+        assert!(query("/pets", "get", &query_pairs, &openapi).is_err());
+
+        assert!(OpenAPI::swagger("swagger: '3.0'\ninfo: {}\npaths: {}").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_openapi_30_schemas() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::validator::body;
+        use serde_json::json;
+
+        let content = r#"
+openapi: 3.0.3
+info:
+  title: Legacy API
+  version: '1.0.0'
+paths:
+  /items:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Item'
+      responses:
+        '201':
+          description: Created
+components:
+  schemas:
+    Item:
+      type: object
+      required: [price]
+      properties:
+        nickname:
+          type: string
+          nullable: true
+          example: Rex
+        price:
+          type: number
+          minimum: 0
+          exclusiveMinimum: true
+          maximum: 100
+          exclusiveMaximum: false
+        tags:
+          type: array
+          items:
+            type: string
+            nullable: true
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        assert_eq!(openapi.openapi, "3.0.3");
+
+        let item = &openapi.components().unwrap().schemas["Item"];
+        let properties = item.properties.as_ref().unwrap();
+
+        let nickname = &properties["nickname"];
+        assert_eq!(
+            nickname.r#type,
+            Some(TypeOrUnion::Union(vec![Type::String, Type::Null]))
+        );
+        assert!(nickname.example.is_none());
+        assert_eq!(nickname.examples, Some(vec![Value::from("Rex")]));
+
+        let price = &properties["price"];
+        assert_eq!(price.minimum, None);
+        assert_eq!(price.exclusive_minimum, Some(0.0));
+        assert_eq!(price.maximum, Some(100.0));
+        assert_eq!(price.exclusive_maximum, None);
+
+        let tags = properties["tags"].items.as_ref().unwrap();
+        assert_eq!(
+            tags.r#type,
+            Some(TypeOrUnion::Union(vec![Type::String, Type::Null]))
+        );
+
+        assert!(body(
+            "/items",
+            "post",
+            json!({"price": 5, "nickname": null}),
+            &openapi
+        )
+        .is_ok());
+        assert!(body("/items", "post", json!({"price": 100}), &openapi).is_ok());
+        let err = body("/items", "post", json!({"price": 0}), &openapi).unwrap_err();
+        assert!(err.to_string().contains("must be > 0"), "{err}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_leaves_examples_and_extensions() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::normalize;
+
+        let content = r#"
+openapi: 3.0.3
+info:
+  title: Legacy API
+  version: '1.0.0'
+x-defaults:
+  schema:
+    nullable: true
+paths:
+  /items/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+        schema:
+          type: string
+          nullable: true
+    get:
+      x-codegen:
+        schemas:
+          Item:
+            example: 1
+      responses:
+        '200':
+          description: Item
+          content:
+            application/json:
+              schema:
+                type: object
+                example: {}
+              examples:
+                raw:
+                  value:
+                    schema:
+                      nullable: true
+"#;
+
+        let mut document: Value = serde_yaml::from_str(content)?;
+        normalize::to_31(&mut document);
+
+        let path_item = &document["paths"]["/items/{id}"];
+        let id = &path_item["parameters"][0]["schema"];
+        assert_eq!(
+            id["type"],
+            serde_yaml::from_str::<Value>("[string, 'null']")?
+        );
+        assert!(id.get("nullable").is_none());
+        let media = &path_item["get"]["responses"]["200"]["content"]["application/json"];
+        assert!(media["schema"].get("example").is_none());
+        assert!(media["schema"]["examples"].is_sequence());
+
+        let example = &media["examples"]["raw"]["value"]["schema"];
+        assert_eq!(example["nullable"], Value::Bool(true));
+        let extension = &path_item["get"]["x-codegen"]["schemas"]["Item"];
+        assert_eq!(extension["example"], Value::from(1));
+        assert_eq!(
+            document["x-defaults"]["schema"]["nullable"],
+            Value::Bool(true)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lint_reports_diagnostics() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::lint::Severity;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Lint API
+  version: '1.0.0'
+paths:
+  /users:
+    get:
+      operationId: listUsers
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/UserList'
+    post:
+      operationId: listUsers
+      security:
+        - bearer: []
+      responses:
+        '201':
+          description: Created
+  /users/{id}:
+    parameters:
+      - name: userId
+        in: path
+        required: true
+        schema:
+          type: string
+    delete: {}
+components:
+  schemas:
+    UserList:
+      type: array
+      items:
+        $ref: '#/components/schemas/User'
+    User:
+      type: object
+    Orphan:
+      type: object
+  parameters:
+    Unused:
+      name: limit
+      in: query
+  securitySchemes:
+    bearer:
+      type: http
+      scheme: bearer
+    legacy:
+      type: apiKey
+      name: X-Key
+      in: header
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let lint = openapi.lint();
+        let diagnostics: Vec<(&str, Severity, &str)> = lint
+            .iter()
+            .map(|d| (d.rule, d.severity, d.pointer.as_str()))
+            .collect();
+
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    "duplicate-operation-id",
+                    Severity::Error,
+                    "/paths/~1users/post/operationId"
+                ),
+                (
+                    "unused-parameter",
+                    Severity::Warning,
+                    "/paths/~1users~1{id}/parameters/0"
+                ),
+                (
+                    "missing-operation-id",
+                    Severity::Warning,
+                    "/paths/~1users~1{id}/delete"
+                ),
+                (
+                    "missing-responses",
+                    Severity::Error,
+                    "/paths/~1users~1{id}/delete/responses"
+                ),
+                (
+                    "unreachable-component",
+                    Severity::Warning,
+                    "/components/schemas/Orphan"
+                ),
+                (
+                    "unreachable-component",
+                    Severity::Warning,
+                    "/components/parameters/Unused"
+                ),
+                (
+                    "unreachable-component",
+                    Severity::Warning,
+                    "/components/securitySchemes/legacy"
+                ),
+            ]
+        );
+
+        assert_eq!(
+            lint[0].to_string(),
+            "error[duplicate-operation-id] /paths/~1users/post/operationId: \
+             operationId 'listUsers' is already used by /paths/~1users/get"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lint_with_ruleset_disables_and_regrades_rules() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::lint::{RuleLevel, Ruleset, Severity};
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Lint API
+  version: '1.0.0'
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  schemas:
+    Orphan:
+      type: object
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let ruleset = Ruleset::yaml(
+            r#"
+rules:
+  missing-operation-id: error
+  unreachable-component: off
+"#,
+        )?;
+        assert_eq!(
+            ruleset,
+            Ruleset::default()
+                .with_rule("missing-operation-id", RuleLevel::Error)
+                .with_rule("unreachable-component", RuleLevel::Off)
+        );
+
+        let diagnostics: Vec<(&str, Severity)> = openapi
+            .lint_with(&ruleset)
+            .iter()
+            .map(|d| (d.rule, d.severity))
+            .collect();
+        assert_eq!(diagnostics, [("missing-operation-id", Severity::Error)]);
+        assert_eq!(openapi.lint_with(&Ruleset::default()), openapi.lint());
+        assert!(Ruleset::yaml("rules:\n  missing-responses: fatal\n").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn strict_parse_reports_unknown_keys() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::strict::UnknownKeys;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Strict API
+  version: '1.0.0'
+  x-team: platform
+paths:
+  /users/{id}:
+    get:
+      parameters:
+        - name: id
+          in: path
+          requird: true
+          schema:
+            type: string
+            maxLenght: 10
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/User'
+components:
+  schemas:
+    User:
+      type: object
+      properties:
+        name:
+          type: string
+          nullable: true
+        tags:
+          type: array
+          itmes:
+            type: string
+"#;
+
+        assert!(OpenAPI::yaml(content).is_ok());
+
+        let err = OpenAPI::yaml_strict(content).unwrap_err();
+        let unknown = err.downcast_ref::<UnknownKeys>().unwrap();
+        assert_eq!(
+            unknown.pointers,
+            [
+                "/paths/~1users~1{id}/get/parameters/0/requird",
+                "/paths/~1users~1{id}/get/parameters/0/schema/maxLenght",
+                "/components/schemas/User/properties/tags/itmes",
+            ]
+        );
+        assert!(err.to_string().starts_with("Unknown keys: /paths"));
+
+        let valid = content
+            .replace("requird", "required")
+            .replace("maxLenght", "maxLength")
+            .replace("itmes", "items");
+        assert!(OpenAPI::yaml_strict(&valid).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn yaml_anchors_and_merge_keys() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::validator::body;
+        use serde_json::json;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: DRY API
+  version: '1.0.0'
+x-shared:
+  ok: &ok
+    '200':
+      description: OK
+  audited: &audited
+    created_at:
+      type: string
+      format: date-time
+  named: &named
+    <<: *audited
+    name:
+      type: string
+      maxLength: 5
+paths:
+  /pets:
+    get:
+      responses: *ok
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+              properties:
+                <<: *named
+                name:
+                  type: string
+                  maxLength: 10
+      responses:
+        <<: *ok
+        '201':
+          description: Created
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let pets = &openapi.paths()["/pets"];
+        assert!(pets.operations["get"].responses.contains_key("200"));
+
+        let post = &pets.operations["post"];
+        let mut codes: Vec<_> = post.responses.keys().collect();
+        codes.sort();
+        assert_eq!(codes, ["200", "201"]);
+
+        let schema = &post.request.as_ref().unwrap().content["application/json"].schema;
+        let properties = schema.properties.as_ref().unwrap();
+        assert!(properties.contains_key("created_at"));
+        assert!(!properties.contains_key("<<"));
+        // Explicit keys take precedence over merged ones
+        assert_eq!(properties["name"].max_length, Some(10));
+
+        let request = json!({"name": "Fluffy Jr", "created_at": "2024-01-01T00:00:00Z"});
+        assert!(body("/pets", "post", request, &openapi).is_ok());
+        let request = json!({"name": "Rex", "created_at": "yesterday"});
+        assert!(body("/pets", "post", request, &openapi).is_err());
+
+        assert!(OpenAPI::yaml_strict(content).is_ok());
+
+        let invalid = "openapi: 3.1.0\ninfo:\n  <<: 1\npaths: {}\n";
+        assert!(OpenAPI::yaml(invalid).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn operations_iterator_and_lookup_by_id() -> Result<(), Box<dyn std::error::Error>> {
+        let content = r#"
+openapi: 3.2.0
+info:
+  title: Users API
+  version: '1.0.0'
+paths:
+  /users:
+    post:
+      operationId: createUser
+      responses:
+        '201':
+          description: Created
+    get:
+      operationId: listUsers
+      responses:
+        '200':
+          description: OK
+    query:
+      operationId: searchUsers
+      responses:
+        '200':
+          description: OK
+  /files:
+    additionalOperations:
+      COPY:
+        operationId: copyFile
+        responses:
+          '201':
+            description: Created
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+
+        let operations: Vec<(&str, &str, Option<&str>)> = openapi
+            .operations()
+            .map(|(path, method, operation)| (path, method, operation.operation_id.as_deref()))
+            .collect();
+        assert_eq!(
+            operations,
+            [
+                ("/users", "get", Some("listUsers")),
+                ("/users", "post", Some("createUser")),
+                ("/users", "query", Some("searchUsers")),
+                ("/files", "COPY", Some("copyFile")),
+            ]
+        );
+
+        let (path, method, operation) = openapi.operation_by_id("createUser").unwrap();
+        assert_eq!((path, method), ("/users", "post"));
+        assert!(operation.responses.contains_key("201"));
+        assert!(openapi.operation_by_id("deleteUser").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn walk_document_with_visitor() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::parse::{Parameter, PathBase, Response};
+        use openapi_rs::model::visit::{walk, SchemaNode, Visitor};
+
+        #[derive(Default)]
+        struct Collect {
+            operations: Vec<String>,
+            parameters: Vec<String>,
+            responses: Vec<String>,
+            schemas: Vec<String>,
+        }
+
+        impl Visitor for Collect {
+            fn visit_operation(&mut self, _: &str, path: &str, method: &str, _: &PathBase) {
+                self.operations.push(format!("{method} {path}"));
+            }
+
+            fn visit_parameter(&mut self, pointer: &str, _: &Parameter) {
+                self.parameters.push(pointer.to_string());
+            }
+
+            fn visit_response(&mut self, _: &str, status: &str, _: &Response) {
+                self.responses.push(status.to_string());
+            }
+
+            fn visit_schema(&mut self, pointer: &str, node: SchemaNode<'_>) {
+                if let SchemaNode::Property(_) = node {
+                    self.schemas.push(pointer.to_string());
+                }
+            }
+        }
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Walk API
+  version: '1.0.0'
+paths:
+  /users/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+        schema:
+          type: string
+    get:
+      parameters:
+        - name: fields
+          in: query
+          schema:
+            type: string
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/User'
+        '404':
+          description: Not found
+    delete:
+      responses:
+        '204':
+          description: Deleted
+components:
+  schemas:
+    User:
+      type: object
+      properties:
+        name:
+          type: string
+        address:
+          type: object
+          properties:
+            city:
+              type: string
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let mut collect = Collect::default();
+        walk(&openapi, &mut collect);
+
+        assert_eq!(
+            collect.operations,
+            ["get /users/{id}", "delete /users/{id}"]
+        );
+        assert_eq!(
+            collect.parameters,
+            [
+                "/paths/~1users~1{id}/parameters/0",
+                "/paths/~1users~1{id}/get/parameters/0",
+            ]
+        );
+        assert_eq!(collect.responses, ["200", "404", "204"]);
+        assert_eq!(
+            collect.schemas,
+            [
+                "/components/schemas/User/properties/name",
+                "/components/schemas/User/properties/address",
+                "/components/schemas/User/properties/address/properties/city",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_preserves_extensions_and_order() -> Result<(), Box<dyn std::error::Error>> {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+  x-audience: internal
+x-owner: platform
+paths:
+  /users:
+    summary: Users collection
+    x-rate-limit: 100
+    post:
+      operationId: createUser
+      x-internal: true
+      responses:
+        '201':
+          description: Created
+    get:
+      operationId: listUsers
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: '#/components/schemas/User'
+  /accounts:
+    get:
+      responses:
+        '200':
+          description: OK
+components:
+  schemas:
+    User:
+      type: object
+      x-table: users
+      properties:
+        zone:
+          type: string
+          format: timezone
+        id:
+          type: integer
+          format: int64
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let yaml = openapi.to_yaml()?;
+        let value: Value = serde_yaml::from_str(&yaml)?;
+
+        assert_eq!(value, serde_yaml::from_str::<Value>(content)?);
+        let paths: Vec<&str> = value["paths"]
+            .as_mapping()
+            .unwrap()
+            .keys()
+            .filter_map(Value::as_str)
+            .collect();
+        assert_eq!(paths, ["/users", "/accounts"]);
+        let methods: Vec<&str> = value["paths"]["/users"]
+            .as_mapping()
+            .unwrap()
+            .keys()
+            .filter_map(Value::as_str)
+            .collect();
+        assert_eq!(methods, ["summary", "post", "get", "x-rate-limit"]);
+        assert!(!yaml.contains("null"));
+
+        let json: serde_json::Value = serde_json::from_str(&openapi.to_json()?)?;
+        assert_eq!(json["x-owner"], "platform");
+        assert_eq!(
+            json["components"]["schemas"]["User"]["properties"]["zone"]["format"],
+            "timezone"
+        );
+        assert_eq!(OpenAPI::yaml(&yaml)?.to_yaml()?, yaml);
+
+        Ok(())
+    }
+
+    #[test]
+    fn upgrade_declares_later_versions() -> Result<(), Box<dyn std::error::Error>> {
+        let content = r#"
+openapi: 3.0.3
+info:
+  title: Users
+  version: 1.0.0
+paths: {}
+"#;
+
+        let parse = |version: &str| OpenAPI::yaml(&content.replace("3.0.3", version));
+        assert_eq!(parse("3.0.3")?.upgrade("3.1")?.openapi, "3.1.0");
+        assert_eq!(parse("3.0.3")?.upgrade("3.2.0")?.openapi, "3.2.0");
+        assert_eq!(parse("3.1.1")?.upgrade("3.1")?.openapi, "3.1.1");
+        assert!(parse("3.1.1")?.upgrade("3.0").is_err());
+        assert!(parse("3.1.1")?.upgrade("4").is_err());
+        assert_eq!(
+            parse("3.2.0")?.upgrade("3.1").unwrap_err().to_string(),
+            "Cannot downgrade OpenAPI 3.2.0 to 3.1"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn typed_extension_access() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct RateLimit {
+            requests: u32,
+            window: String,
+        }
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+  x-audience: internal
+x-owner: platform
+paths:
+  /users:
+    get:
+      x-rate-limit:
+        requests: 100
+        window: 1m
+      parameters:
+        - name: page
+          in: query
+          x-deprecated-since: 2
+          schema:
+            type: integer
+            x-sensitive: false
+      responses:
+        '200':
+          description: OK
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        assert_eq!(
+            openapi.extensions().get::<String>("x-owner")?.as_deref(),
+            Some("platform")
+        );
+        assert!(openapi.info.extensions().contains("x-audience"));
+
+        let (_, _, operation) = openapi.operations().next().unwrap();
+        assert_eq!(
+            operation.extensions().get::<RateLimit>("x-rate-limit")?,
+            Some(RateLimit {
+                requests: 100,
+                window: "1m".to_string(),
+            })
+        );
+        assert_eq!(operation.extensions().get::<u32>("x-missing")?, None);
+        assert!(operation.extensions().get::<u32>("x-rate-limit").is_err());
+
+        let parameter = &operation.parameters.as_ref().unwrap()[0];
+        assert_eq!(
+            parameter.extensions().get::<u8>("x-deprecated-since")?,
+            Some(2)
+        );
+        let schema = parameter.schema.as_ref().unwrap();
+        assert_eq!(schema.extensions().get::<bool>("x-sensitive")?, Some(false));
+        assert_eq!(schema.extensions().iter().count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn info_contact_and_license() -> Result<(), Box<dyn std::error::Error>> {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+  termsOfService: https://example.com/terms
+  contact:
+    name: API Support
+    url: https://example.com/support
+    email: support@example.com
+  license:
+    name: Apache 2.0
+    identifier: Apache-2.0
+paths: {}
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let info = &openapi.info;
+        assert_eq!(
+            info.terms_of_service.as_deref(),
+            Some("https://example.com/terms")
+        );
+        let contact = info.contact.as_ref().unwrap();
+        assert_eq!(contact.name.as_deref(), Some("API Support"));
+        assert_eq!(contact.email.as_deref(), Some("support@example.com"));
+        let license = info.license.as_ref().unwrap();
+        assert_eq!(license.name, "Apache 2.0");
+        assert_eq!(license.identifier.as_deref(), Some("Apache-2.0"));
+        assert_eq!(license.url, None);
+
+        let value: Value = serde_yaml::from_str(&openapi.to_yaml()?)?;
+        assert_eq!(
+            value["info"],
+            serde_yaml::from_str::<Value>(content)?["info"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn tags_and_external_docs() -> Result<(), Box<dyn std::error::Error>> {
+        let content = r#"
+openapi: 3.2.0
+info:
+  title: Store
+  version: 1.0.0
+externalDocs:
+  url: https://example.com/docs
+tags:
+  - name: store
+    summary: Store
+    kind: nav
+  - name: orders
+    description: Order management
+    parent: store
+    externalDocs:
+      description: Ordering guide
+      url: https://example.com/docs/orders
+paths:
+  /orders:
+    get:
+      tags: [orders]
+      externalDocs:
+        url: https://example.com/docs/orders/list
+      responses:
+        '200':
+          description: OK
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        assert_eq!(
+            openapi.external_docs.as_ref().map(|docs| docs.url.as_str()),
+            Some("https://example.com/docs")
+        );
+        assert_eq!(openapi.tags.len(), 2);
+        assert_eq!(openapi.tags[0].kind.as_deref(), Some("nav"));
+        let orders = &openapi.tags[1];
+        assert_eq!(orders.parent.as_deref(), Some("store"));
+        assert_eq!(orders.description.as_deref(), Some("Order management"));
+        assert_eq!(
+            orders
+                .external_docs
+                .as_ref()
+                .and_then(|docs| docs.description.as_deref()),
+            Some("Ordering guide")
+        );
+
+        let (_, _, operation) = openapi.operations().next().unwrap();
+        assert_eq!(operation.tags, ["orders"]);
+        assert!(operation.external_docs.is_some());
+
+        let value: Value = serde_yaml::from_str(&openapi.to_yaml()?)?;
+        assert_eq!(value, serde_yaml::from_str::<Value>(content)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn server_variables_expand_and_match() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::validator::ValidationOptions;
+        use std::collections::HashMap;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+servers:
+  - url: https://{region}.example.com:{port}/{version}
+    variables:
+      region:
+        enum: [eu, us]
+        default: eu
+      port:
+        default: '443'
+      version:
+        default: v1
+        description: API version
+  - url: /internal
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          description: OK
+"#;
+
+        let openapi = OpenAPI::yaml(content)?
+            .with_options(ValidationOptions::new().with_strip_base_path(true));
+        let server = &openapi.servers[0];
+        assert_eq!(
+            server.variables["version"].description.as_deref(),
+            Some("API version")
+        );
+        assert_eq!(server.default_url()?, "https://eu.example.com:443/v1");
+        assert_eq!(
+            server.expand(&HashMap::from([("region", "us"), ("version", "v2")]))?,
+            "https://us.example.com:443/v2"
+        );
+        assert!(server.expand(&HashMap::from([("region", "ap")])).is_err());
+
+        let matched = openapi
+            .server_for(Some("US.example.com:443"), "/v2/users")
+            .unwrap();
+        assert_eq!(matched.server.url, server.url);
+        assert_eq!(matched.path, "/users");
+        assert_eq!(
+            matched.variables.into_iter().collect::<Vec<_>>(),
+            [
+                ("region".to_string(), "US".to_string()),
+                ("port".to_string(), "443".to_string()),
+                ("version".to_string(), "v2".to_string()),
+            ]
+        );
+        assert!(openapi
+            .server_for(Some("ap.example.com:443"), "/v2/users")
+            .is_none());
+
+        let matched = openapi
+            .server_for(Some("localhost"), "/internal/users")
+            .unwrap();
+        assert_eq!(matched.server.url, "/internal");
+        assert!(matched.variables.is_empty());
+
+        assert!(openapi.path_item("/v3/users").is_some());
+        assert!(openapi.path_item("/internal/users").is_some());
+        assert!(openapi.path_item("/internal/orders").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn response_headers_and_links() -> Result<(), Box<dyn std::error::Error>> {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      operationId: createUser
+      responses:
+        '201':
+          description: Created
+          headers:
+            Location:
+              description: URL of the new user
+              required: true
+              schema:
+                type: string
+                format: uri
+            X-Rate-Limit:
+              $ref: '#/components/headers/RateLimit'
+          links:
+            GetUser:
+              operationId: getUser
+              parameters:
+                id: $response.body#/id
+          content:
+            application/json:
+              schema:
+                type: object
+  /users/{id}:
+    get:
+      operationId: getUser
+      responses:
+        '200':
+          description: OK
+components:
+  headers:
+    RateLimit:
+      schema:
+        type: integer
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let (_, _, operation) = openapi.operation_by_id("createUser").unwrap();
+        let created = &operation.responses["201"];
+        assert_eq!(created.description.as_deref(), Some("Created"));
+
+        let location = &created.headers["Location"];
+        assert!(location.required);
+        assert_eq!(
+            location
+                .schema
+                .as_ref()
+                .and_then(|schema| schema.format.clone()),
+            Some(Format::URI)
+        );
+        assert_eq!(
+            created.headers["X-Rate-Limit"].r#ref.as_deref(),
+            Some("#/components/headers/RateLimit")
+        );
+
+        let link = &created.links["GetUser"];
+        assert_eq!(link.operation_id.as_deref(), Some("getUser"));
+        assert_eq!(
+            link.parameters["id"],
+            Value::String("$response.body#/id".to_string())
+        );
+        assert!(openapi
+            .components()
+            .unwrap()
+            .headers
+            .contains_key("RateLimit"));
+        assert!(openapi
+            .lint()
+            .iter()
+            .all(|diagnostic| diagnostic.rule != "unreachable-component"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn traverse_response_links() -> Result<(), Box<dyn std::error::Error>> {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      operationId: createUser
+      responses:
+        '201':
+          description: Created
+          links:
+            GetUser:
+              operationId: getUser
+              parameters:
+                id: $response.body#/id
+            DeleteUser:
+              operationRef: '#/paths/~1users~1{id}/delete'
+        default:
+          $ref: '#/components/responses/Error'
+  /users/{id}:
+    get:
+      operationId: getUser
+      responses:
+        '200':
+          description: OK
+    delete:
+      responses:
+        '204':
+          description: Deleted
+components:
+  responses:
+    Error:
+      description: Error
+      links:
+        Status:
+          $ref: '#/components/links/Status'
+  links:
+    Status:
+      operationRef: https://example.com/status.yaml#/paths/~1status/get
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let (_, _, operation) = openapi.operation_by_id("createUser").unwrap();
+        let links = openapi.links(operation);
+        let names: Vec<(&str, &str)> = links.iter().map(|link| (link.status, link.name)).collect();
+        assert_eq!(
+            names,
+            [
+                ("201", "GetUser"),
+                ("201", "DeleteUser"),
+                ("default", "Status")
+            ]
+        );
+
+        let targets: Vec<Option<(&str, &str)>> = links
+            .iter()
+            .map(|link| {
+                openapi
+                    .link_target(link.link)
+                    .map(|(path, method, _)| (path, method))
+            })
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                Some(("/users/{id}", "get")),
+                Some(("/users/{id}", "delete")),
+                None
+            ]
+        );
+        assert!(links[2].link.operation_ref.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parameter_and_media_type_examples() -> Result<(), Box<dyn std::error::Error>> {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+paths:
+  /users:
+    get:
+      parameters:
+        - name: role
+          in: query
+          schema:
+            type: string
+          examples:
+            admin:
+              summary: Administrators
+              value: admin
+            guest:
+              $ref: '#/components/examples/Guest'
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+              example:
+                id: 1
+            image/png:
+              examples:
+                avatar:
+                  externalValue: https://example.com/avatar.png
+components:
+  examples:
+    Guest:
+      summary: Guests
+      description: Anonymous users
+      value: guest
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let (_, _, operation) = openapi.operations().next().unwrap();
+        let parameter = &operation.parameters.as_ref().unwrap()[0];
+        let admin = &parameter.examples["admin"];
+        assert_eq!(admin.summary.as_deref(), Some("Administrators"));
+        assert_eq!(admin.value, Some(Value::String("admin".to_string())));
+
+        let guest = openapi.resolve_example(&parameter.examples["guest"]);
+        assert_eq!(guest.description.as_deref(), Some("Anonymous users"));
+        assert_eq!(guest.value, Some(Value::String("guest".to_string())));
+
+        let content = &operation.responses["200"].content;
+        assert_eq!(
+            content["application/json"].example,
+            Some(serde_yaml::from_str("id: 1")?)
+        );
+        assert_eq!(
+            content["image/png"].examples["avatar"]
+                .external_value
+                .as_deref(),
+            Some("https://example.com/avatar.png")
+        );
+        assert!(openapi
+            .lint()
+            .iter()
+            .all(|diagnostic| diagnostic.rule != "unreachable-component"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn lint_reports_invalid_examples() -> Result<(), Box<dyn std::error::Error>> {
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+paths:
+  /users:
+    get:
+      operationId: listUsers
+      parameters:
+        - name: limit
+          in: query
+          schema:
+            type: integer
+            maximum: 100
+          example: 10
+          examples:
+            tooMany:
+              value: 500
+            stale:
+              $ref: '#/components/examples/Stale'
+      responses:
+        '200':
+          description: OK
+          headers:
+            X-Total:
+              schema:
+                type: integer
+              example: many
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/User'
+              example:
+                name: 42
+components:
+  examples:
+    Stale:
+      value: ten
+  schemas:
+    User:
+      type: object
+      properties:
+        name:
+          type: string
+          examples: [alice, 7]
+        email:
+          type: string
+          format: email
+      examples:
+        - name: bob
+          email: not-an-email
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+        let pointers: Vec<String> = openapi
+            .invalid_examples()
+            .into_iter()
+            .inspect(|diagnostic| assert_eq!(diagnostic.rule, "invalid-example"))
+            .map(|diagnostic| diagnostic.pointer)
+            .collect();
+        assert_eq!(
+            pointers,
+            [
+                "/paths/~1users/get/parameters/0/examples/tooMany/value",
+                "/paths/~1users/get/parameters/0/examples/stale/value",
+                "/paths/~1users/get/responses/200/headers/X-Total/example",
+                "/paths/~1users/get/responses/200/content/application~1json/example",
+                "/components/schemas/User/examples/0",
+                "/components/schemas/User/properties/name/examples/1",
+            ]
+        );
+        assert_eq!(
+            openapi
+                .lint()
+                .iter()
+                .filter(|diagnostic| diagnostic.rule == "invalid-example")
+                .count(),
+            6
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn match_concrete_paths_against_templates() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::router::PathRouter;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      responses:
+        '200':
+          description: OK
+  /users/me:
+    get:
+      responses:
+        '200':
+          description: OK
+  /users/{userId}/files/{name}.{ext}:
+    get:
+      responses:
+        '200':
+          description: OK
+  /:
+    get:
+      responses:
+        '200':
+          description: OK
+"#;
+
+        let openapi = OpenAPI::yaml(content)?;
+
+        let route = openapi.match_path("/users/42").unwrap();
+        assert_eq!(route.path, "/users/{id}");
+        assert_eq!(route.params["id"], "42");
+        assert_eq!(openapi.match_path("/users/me").unwrap().path, "/users/me");
+        assert_eq!(openapi.match_path("/").unwrap().path, "/");
+
+        let route = openapi.match_path("/users/7/files/report.v2.pdf").unwrap();
+        assert_eq!(route.path, "/users/{userId}/files/{name}.{ext}");
+        assert_eq!(
+            route.params.into_iter().collect::<Vec<_>>(),
+            [
+                ("userId".to_string(), "7".to_string()),
+                ("name".to_string(), "report".to_string()),
+                ("ext".to_string(), "v2.pdf".to_string()),
+            ]
+        );
+
+        assert!(openapi.match_path("/users/7/files/report").is_none());
+        assert!(openapi.match_path("/users").is_none());
+        assert!(openapi.match_path("/users/42/extra").is_none());
+        assert!(openapi.path_item("/users/42").is_some());
+        assert!(openapi.path_item("/users/{id}").is_some());
+
+        let paths: Vec<String> = (0..2000)
+            .map(|index| format!("/resource{index}/{{id}}"))
+            .collect();
+        let router = PathRouter::new(paths.iter().map(String::as_str));
+        assert_eq!(
+            router.at("/resource1999/abc").unwrap().path,
+            "/resource1999/{id}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn shared_document_replacement() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::shared::SharedOpenAPI;
+        use std::sync::Arc;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Shared API
+  version: 1.0.0
+paths:
+  /v1:
+    get:
+      operationId: first
+"#;
+        let shared = SharedOpenAPI::new(OpenAPI::yaml(content)?);
+        let handle = shared.clone();
+        let before = handle.load();
+
+        let previous = shared.replace(OpenAPI::yaml(&content.replace("first", "second"))?);
+        assert!(Arc::ptr_eq(&previous, &before));
+
+        assert!(before.operation_by_id("first").is_some());
+        let current = handle.load();
+        assert!(current.operation_by_id("first").is_none());
+        assert!(current.operation_by_id("second").is_some());
+        Ok(())
+    }
+
+    #[cfg(feature = "hot-reload")]
+    #[test]
+    fn watched_document_reloads_on_change() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::shared::SharedOpenAPI;
+        use openapi_rs::model::watch::ReloadEvent;
+        use openapi_rs::validator::ValidationOptions;
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Watched API
+  version: 1.0.0
+paths:
+  /v1:
+    get:
+      operationId: first
+"#;
+        let directory = env::temp_dir().join(format!("openapi-rs-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&directory)?;
+        let path = directory.join("api.yaml");
+        std::fs::write(&path, content)?;
+
+        let options = ValidationOptions::new().with_strict(true);
+        let shared = SharedOpenAPI::new(OpenAPI::yaml(content)?.with_options(options));
+        let (sender, events) = mpsc::channel();
+        let _watcher = shared.watch(&path, move |event| {
+            let _ = sender.send(matches!(event, ReloadEvent::Reloaded { .. }));
+        })?;
+
+        std::fs::write(&path, "openapi: [")?;
+        while events.recv_timeout(Duration::from_secs(5))? {}
+        assert!(shared.load().operation_by_id("first").is_some());
+
+        std::fs::write(&path, content.replace("first", "second"))?;
+        while !events.recv_timeout(Duration::from_secs(5))? {}
+        let current = shared.load();
+        assert!(current.operation_by_id("second").is_some());
+        assert!(current.options().strict);
+
+        std::fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
+
+    #[test]
+    fn registry_routes_by_longest_prefix() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::registry::SpecRegistry;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Gateway
+  version: 1.0.0
+paths: {}
+"#;
+        let spec = |title: &str| OpenAPI::yaml(&content.replace("Gateway", title));
+        let registry = SpecRegistry::new()
+            .with("/", spec("Root")?)
+            .with("/billing", spec("Billing")?)
+            .with("billing/v2/", spec("Billing v2")?);
+
+        let title = |path: &str| {
+            let routed = registry.route(path).unwrap();
+            (
+                routed.prefix.to_string(),
+                routed.spec.load().info.title.clone(),
+                routed.path,
+            )
+        };
+        assert_eq!(
+            title("/billing/v2/invoices"),
+            (
+                "/billing/v2".into(),
+                "Billing v2".into(),
+                "/invoices".into()
+            )
+        );
+        assert_eq!(
+            title("/billing"),
+            ("/billing".into(), "Billing".into(), "/".into())
+        );
+        assert_eq!(
+            title("/billingx"),
+            ("".into(), "Root".into(), "/billingx".into())
+        );
+
+        let prefixes: Vec<&str> = registry.iter().map(|(prefix, _)| prefix).collect();
+        assert_eq!(prefixes, ["/billing/v2", "/billing", ""]);
+        assert!(registry.get("/billing/").is_some());
+        assert!(SpecRegistry::new().route("/billing").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn versions_selected_by_header_accept_and_prefix() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::model::version::VersionedSpecs;
+        use std::collections::HashMap;
+
+        let content = r#"
+openapi: 3.1.0
+info:
+  title: Versioned
+  version: 1.0.0
+paths: {}
+"#;
+        let versions = VersionedSpecs::new()
+            .with_version("v1", OpenAPI::yaml(content)?)
+            .with_version("v2", OpenAPI::yaml(&content.replace("1.0.0", "2.0.0"))?)
+            .with_header("X-API-Version")
+            .with_accept_parameter("version")
+            .with_prefix(true);
+
+        let select = |path: &str, headers: &[(&str, &str)]| {
+            let headers: HashMap<String, String> = headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            versions
+                .select(path, &headers)
+                .map(|selected| (selected.version, selected.path))
+        };
+
+        assert_eq!(
+            select("/users", &[("x-api-version", "V2")]),
+            Some(("v2", "/users".to_string()))
+        );
+        assert_eq!(
+            select(
+                "/users",
+                &[("accept", "text/plain, application/json; version=\"1\"")]
+            ),
+            Some(("v1", "/users".to_string()))
+        );
+        assert_eq!(select("/v2/users", &[]), Some(("v2", "/users".to_string())));
+        assert_eq!(
+            select("/v1/users", &[("x-api-version", "2")]),
+            Some(("v2", "/v1/users".to_string()))
+        );
+        assert_eq!(select("/users", &[]), None);
+        assert_eq!(select("/users", &[("x-api-version", "3")]), None);
+
+        let defaulted = versions.with_default("v1");
+        let selected = defaulted.select("/users", &HashMap::new()).unwrap();
+        assert_eq!(selected.version, "v1");
+        assert_eq!(selected.spec.load().info.version, "1.0.0");
+        Ok(())
+    }
+
+    #[cfg(feature = "test-util")]
+    const TEST_UTIL_SPEC: &str = r#"
+openapi: 3.1.0
+info:
+  title: Users
+  version: 1.0.0
+paths:
+  /users/{id}:
+    put:
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: integer
+        - name: notify
+          in: query
+          schema:
+            type: boolean
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+              properties:
+                name:
+                  type: string
+                  minLength: 2
+      responses:
+        '204':
+          description: Updated
+"#;
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn request_builder_assertions() -> Result<(), Box<dyn std::error::Error>> {
+        use openapi_rs::test::{validate, RequestBuilder};
+        use openapi_rs::{assert_invalid, assert_valid};
+        use serde_json::json;
+
+        let openapi = OpenAPI::yaml(TEST_UTIL_SPEC)?;
+        let update = || RequestBuilder::put("/users/7").with_header("X-Request-Id", "req-1");
+
+        assert_valid!(openapi, update().with_json(&json!({"name": "alice"})));
+        assert_valid!(
+            openapi,
+            update()
+                .with_query("notify", "true")
+                .with_json(&json!({"name": "bob"}))
+                .build(),
+        );
+        assert_invalid!(openapi, update().with_json(&json!({"name": "a"})), "name");
+        assert_invalid!(
+            openapi,
+            update()
+                .with_query("notify", "maybe")
+                .with_json(&json!({"name": "alice"})),
+            "notify"
+        );
+        assert_invalid!(openapi, RequestBuilder::get("/users/7"));
+
+        assert!(validate(&openapi, update().with_body("application/json", "{")).is_err());
+        assert_eq!(
+            validate(
+                &openapi,
+                update().with_body("application/json", r#"{"name": "carol"}"#)
+            ),
+            Ok(())
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    #[should_panic(expected = "expected a valid request")]
+    fn assert_valid_names_the_error() {
+        use openapi_rs::assert_valid;
+        use openapi_rs::test::RequestBuilder;
+
+        let openapi = OpenAPI::yaml(TEST_UTIL_SPEC).unwrap();
+        assert_valid!(openapi, RequestBuilder::put("/users/x").with_json(&"{}"));
+    }
+
+    mod generated {
+        include!("codegen/api.rs");
+    }
+
+    #[test]
+    fn codegen_fixture_is_up_to_date() -> Result<(), Box<dyn std::error::Error>> {
+        let openapi = OpenAPI::yaml(include_str!("codegen/api.yaml"))?;
+        let source = openapi_rs::codegen::Codegen::new().generate(&openapi);
+        assert_eq!(
+            source,
+            include_str!("codegen/api.rs"),
+            "tests/codegen/api.rs is stale"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn codegen_output_round_trips() -> Result<(), Box<dyn std::error::Error>> {
+        use generated::{Discount, Line, Order, Status};
+
+        let json = r#"{"id":7,"status":"in-transit","lines":[{"sku":"a-1","quantity":2}],"shipping":{"city":"Oslo"},"metadata":{"source":"web"}}"#;
+        let order: Order = serde_json::from_str(json)?;
+        assert_eq!(order.status, Status::InTransit);
+        assert_eq!(
+            order.lines,
+            vec![Line {
+                sku: "a-1".to_string(),
+                price: None,
+                quantity: 2
+            }]
+        );
+        assert_eq!(order.metadata.as_ref().unwrap()["source"], "web");
+        // Fields keep the order of the schema properties
+        assert_eq!(serde_json::to_string(&order)?, json);
 
+        let discount: Discount = serde_json::from_str("0.5")?;
+        assert_eq!(discount, Discount::Number(0.5));
         Ok(())
     }
 }